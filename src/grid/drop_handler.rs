@@ -1,6 +1,24 @@
 //! Drag-and-drop handling for the panel grid
 
+use std::rc::Rc;
 use makepad_widgets::*;
+use crate::grid::size_limits::SizeLimits;
+use crate::panel::PanelId;
+
+/// Where a dragged panel would land, as passed to a `DropValidator`
+#[derive(Clone, Debug, PartialEq)]
+pub enum DropTarget {
+    /// Row and column of the main panel grid
+    Grid { row: usize, col: usize },
+
+    /// Top (`bottom: false`) or bottom half of a footer slot
+    Footer { slot: usize, bottom: bool },
+}
+
+/// App hook deciding whether a panel (by ID) may be dropped on a target
+///
+/// Returning false shows the "cannot drop here" preview and refuses the drop.
+pub type DropValidator = Rc<dyn Fn(&str, &DropTarget) -> bool>;
 
 /// Drop position calculated during drag operations.
 ///
@@ -15,12 +33,15 @@ pub struct DropPosition {
 
     /// Rectangle for drawing drop preview overlay
     pub rect: Rect,
+
+    /// The panel may be dropped here (false shows a "cannot drop here" preview)
+    pub allowed: bool,
 }
 
 impl DropPosition {
     /// Create a new drop position
     pub fn new(row: usize, col: usize, rect: Rect) -> Self {
-        Self { row, col, rect }
+        Self { row, col, rect, allowed: true }
     }
 }
 
@@ -37,7 +58,7 @@ impl DropPosition {
 pub fn calculate_drop_position(
     abs: DVec2,
     container_rect: Rect,
-    rows_with_panels: &[Vec<String>],
+    rows_with_panels: &[Vec<PanelId>],
     row_to_actual: &[usize],
 ) -> Option<DropPosition> {
     let num_rows = rows_with_panels.len();
@@ -82,3 +103,62 @@ pub fn calculate_drop_position(
 
     Some(DropPosition::new(actual_row, col, rect))
 }
+
+/// Whether a panel may be dropped on a target
+///
+/// * `limits` - Size limits of the layout before and after the drop and the space
+///   it gets: the result must fit, or need no more than before (None = no check)
+/// * `pinned` - Position of each pinned panel before and after the drop; none may move
+/// * `validator` - The app's hook has the last word
+pub fn drop_allowed<P: PartialEq>(
+    limits: Option<(SizeLimits, SizeLimits, DVec2)>,
+    pinned: impl IntoIterator<Item = (P, P)>,
+    validator: Option<&DropValidator>,
+    panel_id: &str,
+    target: &DropTarget,
+) -> bool {
+    limits.map_or(true, |(before, after, size)| after.fits(size) || after.no_larger_than(&before))
+        && pinned.into_iter().all(|(before, after)| before == after)
+        && validator.map_or(true, |validate| validate(panel_id, target))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TARGET: DropTarget = DropTarget::Grid { row: 0, col: 1 };
+
+    fn min_width(min_width: f64) -> SizeLimits {
+        SizeLimits { min_width, ..SizeLimits::default() }
+    }
+
+    #[test]
+    fn test_drop_must_fit_or_get_no_tighter() {
+        let size = dvec2(500.0, 400.0);
+        let fits = Some((min_width(600.0), min_width(400.0), size));
+        let no_tighter = Some((min_width(600.0), min_width(550.0), size));
+        let tighter = Some((min_width(400.0), min_width(550.0), size));
+
+        assert!(drop_allowed::<usize>(fits, [], None, "editor", &TARGET));
+        assert!(drop_allowed::<usize>(no_tighter, [], None, "editor", &TARGET));
+        assert!(!drop_allowed::<usize>(tighter, [], None, "editor", &TARGET));
+    }
+
+    #[test]
+    fn test_pinned_panels_stay_put() {
+        assert!(drop_allowed(None, [(Some((0, 1)), Some((0, 1)))], None, "editor", &TARGET));
+        assert!(!drop_allowed(None, [(Some((0, 1)), Some((0, 2)))], None, "editor", &TARGET));
+    }
+
+    #[test]
+    fn test_validator_gets_the_panel_and_target() {
+        let validator: DropValidator = Rc::new(|panel_id, target| {
+            panel_id != "console" || matches!(target, DropTarget::Footer { .. })
+        });
+        let footer = DropTarget::Footer { slot: 0, bottom: false };
+
+        assert!(drop_allowed::<usize>(None, [], Some(&validator), "editor", &TARGET));
+        assert!(!drop_allowed::<usize>(None, [], Some(&validator), "console", &TARGET));
+        assert!(drop_allowed::<usize>(None, [], Some(&validator), "console", &footer));
+    }
+}
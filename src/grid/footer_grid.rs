@@ -14,18 +14,23 @@
 //! - `{1,0}` to `{1,6}`: Panel slots (can be single or vertically split)
 
 use std::cell::RefCell;
+use std::collections::HashMap;
 use makepad_widgets::*;
-use crate::panel::PanelAction;
+use crate::panel::{PanelAction, PanelDecoration, PanelId, PanelTitleAction};
+use crate::panel::title_actions::set_action_checked;
+use crate::registry::PanelDefinition;
 use crate::panel::panel::PanelWidgetRefExt;
 use crate::shell::sidebar::ShellSidebarWidgetExt;
-use crate::grid::{FooterLayoutState, FooterSlotState};
-use crate::theme::get_global_dark_mode;
+use crate::grid::{DropTarget, DropValidator, FooterLayoutState, FooterSlotState, SizeLimits, fit_sizes};
+use crate::grid::drop_handler::drop_allowed;
+use crate::theme::{ShellPalette, ThemeListener, ThemeTracker};
 
 // Thread-local storage for pending footer layout state (used when set_layout_state is called before first draw)
 thread_local! {
     static PENDING_FOOTER_LAYOUT: RefCell<Option<FooterLayoutState>> = RefCell::new(None);
     static PENDING_FOOTER_RESET: RefCell<bool> = RefCell::new(false);
     static PENDING_PANEL_TITLES: RefCell<Vec<(usize, usize, String)>> = RefCell::new(Vec::new());
+    static PENDING_FOOTER_LOCKED: RefCell<Option<bool>> = RefCell::new(None);
 }
 
 live_design! {
@@ -138,11 +143,9 @@ live_design! {
 
         show_bg: true
         draw_bg: {
-            instance dark_mode: 0.0
+            uniform bg_sidebar: (PALETTE_BG_SIDEBAR)
             fn pixel(self) -> vec4 {
-                let light = vec4(0.945, 0.961, 0.976, 1.0);
-                let dark = vec4(0.122, 0.161, 0.231, 1.0);
-                return mix(light, dark, self.dark_mode);
+                return self.bg_sidebar;
             }
         }
 
@@ -152,6 +155,12 @@ live_design! {
             color: #3b82f680
         }
 
+        // Drop preview where the panel can't go (it would move a pinned panel or the app forbids it)
+        drop_preview_blocked: {
+            draw_depth: 10.0
+            color: #d0404080
+        }
+
         // Use Dock with independent horizontal splitter
         dock = <Dock> {
             width: Fill
@@ -196,11 +205,9 @@ live_design! {
 
                 show_bg: true
                 draw_bg: {
-                    instance dark_mode: 0.0
+                    uniform bg_canvas: (PALETTE_BG_CANVAS)
                     fn pixel(self) -> vec4 {
-                        let light = vec4(0.886, 0.910, 0.941, 1.0);
-                        let dark = vec4(0.059, 0.090, 0.165, 1.0);
-                        return mix(light, dark, self.dark_mode);
+                        return self.bg_canvas;
                     }
                 }
 
@@ -222,12 +229,7 @@ pub struct SlotState {
     /// Is this slot visible?
     pub visible: bool,
     /// Panel IDs stacked vertically (top to bottom)
-    pub panel_ids: Vec<String>,
-}
-
-/// Helper to convert string panel ID to LiveId
-fn panel_id_to_live_id(panel_id: &str) -> LiveId {
-    LiveId::from_str_lc(panel_id)
+    pub panel_ids: Vec<PanelId>,
 }
 
 /// Get panel index from panel ID (extracts number from "footer_panel_N" format)
@@ -255,6 +257,9 @@ pub struct FooterGrid {
     #[live]
     drop_preview: DrawColor,
 
+    #[live]
+    drop_preview_blocked: DrawColor,
+
     /// Number of initially visible panels (default: 3)
     #[live(3i64)]
     initial_panels: i64,
@@ -263,33 +268,145 @@ pub struct FooterGrid {
     #[rust]
     slots: Vec<SlotState>,
 
-    /// Currently fullscreen panel ID (None if no fullscreen)
+    /// Currently fullscreen panel (None if no fullscreen)
     #[rust]
-    fullscreen_panel: Option<String>,
+    fullscreen_panel: Option<PanelId>,
 
-    /// Currently dragging panel ID
+    /// Currently dragging panel
     #[rust]
-    dragging_panel: Option<String>,
+    dragging_panel: Option<PanelId>,
 
     /// Current drop target (slot index, is_bottom_half)
     #[rust]
     drop_target: Option<(usize, bool)>,
 
+    /// The panel may be dropped on `drop_target` (false shows the blocked preview)
+    #[rust]
+    drop_target_allowed: bool,
+
     #[rust]
     initialized: bool,
 
     #[rust]
     needs_layout_update: bool,
+
+    #[rust]
+    theme_tracker: ThemeTracker,
+
+    /// Panels whose close button asks for confirmation first
+    #[rust]
+    confirm_close: Vec<PanelId>,
+
+    /// Title bar actions by panel ID, with their current toggle states
+    #[rust]
+    title_actions: HashMap<PanelId, Vec<PanelTitleAction>>,
+
+    /// Icon, badge and "modified" dot by panel ID
+    #[rust]
+    decorations: HashMap<PanelId, PanelDecoration>,
+
+    /// Minimum and maximum sizes by panel ID (from the panel definitions)
+    #[rust]
+    size_limits: HashMap<PanelId, SizeLimits>,
+
+    /// Panel strip width the slot widths were computed for
+    #[rust]
+    laid_out_width: f64,
+
+    /// Panels the user pinned in place (persisted in `FooterLayoutState`)
+    #[rust]
+    pinned_panels: Vec<PanelId>,
+
+    /// Panels pinned by their definition
+    #[rust]
+    definition_pins: Vec<PanelId>,
+
+    /// Closed panels, most recently closed last (persisted in `FooterLayoutState`)
+    #[rust]
+    closed_panels: Vec<PanelId>,
+
+    /// No panel can be dragged or closed (the header's lock toggle)
+    #[rust]
+    layout_locked: bool,
+
+    /// App hook that can forbid drops
+    #[rust]
+    drop_validator: Option<DropValidator>,
+}
+
+impl ThemeListener for FooterGrid {
+    fn theme_tracker(&mut self) -> &mut ThemeTracker {
+        &mut self.theme_tracker
+    }
+
+    fn on_dark_mode_change(&mut self, _cx: &mut Cx, _dark_mode: f64) {}
+
+    fn on_palette_change(&mut self, cx: &mut Cx, palette: &ShellPalette) {
+        self.apply_palette_internal(cx, palette);
+    }
 }
 
 impl Widget for FooterGrid {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
-        // IMPORTANT: Don't capture actions - let them propagate to parent widgets
-        // This allows PlaybackControls, Timeline, etc. to emit actions that reach the app
-        self.view.handle_event(cx, event, scope);
+        // Capture actions to react to our own panels, then forward everything below so
+        // PlaybackControls, Timeline, etc. can still emit actions that reach the app
+        let actions = cx.capture_actions(|cx| {
+            self.view.handle_event(cx, event, scope);
+        });
 
         let mut layout_changed = false;
 
+        // Escape cancels a drag and leaves the slots unchanged
+        if let Event::KeyDown(ke) = event {
+            if ke.key_code == KeyCode::Escape && self.dragging_panel.is_some() {
+                self.end_drag(cx);
+            }
+        }
+
+        for action in actions.iter() {
+            match action.as_widget_action().cast::<PanelAction>() {
+                PanelAction::Close(id) => {
+                    if let Some(panel_id) = self.find_panel(id).filter(|id| !self.is_locked(id)) {
+                        if self.confirm_close.contains(&panel_id) {
+                            cx.widget_action(self.widget_uid(), &scope.path, PanelAction::CloseRequested(panel_id));
+                        } else {
+                            self.close_panel(cx, panel_id);
+                            layout_changed = true;
+                        }
+                    }
+                }
+                PanelAction::Fullscreen(id) => {
+                    if let Some(panel_id) = self.find_panel(id) {
+                        self.toggle_fullscreen(cx, panel_id);
+                        layout_changed = true;
+                    }
+                }
+                PanelAction::TitleAction { panel_id, action_id, checked: Some(checked) } => {
+                    // Remember toggle states across layout changes
+                    if let Some(actions) = self.title_actions.get_mut(&panel_id) {
+                        set_action_checked(actions, &action_id, checked);
+                    }
+                }
+                PanelAction::StartDrag(id) => {
+                    if let Some(panel_id) = self.find_panel(id).filter(|id| !self.is_locked(id)) {
+                        self.dragging_panel = Some(panel_id);
+                    }
+                }
+                PanelAction::EndDrag(id, abs) => {
+                    if let Some(panel_id) = self.find_panel(id) {
+                        if self.dragging_panel == Some(panel_id) {
+                            self.handle_drop(cx, panel_id, abs);
+                            layout_changed = true;
+                        }
+                    }
+                    self.end_drag(cx);
+                }
+                _ => {}
+            }
+        }
+
+        cx.extend_actions(actions);
+
         // Handle drag-and-drop
         if self.dragging_panel.is_some() {
             match event.hits_with_capture_overload(cx, self.view.area(), true) {
@@ -300,9 +417,7 @@ impl Widget for FooterGrid {
                 Hit::FingerUp(_) => {
                     // Clear state on any FingerUp as fallback
                     // (actual drop is handled via EndDrag action from Panel)
-                    self.dragging_panel = None;
-                    self.drop_target = None;
-                    self.view.redraw(cx);
+                    self.end_drag(cx);
                 }
                 _ => {}
             }
@@ -319,9 +434,8 @@ impl Widget for FooterGrid {
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
-        // Apply global theme on every draw
-        let dm = get_global_dark_mode();
-        self.apply_dark_mode_internal(cx, dm);
+        // Apply the shell theme when it changed
+        self.sync_theme(cx);
 
         // Check for pending reset
         let should_reset = PENDING_FOOTER_RESET.with(|p| {
@@ -332,6 +446,12 @@ impl Widget for FooterGrid {
         if should_reset {
             self.initialize_slots();
             self.fullscreen_panel = None;
+            self.pinned_panels.clear();
+            self.needs_layout_update = true;
+        }
+
+        if let Some(locked) = PENDING_FOOTER_LOCKED.with(|p| p.borrow_mut().take()) {
+            self.layout_locked = locked;
             self.needs_layout_update = true;
         }
 
@@ -346,6 +466,7 @@ impl Widget for FooterGrid {
                     panel_ids: s.panel_ids,
                 }).collect();
                 self.fullscreen_panel = state.fullscreen_panel;
+                self.pinned_panels = state.pinned_panels;
             } else {
                 self.initialize_slots();
             }
@@ -367,10 +488,21 @@ impl Widget for FooterGrid {
 
         let result = self.view.draw_walk(cx, scope, walk);
 
+        // Slot widths depend on the strip width when panels have size limits
+        let width = self.view.view(id!(panel_strip_content)).area().rect(cx).size.x;
+        if width != self.laid_out_width && !self.size_limits.is_empty() {
+            self.needs_layout_update = true;
+            self.view.redraw(cx);
+        }
+
         // Draw drop preview if dragging
         if let Some((slot_idx, is_bottom)) = self.drop_target {
             if let Some(rect) = self.get_slot_drop_rect(cx, slot_idx, is_bottom) {
-                self.drop_preview.draw_abs(cx, rect);
+                if self.drop_target_allowed {
+                    self.drop_preview.draw_abs(cx, rect);
+                } else {
+                    self.drop_preview_blocked.draw_abs(cx, rect);
+                }
             }
         }
 
@@ -390,43 +522,79 @@ impl FooterGrid {
         self.slots = (0..Self::SLOT_COUNT)
             .map(|i| SlotState {
                 visible: i < count,
-                panel_ids: vec![format!("footer_panel_{}", i)],
+                panel_ids: vec![PanelId::from(format!("footer_panel_{}", i))],
             })
             .collect();
     }
 
-    /// Apply dark mode to this grid (internal, called during draw)
-    fn apply_dark_mode_internal(&mut self, cx: &mut Cx, dark_mode: f64) {
-        self.view.apply_over(cx, live! {
-            draw_bg: { dark_mode: (dark_mode) }
-        });
-
-        self.view.view(id!(panel_strip_content)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dark_mode) }
-        });
+    /// Apply the palette to this grid (internal, called during draw)
+    fn apply_palette_internal(&mut self, cx: &mut Cx, palette: &ShellPalette) {
+        let bg = palette.live_nodes(&[live_id!(draw_bg)]);
+        self.view.apply_over(cx, &bg);
+        self.view.view(id!(panel_strip_content)).apply_over(cx, &bg);
     }
 
     fn panel_slot_ids() -> [&'static [LiveId]; 5] {
         [id!(p0), id!(p1), id!(p2), id!(p3), id!(p4)]
     }
 
-    /// Find panel string ID by LiveId (reverse lookup through all slot panels)
-    fn find_panel_by_live_id(&self, id: LiveId) -> Option<String> {
-        for slot in &self.slots {
-            for panel_id in &slot.panel_ids {
-                if panel_id_to_live_id(panel_id) == id {
-                    return Some(panel_id.clone());
-                }
-            }
-        }
-        None
+    /// The panel if it is in one of this grid's slots
+    fn find_panel(&self, id: PanelId) -> Option<PanelId> {
+        self.has_panel(&id).then_some(id)
+    }
+
+    /// Whether a panel is in one of this grid's slots
+    fn has_panel(&self, panel_id: &str) -> bool {
+        Self::slot_position(&self.slots, panel_id).is_some()
+    }
+
+    /// Whether a panel is pinned by the user or by its definition
+    fn is_pinned(&self, panel_id: &str) -> bool {
+        self.pinned_panels.iter().chain(&self.definition_pins).any(|id| id == panel_id)
+    }
+
+    /// Whether a panel can't be dragged or closed
+    fn is_locked(&self, panel_id: &str) -> bool {
+        self.layout_locked || self.is_pinned(panel_id)
+    }
+
+    /// Slot and stack position of a panel
+    fn slot_position(slots: &[SlotState], panel_id: &str) -> Option<(usize, usize)> {
+        slots.iter().enumerate().find_map(|(slot_idx, slot)| {
+            slot.panel_ids.iter().position(|id| id == panel_id).map(|pos| (slot_idx, pos))
+        })
+    }
+
+    /// Slot and stack position of each pinned panel now and in `slots`
+    fn pinned_moves(&self, slots: &[SlotState]) -> Vec<(Option<(usize, usize)>, Option<(usize, usize)>)> {
+        self.slots.iter()
+            .flat_map(|slot| &slot.panel_ids)
+            .filter(|panel_id| self.is_pinned(panel_id))
+            .map(|panel_id| (Self::slot_position(&self.slots, panel_id), Self::slot_position(slots, panel_id)))
+            .collect()
+    }
+
+    /// Limits of a slot's panels, stacked top to bottom
+    fn slot_limits(&self, panel_ids: &[PanelId]) -> SizeLimits {
+        SizeLimits::stacked(panel_ids.iter().map(|id| self.size_limits.get(id).copied().unwrap_or_default()))
+    }
+
+    /// Limits of the visible slots side by side
+    fn strip_limits(&self) -> SizeLimits {
+        SizeLimits::side_by_side(self.slots.iter()
+            .filter(|slot| slot.visible && !slot.panel_ids.is_empty())
+            .map(|slot| self.slot_limits(&slot.panel_ids)))
     }
 
     fn apply_layout(&mut self, cx: &mut Cx) {
         let slot_ids = Self::slot_ids();
 
+        // Size limits are applied against the strip width of the last frame
+        let strip_width = self.view.view(id!(panel_strip_content)).area().rect(cx).size.x;
+        self.laid_out_width = strip_width;
+
         // Handle fullscreen mode
-        if let Some(ref fs_id) = self.fullscreen_panel.clone() {
+        if let Some(fs_id) = self.fullscreen_panel {
             // Hide all slots
             for (i, slot_id) in slot_ids.iter().enumerate() {
                 self.view.view(*slot_id).apply_over(cx, live! {
@@ -440,13 +608,24 @@ impl FooterGrid {
                             visible: true, width: Fill, height: Fill
                         });
                         // Configure as single panel in fullscreen
-                        self.configure_slot(cx, *slot_id, &[fs_id.clone()], true);
+                        self.configure_slot(cx, *slot_id, &[fs_id], true);
                     }
                 }
             }
             return;
         }
 
+        // Fixed widths for visible slots whose panels have limits (None = Fill)
+        let limits: Vec<_> = self.slots.iter()
+            .filter(|slot| slot.visible && !slot.panel_ids.is_empty())
+            .map(|slot| self.slot_limits(&slot.panel_ids).width())
+            .collect();
+        let mut widths = if strip_width > 0.0 {
+            fit_sizes(strip_width, &limits)
+        } else {
+            vec![None; limits.len()]
+        }.into_iter();
+
         // Normal layout
         for (i, slot_id) in slot_ids.iter().enumerate() {
             if let Some(slot) = self.slots.get(i) {
@@ -457,9 +636,14 @@ impl FooterGrid {
                     continue;
                 }
 
-                self.view.view(*slot_id).apply_over(cx, live! {
-                    visible: true, width: Fill, height: Fill
-                });
+                match widths.next().flatten() {
+                    Some(width) => self.view.view(*slot_id).apply_over(cx, live! {
+                        visible: true, width: (width), height: Fill
+                    }),
+                    None => self.view.view(*slot_id).apply_over(cx, live! {
+                        visible: true, width: Fill, height: Fill
+                    }),
+                }
 
                 // Clone panel_ids to avoid borrow conflict
                 let panel_ids = slot.panel_ids.clone();
@@ -468,7 +652,7 @@ impl FooterGrid {
         }
     }
 
-    fn configure_slot(&mut self, cx: &mut Cx, slot_id: &[LiveId], panel_ids: &[String], is_fullscreen: bool) {
+    fn configure_slot(&mut self, cx: &mut Cx, slot_id: &[LiveId], panel_ids: &[PanelId], is_fullscreen: bool) {
         let panel_slot_ids = Self::panel_slot_ids();
         let count = panel_ids.len().min(5);
 
@@ -479,9 +663,14 @@ impl FooterGrid {
                     visible: true, width: Fill, height: Fill
                 });
                 let panel_ref = self.view.view(slot_id).panel(*p_slot_id);
-                panel_ref.set_panel_id_str(&panel_ids[i]);
+                panel_ref.set_panel_id(panel_ids[i]);
                 panel_ref.set_panel_index(cx, panel_index_from_id(&panel_ids[i]));
                 panel_ref.set_fullscreen(is_fullscreen && count == 1);
+                // Footer titles aren't stored in the footer layout state
+                panel_ref.set_renamable(false);
+                panel_ref.set_locked(cx, self.is_locked(&panel_ids[i]));
+                panel_ref.set_title_actions(cx, self.title_actions.get(&panel_ids[i]).cloned().unwrap_or_default());
+                panel_ref.set_decoration(cx, self.decorations.get(&panel_ids[i]).cloned().unwrap_or_default());
             } else {
                 // Hide unused panel slots
                 self.view.view(slot_id).view(*p_slot_id).apply_over(cx, live! {
@@ -491,14 +680,14 @@ impl FooterGrid {
         }
     }
 
-    fn close_panel(&mut self, cx: &mut Cx, panel_id: &str) {
+    fn close_panel(&mut self, cx: &mut Cx, panel_id: PanelId) {
         // Exit fullscreen if closing fullscreen panel
-        if self.fullscreen_panel.as_deref() == Some(panel_id) {
+        if self.fullscreen_panel == Some(panel_id) {
             self.fullscreen_panel = None;
         }
 
         for slot in &mut self.slots {
-            if let Some(pos) = slot.panel_ids.iter().position(|id| id == panel_id) {
+            if let Some(pos) = slot.panel_ids.iter().position(|id| *id == panel_id) {
                 slot.panel_ids.remove(pos);
                 if slot.panel_ids.is_empty() {
                     slot.visible = false;
@@ -508,17 +697,41 @@ impl FooterGrid {
         }
 
         // Auto-compact after closing
-        self.compact_slots();
+        Self::compact_slots(&mut self.slots);
 
+        self.closed_panels.retain(|id| *id != panel_id);
+        self.closed_panels.push(panel_id);
         self.needs_layout_update = true;
         self.view.redraw(cx);
     }
 
-    fn toggle_fullscreen(&mut self, cx: &mut Cx, panel_id: &str) {
-        if self.fullscreen_panel.as_deref() == Some(panel_id) {
+    /// Show a closed panel again in the first free slot, or stacked in the last one
+    ///
+    /// Returns false if the panel wasn't closed or there is no room for it.
+    fn reopen_panel(&mut self, cx: &mut Cx, panel_id: PanelId) -> bool {
+        if !self.closed_panels.contains(&panel_id) {
+            return false;
+        }
+        if let Some(slot) = self.slots.iter_mut().find(|slot| !slot.visible || slot.panel_ids.is_empty()) {
+            slot.visible = true;
+            slot.panel_ids = vec![panel_id];
+        } else if let Some(slot) = self.slots.last_mut().filter(|slot| slot.panel_ids.len() < Self::panel_slot_ids().len()) {
+            slot.panel_ids.push(panel_id);
+        } else {
+            return false;
+        }
+
+        self.closed_panels.retain(|id| *id != panel_id);
+        self.needs_layout_update = true;
+        self.view.redraw(cx);
+        true
+    }
+
+    fn toggle_fullscreen(&mut self, cx: &mut Cx, panel_id: PanelId) {
+        if self.fullscreen_panel == Some(panel_id) {
             self.fullscreen_panel = None;
         } else {
-            self.fullscreen_panel = Some(panel_id.to_string());
+            self.fullscreen_panel = Some(panel_id);
         }
         self.needs_layout_update = true;
         self.view.redraw(cx);
@@ -535,8 +748,8 @@ impl FooterGrid {
                 }
 
                 // Skip if dragging a panel that's already in this slot
-                if let Some(ref dragging) = self.dragging_panel {
-                    if slot.panel_ids.contains(dragging) {
+                if let Some(dragging) = self.dragging_panel {
+                    if slot.panel_ids.contains(&dragging) {
                         continue;
                     }
                 }
@@ -548,7 +761,14 @@ impl FooterGrid {
                     // Determine if dropping on top or bottom half
                     let mid_y = rect.pos.y + rect.size.y / 2.0;
                     let is_bottom = abs.y > mid_y;
-                    self.drop_target = Some((i, is_bottom));
+
+                    // A target where the drop would move a pinned panel or the app forbids it is blocked
+                    if let Some(dragged) = self.dragging_panel {
+                        if let Some(slots) = self.slots_after_drop(dragged, i, is_bottom) {
+                            self.drop_target_allowed = self.drop_allowed(dragged, i, is_bottom, &slots);
+                            self.drop_target = Some((i, is_bottom));
+                        }
+                    }
                     return;
                 }
             }
@@ -582,65 +802,85 @@ impl FooterGrid {
         })
     }
 
-    fn handle_drop(&mut self, cx: &mut Cx, dragged_id: &str, _abs: DVec2) {
+    fn handle_drop(&mut self, cx: &mut Cx, dragged_id: PanelId, _abs: DVec2) {
         let Some((target_idx, is_bottom)) = self.drop_target else {
             return;
         };
+        let Some(slots) = self.slots_after_drop(dragged_id, target_idx, is_bottom) else {
+            return;
+        };
+        if !self.drop_allowed(dragged_id, target_idx, is_bottom, &slots) {
+            return;
+        }
 
-        // Find the source slot for the dragged panel
-        let mut source_slot_idx: Option<usize> = None;
+        self.slots = slots;
+        self.needs_layout_update = true;
+        self.view.redraw(cx);
+    }
 
-        for (i, slot) in self.slots.iter().enumerate() {
-            if slot.panel_ids.iter().any(|id| id == dragged_id) {
-                source_slot_idx = Some(i);
-                break;
-            }
+    /// Whether a drop keeps the pinned panels in place and the app's drop validator accepts it
+    fn drop_allowed(&self, dragged_id: PanelId, target_idx: usize, is_bottom: bool, slots: &[SlotState]) -> bool {
+        drop_allowed(
+            None,
+            self.pinned_moves(slots),
+            self.drop_validator.as_ref(),
+            &dragged_id,
+            &DropTarget::Footer { slot: target_idx, bottom: is_bottom },
+        )
+    }
+
+    /// Stop dragging and clear the drop preview
+    fn end_drag(&mut self, cx: &mut Cx) {
+        self.dragging_panel = None;
+        self.drop_target = None;
+        self.drop_target_allowed = false;
+        self.view.redraw(cx);
+    }
+
+    /// Slots after dropping a panel on the top or bottom half of a slot
+    ///
+    /// Returns None when nothing would change: the panel is already in the
+    /// target slot, or the slot is full.
+    fn slots_after_drop(&self, dragged_id: PanelId, target_idx: usize, is_bottom: bool) -> Option<Vec<SlotState>> {
+        let mut slots = self.slots.clone();
+
+        // Add to target slot (max 5 panels per slot)
+        let target_slot = slots.get(target_idx)?;
+        if target_slot.panel_ids.contains(&dragged_id) || target_slot.panel_ids.len() >= 5 {
+            return None;
         }
 
         // If dragging from within footer, remove from source slot
-        if let Some(src_idx) = source_slot_idx {
-            // Skip if dropping on same slot
-            if src_idx == target_idx {
-                return;
-            }
-
-            // Remove from source slot
-            let src_slot = &mut self.slots[src_idx];
-            if let Some(pos) = src_slot.panel_ids.iter().position(|id| id == dragged_id) {
-                src_slot.panel_ids.remove(pos);
-            }
+        if let Some((src_idx, pos)) = Self::slot_position(&slots, &dragged_id) {
+            let src_slot = &mut slots[src_idx];
+            src_slot.panel_ids.remove(pos);
             if src_slot.panel_ids.is_empty() {
                 src_slot.visible = false;
             }
         }
 
-        // Add to target slot (max 5 panels per slot)
-        let target_slot = &mut self.slots[target_idx];
-        if target_slot.panel_ids.len() < 5 {
-            if is_bottom {
-                target_slot.panel_ids.push(dragged_id.to_string());
-            } else {
-                target_slot.panel_ids.insert(0, dragged_id.to_string());
-            }
+        let target_slot = &mut slots[target_idx];
+        if is_bottom {
+            target_slot.panel_ids.push(dragged_id);
+        } else {
+            target_slot.panel_ids.insert(0, dragged_id);
         }
 
         // Auto-compact: shift visible slots to fill gaps
-        self.compact_slots();
-
-        self.needs_layout_update = true;
-        self.view.redraw(cx);
+        Self::compact_slots(&mut slots);
+        Some(slots)
     }
 
     /// Compact slots by shifting visible ones to fill gaps
-    fn compact_slots(&mut self) {
+    fn compact_slots(slots: &mut [SlotState]) {
         // Collect visible slots with panels
-        let visible_slots: Vec<SlotState> = self.slots.iter()
+        let visible_slots: Vec<SlotState> = slots.iter()
             .filter(|s| s.visible && !s.panel_ids.is_empty())
             .cloned()
             .collect();
 
         // Reassign slots
-        for (i, slot) in self.slots.iter_mut().enumerate() {
+        for (i, slot) in slots.iter_mut().enumerate() {
             if i < visible_slots.len() {
                 *slot = visible_slots[i].clone();
             } else {
@@ -666,7 +906,9 @@ impl FooterGrid {
                 visible: s.visible,
                 panel_ids: s.panel_ids.clone(),
             }).collect(),
-            fullscreen_panel: self.fullscreen_panel.clone(),
+            fullscreen_panel: self.fullscreen_panel,
+            pinned_panels: self.pinned_panels.clone(),
+            closed_panels: self.closed_panels.clone(),
         }
     }
 
@@ -677,6 +919,8 @@ impl FooterGrid {
             panel_ids: s.panel_ids,
         }).collect();
         self.fullscreen_panel = state.fullscreen_panel;
+        self.pinned_panels = state.pinned_panels;
+        self.closed_panels = state.closed_panels;
         self.initialized = true;
         self.needs_layout_update = true;
         self.view.redraw(cx);
@@ -714,13 +958,184 @@ impl FooterGridRef {
         }
     }
 
+    /// Current layout state
+    pub fn layout_state(&self) -> Option<FooterLayoutState> {
+        self.borrow().map(|inner| inner.get_layout_state())
+    }
+
+    /// Close a panel, returning false if it isn't in the footer
+    pub fn close_panel(&self, cx: &mut Cx, panel_id: &str) -> bool {
+        let Some(mut inner) = self.borrow_mut() else { return false };
+        if !inner.has_panel(panel_id) {
+            return false;
+        }
+        inner.close_panel(cx, PanelId::new(panel_id));
+        true
+    }
+
+    /// Show a closed panel again; false if it wasn't closed or the footer is full
+    pub fn reopen_panel(&self, cx: &mut Cx, panel_id: &str) -> bool {
+        self.borrow_mut().map_or(false, |mut inner| inner.reopen_panel(cx, PanelId::new(panel_id)))
+    }
+
+    /// Closed panels, most recently closed last
+    pub fn closed_panels(&self) -> Vec<PanelId> {
+        self.borrow().map(|inner| inner.closed_panels.clone()).unwrap_or_default()
+    }
+
+    /// Show a panel fullscreen, or leave fullscreen; false if it isn't in the footer
+    pub fn toggle_fullscreen(&self, cx: &mut Cx, panel_id: &str) -> bool {
+        let Some(mut inner) = self.borrow_mut() else { return false };
+        if !inner.has_panel(panel_id) {
+            return false;
+        }
+        inner.toggle_fullscreen(cx, PanelId::new(panel_id));
+        true
+    }
+
+    /// Set the panel definitions (icons and title bar actions by panel ID)
+    pub fn set_panel_definitions(&self, cx: &mut Cx, definitions: &[PanelDefinition]) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.title_actions = definitions.iter()
+                .filter(|def| !def.title_actions.is_empty())
+                .map(|def| (PanelId::new(&def.id), def.title_actions.clone()))
+                .collect();
+            inner.size_limits = definitions.iter()
+                .filter(|def| def.size_limits != SizeLimits::default())
+                .map(|def| (PanelId::new(&def.id), def.size_limits))
+                .collect();
+            inner.definition_pins = definitions.iter()
+                .filter(|def| def.pinned)
+                .map(|def| PanelId::new(&def.id))
+                .collect();
+            for def in definitions {
+                if let Some(icon) = &def.icon {
+                    inner.decorations.entry(PanelId::new(&def.id)).or_default().icon = Some(icon.clone());
+                }
+            }
+            inner.needs_layout_update = true;
+            inner.view.redraw(cx);
+        }
+    }
+
+    /// Set (or clear with None) the app hook that can forbid drops
+    pub fn set_drop_validator(&self, validator: Option<DropValidator>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.drop_validator = validator;
+        }
+    }
+
+    /// Smallest (and largest) size the visible slots need, from the panel definitions
+    pub fn size_limits(&self) -> SizeLimits {
+        self.borrow().map(|inner| inner.strip_limits()).unwrap_or_default()
+    }
+
+    /// Pin or unpin a panel; false if it isn't in the footer, is pinned by its definition or is unchanged
+    pub fn set_panel_pinned(&self, cx: &mut Cx, panel_id: &str, pinned: bool) -> bool {
+        let Some(mut inner) = self.borrow_mut() else { return false };
+        if FooterGrid::slot_position(&inner.slots, panel_id).is_none()
+            || inner.definition_pins.iter().any(|id| id == panel_id)
+            || inner.pinned_panels.iter().any(|id| id == panel_id) == pinned
+        {
+            return false;
+        }
+        if pinned {
+            inner.pinned_panels.push(PanelId::new(panel_id));
+        } else {
+            inner.pinned_panels.retain(|id| id != panel_id);
+        }
+        inner.needs_layout_update = true;
+        inner.view.redraw(cx);
+        true
+    }
+
+    /// Whether a panel is pinned by the user or by its definition
+    pub fn is_panel_pinned(&self, panel_id: &str) -> bool {
+        self.borrow().map_or(false, |inner| inner.is_pinned(panel_id))
+    }
+
+    /// Turn dragging and closing off (true) or on for every panel
+    ///
+    /// Note: If called before first draw, the lock is applied during initialization.
+    pub fn set_layout_locked(&self, cx: &mut Cx, locked: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.layout_locked = locked;
+            inner.end_drag(cx);
+            inner.needs_layout_update = true;
+            inner.view.redraw(cx);
+        } else {
+            PENDING_FOOTER_LOCKED.with(|p| *p.borrow_mut() = Some(locked));
+        }
+    }
+
+    /// Replace a panel's title bar actions
+    pub fn set_title_actions(&self, cx: &mut Cx, panel_id: &str, actions: Vec<PanelTitleAction>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.title_actions.insert(PanelId::new(panel_id), actions);
+            inner.needs_layout_update = true;
+            inner.view.redraw(cx);
+        }
+    }
+
+    /// Set the checked state of a panel's toggle title action
+    ///
+    /// Returns false if the panel has no toggle action with this ID.
+    pub fn set_title_action_checked(&self, cx: &mut Cx, panel_id: &str, action_id: &str, checked: bool) -> bool {
+        let Some(mut inner) = self.borrow_mut() else { return false };
+        let Some(actions) = inner.title_actions.get_mut(panel_id) else { return false };
+        if !set_action_checked(actions, action_id, checked) {
+            return false;
+        }
+        inner.needs_layout_update = true;
+        inner.view.redraw(cx);
+        true
+    }
+
+    /// Set or clear a panel's title icon (SVG path)
+    pub fn set_panel_icon(&self, cx: &mut Cx, panel_id: &str, icon: Option<&str>) {
+        self.update_decoration(cx, panel_id, |decoration| decoration.icon = icon.map(str::to_string));
+    }
+
+    /// Set or clear a panel's badge (e.g. an error count)
+    pub fn set_panel_badge(&self, cx: &mut Cx, panel_id: &str, badge: Option<&str>) {
+        self.update_decoration(cx, panel_id, |decoration| decoration.badge = badge.map(str::to_string));
+    }
+
+    /// Show or hide a panel's "modified" dot
+    pub fn set_panel_dirty(&self, cx: &mut Cx, panel_id: &str, dirty: bool) {
+        self.update_decoration(cx, panel_id, |decoration| decoration.dirty = dirty);
+    }
+
+    /// Icon, badge and "modified" dot of a panel
+    pub fn panel_decoration(&self, panel_id: &str) -> PanelDecoration {
+        self.borrow()
+            .and_then(|inner| inner.decorations.get(panel_id).cloned())
+            .unwrap_or_default()
+    }
+
+    fn update_decoration(&self, cx: &mut Cx, panel_id: &str, update: impl FnOnce(&mut PanelDecoration)) {
+        if let Some(mut inner) = self.borrow_mut() {
+            update(inner.decorations.entry(PanelId::new(panel_id)).or_default());
+            inner.needs_layout_update = true;
+            inner.view.redraw(cx);
+        }
+    }
+
+    /// Set the panels whose close button asks for confirmation first
+    pub fn set_confirm_close_panels(&self, panel_ids: Vec<String>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.confirm_close = panel_ids.iter().map(PanelId::from).collect();
+        }
+    }
+
     /// Reset layout to default state
     pub fn reset_layout(&self, cx: &mut Cx) {
         if let Some(mut inner) = self.borrow_mut() {
             inner.initialize_slots();
             inner.fullscreen_panel = None;
-            inner.dragging_panel = None;
-            inner.drop_target = None;
+            inner.pinned_panels.clear();
+            inner.closed_panels.clear();
+            inner.end_drag(cx);
             inner.needs_layout_update = true;
             inner.view.redraw(cx);
         } else {
@@ -730,24 +1145,24 @@ impl FooterGridRef {
         }
     }
 
+    /// Apply the built-in palettes blended by a dark mode value
     pub fn apply_dark_mode(&self, cx: &mut Cx, dark_mode: f64) {
-        if let Some(mut inner) = self.borrow_mut() {
-            inner.view.apply_over(cx, live! {
-                draw_bg: { dark_mode: (dark_mode) }
-            });
+        self.apply_palette(cx, &ShellPalette::for_dark_mode(dark_mode));
+    }
 
-            inner.view.view(id!(panel_strip_content)).apply_over(cx, live! {
-                draw_bg: { dark_mode: (dark_mode) }
-            });
+    /// Apply a palette to this grid, its sidebar and all panels
+    pub fn apply_palette(&self, cx: &mut Cx, palette: &ShellPalette) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.apply_palette_internal(cx, palette);
 
-            inner.view.shell_sidebar(id!(controller_content)).apply_dark_mode(cx, dark_mode);
+            inner.view.shell_sidebar(id!(controller_content)).apply_palette(cx, palette);
 
             // Apply to all panel slots (p0-p4 in each slot)
             let slot_ids = FooterGrid::slot_ids();
             let panel_slot_ids = FooterGrid::panel_slot_ids();
             for slot_id in &slot_ids {
                 for p_slot_id in &panel_slot_ids {
-                    inner.view.view(*slot_id).panel(*p_slot_id).apply_dark_mode(cx, dark_mode);
+                    inner.view.view(*slot_id).panel(*p_slot_id).apply_palette(cx, palette);
                 }
             }
         }
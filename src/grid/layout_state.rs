@@ -4,6 +4,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
+use crate::panel::PanelId;
 
 /// Layout mode for the panel grid
 #[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
@@ -34,14 +35,14 @@ impl LayoutMode {
 /// Serializable layout state for persistence
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct LayoutState {
-    /// Panel assignments per row (by semantic string ID)
-    pub row_assignments: Vec<Vec<String>>,
+    /// Panel assignments per row
+    pub row_assignments: Vec<Vec<PanelId>>,
 
-    /// Which panels are visible (semantic string IDs)
-    pub visible_panels: HashSet<String>,
+    /// Which panels are visible
+    pub visible_panels: HashSet<PanelId>,
 
     /// Currently maximized panel (if any)
-    pub maximized_panel: Option<String>,
+    pub maximized_panel: Option<PanelId>,
 
     /// Current layout mode
     pub layout_mode: LayoutMode,
@@ -51,7 +52,19 @@ pub struct LayoutState {
 
     /// Panel titles by panel_id
     #[serde(default)]
-    pub panel_titles: HashMap<String, String>,
+    pub panel_titles: HashMap<PanelId, String>,
+
+    /// Minimized panels in the order they were minimized
+    ///
+    /// They stay visible and keep their row position; the grid shows them as chips.
+    #[serde(default)]
+    pub minimized_panels: Vec<PanelId>,
+
+    /// Panels the user pinned in place
+    ///
+    /// A pinned panel can't be dragged or closed, and drops that would shift it are refused.
+    #[serde(default)]
+    pub pinned_panels: Vec<PanelId>,
 }
 
 impl Default for LayoutState {
@@ -62,11 +75,13 @@ impl Default for LayoutState {
                 vec!["panel_3".into(), "panel_4".into(), "panel_5".into()],
                 vec!["panel_6".into(), "panel_7".into(), "panel_8".into()],
             ],
-            visible_panels: (0..9).map(|i| format!("panel_{}", i)).collect(),
+            visible_panels: (0..9).map(|i| PanelId::from(format!("panel_{}", i))).collect(),
             maximized_panel: None,
             layout_mode: LayoutMode::AutoGrid,
             selected_tab: 0,
             panel_titles: HashMap::new(),
+            minimized_panels: Vec::new(),
+            pinned_panels: Vec::new(),
         }
     }
 }
@@ -75,14 +90,14 @@ impl LayoutState {
     /// Create a new layout state with the given number of panels
     pub fn with_panel_count(count: usize) -> Self {
         let mut state = Self::default();
-        state.visible_panels = (0..count).map(|i| format!("panel_{}", i)).collect();
+        state.visible_panels = (0..count).map(|i| PanelId::from(format!("panel_{}", i))).collect();
 
         // Distribute panels across rows (roughly equal)
         let panels_per_row = (count + 2) / 3; // Ceiling division by 3
         state.row_assignments = vec![Vec::new(), Vec::new(), Vec::new()];
 
         for i in 0..count {
-            let panel_id = format!("panel_{}", i);
+            let panel_id = PanelId::from(format!("panel_{}", i));
             let row = i / panels_per_row;
             if row < 3 {
                 state.row_assignments[row].push(panel_id);
@@ -102,6 +117,41 @@ impl LayoutState {
         self.visible_panels.contains(panel_id)
     }
 
+    /// Check if a panel is minimized
+    pub fn is_minimized(&self, panel_id: &str) -> bool {
+        self.minimized_panels.iter().any(|id| id == panel_id)
+    }
+
+    /// Check if a panel takes a slot on screen (visible and not minimized)
+    pub fn is_shown(&self, panel_id: &str) -> bool {
+        self.is_visible(panel_id) && !self.is_minimized(panel_id)
+    }
+
+    /// Check if a panel is pinned
+    pub fn is_pinned(&self, panel_id: &str) -> bool {
+        self.pinned_panels.iter().any(|id| id == panel_id)
+    }
+
+    /// Pin or unpin a panel, returning false if it already was
+    pub fn set_pinned(&mut self, panel_id: &str, pinned: bool) -> bool {
+        if self.is_pinned(panel_id) == pinned {
+            return false;
+        }
+        if pinned {
+            self.pinned_panels.push(PanelId::new(panel_id));
+        } else {
+            self.pinned_panels.retain(|id| id != panel_id);
+        }
+        true
+    }
+
+    /// Row and column where a panel is shown (minimized and closed panels are skipped)
+    pub fn shown_position(&self, panel_id: &str) -> Option<(usize, usize)> {
+        let (row, _) = self.find_panel_row(panel_id)?;
+        let col = self.visible_in_row(row).iter().position(|id| id == panel_id)?;
+        Some((row, col))
+    }
+
     /// Find which row contains a panel
     pub fn find_panel_row(&self, panel_id: &str) -> Option<(usize, usize)> {
         for (row_idx, row) in self.row_assignments.iter().enumerate() {
@@ -112,15 +162,15 @@ impl LayoutState {
         None
     }
 
-    /// Get visible panels in a specific row
-    pub fn visible_in_row(&self, row: usize) -> Vec<String> {
+    /// Get the panels shown in a specific row (minimized panels are left out)
+    pub fn visible_in_row(&self, row: usize) -> Vec<PanelId> {
         if row >= self.row_assignments.len() {
             return vec![];
         }
         self.row_assignments[row]
             .iter()
-            .filter(|id| self.visible_panels.contains(*id))
-            .cloned()
+            .filter(|id| self.is_shown(id))
+            .copied()
             .collect()
     }
 
@@ -139,6 +189,49 @@ impl LayoutState {
         if self.maximized_panel.as_deref() == Some(panel_id) {
             self.maximized_panel = None;
         }
+        self.minimized_panels.retain(|id| id != panel_id);
+    }
+
+    /// Closed panels in row order (they keep their slot until reopened)
+    pub fn closed_panels(&self) -> Vec<PanelId> {
+        self.row_assignments.iter().flatten()
+            .filter(|id| !self.is_visible(id))
+            .copied()
+            .collect()
+    }
+
+    /// Show a closed panel again in its slot
+    ///
+    /// Returns false if the panel isn't in the grid or isn't closed.
+    pub fn reopen_panel(&mut self, panel_id: &str) -> bool {
+        if self.find_panel_row(panel_id).is_none() || self.is_visible(panel_id) {
+            return false;
+        }
+        self.visible_panels.insert(PanelId::new(panel_id));
+        true
+    }
+
+    /// Minimize a shown panel, keeping its row position
+    ///
+    /// Returns false if the panel isn't visible or is already minimized.
+    pub fn minimize_panel(&mut self, panel_id: &str) -> bool {
+        if !self.is_shown(panel_id) {
+            return false;
+        }
+        if self.maximized_panel.as_deref() == Some(panel_id) {
+            self.maximized_panel = None;
+        }
+        self.minimized_panels.push(PanelId::new(panel_id));
+        true
+    }
+
+    /// Show a minimized panel again at its row position
+    ///
+    /// Returns false if the panel isn't minimized.
+    pub fn restore_panel(&mut self, panel_id: &str) -> bool {
+        let before = self.minimized_panels.len();
+        self.minimized_panels.retain(|id| id != panel_id);
+        self.minimized_panels.len() != before
     }
 
     /// Move a panel from one position to another
@@ -171,7 +264,7 @@ impl LayoutState {
         }
 
         // Insert at target position
-        self.row_assignments[target_row].insert(insert_col, panel_id.to_string());
+        self.row_assignments[target_row].insert(insert_col, PanelId::new(panel_id));
     }
 
     /// Get the title for a panel
@@ -181,7 +274,7 @@ impl LayoutState {
 
     /// Set the title for a panel
     pub fn set_panel_title(&mut self, panel_id: &str, title: &str) {
-        self.panel_titles.insert(panel_id.to_string(), title.to_string());
+        self.panel_titles.insert(PanelId::new(panel_id), title.to_string());
     }
 }
 
@@ -207,14 +300,22 @@ impl Default for SplitterPositions {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct FooterSlotState {
     pub visible: bool,
-    pub panel_ids: Vec<String>,
+    pub panel_ids: Vec<PanelId>,
 }
 
 /// Footer grid layout state for persistence
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct FooterLayoutState {
     pub slots: Vec<FooterSlotState>,
-    pub fullscreen_panel: Option<String>,
+    pub fullscreen_panel: Option<PanelId>,
+
+    /// Panels the user pinned in place (see `LayoutState::pinned_panels`)
+    #[serde(default)]
+    pub pinned_panels: Vec<PanelId>,
+
+    /// Closed panels, most recently closed last (they can be reopened)
+    #[serde(default)]
+    pub closed_panels: Vec<PanelId>,
 }
 
 impl Default for FooterLayoutState {
@@ -222,9 +323,75 @@ impl Default for FooterLayoutState {
         Self {
             slots: (0..7).map(|i| FooterSlotState {
                 visible: true,
-                panel_ids: vec![format!("footer_panel_{}", i)],
+                panel_ids: vec![PanelId::from(format!("footer_panel_{}", i))],
             }).collect(),
             fullscreen_panel: None,
+            pinned_panels: Vec::new(),
+            closed_panels: Vec::new(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minimize_keeps_row_position() {
+        let mut state = LayoutState::default();
+        state.maximized_panel = Some("panel_1".into());
+
+        assert!(state.minimize_panel("panel_1"));
+        assert!(!state.minimize_panel("panel_1"), "already minimized");
+        assert_eq!(state.maximized_panel, None);
+        assert_eq!(state.visible_in_row(0), vec![PanelId::new("panel_0"), PanelId::new("panel_2")]);
+        assert_eq!(state.find_panel_row("panel_1"), Some((0, 1)));
+
+        assert!(state.restore_panel("panel_1"));
+        assert!(!state.restore_panel("panel_1"));
+        assert_eq!(state.visible_in_row(0).len(), 3);
+    }
+
+    #[test]
+    fn test_closing_a_minimized_panel_drops_its_chip() {
+        let mut state = LayoutState::default();
+        state.minimize_panel("panel_4");
+        state.close_panel("panel_4");
+
+        assert!(state.minimized_panels.is_empty());
+        assert!(!state.minimize_panel("panel_4"), "closed panels can't be minimized");
+    }
+
+    #[test]
+    fn test_reopen_panel_in_its_slot() {
+        let mut state = LayoutState::default();
+        state.close_panel("panel_4");
+        state.close_panel("panel_1");
+
+        assert_eq!(state.closed_panels(), vec![PanelId::new("panel_1"), PanelId::new("panel_4")]);
+        assert!(state.reopen_panel("panel_4"));
+        assert!(!state.reopen_panel("panel_4"), "already open");
+        assert!(!state.reopen_panel("unknown"));
+        assert_eq!(state.shown_position("panel_4"), Some((1, 1)));
+        assert_eq!(state.closed_panels(), vec![PanelId::new("panel_1")]);
+    }
+
+    #[test]
+    fn test_moving_a_panel_past_a_pinned_one_shifts_it() {
+        let mut state = LayoutState::default();
+        assert!(state.set_pinned("panel_1", true));
+        assert!(!state.set_pinned("panel_1", true), "already pinned");
+
+        let mut moved = state.clone();
+        moved.move_panel("panel_2", 0, 0);
+        assert_eq!(moved.shown_position("panel_1"), Some((0, 2)));
+
+        let mut moved = state.clone();
+        moved.move_panel("panel_0", 1, 0);
+        assert_eq!(moved.shown_position("panel_1"), Some((0, 0)), "removing a panel before it shifts it too");
+
+        let mut moved = state;
+        moved.move_panel("panel_3", 2, 0);
+        assert_eq!(moved.shown_position("panel_1"), Some((0, 1)));
+    }
+}
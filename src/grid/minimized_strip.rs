@@ -0,0 +1,284 @@
+//! Strip of minimized panels
+//!
+//! `PanelGrid` shows a chip for each minimized panel along its bottom edge.
+//! The panel keeps its row, column and content while minimized; clicking its
+//! chip emits `MinimizedStripAction::Restore` and the grid shows it again.
+//! Chips carry the panel's icon, badge and "modified" dot like its title bar.
+
+use makepad_widgets::*;
+use crate::panel::{PanelDecoration, PanelId};
+use crate::shell::sidebar_menu::svg_icon_value;
+use crate::theme::current_palette;
+
+live_design! {
+    use link::theme::*;
+    use link::shaders::*;
+    use link::widgets::*;
+
+    use crate::live_design::*;
+
+    // Chip: icon, title, "modified" dot and badge of the minimized panel
+    pub MinimizedChip = <View> {
+        width: Fit, height: 22
+        padding: { left: 8, right: 10 }
+        margin: { right: 6 }
+        flow: Right
+        align: { y: 0.5 }
+
+        show_bg: true
+        draw_bg: {
+            instance hover: 0.0
+            uniform bg_panel: (PALETTE_BG_PANEL)
+            uniform bg_hover: (PALETTE_BG_HOVER)
+            uniform border: (PALETTE_BORDER)
+
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0.5, 0.5, self.rect_size.x - 1.0, self.rect_size.y - 1.0, 4.0);
+                sdf.fill_keep(mix(self.bg_panel, self.bg_hover, self.hover));
+                sdf.stroke(self.border, 1.0);
+                return sdf.result;
+            }
+        }
+
+        icon = <Icon> {
+            visible: false
+            margin: { right: 6 }
+            icon_walk: { width: 12, height: 12 }
+            draw_icon: {
+                uniform icon: (PALETTE_ICON)
+                fn get_color(self) -> vec4 {
+                    return self.icon;
+                }
+            }
+        }
+
+        label = <Label> {
+            draw_text: {
+                uniform text_secondary: (PALETTE_TEXT_SECONDARY)
+                text_style: <FONT_MEDIUM> { font_size: 10.0 }
+                fn get_color(self) -> vec4 {
+                    return self.text_secondary;
+                }
+            }
+            text: ""
+        }
+
+        dirty_dot = <View> {
+            width: 6, height: 6
+            margin: { left: 6 }
+            visible: false
+
+            show_bg: true
+            draw_bg: {
+                uniform text_secondary: (PALETTE_TEXT_SECONDARY)
+                fn pixel(self) -> vec4 {
+                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                    sdf.circle(self.rect_size.x * 0.5, self.rect_size.y * 0.5, self.rect_size.x * 0.5);
+                    sdf.fill(self.text_secondary);
+                    return sdf.result;
+                }
+            }
+        }
+
+        badge = <View> {
+            width: Fit, height: Fit
+            margin: { left: 6 }
+            padding: { left: 4, right: 4, top: 1, bottom: 1 }
+            visible: false
+
+            show_bg: true
+            draw_bg: {
+                uniform accent: (PALETTE_ACCENT)
+                fn pixel(self) -> vec4 {
+                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                    sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, self.rect_size.y * 0.5);
+                    sdf.fill(self.accent);
+                    return sdf.result;
+                }
+            }
+
+            badge_label = <Label> {
+                draw_text: {
+                    uniform on_accent: (PALETTE_ON_ACCENT)
+                    text_style: <FONT_SEMIBOLD> { font_size: 8.0 }
+                    fn get_color(self) -> vec4 {
+                        return self.on_accent;
+                    }
+                }
+                text: ""
+            }
+        }
+    }
+
+    pub MinimizedStrip = {{MinimizedStrip}} {
+        width: Fill, height: Fit
+        flow: Right
+        padding: { left: 6, right: 6, top: 4, bottom: 4 }
+        align: { y: 0.5 }
+
+        draw_bg: {
+            uniform bg_title_bar: (PALETTE_BG_TITLE_BAR)
+            fn pixel(self) -> vec4 {
+                return self.bg_title_bar;
+            }
+        }
+
+        chip_template: <MinimizedChip> {}
+    }
+}
+
+/// A minimized panel shown as a chip
+#[derive(Clone, Debug, PartialEq)]
+pub struct MinimizedPanel {
+    /// Semantic panel ID
+    pub id: PanelId,
+
+    /// Title shown on the chip
+    pub title: String,
+
+    /// Icon, badge and "modified" dot shown with the title
+    pub decoration: PanelDecoration,
+}
+
+/// Actions emitted by `MinimizedStrip`
+#[derive(Clone, Debug, DefaultNone)]
+pub enum MinimizedStripAction {
+    /// A chip was clicked: restore this panel
+    Restore(PanelId),
+
+    None,
+}
+
+/// Row of chips for the minimized panels (draws nothing when there are none)
+#[derive(Live, LiveHook, Widget)]
+pub struct MinimizedStrip {
+    #[redraw]
+    #[live]
+    draw_bg: DrawColor,
+
+    #[walk]
+    walk: Walk,
+
+    #[layout]
+    layout: Layout,
+
+    #[live]
+    chip_template: Option<LivePtr>,
+
+    #[rust]
+    panels: Vec<MinimizedPanel>,
+
+    #[rust]
+    chips: ComponentMap<LiveId, WidgetRef>,
+}
+
+impl Widget for MinimizedStrip {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if self.panels.is_empty() {
+            return;
+        }
+        let uid = self.widget_uid();
+        let mut clicked = None;
+        for panel in &self.panels {
+            let Some(chip) = self.chips.get(&LiveId::from_str(&panel.id)) else { continue };
+            match event.hits(cx, chip.area()) {
+                Hit::FingerHoverIn(_) => {
+                    cx.set_cursor(MouseCursor::Hand);
+                    chip.apply_over(cx, live! { draw_bg: { hover: 1.0 } });
+                    chip.redraw(cx);
+                }
+                Hit::FingerHoverOut(_) => {
+                    chip.apply_over(cx, live! { draw_bg: { hover: 0.0 } });
+                    chip.redraw(cx);
+                }
+                Hit::FingerUp(fe) if fe.is_over => clicked = Some(panel.id),
+                _ => {}
+            }
+        }
+        if let Some(panel_id) = clicked {
+            cx.widget_action(uid, &scope.path, MinimizedStripAction::Restore(panel_id));
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if self.panels.is_empty() {
+            return DrawStep::done();
+        }
+        let palette = current_palette();
+        let bg = palette.live_nodes(&[live_id!(draw_bg)]);
+        let icon = palette.live_nodes(&[live_id!(draw_icon)]);
+        let text = palette.live_nodes(&[live_id!(draw_text)]);
+        self.draw_bg.apply_over(cx, &palette.uniform_nodes());
+
+        self.draw_bg.begin(cx, walk, self.layout);
+        for panel in &self.panels {
+            if let Some(chip) = self.chips.get(&LiveId::from_str(&panel.id)) {
+                chip.apply_over(cx, &bg);
+                chip.widget(id!(icon)).apply_over(cx, &icon);
+                chip.label(id!(label)).apply_over(cx, &text);
+                chip.view(id!(dirty_dot)).apply_over(cx, &bg);
+                chip.view(id!(badge)).apply_over(cx, &bg);
+                chip.label(id!(badge.badge_label)).apply_over(cx, &text);
+                chip.draw_all(cx, scope);
+            }
+        }
+        self.draw_bg.end(cx);
+
+        DrawStep::done()
+    }
+}
+
+impl MinimizedStrip {
+    /// Replace the minimized panels (unchanged panels keep their chips)
+    pub fn set_panels(&mut self, cx: &mut Cx, panels: Vec<MinimizedPanel>) {
+        if self.panels == panels {
+            return;
+        }
+        let template = self.chip_template;
+        for panel in &panels {
+            let chip = self.chips.get_or_insert(cx, LiveId::from_str(&panel.id), |cx| {
+                WidgetRef::new_from_ptr(cx, template)
+            });
+            chip.label(id!(label)).set_text(cx, &panel.title);
+            let decoration = &panel.decoration;
+            let icon_widget = chip.widget(id!(icon));
+            if let Some(icon) = &decoration.icon {
+                let icon = svg_icon_value(icon);
+                icon_widget.apply_over(cx, live! {
+                    draw_icon: { svg_file: (icon) }
+                });
+            }
+            icon_widget.set_visible(cx, decoration.icon.is_some());
+            chip.view(id!(dirty_dot)).set_visible(cx, decoration.dirty);
+            chip.view(id!(badge)).set_visible(cx, decoration.badge.is_some());
+            chip.label(id!(badge.badge_label)).set_text(cx, decoration.badge.as_deref().unwrap_or(""));
+        }
+        self.chips.retain(|id, _| panels.iter().any(|panel| LiveId::from_str(&panel.id) == *id));
+
+        self.panels = panels;
+        self.redraw(cx);
+    }
+
+    /// Current minimized panels in chip order
+    pub fn panels(&self) -> &[MinimizedPanel] {
+        &self.panels
+    }
+}
+
+impl MinimizedStripRef {
+    /// Replace the minimized panels
+    pub fn set_panels(&self, cx: &mut Cx, panels: Vec<MinimizedPanel>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_panels(cx, panels);
+        }
+    }
+
+    /// Returns the panel ID of a clicked chip
+    pub fn restored(&self, actions: &Actions) -> Option<PanelId> {
+        match actions.find_widget_action(self.widget_uid()).cast() {
+            MinimizedStripAction::Restore(panel_id) => Some(panel_id),
+            _ => None,
+        }
+    }
+}
@@ -5,10 +5,14 @@
 
 mod drop_handler;
 mod layout_state;
+mod reflow;
+mod size_limits;
 pub mod panel_grid;
 pub mod footer_grid;
+pub mod minimized_strip;
 
-pub use drop_handler::DropPosition;
+pub use drop_handler::{DropPosition, DropTarget, DropValidator};
+pub use size_limits::{SizeLimits, fit_sizes};
 pub use layout_state::{LayoutMode, LayoutState, SplitterPositions, FooterLayoutState, FooterSlotState};
 pub use panel_grid::{PanelGrid, PanelGridRef, PanelGridWidgetRefExt};
 pub use footer_grid::{FooterGrid, FooterGridRef, FooterGridWidgetRefExt};
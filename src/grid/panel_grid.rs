@@ -5,17 +5,23 @@
 use std::cell::RefCell;
 use std::collections::HashMap;
 use makepad_widgets::*;
-use crate::panel::PanelAction;
+use crate::panel::{PanelAction, PanelDecoration, PanelId, PanelTitleAction};
+use crate::panel::title_actions::set_action_checked;
 use crate::panel::panel::PanelWidgetExt;
-use crate::grid::drop_handler::{DropPosition, calculate_drop_position};
+use crate::registry::PanelDefinition;
+use crate::grid::drop_handler::{DropPosition, DropTarget, DropValidator, calculate_drop_position, drop_allowed};
 use crate::grid::layout_state::LayoutState;
-use crate::theme::get_global_dark_mode;
+use crate::grid::reflow::{DragGhost, reflow_offsets, slot_rects};
+use crate::grid::size_limits::{SizeLimits, fit_sizes};
+use crate::grid::minimized_strip::{MinimizedPanel, MinimizedStripWidgetExt};
+use crate::theme::{ShellPalette, ThemeTracker, ANIMATION_DURATION, ease_out_cubic};
 
 // Thread-local storage for pending state (used when methods are called before first draw)
 thread_local! {
     static PENDING_LAYOUT: RefCell<Option<LayoutState>> = RefCell::new(None);
     static PENDING_RESET: RefCell<bool> = RefCell::new(false);
-    static PENDING_TITLES: RefCell<HashMap<String, String>> = RefCell::new(HashMap::new());
+    static PENDING_TITLES: RefCell<HashMap<PanelId, String>> = RefCell::new(HashMap::new());
+    static PENDING_LOCKED: RefCell<Option<bool>> = RefCell::new(None);
 }
 
 live_design! {
@@ -23,8 +29,11 @@ live_design! {
     use link::shaders::*;
     use link::widgets::*;
 
+    use crate::live_design::*;
+
     // Import Panel widget - must use crate path for cross-module visibility
     use crate::panel::panel::Panel;
+    use crate::grid::minimized_strip::MinimizedStrip;
 
     // ========================================
     // PANEL GRID WIDGET
@@ -34,16 +43,14 @@ live_design! {
         width: Fill
         height: Fill
         padding: 0
+        flow: Down
         cursor: Default
 
         show_bg: true
         draw_bg: {
-            instance dark_mode: 0.0
+            uniform bg_canvas: (PALETTE_BG_CANVAS)
             fn pixel(self) -> vec4 {
-                // Light: slate-200, Dark: slate-900
-                let light = vec4(0.886, 0.910, 0.941, 1.0);
-                let dark = vec4(0.059, 0.090, 0.165, 1.0);
-                return mix(light, dark, self.dark_mode);
+                return self.bg_canvas;
             }
         }
 
@@ -53,6 +60,35 @@ live_design! {
             color: #4080c080
         }
 
+        // Drop preview where the panel can't go (it would break a minimum size or move a pinned panel)
+        drop_preview_blocked: {
+            draw_depth: 10.0
+            color: #d0404080
+        }
+
+        // Copy of the dragged panel's title bar that follows the cursor
+        drag_ghost: {
+            draw_depth: 11.0
+            uniform bg_title_bar: (PALETTE_BG_TITLE_BAR)
+            uniform accent: (PALETTE_ACCENT)
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0.5, 0.5, self.rect_size.x - 1.0, self.rect_size.y - 1.0, 4.0);
+                sdf.fill_keep(vec4(self.bg_title_bar.rgb, 0.8));
+                sdf.stroke(self.accent, 1.0);
+                return sdf.result;
+            }
+        }
+
+        drag_ghost_title: {
+            draw_depth: 11.0
+            uniform text_primary: (PALETTE_TEXT_PRIMARY)
+            text_style: <FONT_MEDIUM> { font_size: 11.0 }
+            fn get_color(self) -> vec4 {
+                return vec4(self.text_primary.rgb, 0.9);
+            }
+        }
+
         // Container with explicit row structure for precise layout
         // Each row has 9 slots to allow all panels in one row if desired
         window_container = <View> {
@@ -65,6 +101,9 @@ live_design! {
                 width: Fill
                 height: Fill
                 flow: Right
+                // Panels moving between rows are drawn outside their row while they glide
+                clip_x: false
+                clip_y: false
 
                 s1_1 = <Panel> { width: Fill, height: Fill }
                 s1_2 = <Panel> { width: Fill, height: Fill }
@@ -82,6 +121,8 @@ live_design! {
                 width: Fill
                 height: Fill
                 flow: Right
+                clip_x: false
+                clip_y: false
 
                 s2_1 = <Panel> { width: Fill, height: Fill }
                 s2_2 = <Panel> { width: Fill, height: Fill }
@@ -99,6 +140,8 @@ live_design! {
                 width: Fill
                 height: Fill
                 flow: Right
+                clip_x: false
+                clip_y: false
 
                 s3_1 = <Panel> { width: Fill, height: Fill }
                 s3_2 = <Panel> { width: Fill, height: Fill }
@@ -111,6 +154,9 @@ live_design! {
                 s3_9 = <Panel> { width: Fill, height: Fill }
             }
         }
+
+        // Chips of minimized panels along the bottom edge
+        minimized_strip = <MinimizedStrip> {}
     }
 }
 
@@ -121,7 +167,7 @@ live_design! {
 /// Container widget managing a grid of Panel widgets with drag-and-drop support.
 ///
 /// ## Layout Model
-/// Uses `LayoutState` with `row_assignments: Vec<Vec<String>>` as the source of truth.
+/// Uses `LayoutState` with `row_assignments: Vec<Vec<PanelId>>` as the source of truth.
 /// Each row maintains its own list of panel IDs, enabling true physical movement
 /// of panels between rows.
 ///
@@ -137,6 +183,16 @@ pub struct PanelGrid {
     #[live]
     drop_preview: DrawColor,
 
+    #[live]
+    drop_preview_blocked: DrawColor,
+
+    /// Translucent copy of the dragged panel's title bar, following the cursor
+    #[live]
+    drag_ghost: DrawColor,
+
+    #[live]
+    drag_ghost_title: DrawText,
+
     /// Maximum number of rows
     #[live]
     max_rows: usize,
@@ -154,26 +210,96 @@ pub struct PanelGrid {
     #[rust]
     needs_layout_update: bool,
 
-    /// Currently dragging panel ID (semantic string ID)
+    /// Currently dragging panel
     #[rust]
-    dragging_panel: Option<String>,
+    dragging_panel: Option<PanelId>,
 
     /// Current drop target position
     #[rust]
     drop_state: Option<DropPosition>,
 
-    /// Panel titles by panel_id (persists across layout state changes)
+    /// Ghost of the panel being dragged
     #[rust]
-    panel_titles: HashMap<String, String>,
-}
+    ghost: Option<DragGhost>,
+
+    /// Layout shown while dragging: the drop applied, so the other panels make room
+    #[rust]
+    preview_layout: Option<LayoutState>,
+
+    /// Row and slot of each shown panel as last laid out
+    #[rust]
+    shown_slots: HashMap<PanelId, (usize, usize)>,
+
+    /// Panel rects before a layout change, to animate from once it is drawn
+    #[rust]
+    reflow_pending: Option<HashMap<PanelId, Rect>>,
+
+    /// Offset of each moving panel from its slot when the reflow started
+    #[rust]
+    reflow_offsets: HashMap<PanelId, DVec2>,
+
+    #[rust]
+    reflow_animating: bool,
+
+    #[rust]
+    reflow_anim_start: f64,
+
+    /// Panels jump into place instead of gliding
+    #[rust]
+    reduce_motion: bool,
+
+    /// Container size the slot sizes were computed for
+    #[rust]
+    laid_out_size: DVec2,
+
+    /// Panel titles by panel ID (persists across layout state changes)
+    #[rust]
+    panel_titles: HashMap<PanelId, String>,
 
-/// Helper to convert string panel ID to LiveId
-fn panel_id_to_live_id(panel_id: &str) -> LiveId {
-    LiveId::from_str_lc(panel_id)
+    #[rust]
+    theme_tracker: ThemeTracker,
+
+    /// Panels whose close button asks for confirmation first
+    #[rust]
+    confirm_close: Vec<PanelId>,
+
+    /// Registered panel definitions by panel ID (default titles, title actions)
+    #[rust]
+    panel_definitions: HashMap<PanelId, PanelDefinition>,
+
+    /// Title bar actions by panel ID, with their current toggle states
+    #[rust]
+    title_actions: HashMap<PanelId, Vec<PanelTitleAction>>,
+
+    /// Icon, badge and "modified" dot by panel ID
+    #[rust]
+    decorations: HashMap<PanelId, PanelDecoration>,
+
+    /// No panel can be dragged or closed (the header's lock toggle)
+    #[rust]
+    layout_locked: bool,
+
+    /// App hook that can forbid drops
+    #[rust]
+    drop_validator: Option<DropValidator>,
 }
 
 impl Widget for PanelGrid {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if let Event::NextFrame(_) = event {
+            if self.reflow_animating {
+                self.update_reflow_animation(cx);
+            }
+        }
+
+        // Escape cancels a drag: the panel goes back and the layout is unchanged.
+        // An open title editor takes Escape first.
+        if let Event::KeyDown(ke) = event {
+            if ke.key_code == KeyCode::Escape && self.dragging_panel.is_some() && !self.is_editing_title() {
+                self.end_drag(cx);
+            }
+        }
+
         let actions = cx.capture_actions(|cx| {
             self.view.handle_event(cx, event, scope);
         });
@@ -184,66 +310,92 @@ impl Widget for PanelGrid {
         for action in actions.iter() {
             match action.as_widget_action().cast::<PanelAction>() {
                 PanelAction::Close(id) => {
-                    // Find panel by LiveId and close it
-                    if let Some(panel_id) = self.find_panel_by_live_id(id) {
-                        self.close_panel(cx, &panel_id);
-                        layout_changed = true;
+                    // Find panel by ID and close it
+                    if let Some(panel_id) = self.find_panel(id).filter(|id| !self.is_locked(id)) {
+                        if self.confirm_close.contains(&panel_id) {
+                            cx.widget_action(self.widget_uid(), &scope.path, PanelAction::CloseRequested(panel_id));
+                        } else {
+                            self.close_panel(cx, panel_id);
+                            layout_changed = true;
+                        }
+                    }
+                }
+                PanelAction::Minimize(id) => {
+                    if let Some(panel_id) = self.find_panel(id) {
+                        layout_changed |= self.minimize_panel(cx, panel_id);
                     }
                 }
                 PanelAction::Maximize(id) => {
-                    if let Some(panel_id) = self.find_panel_by_live_id(id) {
-                        self.toggle_maximize(cx, &panel_id);
+                    if let Some(panel_id) = self.find_panel(id) {
+                        self.toggle_maximize(cx, panel_id);
                         layout_changed = true;
                     }
                 }
                 PanelAction::Fullscreen(_) => {
                     // Fullscreen is handled by FooterGrid, not main PanelGrid
                 }
+                PanelAction::TitleAction { panel_id, action_id, checked: Some(checked) } => {
+                    // Remember toggle states across layout changes
+                    if let Some(actions) = self.title_actions.get_mut(&panel_id) {
+                        set_action_checked(actions, &action_id, checked);
+                    }
+                }
+                PanelAction::Renamed { panel_id, title } => {
+                    // Keep user titles in the layout state so they persist
+                    self.panel_titles.insert(panel_id, title.clone());
+                    self.layout_state.set_panel_title(&panel_id, &title);
+                    layout_changed = true;
+                }
+                PanelAction::Focus(_) | PanelAction::ContextMenu(..) | PanelAction::MenuCommand { .. }
+                | PanelAction::TitleAction { .. } => {
+                    // Focus, context menus and push actions are handled by ShellLayout
+                }
                 PanelAction::StartDrag(id) => {
-                    if let Some(panel_id) = self.find_panel_by_live_id(id) {
-                        self.dragging_panel = Some(panel_id);
+                    if let Some(panel_id) = self.find_panel(id).filter(|id| !self.is_locked(id)) {
+                        self.start_drag(cx, panel_id);
                     }
                 }
-                PanelAction::EndDrag(id, abs) => {
-                    // Complete the drop operation
-                    if let Some(panel_id) = self.find_panel_by_live_id(id) {
-                        if self.dragging_panel.as_deref() == Some(&panel_id) {
-                            self.handle_drop(cx, abs, &panel_id);
-                            layout_changed = true;
-                        }
+                PanelAction::EndDrag(_, abs) => {
+                    // Complete the drop operation. The drop preview moves panels between
+                    // slots, so the panel widget that started the drag may show another
+                    // panel by now: the drop is always for the dragged one.
+                    if let Some(panel_id) = self.dragging_panel {
+                        layout_changed |= self.handle_drop(cx, abs, panel_id);
                     }
-                    self.dragging_panel = None;
-                    self.drop_state = None;
-                    self.view.redraw(cx);
+                    self.end_drag(cx);
                 }
-                PanelAction::LayoutChanged(_) | PanelAction::FooterLayoutChanged(_) | PanelAction::ResetLayout => {
+                PanelAction::LayoutChanged(_) | PanelAction::FooterLayoutChanged(_) | PanelAction::ResetLayout
+                | PanelAction::CloseRequested(_) => {
                     // Ignore - we emit these or handle via thread-local
                 }
                 PanelAction::None => {}
             }
         }
 
+        if let Some(panel_id) = self.view.minimized_strip(id!(minimized_strip)).restored(&actions) {
+            layout_changed |= self.restore_panel(cx, panel_id);
+        }
+
         // Handle internal drag via hits on the view
         match event.hits_with_capture_overload(cx, self.view.area(), self.dragging_panel.is_some()) {
             Hit::FingerMove(fe) if self.dragging_panel.is_some() => {
-                // Update drop preview based on cursor position
-                if let Some(pos) = self.find_drop_position(cx, fe.abs) {
-                    self.drop_state = Some(pos);
-                } else {
-                    self.drop_state = None;
-                }
-                self.view.redraw(cx);
+                // Move the ghost and preview the drop at the cursor
+                self.update_drag(cx, fe.abs);
             }
-            Hit::FingerUp(_) => {
-                // Clear state on any FingerUp as fallback
-                // (actual drop is handled via EndDrag action from Panel)
-                self.dragging_panel = None;
-                self.drop_state = None;
-                self.view.redraw(cx);
+            Hit::FingerUp(fe) => {
+                // Fallback when no EndDrag arrived (the dragging panel's slot was hidden
+                // by the preview); usually the drop is handled via EndDrag from Panel
+                if let Some(panel_id) = self.dragging_panel {
+                    layout_changed |= self.handle_drop(cx, fe.abs, panel_id);
+                    self.end_drag(cx);
+                }
             }
             _ => {}
         }
 
+        // Forward captured actions so panel content and focus actions reach the shell/app
+        cx.extend_actions(actions);
+
         // Emit layout changed action if needed
         if layout_changed {
             cx.widget_action(
@@ -255,11 +407,12 @@ impl Widget for PanelGrid {
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
-        // Apply global theme on every draw
-        let dm = get_global_dark_mode();
-        self.view.apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-        });
+        // Apply the shell theme when it changed
+        if let Some(theme) = self.theme_tracker.changed() {
+            self.view.apply_over(cx, &theme.palette.live_nodes(&[live_id!(draw_bg)]));
+            self.drag_ghost.apply_over(cx, &theme.palette.uniform_nodes());
+            self.drag_ghost_title.apply_over(cx, &theme.palette.uniform_nodes());
+        }
 
         // Check for pending reset
         let should_reset = PENDING_RESET.with(|p| {
@@ -279,6 +432,11 @@ impl Widget for PanelGrid {
             self.needs_layout_update = true;
         }
 
+        if let Some(locked) = PENDING_LOCKED.with(|p| p.borrow_mut().take()) {
+            self.layout_locked = locked;
+            self.needs_layout_update = true;
+        }
+
         // Initialize on first draw
         if !self.initialized {
             self.initialized = true;
@@ -298,15 +456,42 @@ impl Widget for PanelGrid {
         // Apply layout before drawing
         if self.needs_layout_update {
             self.needs_layout_update = false;
-            self.apply_row_layout(cx);
+            self.stop_reflow_animation(cx);
+            let panel_rects = self.apply_row_layout(cx);
+
+            // Panels moved: start them where they were drawn before and let them glide into place
+            if let Some(from) = self.reflow_pending.take() {
+                self.start_reflow_animation(cx, reflow_offsets(&from, &panel_rects));
+            }
         }
 
         // Draw the main view
         let result = self.view.draw_walk(cx, scope, walk);
 
-        // Draw drop preview overlay if dragging
-        if let Some(ref pos) = self.drop_state {
-            self.drop_preview.draw_abs(cx, pos.rect);
+        // While dragging, mark where the panel lands (its slot in the preview) or where it can't go
+        let landing = self.dragging_panel.and_then(|panel_id| self.panel_rect(cx, panel_id));
+        match self.drop_state {
+            Some(ref pos) if !pos.allowed => self.drop_preview_blocked.draw_abs(cx, pos.rect),
+            _ => {
+                if let Some(rect) = landing {
+                    self.drop_preview.draw_abs(cx, rect);
+                }
+            }
+        }
+
+        if let Some(ghost) = &self.ghost {
+            if let Some(rect) = ghost.rect() {
+                self.drag_ghost.draw_abs(cx, rect);
+                // Inset like the title in the panel's title bar
+                self.drag_ghost_title.draw_abs(cx, rect.pos + dvec2(12.0, 9.0), &ghost.title);
+            }
+        }
+
+        // Slot sizes depend on the container size when panels have size limits
+        let size = self.view.view(id!(window_container)).area().rect(cx).size;
+        if size != self.laid_out_size && self.has_size_limits() {
+            self.needs_layout_update = true;
+            self.view.redraw(cx);
         }
 
         result
@@ -327,7 +512,7 @@ impl PanelGrid {
         // Merge titles from state into PanelGrid's title map
         // This allows setting titles via LayoutState atomically
         for (panel_id, title) in &state.panel_titles {
-            self.panel_titles.insert(panel_id.clone(), title.clone());
+            self.panel_titles.insert(*panel_id, title.clone());
         }
         self.layout_state = state;
         self.initialized = true;
@@ -335,39 +520,73 @@ impl PanelGrid {
         self.view.redraw(cx);
     }
 
-    /// Find panel string ID by LiveId (reverse lookup through visible panels)
-    fn find_panel_by_live_id(&self, id: LiveId) -> Option<String> {
-        // Check all visible panels for matching LiveId
-        for panel_id in &self.layout_state.visible_panels {
-            if panel_id_to_live_id(panel_id) == id {
-                return Some(panel_id.clone());
-            }
-        }
-        None
+    /// The panel if it belongs to this grid (hidden and minimized ones included)
+    fn find_panel(&self, id: PanelId) -> Option<PanelId> {
+        self.layout_state.find_panel_row(&id).map(|_| id)
+    }
+
+    /// Whether a panel belongs to this grid (hidden and minimized ones included)
+    fn has_panel(&self, panel_id: &str) -> bool {
+        self.layout_state.find_panel_row(panel_id).is_some()
     }
 
     /// Close a panel
-    fn close_panel(&mut self, cx: &mut Cx, panel_id: &str) {
+    fn close_panel(&mut self, cx: &mut Cx, panel_id: PanelId) {
         self.layout_state.close_panel(panel_id);
         self.needs_layout_update = true;
         self.view.redraw(cx);
     }
 
+    /// Show a closed panel again in its slot; false if it isn't closed
+    fn reopen_panel(&mut self, cx: &mut Cx, panel_id: PanelId) -> bool {
+        if !self.layout_state.reopen_panel(&panel_id) {
+            return false;
+        }
+        self.needs_layout_update = true;
+        self.view.redraw(cx);
+        true
+    }
+
+    /// Collapse a panel into a chip, keeping its slot; false if it isn't shown
+    fn minimize_panel(&mut self, cx: &mut Cx, panel_id: PanelId) -> bool {
+        if !self.layout_state.minimize_panel(&panel_id) {
+            return false;
+        }
+        self.needs_layout_update = true;
+        self.view.redraw(cx);
+        true
+    }
+
+    /// Show a minimized panel again in its slot; false if it isn't minimized
+    fn restore_panel(&mut self, cx: &mut Cx, panel_id: PanelId) -> bool {
+        if !self.layout_state.restore_panel(&panel_id) {
+            return false;
+        }
+        self.needs_layout_update = true;
+        self.view.redraw(cx);
+        true
+    }
+
     /// Toggle maximize state for a panel
-    fn toggle_maximize(&mut self, cx: &mut Cx, panel_id: &str) {
-        if self.layout_state.maximized_panel.as_deref() == Some(panel_id) {
+    fn toggle_maximize(&mut self, cx: &mut Cx, panel_id: PanelId) {
+        if self.layout_state.maximized_panel == Some(panel_id) {
             self.layout_state.maximized_panel = None;
         } else {
-            self.layout_state.maximized_panel = Some(panel_id.to_string());
+            self.layout_state.restore_panel(&panel_id);
+            self.layout_state.maximized_panel = Some(panel_id);
         }
         self.needs_layout_update = true;
         self.view.redraw(cx);
     }
 
     /// Find the drop position based on cursor location
-    fn find_drop_position(&self, cx: &Cx, abs: DVec2) -> Option<DropPosition> {
+    ///
+    /// The position isn't `allowed` when the move would shift a pinned panel,
+    /// break a panel's minimum size unless the layout already doesn't fit and gets
+    /// no tighter, or when the app's drop validator refuses it.
+    fn find_drop_position(&self, cx: &Cx, abs: DVec2, dragged_panel_id: PanelId) -> Option<DropPosition> {
         // Get visible panels per row
-        let rows_with_panels: Vec<Vec<String>> = (0..3)
+        let rows_with_panels: Vec<Vec<PanelId>> = (0..3)
             .map(|r| self.layout_state.visible_in_row(r))
             .filter(|row| !row.is_empty())
             .collect();
@@ -384,20 +603,201 @@ impl PanelGrid {
         let container = self.view.view(id!(window_container));
         let container_rect = container.area().rect(cx);
 
-        calculate_drop_position(abs, container_rect, &rows_with_panels, &row_to_actual)
+        let mut pos = calculate_drop_position(abs, container_rect, &rows_with_panels, &row_to_actual)?;
+
+        let mut moved = self.layout_state.clone();
+        moved.move_panel(&dragged_panel_id, pos.row, pos.col);
+        let limits = (self.grid_limits(&self.layout_state), self.grid_limits(&moved), container_rect.size);
+        pos.allowed = drop_allowed(
+            Some(limits),
+            self.pinned_moves(&moved),
+            self.drop_validator.as_ref(),
+            &dragged_panel_id,
+            &DropTarget::Grid { row: pos.row, col: pos.col },
+        );
+        Some(pos)
     }
 
-    /// Handle a drop operation - move panel to new row/position
-    fn handle_drop(&mut self, cx: &mut Cx, abs: DVec2, dragged_panel_id: &str) {
-        let Some(drop_pos) = self.find_drop_position(cx, abs) else {
-            return;
+    /// Whether a panel is pinned by the user or by its definition
+    fn is_pinned(&self, panel_id: &str) -> bool {
+        self.layout_state.is_pinned(panel_id)
+            || self.panel_definitions.get(panel_id).map_or(false, |def| def.pinned)
+    }
+
+    /// Whether a panel can't be dragged or closed
+    fn is_locked(&self, panel_id: &str) -> bool {
+        self.layout_locked || self.is_pinned(panel_id)
+    }
+
+    /// Row and column each pinned panel is shown at now and in `moved`
+    fn pinned_moves(&self, moved: &LayoutState) -> Vec<(Option<(usize, usize)>, Option<(usize, usize)>)> {
+        self.layout_state.row_assignments.iter().flatten()
+            .filter(|panel_id| self.is_pinned(panel_id))
+            .map(|panel_id| (self.layout_state.shown_position(panel_id), moved.shown_position(panel_id)))
+            .collect()
+    }
+
+    /// Size limits of a panel from its definition
+    fn panel_limits(&self, panel_id: &str) -> SizeLimits {
+        self.panel_definitions.get(panel_id).map(|def| def.size_limits).unwrap_or_default()
+    }
+
+    /// Limits of a row of panels side by side
+    fn row_limits(&self, panel_ids: &[PanelId]) -> SizeLimits {
+        SizeLimits::side_by_side(panel_ids.iter().map(|panel_id| self.panel_limits(panel_id)))
+    }
+
+    /// Limits of the shown panels of a layout, rows stacked
+    fn grid_limits(&self, state: &LayoutState) -> SizeLimits {
+        SizeLimits::stacked((0..3).map(|row| self.row_limits(&state.visible_in_row(row))))
+    }
+
+    /// Whether any registered panel declares a minimum or maximum size
+    fn has_size_limits(&self) -> bool {
+        self.panel_definitions.values().any(|def| def.size_limits != SizeLimits::default())
+    }
+
+    /// Handle a drop operation - move panel to new row/position; false if it can't go there
+    fn handle_drop(&mut self, cx: &mut Cx, abs: DVec2, dragged_panel_id: PanelId) -> bool {
+        let Some(drop_pos) = self.find_drop_position(cx, abs, dragged_panel_id) else {
+            return false;
         };
+        if !drop_pos.allowed {
+            return false;
+        }
+
+        self.layout_state.move_panel(&dragged_panel_id, drop_pos.row, drop_pos.col);
+        self.needs_layout_update = true;
+        self.view.redraw(cx);
+        true
+    }
 
-        self.layout_state.move_panel(dragged_panel_id, drop_pos.row, drop_pos.col);
+    /// Start dragging a panel: a ghost of its title bar follows the cursor
+    fn start_drag(&mut self, cx: &mut Cx, panel_id: PanelId) {
+        self.ghost = Some(DragGhost {
+            title: self.display_title(&panel_id),
+            origin: self.panel_rect(cx, panel_id).unwrap_or_default(),
+            ..DragGhost::default()
+        });
+        self.dragging_panel = Some(panel_id);
+    }
+
+    /// Move the ghost to the cursor and show the panels as they would be after the drop
+    fn update_drag(&mut self, cx: &mut Cx, abs: DVec2) {
+        let Some(panel_id) = self.dragging_panel else { return };
+        if let Some(ghost) = &mut self.ghost {
+            ghost.move_to(abs);
+        }
+        self.drop_state = self.find_drop_position(cx, abs, panel_id);
+
+        let preview = self.drop_state.as_ref()
+            .filter(|pos| pos.allowed)
+            .map(|pos| {
+                let mut moved = self.layout_state.clone();
+                moved.move_panel(&panel_id, pos.row, pos.col);
+                moved
+            })
+            .filter(|moved| moved.row_assignments != self.layout_state.row_assignments);
+        let rows = |state: &Option<LayoutState>| state.as_ref().map(|state| state.row_assignments.clone());
+        if rows(&preview) != rows(&self.preview_layout) {
+            // The insertion point changed: the other panels glide out of the way
+            self.reflow(cx, HashMap::new());
+            self.preview_layout = preview;
+        }
+        self.view.redraw(cx);
+    }
+
+    /// Stop dragging; the panel glides from the ghost into the slot it ends up in
+    fn end_drag(&mut self, cx: &mut Cx) {
+        if let Some(panel_id) = self.dragging_panel.take() {
+            let from_ghost = self.ghost.as_ref().and_then(DragGhost::rect).map(|rect| (panel_id, rect));
+            self.reflow(cx, from_ghost.into_iter().collect());
+        }
+        self.ghost = None;
+        self.preview_layout = None;
+        self.drop_state = None;
+        self.view.redraw(cx);
+    }
+
+    /// Whether a shown panel has its inline title editor open
+    fn is_editing_title(&self) -> bool {
+        self.shown_slots.values().any(|(row, slot)| self.view.panel(Self::slot_ids()[*row][*slot]).is_editing_title())
+    }
+
+    /// Rect of a shown panel as last drawn
+    fn panel_rect(&self, cx: &Cx, panel_id: PanelId) -> Option<Rect> {
+        let (row, slot) = self.shown_slots.get(&panel_id)?;
+        Some(self.view.view(Self::slot_ids()[*row][*slot]).area().rect(cx))
+    }
+
+    /// Lay the panels out again, animating them from where they are drawn now
+    ///
+    /// `moved_from` overrides where some panels start (a dropped panel starts at the ghost).
+    /// With reduce motion the panels jump into place.
+    fn reflow(&mut self, cx: &mut Cx, moved_from: HashMap<PanelId, Rect>) {
+        if !self.reduce_motion {
+            let mut from: HashMap<PanelId, Rect> = self.shown_slots.keys()
+                .filter_map(|&panel_id| Some((panel_id, self.panel_rect(cx, panel_id)?)))
+                .collect();
+            from.extend(moved_from);
+            self.reflow_pending = Some(from);
+        }
         self.needs_layout_update = true;
         self.view.redraw(cx);
     }
 
+    /// Offset each panel that moved back to where it was drawn, then start gliding
+    fn start_reflow_animation(&mut self, cx: &mut Cx, offsets: HashMap<PanelId, DVec2>) {
+        if offsets.is_empty() {
+            return;
+        }
+        self.reflow_offsets = offsets;
+        self.reflow_animating = true;
+        self.reflow_anim_start = Cx::time_now();
+        self.update_reflow_animation(cx);
+    }
+
+    /// Move the panels a step closer to their slots (called every frame while reflowing)
+    fn update_reflow_animation(&mut self, cx: &mut Cx) {
+        let progress = ((Cx::time_now() - self.reflow_anim_start) / ANIMATION_DURATION).min(1.0);
+        let remaining = 1.0 - ease_out_cubic(progress);
+        for (panel_id, offset) in &self.reflow_offsets {
+            if let Some(&(row, slot)) = self.shown_slots.get(panel_id) {
+                self.set_slot_offset(cx, row, slot, *offset * remaining);
+            }
+        }
+
+        if progress >= 1.0 {
+            self.reflow_animating = false;
+            self.reflow_offsets.clear();
+        } else {
+            cx.new_next_frame();
+        }
+        self.view.redraw(cx);
+    }
+
+    /// Stop a running reflow with every panel in its slot
+    fn stop_reflow_animation(&mut self, cx: &mut Cx) {
+        if !self.reflow_animating {
+            return;
+        }
+        self.reflow_animating = false;
+        self.reflow_offsets.clear();
+        for row in 0..3 {
+            for slot in 0..9 {
+                self.set_slot_offset(cx, row, slot, DVec2::default());
+            }
+        }
+    }
+
+    /// Draw a slot's panel `offset` away from where the layout puts it
+    fn set_slot_offset(&self, cx: &mut Cx, row: usize, slot: usize, offset: DVec2) {
+        // Opposite margins move the panel without changing the space it takes in its row
+        self.view.view(Self::slot_ids()[row][slot]).apply_over(cx, live! {
+            margin: { left: (offset.x), right: (-offset.x), top: (offset.y), bottom: (-offset.y) }
+        });
+    }
+
     /// Get panel index from panel ID (extracts number from "panel_N" format)
     fn panel_index_from_id(panel_id: &str) -> usize {
         // Try to extract index from "panel_N" format
@@ -414,10 +814,49 @@ impl PanelGrid {
         hash % 9
     }
 
-    /// Apply row-based layout using visibility and Fill sizing
-    fn apply_row_layout(&mut self, cx: &mut Cx) {
-        // Slot IDs organized by row (9 slots per row)
-        let row_slot_ids = [
+    /// Show a panel in the panel widget at `slot`: ID, color, title, decoration and title actions
+    fn configure_panel(&self, cx: &mut Cx, slot: &[LiveId], panel_id: PanelId) {
+        let panel = self.view.panel(slot);
+        panel.set_panel_id(panel_id);
+        panel.set_panel_index(cx, Self::panel_index_from_id(&panel_id));
+
+        // Set title from panel_titles, falling back to the registered title
+        let title = self.panel_titles.get(&panel_id)
+            .or_else(|| self.panel_definitions.get(&panel_id).map(|def| &def.title));
+        if let Some(title) = title {
+            panel.set_title(cx, title);
+        }
+        let definition = self.panel_definitions.get(&panel_id);
+        panel.set_renamable(definition.map_or(true, |def| def.renamable));
+        panel.set_minimizable(definition.map_or(false, |def| def.minimizable));
+        panel.set_locked(cx, self.is_locked(&panel_id));
+        panel.set_title_actions(cx, self.title_actions.get(&panel_id).cloned().unwrap_or_default());
+        panel.set_decoration(cx, self.decorations.get(&panel_id).cloned().unwrap_or_default());
+    }
+
+    /// Title shown for a panel: the user's title, then the registered one, then "Panel N"
+    fn display_title(&self, panel_id: &str) -> String {
+        self.panel_titles.get(panel_id)
+            .or_else(|| self.panel_definitions.get(panel_id).map(|def| &def.title))
+            .cloned()
+            .unwrap_or_else(|| format!("Panel {}", Self::panel_index_from_id(panel_id) + 1))
+    }
+
+    /// Show a chip for each minimized panel
+    fn update_minimized_strip(&self, cx: &mut Cx) {
+        let panels = self.layout_state.minimized_panels.iter()
+            .map(|panel_id| MinimizedPanel {
+                id: *panel_id,
+                title: self.display_title(panel_id),
+                decoration: self.decorations.get(panel_id).cloned().unwrap_or_default(),
+            })
+            .collect();
+        self.view.minimized_strip(id!(minimized_strip)).set_panels(cx, panels);
+    }
+
+    /// Slot paths by row (9 slots per row)
+    fn slot_ids() -> [[&'static [LiveId]; 9]; 3] {
+        [
             [
                 id!(window_container.row1.s1_1),
                 id!(window_container.row1.s1_2),
@@ -451,7 +890,28 @@ impl PanelGrid {
                 id!(window_container.row3.s3_8),
                 id!(window_container.row3.s3_9),
             ],
-        ];
+        ]
+    }
+
+    /// Apply row-based layout using visibility and Fill sizing
+    ///
+    /// Returns the rects the shown panels get, worked out from the container
+    /// rect of the last frame (empty before the grid has a size).
+    fn apply_row_layout(&mut self, cx: &mut Cx) -> HashMap<PanelId, Rect> {
+        self.update_minimized_strip(cx);
+
+        // Size limits are applied against the container size of the last frame
+        let container_rect = self.view.view(id!(window_container)).area().rect(cx);
+        let container = container_rect.size;
+        self.laid_out_size = container;
+        let sized = container.x > 0.0 && container.y > 0.0;
+        let mut panel_rects = HashMap::new();
+
+        // While a panel is dragged, lay out the drop preview so other panels make room
+        let state = self.preview_layout.clone().unwrap_or_else(|| self.layout_state.clone());
+        self.shown_slots.clear();
+
+        let row_slot_ids = Self::slot_ids();
 
         let row_view_ids = [
             id!(window_container.row1),
@@ -460,10 +920,10 @@ impl PanelGrid {
         ];
 
         // Get visible panels per row
-        let visible_per_row: [Vec<String>; 3] = [
-            self.layout_state.visible_in_row(0),
-            self.layout_state.visible_in_row(1),
-            self.layout_state.visible_in_row(2),
+        let visible_per_row: [Vec<PanelId>; 3] = [
+            state.visible_in_row(0),
+            state.visible_in_row(1),
+            state.visible_in_row(2),
         ];
 
         let total_visible: usize = visible_per_row.iter().map(|r| r.len()).sum();
@@ -471,7 +931,7 @@ impl PanelGrid {
         const SLOTS_PER_ROW: usize = 9;
 
         // Handle maximized panel
-        if let Some(ref max_id) = self.layout_state.maximized_panel.clone() {
+        if let Some(max_id) = state.maximized_panel {
             // Hide all slots and rows first
             for row_idx in 0..3 {
                 for slot_idx in 0..SLOTS_PER_ROW {
@@ -485,7 +945,7 @@ impl PanelGrid {
             }
 
             // Find which row and slot contains the maximized panel
-            if let Some((row_idx, slot_idx)) = self.layout_state.find_panel_row(max_id) {
+            if let Some((row_idx, slot_idx)) = state.find_panel_row(&max_id) {
                 eprintln!("=== MAXIMIZE: panel={} row={} slot={} ===", max_id, row_idx, slot_idx);
 
                 // Show only that row
@@ -497,15 +957,14 @@ impl PanelGrid {
                 self.view.view(row_slot_ids[row_idx][slot_idx]).apply_over(cx, live! {
                     visible: true, width: Fill, height: Fill
                 });
-                self.view.panel(row_slot_ids[row_idx][slot_idx]).set_panel_id_str(max_id);
-                self.view.panel(row_slot_ids[row_idx][slot_idx]).set_panel_index(cx, Self::panel_index_from_id(max_id));
+                self.configure_panel(cx, row_slot_ids[row_idx][slot_idx], max_id);
                 self.view.panel(row_slot_ids[row_idx][slot_idx]).set_maximized(true);
-                // Set title from panel_titles if available
-                if let Some(title) = self.panel_titles.get(max_id) {
-                    self.view.panel(row_slot_ids[row_idx][slot_idx]).set_title(cx, title);
+                self.shown_slots.insert(max_id, (row_idx, slot_idx));
+                if sized {
+                    panel_rects.insert(max_id, container_rect);
                 }
             }
-            return;
+            return panel_rects;
         }
 
         // Auto-maximize if only 1 panel left
@@ -522,26 +981,23 @@ impl PanelGrid {
                 });
             }
 
-            // Find the only visible panel
-            for row_idx in 0..3 {
-                if !visible_per_row[row_idx].is_empty() {
-                    let panel_id = &visible_per_row[row_idx][0];
+            // Find the only visible panel and show its own slot (others may be minimized)
+            if let Some(&panel_id) = visible_per_row.iter().flatten().next() {
+                if let Some((row_idx, slot_idx)) = state.find_panel_row(&panel_id) {
                     self.view.view(row_view_ids[row_idx]).apply_over(cx, live! {
                         visible: true, height: Fill
                     });
-                    self.view.view(row_slot_ids[row_idx][0]).apply_over(cx, live! {
+                    self.view.view(row_slot_ids[row_idx][slot_idx]).apply_over(cx, live! {
                         visible: true, width: Fill, height: Fill
                     });
-                    self.view.panel(row_slot_ids[row_idx][0]).set_panel_id_str(panel_id);
-                    self.view.panel(row_slot_ids[row_idx][0]).set_panel_index(cx, Self::panel_index_from_id(panel_id));
-                    // Set title from panel_titles if available
-                    if let Some(title) = self.panel_titles.get(panel_id) {
-                        self.view.panel(row_slot_ids[row_idx][0]).set_title(cx, title);
+                    self.configure_panel(cx, row_slot_ids[row_idx][slot_idx], panel_id);
+                    self.shown_slots.insert(panel_id, (row_idx, slot_idx));
+                    if sized {
+                        panel_rects.insert(panel_id, container_rect);
                     }
-                    break;
                 }
             }
-            return;
+            return panel_rects;
         }
 
         // Normal layout: each row shows its assigned panels
@@ -555,18 +1011,32 @@ impl PanelGrid {
             }
         }
 
+        // Fixed row heights for rows whose panels have limits (None = Fill)
+        let mut row_heights = [None; 3];
+        if sized {
+            let shown_rows: Vec<usize> = (0..3).filter(|row| !visible_per_row[*row].is_empty()).collect();
+            let limits: Vec<_> = shown_rows.iter().map(|row| self.row_limits(&visible_per_row[*row]).height()).collect();
+            for (row, height) in shown_rows.iter().zip(fit_sizes(container.y, &limits)) {
+                row_heights[*row] = height;
+            }
+        }
+
+        // Height and slot widths of each shown row, with the panels in order, to place them
+        let mut shown_rows: Vec<(Option<f64>, Vec<Option<f64>>)> = Vec::new();
+        let mut shown_panels: Vec<PanelId> = Vec::new();
+
         // Configure each row - iterate by position to preserve slot-to-content mapping
         for row_idx in 0..3 {
             // Get ALL panels in row (including hidden ones) to preserve positions
-            let all_panels_in_row = if row_idx < self.layout_state.row_assignments.len() {
-                &self.layout_state.row_assignments[row_idx]
+            let all_panels_in_row = if row_idx < state.row_assignments.len() {
+                &state.row_assignments[row_idx]
             } else {
                 continue;
             };
 
-            // Check if row has any visible panels
+            // Check if row has any shown panels
             let has_visible = all_panels_in_row.iter()
-                .any(|id| self.layout_state.visible_panels.contains(id));
+                .any(|id| state.is_shown(id));
 
             if !has_visible {
                 // Hide empty rows
@@ -575,19 +1045,42 @@ impl PanelGrid {
                 });
             } else {
                 // Show row
-                self.view.view(row_view_ids[row_idx]).apply_over(cx, live! {
-                    visible: true, height: Fill
-                });
+                match row_heights[row_idx] {
+                    Some(height) => self.view.view(row_view_ids[row_idx]).apply_over(cx, live! {
+                        visible: true, height: (height)
+                    }),
+                    None => self.view.view(row_view_ids[row_idx]).apply_over(cx, live! {
+                        visible: true, height: Fill
+                    }),
+                }
+
+                // Fixed widths for panels with limits, in the order they are shown
+                let limits: Vec<_> = visible_per_row[row_idx].iter().map(|id| self.panel_limits(id).width()).collect();
+                let mut widths = if sized {
+                    fit_sizes(container.x, &limits)
+                } else {
+                    vec![None; limits.len()]
+                }.into_iter();
+                let mut shown_widths = Vec::new();
 
                 // Show/hide slots by POSITION (not by compacted index)
                 // This preserves the mapping between slot position and content widget
-                for (slot_idx, panel_id) in all_panels_in_row.iter().take(SLOTS_PER_ROW).enumerate() {
-                    let is_visible = self.layout_state.visible_panels.contains(panel_id);
+                for (slot_idx, &panel_id) in all_panels_in_row.iter().take(SLOTS_PER_ROW).enumerate() {
+                    let is_visible = state.is_shown(&panel_id);
 
                     if is_visible {
-                        self.view.view(row_slot_ids[row_idx][slot_idx]).apply_over(cx, live! {
-                            visible: true, width: Fill, height: Fill
-                        });
+                        let width = widths.next().flatten();
+                        match width {
+                            Some(width) => self.view.view(row_slot_ids[row_idx][slot_idx]).apply_over(cx, live! {
+                                visible: true, width: (width), height: Fill
+                            }),
+                            None => self.view.view(row_slot_ids[row_idx][slot_idx]).apply_over(cx, live! {
+                                visible: true, width: Fill, height: Fill
+                            }),
+                        }
+                        self.shown_slots.insert(panel_id, (row_idx, slot_idx));
+                        shown_widths.push(width);
+                        shown_panels.push(panel_id);
                     } else {
                         // Keep slot hidden but preserve its position
                         self.view.view(row_slot_ids[row_idx][slot_idx]).apply_over(cx, live! {
@@ -596,16 +1089,17 @@ impl PanelGrid {
                     }
 
                     // Always set panel info (even for hidden panels, for consistency)
-                    self.view.panel(row_slot_ids[row_idx][slot_idx]).set_panel_id_str(panel_id);
-                    self.view.panel(row_slot_ids[row_idx][slot_idx]).set_panel_index(cx, Self::panel_index_from_id(panel_id));
-
-                    // Set title from panel_titles if available
-                    if let Some(title) = self.panel_titles.get(panel_id) {
-                        self.view.panel(row_slot_ids[row_idx][slot_idx]).set_title(cx, title);
-                    }
+                    self.configure_panel(cx, row_slot_ids[row_idx][slot_idx], panel_id);
                 }
+                shown_rows.push((row_heights[row_idx], shown_widths));
             }
         }
+
+        if sized {
+            let rects = slot_rects(container_rect, &shown_rows);
+            panel_rects.extend(shown_panels.into_iter().zip(rects.into_iter().flatten()));
+        }
+        panel_rects
     }
 }
 
@@ -615,6 +1109,11 @@ impl PanelGridRef {
         self.borrow().map(|inner| inner.layout_state.clone())
     }
 
+    /// Whether a panel's inline title editor is open
+    pub fn is_editing_title(&self) -> bool {
+        self.borrow().is_some_and(|inner| inner.is_editing_title())
+    }
+
     /// Set layout state (for restoring from persistence)
     ///
     /// If the state contains panel_titles, they will be merged into the
@@ -630,7 +1129,7 @@ impl PanelGridRef {
             PENDING_TITLES.with(|p| {
                 let mut pending = p.borrow_mut();
                 for (panel_id, title) in &state.panel_titles {
-                    pending.insert(panel_id.clone(), title.clone());
+                    pending.insert(*panel_id, title.clone());
                 }
             });
             // Store layout in thread-local for retrieval during first draw
@@ -638,9 +1137,204 @@ impl PanelGridRef {
         }
     }
 
+    /// Close a panel, returning false if it isn't in the grid
+    pub fn close_panel(&self, cx: &mut Cx, panel_id: &str) -> bool {
+        let Some(mut inner) = self.borrow_mut() else { return false };
+        if !inner.has_panel(panel_id) {
+            return false;
+        }
+        inner.close_panel(cx, PanelId::new(panel_id));
+        true
+    }
+
+    /// Show a closed panel again in its slot; false if it isn't closed
+    pub fn reopen_panel(&self, cx: &mut Cx, panel_id: &str) -> bool {
+        self.borrow_mut().map_or(false, |mut inner| inner.reopen_panel(cx, PanelId::new(panel_id)))
+    }
+
+    /// Closed panels in row order
+    pub fn closed_panels(&self) -> Vec<PanelId> {
+        self.borrow().map(|inner| inner.layout_state.closed_panels()).unwrap_or_default()
+    }
+
+    /// Maximize a panel, or restore it if it is maximized; false if it isn't in the grid
+    pub fn toggle_maximize(&self, cx: &mut Cx, panel_id: &str) -> bool {
+        let Some(mut inner) = self.borrow_mut() else { return false };
+        if !inner.has_panel(panel_id) {
+            return false;
+        }
+        inner.toggle_maximize(cx, PanelId::new(panel_id));
+        true
+    }
+
+    /// Collapse a panel into a chip at the bottom of the grid; false if it isn't shown
+    pub fn minimize_panel(&self, cx: &mut Cx, panel_id: &str) -> bool {
+        self.borrow_mut().map_or(false, |mut inner| inner.minimize_panel(cx, PanelId::new(panel_id)))
+    }
+
+    /// Show a minimized panel again at its row and column; false if it isn't minimized
+    pub fn restore_panel(&self, cx: &mut Cx, panel_id: &str) -> bool {
+        self.borrow_mut().map_or(false, |mut inner| inner.restore_panel(cx, PanelId::new(panel_id)))
+    }
+
+    /// Pin or unpin a panel; false if it isn't in the grid, is pinned by its definition or is unchanged
+    pub fn set_panel_pinned(&self, cx: &mut Cx, panel_id: &str, pinned: bool) -> bool {
+        let Some(mut inner) = self.borrow_mut() else { return false };
+        if !inner.has_panel(panel_id)
+            || inner.panel_definitions.get(panel_id).map_or(false, |def| def.pinned)
+            || !inner.layout_state.set_pinned(panel_id, pinned)
+        {
+            return false;
+        }
+        inner.needs_layout_update = true;
+        inner.view.redraw(cx);
+        true
+    }
+
+    /// Whether a panel is pinned by the user or by its definition
+    pub fn is_panel_pinned(&self, panel_id: &str) -> bool {
+        self.borrow().map_or(false, |inner| inner.is_pinned(panel_id))
+    }
+
+    /// Turn dragging and closing off (true) or on for every panel
+    ///
+    /// Note: If called before first draw, the lock is applied during initialization.
+    pub fn set_layout_locked(&self, cx: &mut Cx, locked: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.layout_locked = locked;
+            inner.end_drag(cx);
+            inner.needs_layout_update = true;
+            inner.view.redraw(cx);
+        } else {
+            PENDING_LOCKED.with(|p| *p.borrow_mut() = Some(locked));
+        }
+    }
+
+    /// Set (or clear with None) the app hook that can forbid drops
+    pub fn set_drop_validator(&self, validator: Option<DropValidator>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.drop_validator = validator;
+        }
+    }
+
+    /// Make panels jump into place (true) or glide when the layout changes
+    pub fn set_reduce_motion(&self, cx: &mut Cx, reduce_motion: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.reduce_motion = reduce_motion;
+            if reduce_motion {
+                // Settle a running reflow
+                inner.reflow_pending = None;
+                inner.stop_reflow_animation(cx);
+                inner.view.redraw(cx);
+            }
+        }
+    }
+
+    /// Smallest (and largest) size the shown panels need, from their definitions
+    pub fn size_limits(&self) -> SizeLimits {
+        self.borrow().map(|inner| inner.grid_limits(&inner.layout_state)).unwrap_or_default()
+    }
+
+    /// Minimized panels in chip order
+    pub fn minimized_panels(&self) -> Vec<PanelId> {
+        self.borrow().map(|inner| inner.layout_state.minimized_panels.clone()).unwrap_or_default()
+    }
+
+    /// Change a panel's title and record it in the layout state; false if it isn't in the grid
+    pub fn rename_panel(&self, cx: &mut Cx, panel_id: &str, title: &str) -> bool {
+        let Some(mut inner) = self.borrow_mut() else { return false };
+        if !inner.has_panel(panel_id) {
+            return false;
+        }
+        inner.panel_titles.insert(PanelId::new(panel_id), title.to_string());
+        inner.layout_state.set_panel_title(panel_id, title);
+        inner.needs_layout_update = true;
+        inner.view.redraw(cx);
+        true
+    }
+
+    /// Set the panel definitions: default titles, icons and title bar actions by panel ID
+    pub fn set_panel_definitions(&self, cx: &mut Cx, definitions: &[PanelDefinition]) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.title_actions = definitions.iter()
+                .filter(|def| !def.title_actions.is_empty())
+                .map(|def| (PanelId::new(&def.id), def.title_actions.clone()))
+                .collect();
+            for def in definitions {
+                if let Some(icon) = &def.icon {
+                    inner.decorations.entry(PanelId::new(&def.id)).or_default().icon = Some(icon.clone());
+                }
+            }
+            inner.panel_definitions = definitions.iter().map(|def| (PanelId::new(&def.id), def.clone())).collect();
+            inner.needs_layout_update = true;
+            inner.view.redraw(cx);
+        }
+    }
+
+    /// Replace a panel's title bar actions
+    pub fn set_title_actions(&self, cx: &mut Cx, panel_id: &str, actions: Vec<PanelTitleAction>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.title_actions.insert(PanelId::new(panel_id), actions);
+            inner.needs_layout_update = true;
+            inner.view.redraw(cx);
+        }
+    }
+
+    /// Set the checked state of a panel's toggle title action
+    ///
+    /// Returns false if the panel has no toggle action with this ID.
+    pub fn set_title_action_checked(&self, cx: &mut Cx, panel_id: &str, action_id: &str, checked: bool) -> bool {
+        let Some(mut inner) = self.borrow_mut() else { return false };
+        let Some(actions) = inner.title_actions.get_mut(panel_id) else { return false };
+        if !set_action_checked(actions, action_id, checked) {
+            return false;
+        }
+        inner.needs_layout_update = true;
+        inner.view.redraw(cx);
+        true
+    }
+
+    /// Set or clear a panel's title icon (SVG path)
+    pub fn set_panel_icon(&self, cx: &mut Cx, panel_id: &str, icon: Option<&str>) {
+        self.update_decoration(cx, panel_id, |decoration| decoration.icon = icon.map(str::to_string));
+    }
+
+    /// Set or clear a panel's badge (e.g. an error count)
+    pub fn set_panel_badge(&self, cx: &mut Cx, panel_id: &str, badge: Option<&str>) {
+        self.update_decoration(cx, panel_id, |decoration| decoration.badge = badge.map(str::to_string));
+    }
+
+    /// Show or hide a panel's "modified" dot
+    pub fn set_panel_dirty(&self, cx: &mut Cx, panel_id: &str, dirty: bool) {
+        self.update_decoration(cx, panel_id, |decoration| decoration.dirty = dirty);
+    }
+
+    /// Icon, badge and "modified" dot of a panel
+    pub fn panel_decoration(&self, panel_id: &str) -> PanelDecoration {
+        self.borrow()
+            .and_then(|inner| inner.decorations.get(panel_id).cloned())
+            .unwrap_or_default()
+    }
+
+    fn update_decoration(&self, cx: &mut Cx, panel_id: &str, update: impl FnOnce(&mut PanelDecoration)) {
+        if let Some(mut inner) = self.borrow_mut() {
+            update(inner.decorations.entry(PanelId::new(panel_id)).or_default());
+            inner.needs_layout_update = true;
+            inner.view.redraw(cx);
+        }
+    }
+
+    /// Set the panels whose close button asks for confirmation first
+    pub fn set_confirm_close_panels(&self, panel_ids: Vec<String>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.confirm_close = panel_ids.iter().map(PanelId::from).collect();
+        }
+    }
+
     /// Reset layout to default state
     pub fn reset_layout(&self, cx: &mut Cx) {
         if let Some(mut inner) = self.borrow_mut() {
+            inner.end_drag(cx);
             inner.layout_state = LayoutState::default();
             inner.needs_layout_update = true;
             inner.view.redraw(cx);
@@ -651,13 +1345,16 @@ impl PanelGridRef {
         }
     }
 
-    /// Apply dark mode value to this grid and all panels
+    /// Apply the built-in palettes blended by a dark mode value to this grid and all panels
     pub fn apply_dark_mode(&self, cx: &mut Cx, dark_mode: f64) {
+        self.apply_palette(cx, &ShellPalette::for_dark_mode(dark_mode));
+    }
+
+    /// Apply a palette to this grid and all panels
+    pub fn apply_palette(&self, cx: &mut Cx, palette: &ShellPalette) {
         if let Some(mut inner) = self.borrow_mut() {
             // Apply to grid background
-            inner.view.apply_over(cx, live! {
-                draw_bg: { dark_mode: (dark_mode) }
-            });
+            inner.view.apply_over(cx, &palette.live_nodes(&[live_id!(draw_bg)]));
 
             // Apply to all panels in all slots
             let slot_ids = [
@@ -682,7 +1379,7 @@ impl PanelGridRef {
             ];
 
             for slot_id in &slot_ids {
-                inner.view.panel(*slot_id).apply_dark_mode(cx, dark_mode);
+                inner.view.panel(*slot_id).apply_palette(cx, palette);
             }
         }
     }
@@ -691,11 +1388,11 @@ impl PanelGridRef {
     /// This title persists across layout state changes
     pub fn set_panel_title(&self, panel_id: &str, title: &str) {
         if let Some(mut inner) = self.borrow_mut() {
-            inner.panel_titles.insert(panel_id.to_string(), title.to_string());
+            inner.panel_titles.insert(PanelId::new(panel_id), title.to_string());
         } else {
             // Store in thread-local for retrieval during first draw
             PENDING_TITLES.with(|p| {
-                p.borrow_mut().insert(panel_id.to_string(), title.to_string());
+                p.borrow_mut().insert(PanelId::new(panel_id), title.to_string());
             });
         }
     }
@@ -704,14 +1401,14 @@ impl PanelGridRef {
     pub fn set_panel_titles(&self, titles: &[(&str, &str)]) {
         if let Some(mut inner) = self.borrow_mut() {
             for (panel_id, title) in titles {
-                inner.panel_titles.insert(panel_id.to_string(), title.to_string());
+                inner.panel_titles.insert(PanelId::new(panel_id), title.to_string());
             }
         } else {
             // Store in thread-local for retrieval during first draw
             PENDING_TITLES.with(|p| {
                 let mut pending = p.borrow_mut();
                 for (panel_id, title) in titles {
-                    pending.insert(panel_id.to_string(), title.to_string());
+                    pending.insert(PanelId::new(panel_id), title.to_string());
                 }
             });
         }
@@ -766,7 +1463,7 @@ impl PanelGridRef {
         if let Some(inner) = self.borrow() {
             for (row_idx, row) in inner.layout_state.row_assignments.iter().enumerate() {
                 for (slot_idx, panel_id) in row.iter().enumerate() {
-                    mapping.insert(panel_id.clone(), (row_idx, slot_idx));
+                    mapping.insert(panel_id.to_string(), (row_idx, slot_idx));
                 }
             }
         }
@@ -785,7 +1482,7 @@ impl PanelGridRef {
             for row in &inner.layout_state.row_assignments {
                 for panel_id in row {
                     if inner.layout_state.visible_panels.contains(panel_id) {
-                        panels.push(panel_id.clone());
+                        panels.push(panel_id.to_string());
                     }
                 }
             }
@@ -810,7 +1507,7 @@ impl PanelGridRef {
             inner.layout_state.row_assignments
                 .get(row)
                 .and_then(|r| r.get(slot))
-                .cloned()
+                .map(|panel_id| panel_id.to_string())
         })
     }
 }
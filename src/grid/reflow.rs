@@ -0,0 +1,145 @@
+//! Drag ghost and reflow geometry for the panel grid
+//!
+//! When the layout changes while a panel is dragged or dropped, the panels that
+//! moved glide from where they were drawn into their new slots. PanelGrid works
+//! out the new slot rects in its row layout pass, before drawing, so a panel is
+//! never drawn in its new slot without its offset for a frame.
+
+use std::collections::HashMap;
+use makepad_widgets::{dvec2, DVec2, Rect};
+use crate::panel::PanelId;
+
+/// Height of the dragged panel's title bar
+pub const GHOST_HEIGHT: f64 = 32.0;
+
+/// Offsets this small (in pixels) aren't animated
+const MIN_OFFSET: f64 = 0.5;
+
+/// Translucent copy of the dragged panel's title bar that follows the cursor
+#[derive(Clone, Debug, Default)]
+pub struct DragGhost {
+    pub title: String,
+
+    /// Dragged panel's rect when the drag started
+    pub origin: Rect,
+
+    /// Cursor position (None until the first move)
+    pub cursor: Option<DVec2>,
+
+    /// Where the title bar was grabbed, relative to its top-left corner
+    pub grab: DVec2,
+}
+
+impl DragGhost {
+    pub fn move_to(&mut self, abs: DVec2) {
+        if self.cursor.is_none() {
+            // The drag starts a few pixels from where the title bar was pressed
+            self.grab = dvec2(
+                (abs.x - self.origin.pos.x).clamp(0.0, self.origin.size.x),
+                (abs.y - self.origin.pos.y).clamp(0.0, GHOST_HEIGHT),
+            );
+        }
+        self.cursor = Some(abs);
+    }
+
+    /// Where the ghost is drawn
+    pub fn rect(&self) -> Option<Rect> {
+        let cursor = self.cursor?;
+        Some(Rect { pos: cursor - self.grab, size: dvec2(self.origin.size.x, GHOST_HEIGHT) })
+    }
+}
+
+/// Split `total` between fixed sizes and `Fill` (None), which share what is left evenly
+fn share(total: f64, sizes: &[Option<f64>]) -> Vec<f64> {
+    let fixed: f64 = sizes.iter().flatten().sum();
+    let fills = sizes.iter().filter(|size| size.is_none()).count().max(1);
+    let fill = (total - fixed).max(0.0) / fills as f64;
+    sizes.iter().map(|size| size.unwrap_or(fill)).collect()
+}
+
+/// Rects of the shown slots when the rows are laid out in `container`
+///
+/// `rows` holds each shown row's height and the widths of its shown slots,
+/// top to bottom and left to right (None = `Fill`).
+pub fn slot_rects(container: Rect, rows: &[(Option<f64>, Vec<Option<f64>>)]) -> Vec<Vec<Rect>> {
+    let heights: Vec<Option<f64>> = rows.iter().map(|(height, _)| *height).collect();
+    let mut y = container.pos.y;
+    rows.iter().zip(share(container.size.y, &heights)).map(|((_, widths), height)| {
+        let mut x = container.pos.x;
+        let rects = share(container.size.x, widths).into_iter().map(|width| {
+            let rect = Rect { pos: dvec2(x, y), size: dvec2(width, height) };
+            x += width;
+            rect
+        }).collect();
+        y += height;
+        rects
+    }).collect()
+}
+
+/// Offset from each panel's new rect back to where it was drawn
+///
+/// Panels that weren't drawn before or that stay in place get none.
+pub fn reflow_offsets(from: &HashMap<PanelId, Rect>, to: &HashMap<PanelId, Rect>) -> HashMap<PanelId, DVec2> {
+    to.iter()
+        .filter_map(|(panel_id, to)| {
+            let offset = from.get(panel_id)?.pos - to.pos;
+            (offset.x.abs() > MIN_OFFSET || offset.y.abs() > MIN_OFFSET).then_some((*panel_id, offset))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: f64, y: f64, w: f64, h: f64) -> Rect {
+        Rect { pos: dvec2(x, y), size: dvec2(w, h) }
+    }
+
+    #[test]
+    fn test_ghost_keeps_the_grab_point_under_the_cursor() {
+        let mut ghost = DragGhost { origin: rect(100.0, 50.0, 300.0, 200.0), ..DragGhost::default() };
+        assert_eq!(ghost.rect(), None, "not drawn before the first move");
+
+        ghost.move_to(dvec2(130.0, 60.0));
+        ghost.move_to(dvec2(500.0, 400.0));
+        assert_eq!(ghost.rect(), Some(rect(470.0, 390.0, 300.0, GHOST_HEIGHT)));
+    }
+
+    #[test]
+    fn test_ghost_grab_stays_on_the_title_bar() {
+        // The first move is already below the title bar
+        let mut ghost = DragGhost { origin: rect(100.0, 50.0, 300.0, 200.0), ..DragGhost::default() };
+        ghost.move_to(dvec2(90.0, 150.0));
+        assert_eq!(ghost.grab, dvec2(0.0, GHOST_HEIGHT));
+    }
+
+    #[test]
+    fn test_fill_slots_share_what_fixed_ones_leave() {
+        let rows = [
+            (Some(100.0), vec![Some(200.0), None, None]),
+            (None, vec![None]),
+        ];
+        let rects = slot_rects(rect(10.0, 20.0, 600.0, 400.0), &rows);
+        assert_eq!(rects[0], vec![
+            rect(10.0, 20.0, 200.0, 100.0),
+            rect(210.0, 20.0, 200.0, 100.0),
+            rect(410.0, 20.0, 200.0, 100.0),
+        ]);
+        assert_eq!(rects[1], vec![rect(10.0, 120.0, 600.0, 300.0)]);
+    }
+
+    #[test]
+    fn test_only_moved_panels_get_offsets() {
+        let (a, b, c) = (PanelId::new("a"), PanelId::new("b"), PanelId::new("c"));
+        let from = HashMap::from([(a, rect(0.0, 0.0, 100.0, 100.0)), (b, rect(100.0, 0.0, 100.0, 100.0))]);
+        let to = HashMap::from([
+            (a, rect(0.2, 0.0, 50.0, 100.0)),
+            (b, rect(50.0, 0.0, 50.0, 100.0)),
+            (c, rect(100.0, 0.0, 50.0, 100.0)),
+        ]);
+
+        let offsets = reflow_offsets(&from, &to);
+        assert_eq!(offsets, HashMap::from([(b, dvec2(50.0, 0.0))]));
+    }
+}
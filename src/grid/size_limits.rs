@@ -0,0 +1,132 @@
+//! Minimum and maximum panel sizes
+//!
+//! Grid slots are `width: Fill`, so without limits a panel in a crowded row
+//! can shrink to a sliver. `PanelDefinition` declares `SizeLimits`; the grids
+//! combine them per row, slot and grid to size their slots, to refuse drops
+//! that can't fit, and to keep the dock splitters from squeezing the grids.
+
+use makepad_widgets::DVec2;
+
+/// Minimum and maximum size of a panel, row or grid (in pixels)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SizeLimits {
+    pub min_width: f64,
+    pub min_height: f64,
+
+    /// `None` = no maximum
+    pub max_width: Option<f64>,
+    pub max_height: Option<f64>,
+}
+
+impl SizeLimits {
+    /// Limits of items laid out left to right (a grid row)
+    pub fn side_by_side(items: impl IntoIterator<Item = SizeLimits>) -> Self {
+        let items: Vec<SizeLimits> = items.into_iter().collect();
+        Self {
+            min_width: items.iter().map(|item| item.min_width).sum(),
+            min_height: items.iter().map(|item| item.min_height).fold(0.0, f64::max),
+            // Only bounded when every item is
+            max_width: items.iter().map(|item| item.max_width).sum::<Option<f64>>().filter(|_| !items.is_empty()),
+            max_height: items.iter().filter_map(|item| item.max_height).reduce(f64::min),
+        }
+    }
+
+    /// Limits of items laid out top to bottom (grid rows, a footer slot)
+    pub fn stacked(items: impl IntoIterator<Item = SizeLimits>) -> Self {
+        let items: Vec<SizeLimits> = items.into_iter().collect();
+        Self {
+            min_width: items.iter().map(|item| item.min_width).fold(0.0, f64::max),
+            min_height: items.iter().map(|item| item.min_height).sum(),
+            max_width: items.iter().filter_map(|item| item.max_width).reduce(f64::min),
+            max_height: items.iter().map(|item| item.max_height).sum::<Option<f64>>().filter(|_| !items.is_empty()),
+        }
+    }
+
+    /// Whether `size` meets the minimums
+    pub fn fits(&self, size: DVec2) -> bool {
+        size.x >= self.min_width && size.y >= self.min_height
+    }
+
+    /// Whether these minimums are no larger than `other`'s
+    pub fn no_larger_than(&self, other: &SizeLimits) -> bool {
+        self.min_width <= other.min_width && self.min_height <= other.min_height
+    }
+
+    /// Width limits as `(min, max)`
+    pub fn width(&self) -> (f64, Option<f64>) {
+        (self.min_width, self.max_width)
+    }
+
+    /// Height limits as `(min, max)`
+    pub fn height(&self) -> (f64, Option<f64>) {
+        (self.min_height, self.max_height)
+    }
+}
+
+/// Share `total` between items with `(min, max)` limits
+///
+/// Items start with an equal share; those whose share breaks a limit get a fixed
+/// size (`Some`) and the rest share what is left (`None` = `Fill`). When the
+/// minimums don't fit, the fixed sizes overflow `total`.
+pub fn fit_sizes(total: f64, limits: &[(f64, Option<f64>)]) -> Vec<Option<f64>> {
+    let mut sizes: Vec<Option<f64>> = vec![None; limits.len()];
+    loop {
+        let free = sizes.iter().filter(|size| size.is_none()).count();
+        if free == 0 {
+            return sizes;
+        }
+        let fixed: f64 = sizes.iter().flatten().sum();
+        let share = (total - fixed).max(0.0) / free as f64;
+
+        let mut changed = false;
+        for (size, (min, max)) in sizes.iter_mut().zip(limits) {
+            if size.is_some() {
+                continue;
+            }
+            if share < *min {
+                *size = Some(*min);
+                changed = true;
+            } else if let Some(max) = max.filter(|max| share > *max) {
+                *size = Some(max);
+                changed = true;
+            }
+        }
+        if !changed {
+            return sizes;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use makepad_widgets::dvec2;
+
+    #[test]
+    fn test_unlimited_items_fill() {
+        assert_eq!(fit_sizes(900.0, &[(0.0, None); 3]), vec![None, None, None]);
+    }
+
+    #[test]
+    fn test_limits_fix_sizes_and_others_share() {
+        // An equal share is ~133: the first needs 300, the last may not exceed 50
+        let sizes = fit_sizes(400.0, &[(300.0, None), (0.0, None), (0.0, Some(50.0))]);
+        assert_eq!(sizes, vec![Some(300.0), None, Some(50.0)]);
+    }
+
+    #[test]
+    fn test_combined_limits() {
+        let editor = SizeLimits { min_width: 300.0, min_height: 200.0, ..SizeLimits::default() };
+        let console = SizeLimits { min_width: 200.0, max_height: Some(250.0), ..SizeLimits::default() };
+        let row = SizeLimits::side_by_side([editor, console]);
+
+        assert_eq!(row.width(), (500.0, None));
+        assert_eq!(row.height(), (200.0, Some(250.0)));
+        assert!(row.fits(dvec2(500.0, 200.0)));
+        assert!(!row.fits(dvec2(499.0, 600.0)));
+
+        let grid = SizeLimits::stacked([row, editor]);
+        assert_eq!(grid.width(), (500.0, None));
+        assert_eq!(grid.height(), (400.0, None));
+    }
+}
@@ -43,10 +43,19 @@ use makepad_widgets::*;
 
 /// Prelude module for convenient imports
 pub mod prelude {
-    pub use crate::theme::{ShellTheme, ThemeListener};
+    pub use crate::theme::{ShellPalette, ShellTheme, ThemeContext, ThemeDefinition, ThemeListener, ThemeMode, ThemeTracker};
     pub use crate::shell::config::ShellConfig;
-    pub use crate::panel::{Panel, PanelAction};
-    pub use crate::grid::{PanelGrid, FooterGrid, LayoutState};
+    pub use crate::shell::events::ShellEvent;
+    pub use crate::shell::sidebar_menu::SidebarItem;
+    pub use crate::shell::menu::{MenuEntry, MenuItem, MenuModel};
+    pub use crate::shell::toolbar::{ToolbarGroup, ToolbarItem};
+    pub use crate::shell::status_bar::{StatusAlignment, StatusItem, StatusProgress};
+    pub use crate::shell::notifications::{Notification, NotificationKind, NotificationRecord};
+    pub use crate::shell::dialog::{Dialog, DialogButtonRole, DialogResult};
+    pub use crate::shell::sidebar_tree::SidebarTreeNode;
+    pub use crate::shell::activity_bar::{SidebarSide, SidebarView};
+    pub use crate::panel::{Panel, PanelAction, PanelDecoration, PanelId, PanelMenuCommand, PanelTitleAction};
+    pub use crate::grid::{PanelGrid, FooterGrid, DropTarget, DropValidator, LayoutState, SizeLimits};
     pub use crate::callbacks::ShellCallbacks;
     pub use crate::persistence::ShellPreferences;
     pub use crate::registry::{PanelDefinition, PanelRegistry};
@@ -54,11 +63,22 @@ pub mod prelude {
 
 /// Widget exports for use in live_design!
 pub mod widgets {
-    pub use crate::shell::layout::{ShellLayout, ShellLayoutRef};
+    pub use crate::shell::layout::{ShellLayout, ShellLayoutRef, ShellLayoutWidgetRefExt};
+    pub use crate::shell::events::ShellEvent;
     pub use crate::shell::header::{ShellHeader, ShellHeaderRef};
+    pub use crate::shell::menu::{ShellMenu, ShellMenuRef};
+    pub use crate::shell::menu_bar::{MenuBar, MenuBarRef};
+    pub use crate::shell::toolbar::{HeaderToolbar, HeaderToolbarRef};
+    pub use crate::shell::status_bar::{StatusBarGroup, StatusBarGroupRef};
     pub use crate::shell::footer::{ShellFooter, ShellFooterRef};
-    pub use crate::shell::sidebar::{ShellSidebar, ShellSidebarRef, SidebarAction, SidebarSelection};
+    pub use crate::shell::notifications::{ShellNotifications, ShellNotificationsRef};
+    pub use crate::shell::dialog::{ShellDialog, ShellDialogRef};
+    pub use crate::shell::sidebar::{ShellSidebar, ShellSidebarRef, ShellSidebarWidgetRefExt, SidebarAction, SidebarSelection};
+    pub use crate::shell::sidebar_menu::{SidebarItem, SidebarMenu, SidebarMenuRef};
+    pub use crate::shell::sidebar_tree::{SidebarTree, SidebarTreeRef, SidebarTreeNode};
+    pub use crate::shell::activity_bar::{ActivityBar, ActivityBarRef, SidebarSide, SidebarView};
     pub use crate::panel::{Panel, PanelRef};
+    pub use crate::panel::title_actions::{PanelTitleActions, PanelTitleActionsRef};
     pub use crate::grid::{PanelGrid, PanelGridRef, FooterGrid, FooterGridRef};
 }
 
@@ -70,17 +90,30 @@ pub fn live_design(cx: &mut Cx) {
     // Register base live_design (colors, styles)
     crate::live_design::live_design(cx);
 
-    // Register panel widget
+    // Register the popup menu first: panel title actions and the header use it
+    crate::shell::menu::live_design(cx);
+
+    // Register panel widget (title actions before the panel that hosts them)
+    crate::panel::title_actions::live_design(cx);
     crate::panel::panel::live_design(cx);
 
-    // Register grid widgets
+    // Register grid widgets (the minimized strip before the grid that hosts it)
+    crate::grid::minimized_strip::live_design(cx);
     crate::grid::panel_grid::live_design(cx);
     crate::grid::footer_grid::live_design(cx);
 
-    // Register shell components (sidebar_menu must come before sidebar)
+    // Register shell components (menu_bar and toolbar must come before header,
+    // status_bar before footer, sidebar_menu before sidebar)
+    crate::shell::menu_bar::live_design(cx);
+    crate::shell::toolbar::live_design(cx);
     crate::shell::header::live_design(cx);
+    crate::shell::status_bar::live_design(cx);
     crate::shell::footer::live_design(cx);
+    crate::shell::notifications::live_design(cx);
+    crate::shell::dialog::live_design(cx);
     crate::shell::sidebar_menu::live_design(cx);
+    crate::shell::sidebar_tree::live_design(cx);
     crate::shell::sidebar::live_design(cx);
+    crate::shell::activity_bar::live_design(cx);
     crate::shell::layout::live_design(cx);
 }
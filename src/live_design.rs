@@ -49,4 +49,40 @@ live_design! {
     pub TEXT_SMALL = <FONT_REGULAR> {
         font_size: 11.0
     }
+
+    // ============================================================================
+    // PALETTE DEFAULTS (light palette, see theme::palette::ShellPalette)
+    // ============================================================================
+
+    pub PALETTE_BG_APP = #ffffff
+    pub PALETTE_BG_CANVAS = #e2e8f0
+    pub PALETTE_BG_PANEL = #ffffff
+    pub PALETTE_BG_SIDEBAR = #f1f5f9
+    pub PALETTE_BG_LIST = #f8fafc
+    pub PALETTE_BG_TITLE_BAR = #f1f5f9
+    pub PALETTE_BG_ACTIVITY_BAR = #f1f5f9
+    pub PALETTE_BG_HOVER = #e2e8f0
+    pub PALETTE_BG_SELECTED = #dbe9fc
+    pub PALETTE_BG_PRESSED = #cad5e3
+
+    pub PALETTE_TEXT_PRIMARY = #1f2937
+    pub PALETTE_TEXT_SECONDARY = #3f4855
+    pub PALETTE_TEXT_MUTED = #64748b
+    pub PALETTE_TEXT_FAINT = #94a3b8
+    pub PALETTE_ICON = #6b7280
+
+    pub PALETTE_BORDER = #e2e8f0
+    pub PALETTE_ACCENT = #3b82f6
+    pub PALETTE_ON_ACCENT = #ffffff
+    pub PALETTE_DANGER = #ef4444
+    pub PALETTE_WARNING = #f59e0b
+    pub PALETTE_SUCCESS = #38cc75
+
+    pub PALETTE_BG_OVERLAY = #ffffff
+    pub PALETTE_BG_OVERLAY_HEADER = #f1edfc
+    pub PALETTE_BG_OVERLAY_HOVER = #eeeafa
+    pub PALETTE_BG_OVERLAY_PRESSED = #e1dbf4
+    pub PALETTE_OVERLAY_TEXT = #584a80
+    pub PALETTE_OVERLAY_ICON = #8b77c1
+    pub PALETTE_OVERLAY_BORDER = #d8d2f0
 }
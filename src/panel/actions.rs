@@ -2,30 +2,66 @@
 
 use makepad_widgets::*;
 use crate::grid::{LayoutState, FooterLayoutState};
+use crate::panel::context_menu::PanelMenuCommand;
+use crate::panel::panel_id::PanelId;
 
 /// Actions emitted by Panel widgets to communicate with parent containers.
 ///
 /// These are dispatched via `cx.widget_action()` and handled by PanelGrid/FooterGrid.
+/// Panels are named by their interned `PanelId`, which maps back to the semantic ID.
 #[derive(Clone, Debug, DefaultNone)]
 pub enum PanelAction {
     /// Panel close button clicked
-    Close(LiveId),
+    Close(PanelId),
+
+    /// Close button of a panel that needs confirmation was clicked (panel ID)
+    ///
+    /// Emitted by PanelGrid/FooterGrid instead of closing; ShellLayout asks the
+    /// user and closes the panel through the grid's `close_panel`.
+    CloseRequested(PanelId),
+
+    /// Minimize button clicked (for main grid panels)
+    ///
+    /// PanelGrid collapses the panel into a chip; clicking the chip restores it.
+    Minimize(PanelId),
 
     /// Maximize/restore button clicked (for main grid panels)
-    Maximize(LiveId),
+    Maximize(PanelId),
 
     /// Fullscreen button clicked (panel takes entire dock space)
-    Fullscreen(LiveId),
+    Fullscreen(PanelId),
+
+    /// Panel was clicked and should become the focused panel
+    Focus(PanelId),
+
+    /// Title bar was right-clicked at the given absolute position
+    ///
+    /// ShellLayout opens the panel's context menu there.
+    ContextMenu(PanelId, DVec2),
+
+    /// The title was edited in place (double-click, then Enter)
+    ///
+    /// PanelGrid records the title in its `LayoutState` and emits `LayoutChanged`.
+    Renamed { panel_id: PanelId, title: String },
+
+    /// A title bar action was clicked (`checked` = new state of a toggle action)
+    TitleAction { panel_id: PanelId, action_id: String, checked: Option<bool> },
+
+    /// An entry of a panel's context menu was chosen (emitted by ShellLayout)
+    ///
+    /// Built-in commands the shell can perform (close, close others, maximize,
+    /// rename) are already done when this arrives; the rest are up to the app.
+    MenuCommand { panel_id: PanelId, command: PanelMenuCommand },
 
     /// Drag operation started (threshold exceeded)
-    StartDrag(LiveId),
+    StartDrag(PanelId),
 
     /// Drag operation ended - finger released at position
     ///
     /// Emitted by Panel when FingerUp occurs during an active drag.
     /// Contains the panel ID and the absolute cursor position for drop calculation.
     /// PanelGrid/FooterGrid handle this to complete the drop operation.
-    EndDrag(LiveId, DVec2),
+    EndDrag(PanelId, DVec2),
 
     /// Layout has changed (emitted by PanelGrid for persistence)
     LayoutChanged(LayoutState),
@@ -0,0 +1,275 @@
+//! Title bar context menu of a panel
+//!
+//! Right-clicking a panel's title bar opens a menu with the built-in panel
+//! commands followed by the entries of the panel's `PanelDefinition`.
+//! ShellLayout shows the menu and reports the choice as
+//! `PanelAction::MenuCommand` with the semantic panel ID.
+//!
+//! A "Reopen Panel" submenu lists the closed panels with their icon, badge
+//! and "modified" dot; ShellLayout can also show it on its own.
+
+use crate::panel::{PanelDecoration, PanelId};
+use crate::registry::PanelDefinition;
+use crate::shell::menu::{MenuEntry, MenuItem};
+
+/// Item IDs of the built-in entries
+const CLOSE: &str = "panel.close";
+const CLOSE_OTHERS: &str = "panel.close_others";
+const MAXIMIZE: &str = "panel.maximize";
+const PIN: &str = "panel.pin";
+const MOVE_TO_FOOTER: &str = "panel.move_to_footer";
+const POP_OUT: &str = "panel.pop_out";
+const RENAME: &str = "panel.rename";
+const RESET: &str = "panel.reset";
+const REOPEN_MENU: &str = "panel.reopen";
+/// Prefix of the reopen entries; the panel ID follows
+const REOPEN: &str = "panel.reopen:";
+
+/// A command chosen from a panel's context menu
+#[derive(Clone, Debug, PartialEq)]
+pub enum PanelMenuCommand {
+    /// Close the panel
+    Close,
+
+    /// Close the other panels of the same grid
+    CloseOthers,
+
+    /// Toggle maximize (main grid) or fullscreen (footer)
+    Maximize,
+
+    /// Pin the panel in place, or unpin it
+    Pin,
+
+    /// Move the panel into the footer (handled by the app)
+    MoveToFooter,
+
+    /// Show the panel in its own window (handled by the app)
+    PopOut,
+
+    /// Change the panel's title
+    Rename,
+
+    /// Return the panel's content to its initial state (handled by the app)
+    Reset,
+
+    /// Show a closed panel again (from the "Reopen Panel" menu)
+    Reopen(PanelId),
+
+    /// An entry from the panel's `PanelDefinition` (item ID)
+    Custom(String),
+}
+
+impl PanelMenuCommand {
+    /// Map a menu item ID back to its command
+    pub fn from_item_id(id: &str) -> Self {
+        match id {
+            CLOSE => Self::Close,
+            CLOSE_OTHERS => Self::CloseOthers,
+            MAXIMIZE => Self::Maximize,
+            PIN => Self::Pin,
+            MOVE_TO_FOOTER => Self::MoveToFooter,
+            POP_OUT => Self::PopOut,
+            RENAME => Self::Rename,
+            RESET => Self::Reset,
+            id if id.starts_with(REOPEN) => Self::Reopen(PanelId::new(&id[REOPEN.len()..])),
+            custom => Self::Custom(custom.to_string()),
+        }
+    }
+
+    /// Menu item ID of the command
+    pub fn item_id(&self) -> String {
+        match self {
+            Self::Close => CLOSE.to_string(),
+            Self::CloseOthers => CLOSE_OTHERS.to_string(),
+            Self::Maximize => MAXIMIZE.to_string(),
+            Self::Pin => PIN.to_string(),
+            Self::MoveToFooter => MOVE_TO_FOOTER.to_string(),
+            Self::PopOut => POP_OUT.to_string(),
+            Self::Rename => RENAME.to_string(),
+            Self::Reset => RESET.to_string(),
+            Self::Reopen(panel_id) => format!("{}{}", REOPEN, panel_id),
+            Self::Custom(id) => id.clone(),
+        }
+    }
+}
+
+/// Where a panel sits when its context menu opens
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PanelMenuContext {
+    /// The panel is in the footer rather than the main grid
+    pub in_footer: bool,
+
+    /// The panel is maximized (main grid) or fullscreen (footer)
+    pub maximized: bool,
+
+    /// Other panels share the panel's grid
+    pub has_others: bool,
+
+    /// The panel is pinned (by the user or its definition)
+    pub pinned: bool,
+
+    /// The header's layout lock is on
+    pub layout_locked: bool,
+}
+
+/// Build the context menu of a panel
+///
+/// Built-in entries come first; the definition's entries follow after a separator.
+/// Entries the panel can't use (e.g. Close for a panel that isn't closable) are disabled.
+/// Pinned panels and a locked layout disable the entries that close or move panels.
+pub fn panel_menu_entries(definition: Option<&PanelDefinition>, context: PanelMenuContext) -> Vec<MenuEntry> {
+    let movable = !context.pinned && !context.layout_locked;
+    let closable = definition.map_or(true, |def| def.closable) && movable;
+    // Panels pinned by their definition stay pinned
+    let pin_toggle = definition.map_or(true, |def| !def.pinned);
+    let renamable = definition.map_or(true, |def| def.renamable);
+    let maximizable = definition.map_or(true, |def| {
+        if context.in_footer { def.fullscreenable } else { def.maximizable }
+    });
+    let maximize_label = match (context.in_footer, context.maximized) {
+        (false, false) => "Maximize",
+        (false, true) => "Restore",
+        (true, false) => "Fullscreen",
+        (true, true) => "Exit Fullscreen",
+    };
+
+    let pin_label = if context.pinned { "Unpin Panel" } else { "Pin Panel" };
+
+    let enabled = |item: MenuItem, enabled: bool| if enabled { item } else { item.disabled() };
+    let mut entries: Vec<MenuEntry> = vec![
+        enabled(MenuItem::new(CLOSE, "Close"), closable).into(),
+        enabled(MenuItem::new(CLOSE_OTHERS, "Close Others"), context.has_others && !context.layout_locked).into(),
+        MenuEntry::Separator,
+        enabled(MenuItem::new(MAXIMIZE, maximize_label), maximizable).into(),
+        enabled(MenuItem::new(PIN, pin_label), pin_toggle).into(),
+        enabled(MenuItem::new(MOVE_TO_FOOTER, "Move to Footer"), !context.in_footer && movable).into(),
+        MenuItem::new(POP_OUT, "Pop Out").into(),
+        MenuEntry::Separator,
+        // Footer titles are per slot, not per panel
+        enabled(MenuItem::new(RENAME, "Rename..."), renamable && !context.in_footer).into(),
+        MenuItem::new(RESET, "Reset Panel").into(),
+    ];
+
+    if let Some(def) = definition.filter(|def| !def.menu_entries.is_empty()) {
+        entries.push(MenuEntry::Separator);
+        entries.extend(def.menu_entries.iter().cloned());
+    }
+    entries
+}
+
+/// A closed panel as listed in the "Reopen Panel" menu
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClosedPanel {
+    /// Semantic panel ID
+    pub id: PanelId,
+
+    /// Display title
+    pub title: String,
+
+    /// Icon, badge and "modified" dot shown with the title
+    pub decoration: PanelDecoration,
+}
+
+/// Build the entries of the "Reopen Panel" menu, one per closed panel
+pub fn reopen_menu_entries(closed: &[ClosedPanel]) -> Vec<MenuEntry> {
+    closed.iter().map(|panel| {
+        let mut item = MenuItem::new(PanelMenuCommand::Reopen(panel.id).item_id(), panel.title.clone())
+            .with_dirty(panel.decoration.dirty);
+        item.icon = panel.decoration.icon.clone();
+        item.badge = panel.decoration.badge.clone();
+        item.into()
+    }).collect()
+}
+
+/// Build the "Reopen Panel" submenu item (disabled when no panel is closed)
+pub fn reopen_menu_item(closed: &[ClosedPanel]) -> MenuItem {
+    let item = MenuItem::new(REOPEN_MENU, "Reopen Panel").with_submenu(reopen_menu_entries(closed));
+    if closed.is_empty() { item.disabled() } else { item }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_item_ids_round_trip() {
+        let commands = [
+            PanelMenuCommand::Close,
+            PanelMenuCommand::CloseOthers,
+            PanelMenuCommand::Maximize,
+            PanelMenuCommand::Pin,
+            PanelMenuCommand::MoveToFooter,
+            PanelMenuCommand::PopOut,
+            PanelMenuCommand::Rename,
+            PanelMenuCommand::Reset,
+            PanelMenuCommand::Reopen(PanelId::new("console")),
+            PanelMenuCommand::Custom("export".to_string()),
+        ];
+        for command in commands {
+            assert_eq!(PanelMenuCommand::from_item_id(&command.item_id()), command);
+        }
+    }
+
+    #[test]
+    fn test_reopen_entries_carry_decorations() {
+        let closed = [ClosedPanel {
+            id: PanelId::new("problems"),
+            title: "Problems".to_string(),
+            decoration: PanelDecoration { icon: Some("icons/warning.svg".to_string()), badge: Some("3".to_string()), dirty: true },
+        }];
+        let item = reopen_menu_item(&closed);
+        assert!(item.enabled);
+
+        let entry = item.submenu[0].item().unwrap();
+        assert_eq!(PanelMenuCommand::from_item_id(&entry.id), PanelMenuCommand::Reopen(PanelId::new("problems")));
+        assert_eq!(entry.label, "Problems");
+        assert_eq!(entry.icon.as_deref(), Some("icons/warning.svg"));
+        assert_eq!(entry.badge.as_deref(), Some("3"));
+        assert!(entry.dirty);
+
+        assert!(!reopen_menu_item(&[]).enabled, "nothing to reopen");
+    }
+
+    #[test]
+    fn test_definition_entries_are_appended() {
+        let def = PanelDefinition::new("editor", "Editor")
+            .with_closable(false)
+            .with_renamable(false)
+            .with_menu_entry(MenuItem::new("format", "Format Document"));
+        let entries = panel_menu_entries(Some(&def), PanelMenuContext::default());
+
+        assert_eq!(entries.last().and_then(|e| e.item()).map(|i| i.id.as_str()), Some("format"));
+        assert_eq!(entries[entries.len() - 2], MenuEntry::Separator);
+        assert!(!entries[0].is_selectable(), "close is disabled for a non-closable panel");
+        assert!(!entries[1].is_selectable(), "close others needs other panels");
+        let rename = entries.iter().filter_map(|e| e.item()).find(|i| i.id == RENAME).unwrap();
+        assert!(!rename.enabled, "rename is disabled for a non-renamable panel");
+    }
+
+    #[test]
+    fn test_footer_panel_entries() {
+        let def = PanelDefinition::footer("console", "Console");
+        let context = PanelMenuContext { in_footer: true, maximized: true, has_others: true, ..PanelMenuContext::default() };
+        let entries = panel_menu_entries(Some(&def), context);
+        let item = |id: &str| entries.iter().filter_map(|e| e.item()).find(|i| i.id == id).unwrap();
+
+        assert_eq!(item(MAXIMIZE).label, "Exit Fullscreen");
+        assert!(item(MAXIMIZE).enabled);
+        assert!(!item(MOVE_TO_FOOTER).enabled);
+        assert!(item(CLOSE_OTHERS).enabled);
+    }
+
+    #[test]
+    fn test_pinned_panel_entries() {
+        let def = PanelDefinition::new("editor", "Editor").with_pinned(true);
+        let context = PanelMenuContext { pinned: true, has_others: true, ..PanelMenuContext::default() };
+        let entries = panel_menu_entries(Some(&def), context);
+        let item = |id: &str| entries.iter().filter_map(|e| e.item()).find(|i| i.id == id).unwrap();
+
+        assert_eq!(item(PIN).label, "Unpin Panel");
+        assert!(!item(PIN).enabled, "panels pinned by their definition stay pinned");
+        assert!(!item(CLOSE).enabled);
+        assert!(!item(MOVE_TO_FOOTER).enabled);
+        assert!(item(CLOSE_OTHERS).enabled);
+    }
+}
@@ -4,7 +4,13 @@
 //! draggable panel in the grid layout.
 
 mod actions;
+pub mod context_menu;
 pub mod panel;
+pub mod panel_id;
+pub mod title_actions;
 
 pub use actions::PanelAction;
-pub use panel::{Panel, PanelRef};
+pub use context_menu::{ClosedPanel, PanelMenuCommand, PanelMenuContext, panel_menu_entries, reopen_menu_entries, reopen_menu_item};
+pub use panel::{Panel, PanelDecoration, PanelRef};
+pub use panel_id::PanelId;
+pub use title_actions::PanelTitleAction;
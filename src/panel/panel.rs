@@ -3,9 +3,10 @@
 //! This is the core panel component for the app shell grid system.
 
 use makepad_widgets::*;
-use crate::panel::PanelAction;
-use crate::theme::colors::panel_colors;
-use crate::theme::get_global_dark_mode;
+use crate::panel::{PanelAction, PanelId};
+use crate::panel::title_actions::{PanelTitleAction, PanelTitleActionsWidgetExt};
+use crate::shell::sidebar_menu::svg_icon_value;
+use crate::theme::{ShellPalette, ThemeListener, ThemeTracker};
 
 live_design! {
     use link::theme::*;
@@ -13,18 +14,22 @@ live_design! {
     use link::widgets::*;
 
     use crate::live_design::*;
+    use crate::panel::title_actions::PanelTitleActions;
 
     pub Panel = {{Panel}} {
         width: 200
         height: 150
 
         closable: true
+        minimizable: false
         maximizable: true
         fullscreenable: false
+        renamable: true
 
         show_bg: true
         draw_bg: {
-            instance dark_mode: 0.0
+            uniform bg_panel: (PALETTE_BG_PANEL)
+            uniform border: (PALETTE_BORDER)
             uniform border_width: 1.0
 
             fn pixel(self) -> vec4 {
@@ -32,19 +37,11 @@ live_design! {
                 // Square corners - no border radius
                 sdf.rect(0.0, 0.0, self.rect_size.x, self.rect_size.y);
 
-                // Panel background - responds to dark_mode
-                let light_bg = vec4(1.0, 1.0, 1.0, 1.0);           // white
-                let dark_bg = vec4(0.122, 0.161, 0.231, 1.0);      // slate-800
-                let bg_color = mix(light_bg, dark_bg, self.dark_mode);
-                sdf.fill(bg_color);
+                // Panel background from the palette
+                sdf.fill(self.bg_panel);
 
                 // Border
-                let border_color = mix(
-                    vec4(0.886, 0.910, 0.941, 1.0),  // slate-200
-                    vec4(0.200, 0.255, 0.333, 1.0),  // slate-700
-                    self.dark_mode
-                );
-                sdf.stroke(border_color, self.border_width);
+                sdf.stroke(self.border, self.border_width);
                 return sdf.result;
             }
         }
@@ -62,12 +59,9 @@ live_design! {
 
             show_bg: true
             draw_bg: {
-                instance dark_mode: 0.0
+                uniform bg_title_bar: (PALETTE_BG_TITLE_BAR)
                 fn pixel(self) -> vec4 {
-                    // Light: slate-100, Dark: slate-700
-                    let light = vec4(0.945, 0.961, 0.976, 1.0);
-                    let dark = vec4(0.200, 0.255, 0.333, 1.0);
-                    return mix(light, dark, self.dark_mode);
+                    return self.bg_title_bar;
                 }
             }
 
@@ -80,16 +74,12 @@ live_design! {
 
                 show_bg: true
                 draw_bg: {
-                    instance dark_mode: 0.0
+                    uniform text_faint: (PALETTE_TEXT_FAINT)
 
                     fn pixel(self) -> vec4 {
                         let sdf = Sdf2d::viewport(self.pos * self.rect_size);
                         let dot_r = 1.5;
-
-                        // Dot color based on theme
-                        let light_dot = vec4(0.580, 0.639, 0.722, 1.0);  // slate-400
-                        let dark_dot = vec4(0.392, 0.455, 0.545, 1.0);   // slate-500
-                        let dot_color = mix(light_dot, dark_dot, self.dark_mode);
+                        let dot_color = self.text_faint;
 
                         let col1_x = 5.0;
                         let col2_x = 11.0;
@@ -115,21 +105,115 @@ live_design! {
                 }
             }
 
+            // Lock shown in place of the drag handle while the panel can't be moved
+            lock_icon = <View> {
+                width: 16
+                height: 20
+                margin: { right: 8 }
+                visible: false
+
+                show_bg: true
+                draw_bg: {
+                    uniform text_faint: (PALETTE_TEXT_FAINT)
+
+                    fn pixel(self) -> vec4 {
+                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                        let color = self.text_faint;
+
+                        // Shackle, its lower half hidden by the body
+                        sdf.circle(8.0, 8.0, 3.0);
+                        sdf.stroke(color, 1.3);
+
+                        // Body
+                        sdf.box(3.5, 9.5, 9.0, 6.5, 1.0);
+                        sdf.fill(color);
+                        return sdf.result;
+                    }
+                }
+            }
+
+            title_icon = <Icon> {
+                visible: false
+                margin: { right: 6 }
+                icon_walk: { width: 14, height: 14 }
+                draw_icon: {
+                    uniform icon: (PALETTE_ICON)
+                    fn get_color(self) -> vec4 {
+                        return self.icon;
+                    }
+                }
+            }
+
             title = <Label> {
                 draw_text: {
-                    instance dark_mode: 0.0
+                    uniform text_secondary: (PALETTE_TEXT_SECONDARY)
                     text_style: <FONT_MEDIUM> { font_size: 11.0 }
                     fn get_color(self) -> vec4 {
-                        // Light: gray-700, Dark: slate-200
-                        let light = vec4(0.247, 0.282, 0.333, 1.0);
-                        let dark = vec4(0.886, 0.910, 0.941, 1.0);
-                        return mix(light, dark, self.dark_mode);
+                        return self.text_secondary;
                     }
                 }
                 text: "Panel"
             }
 
-            <View> { width: Fill }
+            // Inline title editor, shown in place of the title on double-click
+            title_input = <TextInput> {
+                width: 160, height: Fit
+                visible: false
+                empty_text: ""
+            }
+
+            // "Modified" indicator
+            dirty_dot = <View> {
+                width: 6, height: 6
+                margin: { left: 6 }
+                visible: false
+
+                show_bg: true
+                draw_bg: {
+                    uniform text_secondary: (PALETTE_TEXT_SECONDARY)
+                    fn pixel(self) -> vec4 {
+                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                        sdf.circle(self.rect_size.x * 0.5, self.rect_size.y * 0.5, self.rect_size.x * 0.5);
+                        sdf.fill(self.text_secondary);
+                        return sdf.result;
+                    }
+                }
+            }
+
+            // Count or short text, e.g. the number of errors
+            badge = <View> {
+                width: Fit, height: Fit
+                margin: { left: 6 }
+                padding: { left: 5, right: 5, top: 1, bottom: 1 }
+                visible: false
+
+                show_bg: true
+                draw_bg: {
+                    uniform accent: (PALETTE_ACCENT)
+                    fn pixel(self) -> vec4 {
+                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                        sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, self.rect_size.y * 0.5);
+                        sdf.fill(self.accent);
+                        return sdf.result;
+                    }
+                }
+
+                badge_label = <Label> {
+                    draw_text: {
+                        uniform on_accent: (PALETTE_ON_ACCENT)
+                        text_style: <FONT_SEMIBOLD> { font_size: 9.0 }
+                        fn get_color(self) -> vec4 {
+                            return self.on_accent;
+                        }
+                    }
+                    text: ""
+                }
+            }
+
+            title_spacer = <View> { width: Fill }
+
+            // App actions (clear, filter, pause...), overflowing into a menu
+            title_actions = <PanelTitleActions> {}
 
             // Fullscreen button (arrows pointing outward)
             fullscreen_btn = <Button> {
@@ -140,18 +224,15 @@ live_design! {
                 visible: false
                 text: ""
                 draw_bg: {
-                    instance dark_mode: 0.0
+                    uniform icon: (PALETTE_ICON)
+                    uniform accent: (PALETTE_ACCENT)
 
                     fn pixel(self) -> vec4 {
                         let sdf = Sdf2d::viewport(self.pos * self.rect_size);
                         let inset = 5.0;
                         let arrow_len = 4.0;
 
-                        let light_color = vec4(0.420, 0.447, 0.502, 1.0);
-                        let dark_color = vec4(0.580, 0.639, 0.722, 1.0);
-                        let hover_color = vec4(0.231, 0.510, 0.965, 1.0);
-                        let base = mix(light_color, dark_color, self.dark_mode);
-                        let color = mix(base, hover_color, self.hover);
+                        let color = mix(self.icon, self.accent, self.hover);
 
                         // Four corners with arrows pointing outward
                         // Top-left arrow
@@ -192,7 +273,8 @@ live_design! {
                 visible: false
                 text: ""
                 draw_bg: {
-                    instance dark_mode: 0.0
+                    uniform icon: (PALETTE_ICON)
+                    uniform accent: (PALETTE_ACCENT)
 
                     fn pixel(self) -> vec4 {
                         let sdf = Sdf2d::viewport(self.pos * self.rect_size);
@@ -201,11 +283,7 @@ live_design! {
                         let cx = self.rect_size.x / 2.0;
                         let cy = self.rect_size.y / 2.0;
 
-                        let light_color = vec4(0.420, 0.447, 0.502, 1.0);
-                        let dark_color = vec4(0.580, 0.639, 0.722, 1.0);
-                        let hover_color = vec4(0.231, 0.510, 0.965, 1.0);
-                        let base = mix(light_color, dark_color, self.dark_mode);
-                        let color = mix(base, hover_color, self.hover);
+                        let color = mix(self.icon, self.accent, self.hover);
 
                         // Four corners with arrows pointing inward (toward center)
                         // Top-left pointing to center
@@ -233,6 +311,32 @@ live_design! {
                 }
             }
 
+            // Minimize button (a line at the bottom)
+            min_btn = <Button> {
+                width: 20
+                height: 20
+                padding: 0
+                margin: { right: 4 }
+                visible: false
+                text: ""
+                draw_bg: {
+                    uniform icon: (PALETTE_ICON)
+                    uniform accent: (PALETTE_ACCENT)
+
+                    fn pixel(self) -> vec4 {
+                        let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                        let inset = 5.0;
+
+                        let color = mix(self.icon, self.accent, self.hover);
+
+                        sdf.move_to(inset, self.rect_size.y - inset - 1.0);
+                        sdf.line_to(self.rect_size.x - inset, self.rect_size.y - inset - 1.0);
+                        sdf.stroke(color, 1.5);
+                        return sdf.result;
+                    }
+                }
+            }
+
             max_btn = <Button> {
                 width: 20
                 height: 20
@@ -240,18 +344,14 @@ live_design! {
                 margin: { right: 4 }
                 text: ""
                 draw_bg: {
-                    instance dark_mode: 0.0
+                    uniform icon: (PALETTE_ICON)
+                    uniform accent: (PALETTE_ACCENT)
 
                     fn pixel(self) -> vec4 {
                         let sdf = Sdf2d::viewport(self.pos * self.rect_size);
                         let inset = 5.0;
 
-                        // Icon color
-                        let light_color = vec4(0.420, 0.447, 0.502, 1.0);  // gray-500
-                        let dark_color = vec4(0.580, 0.639, 0.722, 1.0);   // slate-400
-                        let hover_color = vec4(0.231, 0.510, 0.965, 1.0);  // blue-500
-                        let base = mix(light_color, dark_color, self.dark_mode);
-                        let color = mix(base, hover_color, self.hover);
+                        let color = mix(self.icon, self.accent, self.hover);
 
                         sdf.rect(inset, inset, self.rect_size.x - inset * 2.0, self.rect_size.y - inset * 2.0);
                         sdf.stroke(color, 1.5);
@@ -268,18 +368,15 @@ live_design! {
                 visible: false
                 text: ""
                 draw_bg: {
-                    instance dark_mode: 0.0
+                    uniform icon: (PALETTE_ICON)
+                    uniform accent: (PALETTE_ACCENT)
 
                     fn pixel(self) -> vec4 {
                         let sdf = Sdf2d::viewport(self.pos * self.rect_size);
                         let inset = 5.0;
                         let offset = 2.0;
 
-                        let light_color = vec4(0.420, 0.447, 0.502, 1.0);
-                        let dark_color = vec4(0.580, 0.639, 0.722, 1.0);
-                        let hover_color = vec4(0.231, 0.510, 0.965, 1.0);
-                        let base = mix(light_color, dark_color, self.dark_mode);
-                        let color = mix(base, hover_color, self.hover);
+                        let color = mix(self.icon, self.accent, self.hover);
 
                         // Back square
                         sdf.rect(inset + offset, inset, self.rect_size.x - inset * 2.0 - offset, self.rect_size.y - inset * 2.0 - offset);
@@ -299,17 +396,14 @@ live_design! {
                 margin: 0
                 text: ""
                 draw_bg: {
-                    instance dark_mode: 0.0
+                    uniform icon: (PALETTE_ICON)
+                    uniform danger: (PALETTE_DANGER)
 
                     fn pixel(self) -> vec4 {
                         let sdf = Sdf2d::viewport(self.pos * self.rect_size);
                         let inset = 6.0;
 
-                        let light_color = vec4(0.420, 0.447, 0.502, 1.0);
-                        let dark_color = vec4(0.580, 0.639, 0.722, 1.0);
-                        let hover_color = vec4(0.937, 0.267, 0.267, 1.0);  // red-500
-                        let base = mix(light_color, dark_color, self.dark_mode);
-                        let color = mix(base, hover_color, self.hover);
+                        let color = mix(self.icon, self.danger, self.hover);
 
                         sdf.move_to(inset, inset);
                         sdf.line_to(self.rect_size.x - inset, self.rect_size.y - inset);
@@ -332,19 +426,31 @@ live_design! {
     }
 }
 
+/// Icon, badge and "modified" dot shown next to a panel's title
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PanelDecoration {
+    /// SVG icon path shown before the title
+    pub icon: Option<String>,
+
+    /// Count or short text shown after the title (e.g. "3" errors)
+    pub badge: Option<String>,
+
+    /// Show the "modified" dot
+    pub dirty: bool,
+}
+
 #[derive(Live, LiveHook, Widget)]
 pub struct Panel {
     #[deref]
     view: View,
 
-    /// LiveId for internal identification (hash of panel_id_str)
+    /// Interned semantic ID for this panel (e.g., "editor", "console")
     #[rust]
-    panel_id: LiveId,
+    panel_id: PanelId,
 
-    /// Semantic string ID for this panel (e.g., "editor", "console")
-    /// This is stored separately because LiveId is a hash and can't be reversed
+    /// LiveId pushed to the scope path of the panel's actions (case preserved)
     #[rust]
-    panel_id_str: String,
+    scope_id: LiveId,
 
     #[live]
     title: String,
@@ -352,12 +458,19 @@ pub struct Panel {
     #[live]
     closable: bool,
 
+    #[live]
+    minimizable: bool,
+
     #[live]
     maximizable: bool,
 
     #[live]
     fullscreenable: bool,
 
+    /// Double-clicking the title edits it in place
+    #[live]
+    renamable: bool,
+
     #[rust]
     panel_index: usize,
 
@@ -373,25 +486,66 @@ pub struct Panel {
     #[rust]
     drag_start: DVec2,
 
+    /// The current press is a right-click that opened the context menu (never a drag)
+    #[rust]
+    context_press: bool,
+
+    /// The title is being edited in place
+    #[rust]
+    editing_title: bool,
+
+    /// The panel can't be dragged or closed (pinned, or the layout is locked)
+    #[rust]
+    locked: bool,
+
+    #[rust]
+    decoration: PanelDecoration,
+
     #[rust]
     needs_visual_update: bool,
 
     /// Reference to user-provided content widget (for programmatic injection)
     #[rust]
     content_widget: Option<WidgetRef>,
+
+    #[rust]
+    theme_tracker: ThemeTracker,
+}
+
+impl ThemeListener for Panel {
+    fn theme_tracker(&mut self) -> &mut ThemeTracker {
+        &mut self.theme_tracker
+    }
+
+    fn on_dark_mode_change(&mut self, _cx: &mut Cx, _dark_mode: f64) {}
+
+    fn on_palette_change(&mut self, cx: &mut Cx, palette: &ShellPalette) {
+        self.apply_palette_internal(cx, palette);
+    }
 }
 
 impl Widget for Panel {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
-        // Push panel ID to scope path so content can identify which panel it's in
+        // Provide the panel ID so content can identify which panel it's in
         // Capture actions to check for button clicks, then forward non-Panel actions
-        let actions = scope.with_id(self.panel_id, |scope| {
-            cx.capture_actions(|cx| {
-                self.view.handle_event(cx, event, scope);
+        let panel_id = self.panel_id;
+        let actions = scope.with_id(self.scope_id, |scope| {
+            panel_id.provide(|| {
+                cx.capture_actions(|cx| {
+                    self.view.handle_event(cx, event, scope);
+                })
             })
         });
 
         // Check for Panel-specific button clicks
+        if let Some((action_id, checked)) = self.view.panel_title_actions(id!(title_bar.title_actions)).clicked(&actions) {
+            cx.widget_action(
+                self.widget_uid(),
+                &scope.path,
+                PanelAction::TitleAction { panel_id: self.panel_id, action_id, checked },
+            );
+        }
+
         if self.view.button(id!(title_bar.close_btn)).clicked(&actions) {
             cx.widget_action(
                 self.widget_uid(),
@@ -400,6 +554,14 @@ impl Widget for Panel {
             );
         }
 
+        if self.view.button(id!(title_bar.min_btn)).clicked(&actions) {
+            cx.widget_action(
+                self.widget_uid(),
+                &scope.path,
+                PanelAction::Minimize(self.panel_id),
+            );
+        }
+
         if self.view.button(id!(title_bar.max_btn)).clicked(&actions)
             || self.view.button(id!(title_bar.restore_btn)).clicked(&actions)
         {
@@ -420,6 +582,10 @@ impl Widget for Panel {
             );
         }
 
+        if self.editing_title {
+            self.handle_title_edit(cx, event, scope);
+        }
+
         // IMPORTANT: Forward all captured actions to the parent so child widget actions
         // (like TimelineAction::Seek, PlaybackAction, etc.) reach the app
         cx.extend_actions(actions);
@@ -433,11 +599,15 @@ impl Widget for Panel {
             Hit::FingerDown(fe) => {
                 self.is_dragging = false;
                 self.drag_start = fe.abs;
+                self.handle_press(cx, scope, &fe);
+                handled = true;
+            }
+            Hit::FingerMove(_) if self.context_press => {
                 handled = true;
             }
             Hit::FingerMove(fe) => {
                 let dist = (fe.abs - self.drag_start).length();
-                if !self.is_dragging && dist > 10.0 {
+                if !self.locked && !self.is_dragging && dist > 10.0 {
                     self.is_dragging = true;
                     cx.widget_action(
                         self.widget_uid(),
@@ -456,6 +626,7 @@ impl Widget for Panel {
                     );
                 }
                 self.is_dragging = false;
+                self.context_press = false;
                 handled = true;
             }
             _ => {}
@@ -467,9 +638,13 @@ impl Widget for Panel {
                 Hit::FingerDown(fe) => {
                     self.is_dragging = false;
                     self.drag_start = fe.abs;
+                    self.handle_press(cx, scope, &fe);
+                    if fe.tap_count == 2 && self.view.label(id!(title_bar.title)).area().rect(cx).contains(fe.abs) {
+                        self.begin_title_edit(cx);
+                    }
                 }
                 Hit::FingerMove(fe) => {
-                    if !self.is_dragging && (fe.abs - self.drag_start).length() > 10.0 {
+                    if !self.context_press && !self.editing_title && !self.locked && !self.is_dragging && (fe.abs - self.drag_start).length() > 10.0 {
                         self.is_dragging = true;
                         cx.widget_action(
                             self.widget_uid(),
@@ -487,19 +662,32 @@ impl Widget for Panel {
                         );
                     }
                     self.is_dragging = false;
+                    self.context_press = false;
                 }
                 _ => {}
             }
         }
+
+        // A press in the content that no child widget claimed focuses the panel
+        // (title bar presses focus it in `handle_press`)
+        if let Hit::FingerDown(_) = event.hits(cx, self.view.area()) {
+            cx.widget_action(
+                self.widget_uid(),
+                &scope.path,
+                PanelAction::Focus(self.panel_id),
+            );
+        }
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
-        // Apply global theme on every draw
-        let dm = get_global_dark_mode();
-        self.apply_dark_mode_internal(cx, dm);
+        // Apply the shell theme when it changed
+        self.sync_theme(cx);
 
         self.apply_visual_update(cx);
 
+        // Minimize button (for main grid)
+        self.view.button(id!(title_bar.min_btn)).set_visible(cx, self.minimizable && !self.is_maximized);
+
         // Maximize buttons (for main grid)
         self.view.button(id!(title_bar.max_btn)).set_visible(cx, !self.is_maximized && self.maximizable);
         self.view.button(id!(title_bar.restore_btn)).set_visible(cx, self.is_maximized && self.maximizable);
@@ -508,18 +696,113 @@ impl Widget for Panel {
         self.view.button(id!(title_bar.fullscreen_btn)).set_visible(cx, !self.is_fullscreen && self.fullscreenable);
         self.view.button(id!(title_bar.restore_fullscreen_btn)).set_visible(cx, self.is_fullscreen && self.fullscreenable);
 
-        // Close button
-        self.view.button(id!(title_bar.close_btn)).set_visible(cx, self.closable);
+        // Close button and drag handle, replaced by a lock while the panel is locked
+        self.view.button(id!(title_bar.close_btn)).set_visible(cx, self.closable && !self.locked);
+        self.view.view(id!(title_bar.drag_handle)).set_visible(cx, !self.locked);
+        self.view.view(id!(title_bar.lock_icon)).set_visible(cx, self.locked);
 
-        // Draw with panel ID in scope path so content can identify which panel it's in
-        // Content widgets can access panel ID via: scope.path.from_end(0)
-        scope.with_id(self.panel_id, |scope| {
-            self.view.draw_walk(cx, scope, walk)
-        })
+        // Title actions get the spacer's free width plus what they used last frame
+        let available = self.title_actions_width(cx);
+        if let Some(width) = available {
+            self.view.panel_title_actions(id!(title_bar.title_actions)).set_available_width(cx, width);
+        }
+
+        // Draw with the panel ID provided so content can identify which panel it's in
+        // Content widgets can access it via: PanelId::current()
+        let panel_id = self.panel_id;
+        let step = scope.with_id(self.scope_id, |scope| {
+            panel_id.provide(|| self.view.draw_walk(cx, scope, walk))
+        });
+
+        // The panel was resized: lay the actions out again with the new width
+        if self.title_actions_width(cx) != available {
+            self.view.redraw(cx);
+        }
+        step
     }
 }
 
 impl Panel {
+    /// A press on the title bar focuses the panel; a right-click asks for the context menu instead of starting a drag
+    fn handle_press(&mut self, cx: &mut Cx, scope: &mut Scope, fe: &FingerDownEvent) {
+        cx.widget_action(
+            self.widget_uid(),
+            &scope.path,
+            PanelAction::Focus(self.panel_id),
+        );
+        self.context_press = fe.mouse_button().is_some_and(|button| button.is_secondary());
+        if self.context_press {
+            cx.widget_action(
+                self.widget_uid(),
+                &scope.path,
+                PanelAction::ContextMenu(self.panel_id, fe.abs),
+            );
+        }
+    }
+
+    /// Show the inline editor with the current title
+    fn begin_title_edit(&mut self, cx: &mut Cx) {
+        if !self.renamable || self.editing_title {
+            return;
+        }
+        self.editing_title = true;
+        let input = self.view.text_input(id!(title_bar.title_input));
+        input.set_text(cx, &self.display_title());
+        input.set_visible(cx, true);
+        input.set_key_focus(cx);
+        self.view.label(id!(title_bar.title)).set_visible(cx, false);
+        self.view.redraw(cx);
+    }
+
+    /// Enter commits, Escape cancels and a click elsewhere commits
+    fn handle_title_edit(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        let input = self.view.text_input(id!(title_bar.title_input));
+        match event {
+            Event::KeyDown(ke) if ke.key_code == KeyCode::Escape => self.end_title_edit(cx, scope, false),
+            Event::KeyDown(ke) if ke.key_code == KeyCode::ReturnKey => self.end_title_edit(cx, scope, true),
+            Event::MouseDown(e) if !input.area().rect(cx).contains(e.abs) => self.end_title_edit(cx, scope, true),
+            _ => {}
+        }
+    }
+
+    /// Hide the inline editor, emitting `PanelAction::Renamed` when a new title is committed
+    fn end_title_edit(&mut self, cx: &mut Cx, scope: &mut Scope, commit: bool) {
+        self.editing_title = false;
+        let input = self.view.text_input(id!(title_bar.title_input));
+        let title = input.text().trim().to_string();
+        input.set_visible(cx, false);
+        cx.set_key_focus(Area::Empty);
+        self.view.label(id!(title_bar.title)).set_visible(cx, true);
+        self.view.redraw(cx);
+
+        // An empty or unchanged title keeps the old one
+        if commit && !title.is_empty() && title != self.display_title() {
+            self.set_title(cx, &title);
+            cx.widget_action(
+                self.widget_uid(),
+                &scope.path,
+                PanelAction::Renamed { panel_id: self.panel_id, title },
+            );
+        }
+    }
+
+    /// Title shown in the title bar ("Panel N" when none is set)
+    fn display_title(&self) -> String {
+        if self.title.is_empty() {
+            format!("Panel {}", self.panel_index + 1)
+        } else {
+            self.title.clone()
+        }
+    }
+
+    /// Width the title bar leaves for the title actions (`None` before the first layout)
+    fn title_actions_width(&self, cx: &Cx) -> Option<f64> {
+        let spacer = self.view.view(id!(title_bar.title_spacer)).area().rect(cx).size.x;
+        let actions = self.view.panel_title_actions(id!(title_bar.title_actions)).area().rect(cx).size.x;
+        let width = spacer + actions;
+        (width > 0.0).then_some(width)
+    }
+
     pub fn set_panel_index(&mut self, cx: &mut Cx, index: usize) {
         if self.panel_index == index {
             return;
@@ -529,19 +812,24 @@ impl Panel {
         self.view.redraw(cx);
     }
 
-    pub fn set_panel_id(&mut self, id: LiveId) {
+    pub fn set_panel_id(&mut self, id: PanelId) {
         self.panel_id = id;
+        self.scope_id = LiveId::from_str(id.as_str());
     }
 
-    /// Set both the LiveId and string ID for this panel
+    /// Set the panel ID from its semantic string
     pub fn set_panel_id_str(&mut self, id_str: &str) {
-        self.panel_id_str = id_str.to_string();
-        self.panel_id = LiveId::from_str_lc(id_str);
+        self.set_panel_id(PanelId::new(id_str));
+    }
+
+    /// Get the interned ID of this panel
+    pub fn panel_id(&self) -> PanelId {
+        self.panel_id
     }
 
     /// Get the semantic string ID for this panel
     pub fn panel_id_str(&self) -> &str {
-        &self.panel_id_str
+        self.panel_id.as_str()
     }
 
     /// Set the panel title
@@ -551,6 +839,26 @@ impl Panel {
         self.view.redraw(cx);
     }
 
+    /// Show or hide the minimize button
+    pub fn set_minimizable(&mut self, minimizable: bool) {
+        self.minimizable = minimizable;
+    }
+
+    /// Set whether double-clicking the title edits it in place
+    pub fn set_renamable(&mut self, renamable: bool) {
+        self.renamable = renamable;
+    }
+
+    /// Lock the panel in place: no dragging, no close button, a lock instead of the drag handle
+    pub fn set_locked(&mut self, cx: &mut Cx, locked: bool) {
+        if self.locked == locked {
+            return;
+        }
+        self.locked = locked;
+        self.is_dragging = false;
+        self.view.redraw(cx);
+    }
+
     pub fn set_maximized(&mut self, maximized: bool) {
         self.is_maximized = maximized;
     }
@@ -559,6 +867,59 @@ impl Panel {
         self.is_fullscreen = fullscreen;
     }
 
+    /// Replace the icon, badge and "modified" dot
+    pub fn set_decoration(&mut self, cx: &mut Cx, decoration: PanelDecoration) {
+        if self.decoration == decoration {
+            return;
+        }
+        let icon = self.view.widget(id!(title_bar.title_icon));
+        if let Some(path) = &decoration.icon {
+            let path = svg_icon_value(path);
+            icon.apply_over(cx, live! {
+                draw_icon: { svg_file: (path) }
+            });
+        }
+        icon.set_visible(cx, decoration.icon.is_some());
+        self.view.view(id!(title_bar.badge)).set_visible(cx, decoration.badge.is_some());
+        self.view.label(id!(title_bar.badge.badge_label)).set_text(cx, decoration.badge.as_deref().unwrap_or(""));
+        self.view.view(id!(title_bar.dirty_dot)).set_visible(cx, decoration.dirty);
+        self.decoration = decoration;
+        self.view.redraw(cx);
+    }
+
+    /// Current icon, badge and "modified" dot
+    pub fn decoration(&self) -> &PanelDecoration {
+        &self.decoration
+    }
+
+    /// Set or clear the title icon (SVG path)
+    pub fn set_icon(&mut self, cx: &mut Cx, icon: Option<&str>) {
+        let decoration = PanelDecoration { icon: icon.map(str::to_string), ..self.decoration.clone() };
+        self.set_decoration(cx, decoration);
+    }
+
+    /// Set or clear the badge text
+    pub fn set_badge(&mut self, cx: &mut Cx, badge: Option<&str>) {
+        let decoration = PanelDecoration { badge: badge.map(str::to_string), ..self.decoration.clone() };
+        self.set_decoration(cx, decoration);
+    }
+
+    /// Show or hide the "modified" dot
+    pub fn set_dirty(&mut self, cx: &mut Cx, dirty: bool) {
+        let decoration = PanelDecoration { dirty, ..self.decoration.clone() };
+        self.set_decoration(cx, decoration);
+    }
+
+    /// Replace the title bar actions
+    pub fn set_title_actions(&mut self, cx: &mut Cx, actions: Vec<PanelTitleAction>) {
+        self.view.panel_title_actions(id!(title_bar.title_actions)).set_actions(cx, actions);
+    }
+
+    /// Set the checked state of a toggle title bar action
+    pub fn set_title_action_checked(&mut self, cx: &mut Cx, id: &str, checked: bool) -> bool {
+        self.view.panel_title_actions(id!(title_bar.title_actions)).set_checked(cx, id, checked)
+    }
+
     /// Set custom content widget for this panel
     pub fn set_content(&mut self, widget: WidgetRef) {
         self.content_widget = Some(widget);
@@ -569,44 +930,35 @@ impl Panel {
         self.view.view(id!(content))
     }
 
-    /// Apply dark mode to this panel (internal, called during draw)
-    fn apply_dark_mode_internal(&mut self, cx: &mut Cx, dark_mode: f64) {
-        // Apply to main panel background
-        self.view.apply_over(cx, live! {
-            draw_bg: { dark_mode: (dark_mode) }
-        });
-
-        // Apply to title bar
-        self.view.view(id!(title_bar)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dark_mode) }
-        });
-
-        // Apply to drag handle
-        self.view.view(id!(title_bar.drag_handle)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dark_mode) }
-        });
-
-        // Apply to title label
-        self.view.label(id!(title_bar.title)).apply_over(cx, live! {
-            draw_text: { dark_mode: (dark_mode) }
-        });
-
-        // Apply to all title bar buttons
-        self.view.button(id!(title_bar.close_btn)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dark_mode) }
-        });
-        self.view.button(id!(title_bar.max_btn)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dark_mode) }
-        });
-        self.view.button(id!(title_bar.restore_btn)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dark_mode) }
-        });
-        self.view.button(id!(title_bar.fullscreen_btn)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dark_mode) }
-        });
-        self.view.button(id!(title_bar.restore_fullscreen_btn)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dark_mode) }
-        });
+    /// Apply the palette to this panel (internal, called during draw)
+    fn apply_palette_internal(&mut self, cx: &mut Cx, palette: &ShellPalette) {
+        let bg = palette.live_nodes(&[live_id!(draw_bg)]);
+
+        // Panel background, title bar, drag handle and lock
+        self.view.apply_over(cx, &bg);
+        self.view.view(id!(title_bar)).apply_over(cx, &bg);
+        self.view.view(id!(title_bar.drag_handle)).apply_over(cx, &bg);
+        self.view.view(id!(title_bar.lock_icon)).apply_over(cx, &bg);
+
+        // Title label, icon, "modified" dot and badge
+        let text = palette.live_nodes(&[live_id!(draw_text)]);
+        self.view.label(id!(title_bar.title)).apply_over(cx, &text);
+        self.view.widget(id!(title_bar.title_icon)).apply_over(cx, &palette.live_nodes(&[live_id!(draw_icon)]));
+        self.view.view(id!(title_bar.dirty_dot)).apply_over(cx, &bg);
+        self.view.view(id!(title_bar.badge)).apply_over(cx, &bg);
+        self.view.label(id!(title_bar.badge.badge_label)).apply_over(cx, &text);
+
+        // All title bar buttons
+        for button in [
+            id!(title_bar.close_btn),
+            id!(title_bar.min_btn),
+            id!(title_bar.max_btn),
+            id!(title_bar.restore_btn),
+            id!(title_bar.fullscreen_btn),
+            id!(title_bar.restore_fullscreen_btn),
+        ] {
+            self.view.button(button).apply_over(cx, &bg);
+        }
     }
 
     fn apply_visual_update(&mut self, cx: &mut Cx2d) {
@@ -615,19 +967,7 @@ impl Panel {
         }
         self.needs_visual_update = false;
 
-        let index = self.panel_index;
-        let colors = panel_colors();
-        let color = colors[index % colors.len()];
-
-        self.view.apply_over(cx, live! {
-            draw_bg: { panel_color: (color) }
-        });
-
-        let title = if self.title.is_empty() {
-            format!("Panel {}", index + 1)
-        } else {
-            self.title.clone()
-        };
+        let title = self.display_title();
         self.view.label(id!(title_bar.title)).set_text(cx, &title);
     }
 }
@@ -639,13 +979,13 @@ impl PanelRef {
         }
     }
 
-    pub fn set_panel_id(&self, id: LiveId) {
+    pub fn set_panel_id(&self, id: PanelId) {
         if let Some(mut inner) = self.borrow_mut() {
             inner.set_panel_id(id);
         }
     }
 
-    /// Set both the LiveId and string ID for this panel
+    /// Set the panel ID from its semantic string
     pub fn set_panel_id_str(&self, id_str: &str) {
         if let Some(mut inner) = self.borrow_mut() {
             inner.set_panel_id_str(id_str);
@@ -664,6 +1004,32 @@ impl PanelRef {
         }
     }
 
+    /// Show or hide the minimize button
+    pub fn set_minimizable(&self, minimizable: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_minimizable(minimizable);
+        }
+    }
+
+    /// Set whether double-clicking the title edits it in place
+    pub fn set_renamable(&self, renamable: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_renamable(renamable);
+        }
+    }
+
+    /// Whether the inline title editor is open (it owns Escape and the keyboard)
+    pub fn is_editing_title(&self) -> bool {
+        self.borrow().is_some_and(|inner| inner.editing_title)
+    }
+
+    /// Lock the panel in place: no dragging, no close button, a lock instead of the drag handle
+    pub fn set_locked(&self, cx: &mut Cx, locked: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_locked(cx, locked);
+        }
+    }
+
     pub fn set_maximized(&self, maximized: bool) {
         if let Some(mut inner) = self.borrow_mut() {
             inner.set_maximized(maximized);
@@ -676,47 +1042,75 @@ impl PanelRef {
         }
     }
 
+    /// Apply the built-in palettes blended by a dark mode value
     pub fn apply_dark_mode(&self, cx: &mut Cx, dark_mode: f64) {
+        self.apply_palette(cx, &ShellPalette::for_dark_mode(dark_mode));
+    }
+
+    /// Apply a palette to this panel
+    pub fn apply_palette(&self, cx: &mut Cx, palette: &ShellPalette) {
         if let Some(mut inner) = self.borrow_mut() {
-            // Apply to main panel background
-            inner.view.apply_over(cx, live! {
-                draw_bg: { dark_mode: (dark_mode) }
-            });
+            inner.apply_palette_internal(cx, palette);
+        }
+    }
 
-            // Apply to title bar
-            inner.view.view(id!(title_bar)).apply_over(cx, live! {
-                draw_bg: { dark_mode: (dark_mode) }
-            });
+    /// Replace the icon, badge and "modified" dot
+    pub fn set_decoration(&self, cx: &mut Cx, decoration: PanelDecoration) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_decoration(cx, decoration);
+        }
+    }
 
-            // Apply to drag handle
-            inner.view.view(id!(title_bar.drag_handle)).apply_over(cx, live! {
-                draw_bg: { dark_mode: (dark_mode) }
-            });
+    /// Current icon, badge and "modified" dot
+    pub fn decoration(&self) -> Option<PanelDecoration> {
+        self.borrow().map(|inner| inner.decoration().clone())
+    }
 
-            // Apply to title label
-            inner.view.label(id!(title_bar.title)).apply_over(cx, live! {
-                draw_text: { dark_mode: (dark_mode) }
-            });
+    /// Set or clear the title icon (SVG path)
+    pub fn set_icon(&self, cx: &mut Cx, icon: Option<&str>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_icon(cx, icon);
+        }
+    }
 
-            // Apply to all title bar buttons
-            inner.view.button(id!(title_bar.close_btn)).apply_over(cx, live! {
-                draw_bg: { dark_mode: (dark_mode) }
-            });
-            inner.view.button(id!(title_bar.max_btn)).apply_over(cx, live! {
-                draw_bg: { dark_mode: (dark_mode) }
-            });
-            inner.view.button(id!(title_bar.restore_btn)).apply_over(cx, live! {
-                draw_bg: { dark_mode: (dark_mode) }
-            });
-            inner.view.button(id!(title_bar.fullscreen_btn)).apply_over(cx, live! {
-                draw_bg: { dark_mode: (dark_mode) }
-            });
-            inner.view.button(id!(title_bar.restore_fullscreen_btn)).apply_over(cx, live! {
-                draw_bg: { dark_mode: (dark_mode) }
-            });
+    /// Set or clear the badge text
+    pub fn set_badge(&self, cx: &mut Cx, badge: Option<&str>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_badge(cx, badge);
+        }
+    }
+
+    /// Show or hide the "modified" dot
+    pub fn set_dirty(&self, cx: &mut Cx, dirty: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_dirty(cx, dirty);
         }
     }
 
+    /// Replace the title bar actions
+    pub fn set_title_actions(&self, cx: &mut Cx, actions: Vec<PanelTitleAction>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_title_actions(cx, actions);
+        }
+    }
+
+    /// Set the checked state of a toggle title bar action
+    pub fn set_title_action_checked(&self, cx: &mut Cx, id: &str, checked: bool) -> bool {
+        self.borrow_mut().map_or(false, |mut inner| inner.set_title_action_checked(cx, id, checked))
+    }
+
+    /// Returns the ID and new checked state of a clicked title bar action
+    pub fn title_action_clicked(&self, actions: &Actions) -> Option<(String, Option<bool>)> {
+        actions
+            .filter_widget_actions(self.widget_uid())
+            .into_iter()
+            .flatten()
+            .find_map(|action| match action.cast::<PanelAction>() {
+                PanelAction::TitleAction { action_id, checked, .. } => Some((action_id, checked)),
+                _ => None,
+            })
+    }
+
     /// Set custom content widget for this panel
     pub fn set_content(&self, widget: WidgetRef) {
         if let Some(mut inner) = self.borrow_mut() {
@@ -0,0 +1,247 @@
+//! Interned panel identity
+//!
+//! Panels are named by semantic string IDs ("editor", "console"). Hashing them
+//! into a `LiveId` loses the string and can map two IDs onto one hash (case is
+//! folded, collisions are possible), so `PanelAction` and the grids carry a
+//! `PanelId` instead: a small copyable handle that is equal exactly when the
+//! strings are equal and that turns back into the string.
+//!
+//! Content inside a panel finds out which panel it's in with
+//! `PanelId::current` while the panel handles events or draws. Like the theme
+//! context, the ID is kept on a per-thread stack for the duration of the
+//! panel's pass rather than in the `Scope`, so it can only be read
+//! synchronously from inside that pass.
+
+use std::borrow::Borrow;
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::{Mutex, OnceLock};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Stable handle for a semantic panel ID
+///
+/// `PanelId::new` interns the string for the lifetime of the process; the same
+/// string always yields the same handle, different strings never share one.
+/// The handle is the interned string itself, so reading it back takes no lock
+/// and comparing two handles only looks at the pointer. It derefs to the
+/// string, so maps keyed by `PanelId` can be looked up with a `&str`, and it
+/// serializes as the plain string.
+#[derive(Clone, Copy)]
+pub struct PanelId(&'static str);
+
+/// The empty ID, interned up front so `PanelId::default()` needs no lookup
+static EMPTY: &str = "";
+
+thread_local! {
+    /// Panels whose event or draw pass is running, innermost last
+    static ACTIVE_PANELS: RefCell<Vec<PanelId>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Pops the panel pushed by `PanelId::provide`, even if the pass panics
+struct ProvideGuard;
+
+impl Drop for ProvideGuard {
+    fn drop(&mut self) {
+        ACTIVE_PANELS.with_borrow_mut(|panels| panels.pop());
+    }
+}
+
+fn interner() -> &'static Mutex<HashSet<&'static str>> {
+    static INTERNER: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+    INTERNER.get_or_init(|| Mutex::new(HashSet::from([EMPTY])))
+}
+
+impl PanelId {
+    /// Intern a semantic panel ID
+    pub fn new(id: &str) -> Self {
+        let mut interner = interner().lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(name) = interner.get(id) {
+            return PanelId(name);
+        }
+        // Panel IDs are a small, fixed set per app, so leaking them is fine
+        let name: &'static str = Box::leak(id.to_string().into_boxed_str());
+        interner.insert(name);
+        PanelId(name)
+    }
+
+    /// The semantic ID this handle was interned from
+    pub fn as_str(&self) -> &'static str {
+        self.0
+    }
+
+    /// Whether this is the empty ID (a panel that was never assigned one)
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Run `f` with this as the ID of the enclosing panel
+    ///
+    /// `Panel` wraps its event handling and drawing in this.
+    pub fn provide<R>(self, f: impl FnOnce() -> R) -> R {
+        ACTIVE_PANELS.with_borrow_mut(|panels| panels.push(self));
+        let _guard = ProvideGuard;
+        f()
+    }
+
+    /// The ID of the innermost panel whose event or draw pass is running, if any
+    pub fn current() -> Option<PanelId> {
+        ACTIVE_PANELS.with_borrow(|panels| panels.last().copied())
+    }
+}
+
+impl Default for PanelId {
+    fn default() -> Self {
+        PanelId(EMPTY)
+    }
+}
+
+// Interned strings are unique, so identity is the string's address
+
+impl PartialEq for PanelId {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.0, other.0)
+    }
+}
+
+impl Eq for PanelId {}
+
+impl PartialEq<str> for PanelId {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for PanelId {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<String> for PanelId {
+    fn eq(&self, other: &String) -> bool {
+        self.0 == other
+    }
+}
+
+// Hashes like the string so `Borrow<str>` lookups find the same bucket
+impl Hash for PanelId {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl PartialOrd for PanelId {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PanelId {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp(other.0)
+    }
+}
+
+impl Deref for PanelId {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.0
+    }
+}
+
+impl Borrow<str> for PanelId {
+    fn borrow(&self) -> &str {
+        self.0
+    }
+}
+
+impl From<&str> for PanelId {
+    fn from(id: &str) -> Self {
+        PanelId::new(id)
+    }
+}
+
+impl From<&String> for PanelId {
+    fn from(id: &String) -> Self {
+        PanelId::new(id)
+    }
+}
+
+impl From<String> for PanelId {
+    fn from(id: String) -> Self {
+        PanelId::new(&id)
+    }
+}
+
+impl Serialize for PanelId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for PanelId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(PanelId::from)
+    }
+}
+
+impl fmt::Display for PanelId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl fmt::Debug for PanelId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PanelId({:?})", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case_differing_ids_stay_distinct() {
+        let lower = PanelId::new("console");
+        let upper = PanelId::new("Console");
+
+        assert_ne!(lower, upper);
+        assert_eq!(lower, PanelId::new("console"));
+        assert_eq!(lower.as_str(), "console");
+        assert_eq!(upper.as_str(), "Console");
+        assert!(PanelId::default().is_empty());
+        assert_eq!(PanelId::new(""), PanelId::default());
+    }
+
+    #[test]
+    fn test_lookup_and_serde_use_the_string() {
+        let editor = PanelId::new("editor");
+        let set: HashSet<PanelId> = HashSet::from([editor]);
+        assert!(set.contains("editor"));
+        assert!(!set.contains("Editor"));
+        assert_eq!(editor, "editor");
+
+        let json = serde_json::to_string(&editor).unwrap();
+        assert_eq!(json, "\"editor\"");
+        assert_eq!(serde_json::from_str::<PanelId>(&json).unwrap(), editor);
+    }
+
+    #[test]
+    fn test_current_is_innermost_panel() {
+        assert_eq!(PanelId::current(), None);
+
+        PanelId::new("editor").provide(|| {
+            PanelId::new("Editor").provide(|| {
+                assert_eq!(PanelId::current().map(|id| id.as_str()), Some("Editor"));
+            });
+            assert_eq!(PanelId::current(), Some(PanelId::new("editor")));
+        });
+        assert_eq!(PanelId::current(), None);
+    }
+}
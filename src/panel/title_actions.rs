@@ -0,0 +1,474 @@
+//! Per-panel title bar actions
+//!
+//! Panels such as a console or log viewer declare `PanelTitleAction`s with an
+//! icon, tooltip and optional toggle state, either on their `PanelDefinition`
+//! or at runtime through `PanelRef`. `PanelTitleActions` draws them between the
+//! title and the window buttons. When the title bar is too narrow, the actions
+//! that don't fit move into an overflow menu behind a "more" button.
+
+use makepad_widgets::*;
+use crate::shell::menu::{MenuEntry, MenuItem, ShellMenuWidgetRefExt};
+use crate::shell::sidebar_menu::svg_icon_value;
+use crate::theme::current_palette;
+
+live_design! {
+    use link::theme::*;
+    use link::shaders::*;
+    use link::widgets::*;
+
+    use crate::live_design::*;
+    use crate::shell::menu::*;
+
+    pub PanelTitleActionButton = <Button> {
+        width: 20, height: 20
+        padding: 0
+        margin: { right: 4 }
+        align: { x: 0.5, y: 0.5 }
+        icon_walk: { width: 12, height: 12, margin: 0 }
+        text: ""
+
+        draw_bg: {
+            instance hover: 0.0
+            instance pressed: 0.0
+            instance active: 0.0
+            uniform bg_hover: (PALETTE_BG_HOVER)
+            uniform bg_selected: (PALETTE_BG_SELECTED)
+
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                let color = mix(self.bg_hover, self.bg_selected, self.active);
+                sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 3.0);
+                sdf.fill(vec4(color.xyz, max(max(self.hover, self.pressed), self.active)));
+                return sdf.result;
+            }
+        }
+
+        draw_icon: {
+            instance hover: 0.0
+            instance active: 0.0
+            uniform icon: (PALETTE_ICON)
+            uniform accent: (PALETTE_ACCENT)
+            fn get_color(self) -> vec4 {
+                return mix(self.icon, self.accent, max(self.hover, self.active));
+            }
+        }
+    }
+
+    // Opens the overflow menu (three dots)
+    pub PanelTitleActionsMore = <Button> {
+        width: 20, height: 20
+        padding: 0
+        margin: { right: 4 }
+        text: ""
+
+        draw_bg: {
+            uniform icon: (PALETTE_ICON)
+            uniform accent: (PALETTE_ACCENT)
+
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                let color = mix(self.icon, self.accent, self.hover);
+                let y = self.rect_size.y * 0.5;
+                sdf.circle(self.rect_size.x * 0.5 - 4.0, y, 1.2);
+                sdf.fill(color);
+                sdf.circle(self.rect_size.x * 0.5, y, 1.2);
+                sdf.fill(color);
+                sdf.circle(self.rect_size.x * 0.5 + 4.0, y, 1.2);
+                sdf.fill(color);
+                return sdf.result;
+            }
+        }
+    }
+
+    pub PanelTitleActions = {{PanelTitleActions}} {
+        width: Fit, height: Fit
+        flow: Right
+        align: { y: 0.5 }
+
+        button_template: <PanelTitleActionButton> {}
+        more_template: <PanelTitleActionsMore> {}
+        menu_template: <ShellMenu> {}
+        tooltip_template: <Tooltip> {}
+    }
+}
+
+/// Width of one action button including its margin
+const ACTION_SLOT_WIDTH: f64 = 24.0;
+
+/// Width of `ShellMenu`, to right-align the overflow menu with its button
+const OVERFLOW_MENU_WIDTH: f64 = 220.0;
+
+/// A button in a panel's title bar
+#[derive(Clone, Debug, PartialEq)]
+pub struct PanelTitleAction {
+    /// Action ID reported on click
+    pub id: String,
+
+    /// SVG icon path
+    pub icon: String,
+
+    /// Text shown while hovering (and as the overflow menu label)
+    pub tooltip: Option<String>,
+
+    /// Checked state of a toggle action (`None` = push button)
+    pub checked: Option<bool>,
+}
+
+impl PanelTitleAction {
+    /// Create a new push button action
+    pub fn new(id: impl Into<String>, icon: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            icon: icon.into(),
+            tooltip: None,
+            checked: None,
+        }
+    }
+
+    /// Set the hover tooltip
+    pub fn with_tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Make the action a toggle with an initial checked state
+    pub fn toggle(mut self, checked: bool) -> Self {
+        self.checked = Some(checked);
+        self
+    }
+}
+
+/// How many of `total` actions fit as buttons in `available` pixels
+///
+/// When some don't fit, one slot is kept for the overflow button.
+pub fn visible_action_count(total: usize, available: f64) -> usize {
+    let fits = (available / ACTION_SLOT_WIDTH).floor().max(0.0) as usize;
+    if total <= fits {
+        total
+    } else {
+        fits.saturating_sub(1)
+    }
+}
+
+/// Set the checked state of the toggle action `id`, returning false if there is none
+pub fn set_action_checked(actions: &mut [PanelTitleAction], id: &str, checked: bool) -> bool {
+    match actions.iter_mut().find(|action| action.id == id && action.checked.is_some()) {
+        Some(action) => {
+            action.checked = Some(checked);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Actions emitted by `PanelTitleActions`
+#[derive(Clone, Debug, DefaultNone)]
+pub enum PanelTitleActionsAction {
+    /// An action was clicked (`checked` = new state of a toggle action)
+    Clicked { id: String, checked: Option<bool> },
+
+    None,
+}
+
+/// Row of title bar action buttons with an overflow menu
+#[derive(Live, LiveHook, Widget)]
+pub struct PanelTitleActions {
+    #[redraw]
+    #[rust]
+    area: Area,
+
+    #[walk]
+    walk: Walk,
+
+    #[layout]
+    layout: Layout,
+
+    #[live]
+    button_template: Option<LivePtr>,
+
+    #[live]
+    more_template: Option<LivePtr>,
+
+    #[live]
+    menu_template: Option<LivePtr>,
+
+    #[live]
+    tooltip_template: Option<LivePtr>,
+
+    #[rust]
+    actions: Vec<PanelTitleAction>,
+
+    #[rust]
+    buttons: ComponentMap<LiveId, WidgetRef>,
+
+    /// Overflow button, menu and tooltip (created with the first action)
+    #[rust]
+    more: WidgetRef,
+
+    #[rust]
+    menu: WidgetRef,
+
+    #[rust]
+    tooltip: WidgetRef,
+
+    /// Width the title bar leaves for the actions (`None` = not measured yet)
+    #[rust]
+    available_width: Option<f64>,
+
+    /// Number of actions drawn as buttons; the rest are in the overflow menu
+    #[rust]
+    shown: usize,
+}
+
+impl Widget for PanelTitleActions {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if self.actions.is_empty() {
+            return;
+        }
+        let uid = self.widget_uid();
+        let actions = cx.capture_actions(|cx| {
+            for button in self.buttons.values_mut() {
+                button.handle_event(cx, event, scope);
+            }
+            self.more.handle_event(cx, event, scope);
+            self.menu.handle_event(cx, event, scope);
+        });
+
+        // Tooltips
+        let shown = &self.actions[..self.shown.min(self.actions.len())];
+        for action in shown {
+            let (Some(tooltip), Some(button)) = (&action.tooltip, self.buttons.get(&LiveId::from_str(&action.id))) else {
+                continue;
+            };
+            match event.hits(cx, button.area()) {
+                Hit::FingerHoverIn(_) => {
+                    let rect = button.area().rect(cx);
+                    let pos = dvec2(rect.pos.x, rect.pos.y + rect.size.y + 4.0);
+                    self.tooltip.as_tooltip().show_with_options(cx, pos, tooltip);
+                }
+                Hit::FingerHoverOut(_) => self.tooltip.as_tooltip().hide(cx),
+                _ => {}
+            }
+        }
+
+        let clicked = shown.iter()
+            .position(|action| {
+                self.buttons.get(&LiveId::from_str(&action.id))
+                    .map_or(false, |button| button.as_button().clicked(&actions))
+            });
+        if let Some(index) = clicked {
+            self.tooltip.as_tooltip().hide(cx);
+            let action = self.activate(cx, index);
+            cx.widget_action(uid, &scope.path, action);
+        }
+
+        if self.more.as_button().clicked(&actions) {
+            let menu = self.menu.as_shell_menu();
+            if menu.is_open() {
+                menu.close(cx);
+            } else {
+                let rect = self.more.area().rect(cx);
+                let position = dvec2(
+                    (rect.pos.x + rect.size.x - OVERFLOW_MENU_WIDTH).max(0.0),
+                    rect.pos.y + rect.size.y + 4.0,
+                );
+                menu.open(cx, self.overflow_entries(), position, rect);
+            }
+        }
+
+        if let Some(id) = self.menu.as_shell_menu().selected(&actions) {
+            if let Some(index) = self.actions.iter().position(|action| action.id == id) {
+                let action = self.activate(cx, index);
+                cx.widget_action(uid, &scope.path, action);
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if self.actions.is_empty() {
+            return DrawStep::done();
+        }
+        let palette = current_palette();
+        let button_nodes = palette.live_nodes(&[live_id!(draw_bg), live_id!(draw_icon)]);
+
+        self.shown = match self.available_width {
+            Some(width) => visible_action_count(self.actions.len(), width),
+            None => self.actions.len(),
+        };
+
+        cx.begin_turtle(walk, self.layout);
+        for action in &self.actions[..self.shown] {
+            if let Some(button) = self.buttons.get(&LiveId::from_str(&action.id)) {
+                button.apply_over(cx, &button_nodes);
+                button.draw_all(cx, scope);
+            }
+        }
+        if self.shown < self.actions.len() {
+            self.more.apply_over(cx, &button_nodes);
+            self.more.draw_all(cx, scope);
+        }
+        cx.end_turtle_with_area(&mut self.area);
+
+        // Both draw into their own overlay
+        self.menu.draw_all(cx, scope);
+        self.tooltip.draw_all(cx, scope);
+
+        DrawStep::done()
+    }
+}
+
+impl PanelTitleActions {
+    /// Replace the actions (unchanged actions keep their buttons)
+    pub fn set_actions(&mut self, cx: &mut Cx, actions: Vec<PanelTitleAction>) {
+        if self.actions == actions {
+            return;
+        }
+        if self.more.is_empty() {
+            self.more = WidgetRef::new_from_ptr(cx, self.more_template);
+            self.menu = WidgetRef::new_from_ptr(cx, self.menu_template);
+            self.tooltip = WidgetRef::new_from_ptr(cx, self.tooltip_template);
+        }
+        self.menu.as_shell_menu().close(cx);
+
+        let template = self.button_template;
+        for action in &actions {
+            let button = self.buttons.get_or_insert(cx, LiveId::from_str(&action.id), |cx| {
+                WidgetRef::new_from_ptr(cx, template)
+            });
+            let icon = svg_icon_value(&action.icon);
+            button.apply_over(cx, live! {
+                draw_icon: { svg_file: (icon) }
+            });
+        }
+        self.buttons.retain(|id, _| actions.iter().any(|action| LiveId::from_str(&action.id) == *id));
+
+        self.shown = actions.len();
+        self.actions = actions;
+        self.apply_checked(cx);
+    }
+
+    /// Current actions with their checked states
+    pub fn actions(&self) -> &[PanelTitleAction] {
+        &self.actions
+    }
+
+    /// Set the checked state of a toggle action
+    ///
+    /// Returns false if no toggle action has this ID.
+    pub fn set_checked(&mut self, cx: &mut Cx, id: &str, checked: bool) -> bool {
+        if !set_action_checked(&mut self.actions, id, checked) {
+            return false;
+        }
+        self.apply_checked(cx);
+        true
+    }
+
+    /// Set the width the title bar leaves for the actions
+    pub fn set_available_width(&mut self, cx: &mut Cx, width: f64) {
+        let shown = visible_action_count(self.actions.len(), width);
+        self.available_width = Some(width);
+        if shown != self.shown {
+            self.redraw(cx);
+        }
+    }
+
+    /// Handle a click on the action at `index`
+    fn activate(&mut self, cx: &mut Cx, index: usize) -> PanelTitleActionsAction {
+        let action = &self.actions[index];
+        let id = action.id.clone();
+        let checked = action.checked.map(|checked| !checked);
+        if let Some(checked) = checked {
+            self.set_checked(cx, &id, checked);
+        }
+        PanelTitleActionsAction::Clicked { id, checked }
+    }
+
+    /// Menu entries for the actions that didn't fit
+    fn overflow_entries(&self) -> Vec<MenuEntry> {
+        self.actions[self.shown.min(self.actions.len())..].iter()
+            .map(|action| {
+                let label = action.tooltip.clone().unwrap_or_else(|| action.id.clone());
+                let item = MenuItem::new(action.id.clone(), label).with_icon(action.icon.clone());
+                match action.checked {
+                    Some(checked) => item.checkable(checked).into(),
+                    None => item.into(),
+                }
+            })
+            .collect()
+    }
+
+    /// Highlight checked toggle actions
+    fn apply_checked(&mut self, cx: &mut Cx) {
+        for action in &self.actions {
+            if let Some(button) = self.buttons.get(&LiveId::from_str(&action.id)) {
+                let active = if action.checked == Some(true) { 1.0 } else { 0.0 };
+                button.apply_over(cx, live! {
+                    draw_bg: { active: (active) }
+                    draw_icon: { active: (active) }
+                });
+            }
+        }
+        self.redraw(cx);
+    }
+}
+
+impl PanelTitleActionsRef {
+    /// Replace the actions
+    pub fn set_actions(&self, cx: &mut Cx, actions: Vec<PanelTitleAction>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_actions(cx, actions);
+        }
+    }
+
+    /// Set the checked state of a toggle action
+    pub fn set_checked(&self, cx: &mut Cx, id: &str, checked: bool) -> bool {
+        self.borrow_mut().map_or(false, |mut inner| inner.set_checked(cx, id, checked))
+    }
+
+    /// Set the width the title bar leaves for the actions
+    pub fn set_available_width(&self, cx: &mut Cx, width: f64) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_available_width(cx, width);
+        }
+    }
+
+    /// Returns the ID and new checked state of a clicked action
+    pub fn clicked(&self, actions: &Actions) -> Option<(String, Option<bool>)> {
+        match actions.find_widget_action(self.widget_uid()).cast() {
+            PanelTitleActionsAction::Clicked { id, checked } => Some((id, checked)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_actions_fit() {
+        assert_eq!(visible_action_count(3, 3.0 * ACTION_SLOT_WIDTH), 3);
+        assert_eq!(visible_action_count(0, 0.0), 0);
+    }
+
+    #[test]
+    fn test_overflow_keeps_a_slot_for_the_more_button() {
+        // Room for 3 buttons, 4 actions: 2 buttons + the overflow button
+        assert_eq!(visible_action_count(4, 3.5 * ACTION_SLOT_WIDTH), 2);
+        assert_eq!(visible_action_count(4, 0.5 * ACTION_SLOT_WIDTH), 0);
+        assert_eq!(visible_action_count(4, -10.0), 0);
+    }
+
+    #[test]
+    fn test_set_action_checked_only_changes_toggles() {
+        let mut actions = vec![
+            PanelTitleAction::new("clear", "icons/clear.svg"),
+            PanelTitleAction::new("pause", "icons/pause.svg").toggle(false),
+        ];
+        assert!(set_action_checked(&mut actions, "pause", true));
+        assert_eq!(actions[1].checked, Some(true));
+        assert!(!set_action_checked(&mut actions, "clear", true));
+        assert_eq!(actions[0].checked, None);
+        assert!(!set_action_checked(&mut actions, "missing", true));
+    }
+}
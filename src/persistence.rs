@@ -2,17 +2,26 @@
 //!
 //! Provides save/load functionality for user preferences.
 
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
 use crate::grid::{LayoutState, FooterLayoutState, SplitterPositions};
+use crate::shell::sidebar::SidebarSelection;
+use crate::theme::ThemeMode;
 
 /// Shell preferences for persistence
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 pub struct ShellPreferences {
-    /// Dark mode preference
+    /// Dark mode state when preferences were saved
+    ///
+    /// Files saved before `theme_mode` existed are migrated from this flag.
     pub dark_mode: bool,
 
+    /// Light, dark or system theme mode (`None` = not chosen yet)
+    #[serde(default)]
+    pub theme_mode: Option<ThemeMode>,
+
     /// Saved layout state (main panel grid)
     pub layout: Option<LayoutState>,
 
@@ -21,6 +30,30 @@ pub struct ShellPreferences {
 
     /// Saved splitter positions
     pub splitter_positions: Option<SplitterPositions>,
+
+    /// Saved main grid layouts by workspace name
+    #[serde(default)]
+    pub workspaces: HashMap<String, LayoutState>,
+
+    /// Name of the workspace that was active when preferences were saved
+    #[serde(default)]
+    pub active_workspace: Option<String>,
+
+    /// Selected sidebar entry by sidebar ("left" / "right")
+    #[serde(default)]
+    pub sidebar_selections: HashMap<String, SidebarSelection>,
+
+    /// Expanded sidebar tree node paths by sidebar ("left" / "right")
+    #[serde(default)]
+    pub sidebar_expanded_nodes: HashMap<String, Vec<Vec<String>>>,
+
+    /// Name of the selected built-in or file-based theme (`None` = default theme)
+    #[serde(default)]
+    pub theme_name: Option<String>,
+
+    /// Whether the header's layout lock is on (`None` = not chosen yet)
+    #[serde(default)]
+    pub layout_locked: Option<bool>,
 }
 
 impl ShellPreferences {
@@ -32,14 +65,21 @@ impl ShellPreferences {
             .join("shell_preferences.json")
     }
 
+    /// Get the directory theme files are loaded from for an app
+    pub fn themes_dir(app_id: &str) -> PathBuf {
+        Self::get_path(app_id).with_file_name("themes")
+    }
+
     /// Load preferences from disk
     ///
     /// Returns default preferences if file doesn't exist or can't be parsed.
+    /// Files without a theme mode get Light or Dark from their `dark_mode` flag.
     pub fn load(app_id: &str) -> Self {
         let path = Self::get_path(app_id);
         if path.exists() {
             if let Ok(content) = fs::read_to_string(&path) {
-                if let Ok(prefs) = serde_json::from_str(&content) {
+                if let Ok(mut prefs) = serde_json::from_str::<Self>(&content) {
+                    prefs.theme_mode.get_or_insert(ThemeMode::from_dark(prefs.dark_mode));
                     return prefs;
                 }
             }
@@ -64,6 +104,12 @@ impl ShellPreferences {
         self.save(app_id)
     }
 
+    /// Set theme mode and save
+    pub fn set_theme_mode(&mut self, app_id: &str, mode: ThemeMode) -> Result<(), std::io::Error> {
+        self.theme_mode = Some(mode);
+        self.save(app_id)
+    }
+
     /// Set layout state and save
     pub fn set_layout(
         &mut self,
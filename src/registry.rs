@@ -4,6 +4,9 @@
 //! enabling semantic panel identification and content injection.
 
 use std::collections::HashMap;
+use crate::grid::SizeLimits;
+use crate::panel::PanelTitleAction;
+use crate::shell::menu::MenuEntry;
 
 /// Defines a panel type that can be instantiated in the grid
 #[derive(Clone, Debug)]
@@ -14,6 +17,9 @@ pub struct PanelDefinition {
     /// Display title for the panel header
     pub title: String,
 
+    /// SVG icon path shown before the title
+    pub icon: Option<String>,
+
     /// Whether the panel can be closed
     pub closable: bool,
 
@@ -22,6 +28,25 @@ pub struct PanelDefinition {
 
     /// Whether the panel can be fullscreened (footer grid only)
     pub fullscreenable: bool,
+
+    /// Whether the panel can be minimized to a chip (main grid only)
+    pub minimizable: bool,
+
+    /// Minimum and maximum size the grids give the panel
+    pub size_limits: SizeLimits,
+
+    /// Whether the user can rename the panel (double-click on the title, main grid only)
+    pub renamable: bool,
+
+    /// Whether the panel is always pinned: it can't be dragged, closed or shifted
+    /// by other drops, and the user can't unpin it
+    pub pinned: bool,
+
+    /// App entries appended to the title bar context menu
+    pub menu_entries: Vec<MenuEntry>,
+
+    /// App buttons shown in the title bar (clear, filter, pause...)
+    pub title_actions: Vec<PanelTitleAction>,
 }
 
 impl PanelDefinition {
@@ -30,12 +55,25 @@ impl PanelDefinition {
         Self {
             id: id.into(),
             title: title.into(),
+            icon: None,
             closable: true,
             maximizable: true,
             fullscreenable: false,
+            minimizable: false,
+            size_limits: SizeLimits::default(),
+            renamable: true,
+            pinned: false,
+            menu_entries: Vec::new(),
+            title_actions: Vec::new(),
         }
     }
 
+    /// Set the title icon (SVG path)
+    pub fn with_icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
     /// Set whether the panel can be closed
     pub fn with_closable(mut self, closable: bool) -> Self {
         self.closable = closable;
@@ -54,14 +92,67 @@ impl PanelDefinition {
         self
     }
 
+    /// Set whether the panel can be minimized
+    pub fn with_minimizable(mut self, minimizable: bool) -> Self {
+        self.minimizable = minimizable;
+        self
+    }
+
+    /// Set the smallest size the grids may give the panel
+    pub fn with_min_size(mut self, width: f64, height: f64) -> Self {
+        self.size_limits.min_width = width;
+        self.size_limits.min_height = height;
+        self
+    }
+
+    /// Set the largest size the grids may give the panel
+    pub fn with_max_size(mut self, width: f64, height: f64) -> Self {
+        self.size_limits.max_width = Some(width);
+        self.size_limits.max_height = Some(height);
+        self
+    }
+
+    /// Set whether the user can rename the panel
+    pub fn with_renamable(mut self, renamable: bool) -> Self {
+        self.renamable = renamable;
+        self
+    }
+
+    /// Set whether the panel is always pinned in place
+    pub fn with_pinned(mut self, pinned: bool) -> Self {
+        self.pinned = pinned;
+        self
+    }
+
+    /// Append an entry to the title bar context menu
+    ///
+    /// Choosing it reports `PanelMenuCommand::Custom` with the item ID.
+    pub fn with_menu_entry(mut self, entry: impl Into<MenuEntry>) -> Self {
+        self.menu_entries.push(entry.into());
+        self
+    }
+
+    /// Add a button to the title bar
+    pub fn with_title_action(mut self, action: PanelTitleAction) -> Self {
+        self.title_actions.push(action);
+        self
+    }
+
     /// Create a footer panel definition (fullscreenable, not maximizable)
     pub fn footer(id: impl Into<String>, title: impl Into<String>) -> Self {
         Self {
             id: id.into(),
             title: title.into(),
+            icon: None,
             closable: true,
             maximizable: false,
             fullscreenable: true,
+            minimizable: false,
+            size_limits: SizeLimits::default(),
+            renamable: true,
+            pinned: false,
+            menu_entries: Vec::new(),
+            title_actions: Vec::new(),
         }
     }
 }
@@ -0,0 +1,307 @@
+//! Activity bar and swappable sidebar views
+//!
+//! An `ActivityBar` is a narrow column of icons (like VS Code's). Each icon
+//! selects one of several registered `SidebarView`s shown next to it inside a
+//! sidebar host. `ShellLayout` owns the left and right hosts and collapses a
+//! sidebar when its active icon is clicked again.
+
+use makepad_widgets::*;
+use serde::{Deserialize, Serialize};
+use crate::shell::sidebar_menu::svg_icon_value;
+use crate::theme::current_palette;
+
+live_design! {
+    use link::theme::*;
+    use link::shaders::*;
+    use link::widgets::*;
+
+    use crate::live_design::*;
+
+    // Icon-only button with an active indicator on the sidebar side
+    pub ActivityBarButton = <Button> {
+        width: 48, height: 44
+        padding: 0
+        margin: 0
+        align: { x: 0.5, y: 0.5 }
+        icon_walk: { width: 22, height: 22, margin: 0 }
+        text: ""
+
+        draw_bg: {
+            instance hover: 0.0
+            instance pressed: 0.0
+            instance active: 0.0
+            // 0.0 = indicator on the left edge, 1.0 = on the right edge
+            instance indicator_side: 0.0
+            uniform bg_hover: (PALETTE_BG_HOVER)
+            uniform accent: (PALETTE_ACCENT)
+
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.rect(0.0, 0.0, self.rect_size.x, self.rect_size.y);
+                sdf.fill(vec4(self.bg_hover.xyz, max(self.hover, self.pressed) * 0.8));
+
+                let x = mix(0.0, self.rect_size.x - 2.0, self.indicator_side);
+                sdf.rect(x, 8.0, 2.0, self.rect_size.y - 16.0);
+                sdf.fill(vec4(self.accent.xyz, self.active));
+                return sdf.result;
+            }
+        }
+
+        draw_icon: {
+            instance active: 0.0
+            instance hover: 0.0
+            uniform text_faint: (PALETTE_TEXT_FAINT)
+            uniform text_primary: (PALETTE_TEXT_PRIMARY)
+            fn get_color(self) -> vec4 {
+                return mix(self.text_faint, self.text_primary, max(self.active, self.hover));
+            }
+        }
+    }
+
+    pub ActivityBar = {{ActivityBar}} {
+        width: 48, height: Fill
+        flow: Down
+        padding: { top: 4 }
+        visible: false
+
+        draw_bg: {
+            uniform bg_activity_bar: (PALETTE_BG_ACTIVITY_BAR)
+            fn pixel(self) -> vec4 {
+                return self.bg_activity_bar;
+            }
+        }
+
+        button_template: <ActivityBarButton> {}
+    }
+
+    // Sidebar host: activity bar on the left, views on the right.
+    // Children of `views` are shown by matching their ID to the active view ID.
+    pub SidebarHost = <View> {
+        width: Fill, height: Fill
+        flow: Right
+
+        activity_bar = <ActivityBar> {}
+        views = <View> {
+            width: Fill, height: Fill
+            flow: Overlay
+        }
+    }
+
+    // Mirrored host for the right sidebar
+    pub RightSidebarHost = <View> {
+        width: Fill, height: Fill
+        flow: Right
+
+        views = <View> {
+            width: Fill, height: Fill
+            flow: Overlay
+        }
+        activity_bar = <ActivityBar> {
+            indicator_side: 1.0
+        }
+    }
+}
+
+/// Width of the activity bar (a collapsed sidebar shrinks to this width)
+pub const ACTIVITY_BAR_WIDTH: f64 = 48.0;
+
+/// Which side of the shell a sidebar is on
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SidebarSide {
+    Left,
+    Right,
+}
+
+/// A view that can be shown in a sidebar, selected from the activity bar
+///
+/// The view's content is the child of the host's `views` container whose
+/// ID matches `id`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SidebarView {
+    /// View ID (also the ID of the content widget)
+    pub id: String,
+
+    /// Display name (e.g. "Explorer")
+    pub label: String,
+
+    /// SVG icon path for the activity bar
+    pub icon: Option<String>,
+}
+
+impl SidebarView {
+    /// Create a new view definition
+    pub fn new(id: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            label: label.into(),
+            icon: None,
+        }
+    }
+
+    /// Set the SVG icon path
+    pub fn with_icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+}
+
+/// Actions emitted by `ActivityBar`
+#[derive(Clone, Debug, DefaultNone)]
+pub enum ActivityBarAction {
+    /// A view icon was clicked
+    ViewClicked(String),
+    None,
+}
+
+/// Narrow column of view icons
+#[derive(Live, LiveHook, Widget)]
+pub struct ActivityBar {
+    #[redraw]
+    #[live]
+    draw_bg: DrawQuad,
+
+    #[walk]
+    walk: Walk,
+
+    #[layout]
+    layout: Layout,
+
+    #[live]
+    button_template: Option<LivePtr>,
+
+    /// Side of the active indicator (0.0 = left, 1.0 = right)
+    #[live]
+    indicator_side: f64,
+
+    #[live(true)]
+    visible: bool,
+
+    #[rust]
+    views: Vec<SidebarView>,
+
+    #[rust]
+    buttons: ComponentMap<LiveId, WidgetRef>,
+
+    #[rust]
+    active: Option<String>,
+}
+
+impl Widget for ActivityBar {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if !self.visible {
+            return;
+        }
+        let actions = cx.capture_actions(|cx| {
+            for button in self.buttons.values_mut() {
+                button.handle_event(cx, event, scope);
+            }
+        });
+
+        let clicked = self.views.iter().find_map(|view| {
+            let button = self.buttons.get(&LiveId::from_str(&view.id))?;
+            button.as_button().clicked(&actions).then(|| view.id.clone())
+        });
+        if let Some(view_id) = clicked {
+            cx.widget_action(self.widget_uid(), &scope.path, ActivityBarAction::ViewClicked(view_id));
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if !self.visible {
+            return DrawStep::done();
+        }
+        let palette = current_palette();
+        self.draw_bg.apply_over(cx, &palette.uniform_nodes());
+        let button_nodes = palette.live_nodes(&[live_id!(draw_bg), live_id!(draw_icon)]);
+
+        self.draw_bg.begin(cx, walk, self.layout);
+        for view in &self.views {
+            if let Some(button) = self.buttons.get(&LiveId::from_str(&view.id)) {
+                button.apply_over(cx, &button_nodes);
+                button.draw_all(cx, scope);
+            }
+        }
+        self.draw_bg.end(cx);
+
+        DrawStep::done()
+    }
+
+    fn visible(&self) -> bool {
+        self.visible
+    }
+
+    fn set_visible(&mut self, cx: &mut Cx, visible: bool) {
+        if self.visible != visible {
+            self.visible = visible;
+            self.redraw(cx);
+        }
+    }
+}
+
+impl ActivityBar {
+    /// Replace the view icons
+    pub fn set_views(&mut self, cx: &mut Cx, views: Vec<SidebarView>) {
+        self.buttons.clear();
+        let template = self.button_template;
+        let indicator_side = self.indicator_side;
+
+        for view in &views {
+            let button = self.buttons.get_or_insert(cx, LiveId::from_str(&view.id), |cx| {
+                WidgetRef::new_from_ptr(cx, template)
+            });
+            button.apply_over(cx, live! {
+                draw_bg: { indicator_side: (indicator_side) }
+            });
+            if let Some(icon) = &view.icon {
+                let icon = svg_icon_value(icon);
+                button.apply_over(cx, live! {
+                    draw_icon: { svg_file: (icon) }
+                });
+            }
+        }
+
+        self.views = views;
+        let active = self.active.clone();
+        self.set_active(cx, active.as_deref());
+    }
+
+    /// Highlight the active view icon (`None` when the sidebar is collapsed)
+    pub fn set_active(&mut self, cx: &mut Cx, view_id: Option<&str>) {
+        for view in &self.views {
+            if let Some(button) = self.buttons.get(&LiveId::from_str(&view.id)) {
+                let active = if Some(view.id.as_str()) == view_id { 1.0 } else { 0.0 };
+                button.apply_over(cx, live! {
+                    draw_bg: { active: (active) }
+                    draw_icon: { active: (active) }
+                });
+            }
+        }
+        self.active = view_id.map(|id| id.to_string());
+        self.redraw(cx);
+    }
+}
+
+impl ActivityBarRef {
+    /// Replace the view icons
+    pub fn set_views(&self, cx: &mut Cx, views: Vec<SidebarView>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_views(cx, views);
+        }
+    }
+
+    /// Highlight the active view icon
+    pub fn set_active(&self, cx: &mut Cx, view_id: Option<&str>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_active(cx, view_id);
+        }
+    }
+
+    /// Returns the ID of the clicked view icon, if any
+    pub fn view_clicked(&self, actions: &Actions) -> Option<String> {
+        if let ActivityBarAction::ViewClicked(id) = actions.find_widget_action(self.widget_uid()).cast() {
+            Some(id)
+        } else {
+            None
+        }
+    }
+}
@@ -1,5 +1,7 @@
 //! Shell configuration
 
+use crate::theme::{ThemeMode, APPEARANCE_POLL_INTERVAL, THEME_POLL_INTERVAL};
+
 /// Configuration for the app shell
 #[derive(Clone, Debug)]
 pub struct ShellConfig {
@@ -45,11 +47,50 @@ pub struct ShellConfig {
     /// Enable panel drag-and-drop
     pub enable_panel_drag: bool,
 
+    /// Start with the layout locked (no panel drag, close or dock resize)
+    /// until the user toggles the header's lock
+    pub layout_locked: bool,
+
     /// Enable layout persistence
     pub enable_persistence: bool,
 
     /// Start in dark mode
     pub dark_mode: bool,
+
+    /// Theme mode used until the user saves one
+    pub theme_mode: ThemeMode,
+
+    /// Seconds between platform appearance checks in `ThemeMode::System`
+    /// (0 checks only when the app regains focus)
+    pub appearance_poll_interval: f64,
+
+    /// Width of the overlay (hover) and pinned (click) sidebars
+    pub overlay_sidebar_width: f64,
+
+    /// Seconds the pointer must rest on the hamburger before the overlay opens
+    pub overlay_hover_delay: f64,
+
+    /// Duration of the sidebar open animation in seconds
+    pub sidebar_open_duration: f64,
+
+    /// Duration of the sidebar close animation in seconds
+    pub sidebar_close_duration: f64,
+
+    /// Toggle the overlay sidebar with Ctrl+B (Cmd+B on macOS) unless a text input has key focus
+    pub enable_overlay_shortcut: bool,
+
+    /// Seconds between scans of the themes directory for changed theme files
+    /// (0 disables hot reload)
+    pub theme_poll_interval: f64,
+
+    /// Skip the theme, sidebar, header button and panel move animations
+    pub reduce_motion: bool,
+
+    /// Ask before resetting the layout or deleting a workspace
+    pub confirm_destructive_actions: bool,
+
+    /// Panels whose close button asks for confirmation first
+    pub confirm_close_panels: Vec<String>,
 }
 
 impl Default for ShellConfig {
@@ -69,8 +110,20 @@ impl Default for ShellConfig {
             enable_panel_close: true,
             enable_panel_maximize: true,
             enable_panel_drag: true,
+            layout_locked: false,
             enable_persistence: false,
             dark_mode: false,
+            theme_mode: ThemeMode::System,
+            appearance_poll_interval: APPEARANCE_POLL_INTERVAL,
+            overlay_sidebar_width: 270.0,
+            overlay_hover_delay: 0.15,
+            sidebar_open_duration: 0.25,
+            sidebar_close_duration: 0.25,
+            enable_overlay_shortcut: true,
+            theme_poll_interval: THEME_POLL_INTERVAL,
+            reduce_motion: false,
+            confirm_destructive_actions: true,
+            confirm_close_panels: Vec::new(),
         }
     }
 }
@@ -146,6 +199,20 @@ impl ShellConfigBuilder {
     /// Enable dark mode by default
     pub fn dark_mode(mut self) -> Self {
         self.config.dark_mode = true;
+        self.config.theme_mode = ThemeMode::Dark;
+        self
+    }
+
+    /// Set the theme mode used until the user saves one
+    pub fn theme_mode(mut self, mode: ThemeMode) -> Self {
+        self.config.dark_mode = mode == ThemeMode::Dark;
+        self.config.theme_mode = mode;
+        self
+    }
+
+    /// Set the seconds between platform appearance checks (0 = only on focus)
+    pub fn appearance_poll_interval(mut self, seconds: f64) -> Self {
+        self.config.appearance_poll_interval = seconds;
         self
     }
 
@@ -173,6 +240,61 @@ impl ShellConfigBuilder {
         self
     }
 
+    /// Start with the layout locked until the user unlocks it
+    pub fn lock_layout(mut self) -> Self {
+        self.config.layout_locked = true;
+        self
+    }
+
+    /// Set the overlay/pinned sidebar width
+    pub fn overlay_sidebar_width(mut self, width: f64) -> Self {
+        self.config.overlay_sidebar_width = width;
+        self
+    }
+
+    /// Set the hover delay before the overlay sidebar opens (seconds)
+    pub fn overlay_hover_delay(mut self, delay: f64) -> Self {
+        self.config.overlay_hover_delay = delay;
+        self
+    }
+
+    /// Set the sidebar open and close animation durations (seconds)
+    pub fn sidebar_durations(mut self, open: f64, close: f64) -> Self {
+        self.config.sidebar_open_duration = open;
+        self.config.sidebar_close_duration = close;
+        self
+    }
+
+    /// Disable the Ctrl/Cmd+B overlay sidebar shortcut
+    pub fn disable_overlay_shortcut(mut self) -> Self {
+        self.config.enable_overlay_shortcut = false;
+        self
+    }
+
+    /// Set how often theme files are checked for changes (seconds, 0 disables hot reload)
+    pub fn theme_poll_interval(mut self, interval: f64) -> Self {
+        self.config.theme_poll_interval = interval;
+        self
+    }
+
+    /// Turn off the theme, sidebar, header button and panel move animations
+    pub fn reduce_motion(mut self) -> Self {
+        self.config.reduce_motion = true;
+        self
+    }
+
+    /// Reset the layout and delete workspaces without asking
+    pub fn skip_confirmations(mut self) -> Self {
+        self.config.confirm_destructive_actions = false;
+        self
+    }
+
+    /// Ask before closing a panel (by panel ID)
+    pub fn confirm_close(mut self, panel_id: impl Into<String>) -> Self {
+        self.config.confirm_close_panels.push(panel_id.into());
+        self
+    }
+
     /// Build the ShellConfig
     pub fn build(self) -> ShellConfig {
         self.config
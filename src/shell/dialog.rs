@@ -0,0 +1,708 @@
+//! Modal dialogs
+//!
+//! `ShellDialog` shows one `Dialog` at a time over a dimmed backdrop. Three
+//! shapes are supported: confirmations, prompts with a text field and
+//! dialogs with an app-supplied body template. Requests made while a dialog
+//! is open are queued.
+//!
+//! While a dialog is open `ShellLayout` sends pointer and keyboard input
+//! to it only, so the rest of the shell can't be used until it closes.
+//!
+//! ## Keyboard
+//!
+//! | Key | Action |
+//! |-----|--------|
+//! | Tab / Shift+Tab | Move focus between the text field and the buttons |
+//! | Enter | Press the focused button, or the primary button |
+//! | Escape | Cancel |
+//!
+//! ## Usage
+//!
+//! ```rust,ignore
+//! shell.open_dialog(cx, Dialog::confirm("discard", "Discard changes?", "Unsaved edits will be lost.")
+//!     .with_confirm_label("Discard")
+//!     .destructive());
+//!
+//! if let Some((id, result)) = shell.dialog_closed(&actions) {
+//!     if id == "discard" && result.is_confirmed() { /* ... */ }
+//! }
+//! ```
+
+use std::collections::VecDeque;
+use makepad_widgets::*;
+use crate::theme::current_palette;
+
+live_design! {
+    use link::theme::*;
+    use link::shaders::*;
+    use link::widgets::*;
+
+    use crate::live_design::*;
+
+    pub ShellDialogButton = <Button> {
+        width: Fit, height: 30
+        padding: { left: 14, right: 14 }
+        margin: 0
+        text: ""
+
+        draw_bg: {
+            instance hover: 0.0
+            instance pressed: 0.0
+            instance focus: 0.0
+            // 0 = normal, 1 = primary, 2 = destructive
+            instance role: 0.0
+            uniform bg_hover: (PALETTE_BG_HOVER)
+            uniform bg_pressed: (PALETTE_BG_PRESSED)
+            uniform border: (PALETTE_BORDER)
+            uniform accent: (PALETTE_ACCENT)
+            uniform danger: (PALETTE_DANGER)
+
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                let filled = mix(self.accent, self.danger, step(1.5, self.role));
+                let plain = mix(vec4(self.bg_hover.xyz, self.hover), self.bg_pressed, self.pressed);
+                let color = mix(plain, filled, step(0.5, self.role));
+                sdf.box(1.5, 1.5, self.rect_size.x - 3.0, self.rect_size.y - 3.0, 4.0);
+                sdf.fill_keep(mix(color, color * 0.85, self.pressed * step(0.5, self.role)));
+                sdf.stroke(mix(self.border, self.accent, self.focus), 1.0 + self.focus);
+                return sdf.result;
+            }
+        }
+
+        draw_text: {
+            instance role: 0.0
+            uniform text_primary: (PALETTE_TEXT_PRIMARY)
+            uniform on_accent: (PALETTE_ON_ACCENT)
+            text_style: <FONT_MEDIUM>{ font_size: 10.0 }
+            fn get_color(self) -> vec4 {
+                return mix(self.text_primary, self.on_accent, step(0.5, self.role));
+            }
+        }
+    }
+
+    pub ShellDialogContent = <View> {
+        width: Fill, height: Fit
+        flow: Down
+        spacing: 10
+
+        title = <Label> {
+            width: Fill
+            draw_text: {
+                uniform text_primary: (PALETTE_TEXT_PRIMARY)
+                text_style: <FONT_SEMIBOLD>{ font_size: 12.0 }
+                wrap: Word
+                fn get_color(self) -> vec4 {
+                    return self.text_primary;
+                }
+            }
+            text: ""
+        }
+
+        message = <Label> {
+            width: Fill
+            draw_text: {
+                uniform text_secondary: (PALETTE_TEXT_SECONDARY)
+                text_style: <FONT_REGULAR>{ font_size: 10.5 }
+                wrap: Word
+                fn get_color(self) -> vec4 {
+                    return self.text_secondary;
+                }
+            }
+            text: ""
+        }
+
+        input = <TextInput> {
+            width: Fill, height: Fit
+            visible: false
+            empty_text: ""
+        }
+    }
+
+    pub ShellDialog = {{ShellDialog}} {
+        card_walk: { width: 420, height: Fit }
+        card_layout: { flow: Down, padding: 20, spacing: 16 }
+        buttons_walk: { width: Fill, height: Fit }
+        buttons_layout: { flow: Right, align: { x: 1.0 }, spacing: 8 }
+
+        draw_backdrop: {
+            uniform bg_app: (PALETTE_BG_APP)
+
+            fn pixel(self) -> vec4 {
+                // Darken light themes, deepen dark ones
+                let luma = dot(self.bg_app.xyz, vec3(0.299, 0.587, 0.114));
+                return vec4(0.0, 0.0, 0.0, mix(0.35, 0.55, step(luma, 0.5)));
+            }
+        }
+
+        draw_bg: {
+            uniform bg_app: (PALETTE_BG_APP)
+            uniform border: (PALETTE_BORDER)
+
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 8.0);
+                sdf.fill_keep(self.bg_app);
+                sdf.stroke(self.border, 1.0);
+                return sdf.result;
+            }
+        }
+
+        content_template: <ShellDialogContent> {}
+        button_template: <ShellDialogButton> {}
+    }
+}
+
+/// ID of the confirm button of `Dialog::confirm` and `Dialog::prompt`
+pub const DIALOG_OK: &str = "ok";
+
+/// ID of the cancel button of `Dialog::confirm` and `Dialog::prompt`
+pub const DIALOG_CANCEL: &str = "cancel";
+
+/// What a dialog button does
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DialogButtonRole {
+    /// Closes the dialog with `DialogResult::Button`
+    #[default]
+    Normal,
+
+    /// Highlighted; pressed by Enter
+    Primary,
+
+    /// Like `Primary`, drawn in the danger color
+    Destructive,
+
+    /// Closes the dialog with `DialogResult::Cancelled`
+    Cancel,
+}
+
+impl DialogButtonRole {
+    /// Value of the `role` shader instance
+    fn shader_value(self) -> f64 {
+        match self {
+            DialogButtonRole::Normal | DialogButtonRole::Cancel => 0.0,
+            DialogButtonRole::Primary => 1.0,
+            DialogButtonRole::Destructive => 2.0,
+        }
+    }
+}
+
+/// A dialog button
+#[derive(Clone, Debug, PartialEq)]
+pub struct DialogButton {
+    pub id: String,
+    pub label: String,
+    pub role: DialogButtonRole,
+}
+
+/// Text field of a prompt dialog
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DialogPrompt {
+    /// Initial text
+    pub text: String,
+
+    /// Shown while the field is empty
+    pub placeholder: String,
+}
+
+/// A modal dialog request
+#[derive(Clone, Debug)]
+pub struct Dialog {
+    /// Reported back with the result
+    pub id: String,
+
+    pub title: String,
+
+    pub message: String,
+
+    /// Text field (prompt dialogs)
+    pub prompt: Option<DialogPrompt>,
+
+    /// Body drawn below the message (custom dialogs)
+    pub body: Option<LivePtr>,
+
+    /// Buttons from left to right
+    pub buttons: Vec<DialogButton>,
+}
+
+impl Dialog {
+    /// Cancel and OK buttons; OK reports `DialogResult::Button(DIALOG_OK)`
+    pub fn confirm(id: impl Into<String>, title: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            title: title.into(),
+            message: message.into(),
+            prompt: None,
+            body: None,
+            buttons: vec![
+                DialogButton { id: DIALOG_CANCEL.to_string(), label: "Cancel".to_string(), role: DialogButtonRole::Cancel },
+                DialogButton { id: DIALOG_OK.to_string(), label: "OK".to_string(), role: DialogButtonRole::Primary },
+            ],
+        }
+    }
+
+    /// Text field with Cancel and OK buttons; OK reports `DialogResult::Text`
+    pub fn prompt(id: impl Into<String>, title: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { prompt: Some(DialogPrompt::default()), ..Self::confirm(id, title, message) }
+    }
+
+    /// App body template with no buttons (add them with `with_button`)
+    pub fn custom(id: impl Into<String>, title: impl Into<String>, body: Option<LivePtr>) -> Self {
+        Self { body, buttons: Vec::new(), ..Self::confirm(id, title, "") }
+    }
+
+    /// Set the message below the title
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    /// Set the initial text of a prompt
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.prompt.get_or_insert_with(DialogPrompt::default).text = text.into();
+        self
+    }
+
+    /// Set the placeholder of a prompt
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.prompt.get_or_insert_with(DialogPrompt::default).placeholder = placeholder.into();
+        self
+    }
+
+    /// Relabel the primary button
+    pub fn with_confirm_label(mut self, label: impl Into<String>) -> Self {
+        if let Some(button) = self.buttons.iter_mut().find(|button| button.is_default()) {
+            button.label = label.into();
+        }
+        self
+    }
+
+    /// Draw the primary button in the danger color
+    pub fn destructive(mut self) -> Self {
+        for button in self.buttons.iter_mut().filter(|button| button.role == DialogButtonRole::Primary) {
+            button.role = DialogButtonRole::Destructive;
+        }
+        self
+    }
+
+    /// Append a button
+    pub fn with_button(mut self, id: impl Into<String>, label: impl Into<String>, role: DialogButtonRole) -> Self {
+        self.buttons.push(DialogButton { id: id.into(), label: label.into(), role });
+        self
+    }
+
+    /// Index of the button pressed by Enter
+    pub fn default_button(&self) -> Option<usize> {
+        self.buttons.iter().position(DialogButton::is_default)
+    }
+
+    /// Result of pressing the button at `index` with the prompt text `text`
+    pub fn result_for(&self, index: usize, text: String) -> DialogResult {
+        let button = &self.buttons[index];
+        match button.role {
+            DialogButtonRole::Cancel => DialogResult::Cancelled,
+            _ if button.is_default() && self.prompt.is_some() => DialogResult::Text(text),
+            _ => DialogResult::Button(button.id.clone()),
+        }
+    }
+}
+
+impl DialogButton {
+    /// Whether Enter presses this button
+    fn is_default(&self) -> bool {
+        matches!(self.role, DialogButtonRole::Primary | DialogButtonRole::Destructive)
+    }
+}
+
+/// How a dialog was closed
+#[derive(Clone, Debug, PartialEq)]
+pub enum DialogResult {
+    /// A button was pressed (button ID)
+    Button(String),
+
+    /// A prompt was accepted (entered text)
+    Text(String),
+
+    /// Escape or a cancel button
+    Cancelled,
+}
+
+impl DialogResult {
+    /// OK of a confirmation, or an accepted prompt
+    pub fn is_confirmed(&self) -> bool {
+        match self {
+            DialogResult::Button(id) => id == DIALOG_OK,
+            DialogResult::Text(_) => true,
+            DialogResult::Cancelled => false,
+        }
+    }
+}
+
+/// Whether the event is pointer or keyboard input (trapped by an open dialog)
+pub fn is_input_event(event: &Event) -> bool {
+    matches!(
+        event,
+        Event::MouseDown(_) | Event::MouseMove(_) | Event::MouseUp(_) | Event::MouseLeave(_)
+            | Event::TouchUpdate(_) | Event::Scroll(_)
+            | Event::KeyDown(_) | Event::KeyUp(_)
+            | Event::TextInput(_) | Event::TextCopy(_) | Event::TextCut(_)
+    )
+}
+
+/// Actions emitted by `ShellDialog`
+#[derive(Clone, Debug, DefaultNone)]
+pub enum ShellDialogAction {
+    /// A dialog closed
+    Closed { id: String, result: DialogResult },
+
+    None,
+}
+
+/// What Tab moved the keyboard focus to
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum DialogFocus {
+    Input,
+    Button(usize),
+}
+
+/// Modal dialog drawn over a backdrop in an overlay
+#[derive(Live, LiveHook, Widget)]
+pub struct ShellDialog {
+    #[live]
+    draw_list: DrawList2d,
+
+    #[live]
+    draw_backdrop: DrawQuad,
+
+    #[redraw]
+    #[live]
+    draw_bg: DrawQuad,
+
+    #[walk]
+    walk: Walk,
+
+    #[live]
+    card_walk: Walk,
+
+    #[live]
+    card_layout: Layout,
+
+    #[live]
+    buttons_walk: Walk,
+
+    #[live]
+    buttons_layout: Layout,
+
+    #[live]
+    content_template: Option<LivePtr>,
+
+    #[live]
+    button_template: Option<LivePtr>,
+
+    /// Showing dialog
+    #[rust]
+    dialog: Option<Dialog>,
+
+    /// Dialogs requested while another was open
+    #[rust]
+    queue: VecDeque<Dialog>,
+
+    /// Title, message and text field
+    #[rust]
+    content: WidgetRef,
+
+    /// Custom body of the showing dialog
+    #[rust]
+    body: WidgetRef,
+
+    #[rust]
+    buttons: Vec<WidgetRef>,
+
+    #[rust]
+    focus: Option<DialogFocus>,
+
+    /// Results waiting to be emitted at the end of `handle_event`
+    #[rust]
+    pending_actions: Vec<ShellDialogAction>,
+}
+
+impl Widget for ShellDialog {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        let uid = self.widget_uid();
+        if self.dialog.is_some() {
+            self.handle_input(cx, event, scope);
+        }
+        for action in std::mem::take(&mut self.pending_actions) {
+            cx.widget_action(uid, &scope.path, action);
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, _walk: Walk) -> DrawStep {
+        if self.dialog.is_none() {
+            return DrawStep::done();
+        }
+        let palette = current_palette();
+        self.draw_backdrop.apply_over(cx, &palette.uniform_nodes());
+        self.draw_bg.apply_over(cx, &palette.uniform_nodes());
+        let text = palette.live_nodes(&[live_id!(draw_text)]);
+        let button_nodes = palette.live_nodes(&[live_id!(draw_bg), live_id!(draw_text)]);
+
+        // Draw above everything else, covering the whole window
+        self.draw_list.begin_overlay_reuse(cx);
+        cx.begin_pass_sized_turtle(Layout::flow_down());
+        let center = Layout { align: Align { x: 0.5, y: 0.5 }, ..Layout::flow_down() };
+        self.draw_backdrop.begin(cx, Walk::fill(), center);
+
+        self.draw_bg.begin(cx, self.card_walk, self.card_layout);
+        for label in [id!(title), id!(message)] {
+            self.content.label(label).apply_over(cx, &text);
+        }
+        self.content.draw_all(cx, scope);
+        self.body.draw_all(cx, scope);
+
+        cx.begin_turtle(self.buttons_walk, self.buttons_layout);
+        for button in &self.buttons {
+            button.apply_over(cx, &button_nodes);
+            button.draw_all(cx, scope);
+        }
+        cx.end_turtle();
+        self.draw_bg.end(cx);
+
+        self.draw_backdrop.end(cx);
+        cx.end_pass_sized_turtle();
+        self.draw_list.end(cx);
+
+        DrawStep::done()
+    }
+}
+
+impl ShellDialog {
+    /// Show a dialog, or queue it behind the one showing
+    pub fn open(&mut self, cx: &mut Cx, dialog: Dialog) {
+        if self.dialog.is_some() {
+            self.queue.push_back(dialog);
+        } else {
+            self.show(cx, dialog);
+        }
+    }
+
+    /// Cancel the showing dialog
+    pub fn close(&mut self, cx: &mut Cx) {
+        if self.dialog.is_some() {
+            self.finish(cx, DialogResult::Cancelled);
+        }
+    }
+
+    /// Whether a dialog is showing
+    pub fn is_open(&self) -> bool {
+        self.dialog.is_some()
+    }
+
+    /// ID of the showing dialog
+    pub fn open_id(&self) -> Option<&str> {
+        self.dialog.as_ref().map(|dialog| dialog.id.as_str())
+    }
+
+    /// Custom body of the showing dialog (empty for other dialogs)
+    pub fn body(&self) -> WidgetRef {
+        self.body.clone()
+    }
+
+    fn handle_input(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        // The body's actions belong to the app, so they aren't captured
+        self.body.handle_event(cx, event, scope);
+        let actions = cx.capture_actions(|cx| {
+            self.content.handle_event(cx, event, scope);
+            for button in &self.buttons {
+                button.handle_event(cx, event, scope);
+            }
+        });
+
+        let mut pressed = self.buttons.iter().position(|button| button.as_button().clicked(&actions));
+        if let Event::KeyDown(ke) = event {
+            match ke.key_code {
+                KeyCode::Escape => {
+                    self.finish(cx, DialogResult::Cancelled);
+                    return;
+                }
+                KeyCode::ReturnKey => {
+                    pressed = match self.focus {
+                        Some(DialogFocus::Button(index)) => Some(index),
+                        _ => self.dialog.as_ref().and_then(Dialog::default_button),
+                    };
+                }
+                KeyCode::Tab => self.move_focus(cx, !ke.modifiers.shift),
+                _ => {}
+            }
+        }
+
+        if let Some(index) = pressed {
+            let text = self.content.text_input(id!(input)).text();
+            if let Some(result) = self.dialog.as_ref().map(|dialog| dialog.result_for(index, text)) {
+                self.finish(cx, result);
+            }
+        }
+    }
+
+    fn show(&mut self, cx: &mut Cx, dialog: Dialog) {
+        if self.content.is_empty() {
+            self.content = WidgetRef::new_from_ptr(cx, self.content_template);
+        }
+        self.content.label(id!(title)).set_text(cx, &dialog.title);
+        let message = self.content.label(id!(message));
+        message.set_text(cx, &dialog.message);
+        message.set_visible(cx, !dialog.message.is_empty());
+
+        let input = self.content.text_input(id!(input));
+        input.set_visible(cx, dialog.prompt.is_some());
+        if let Some(prompt) = &dialog.prompt {
+            input.set_text(cx, &prompt.text);
+            let placeholder = prompt.placeholder.clone();
+            input.apply_over(cx, live! { empty_text: (placeholder) });
+        }
+
+        self.body = match dialog.body {
+            Some(body) => WidgetRef::new_from_ptr(cx, Some(body)),
+            None => WidgetRef::empty(),
+        };
+
+        self.buttons = dialog.buttons.iter().map(|button| {
+            let widget = WidgetRef::new_from_ptr(cx, self.button_template);
+            widget.set_text(cx, &button.label);
+            let role = button.role.shader_value();
+            widget.apply_over(cx, live! {
+                draw_bg: { role: (role) }
+                draw_text: { role: (role) }
+            });
+            widget
+        }).collect();
+
+        // Typing goes to the prompt; otherwise Enter presses the primary button
+        let focus = if dialog.prompt.is_some() { Some(DialogFocus::Input) } else { None };
+        self.dialog = Some(dialog);
+        self.set_focus(cx, focus);
+        self.draw_list.redraw(cx);
+    }
+
+    /// Close the showing dialog with `result` and show the next queued one
+    fn finish(&mut self, cx: &mut Cx, result: DialogResult) {
+        let Some(dialog) = self.dialog.take() else { return };
+        self.body = WidgetRef::empty();
+        self.buttons.clear();
+        self.focus = None;
+        cx.set_key_focus(Area::Empty);
+        self.draw_list.redraw(cx);
+
+        self.pending_actions.push(ShellDialogAction::Closed { id: dialog.id, result });
+
+        if let Some(next) = self.queue.pop_front() {
+            self.show(cx, next);
+        }
+    }
+
+    /// Move focus to the next (or previous) of text field and buttons, wrapping around
+    fn move_focus(&mut self, cx: &mut Cx, forward: bool) {
+        let Some(dialog) = &self.dialog else { return };
+        let mut stops: Vec<DialogFocus> = Vec::new();
+        if dialog.prompt.is_some() {
+            stops.push(DialogFocus::Input);
+        }
+        stops.extend((0..dialog.buttons.len()).map(DialogFocus::Button));
+        if stops.is_empty() {
+            return;
+        }
+
+        let len = stops.len();
+        let next = match self.focus.and_then(|focus| stops.iter().position(|stop| *stop == focus)) {
+            Some(index) if forward => (index + 1) % len,
+            Some(index) => (index + len - 1) % len,
+            None if forward => 0,
+            None => len - 1,
+        };
+        self.set_focus(cx, Some(stops[next]));
+    }
+
+    fn set_focus(&mut self, cx: &mut Cx, focus: Option<DialogFocus>) {
+        self.focus = focus;
+        for (index, button) in self.buttons.iter().enumerate() {
+            let focused = if focus == Some(DialogFocus::Button(index)) { 1.0 } else { 0.0 };
+            button.apply_over(cx, live! {
+                draw_bg: { focus: (focused) }
+            });
+        }
+        match focus {
+            Some(DialogFocus::Input) => self.content.text_input(id!(input)).set_key_focus(cx),
+            // Take key focus away from text fields behind the dialog
+            _ => cx.set_key_focus(self.draw_bg.area()),
+        }
+        self.draw_list.redraw(cx);
+    }
+}
+
+impl ShellDialogRef {
+    /// Show a dialog, or queue it behind the one showing
+    pub fn open(&self, cx: &mut Cx, dialog: Dialog) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.open(cx, dialog);
+        }
+    }
+
+    /// Cancel the showing dialog
+    pub fn close(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.close(cx);
+        }
+    }
+
+    /// Whether a dialog is showing
+    pub fn is_open(&self) -> bool {
+        self.borrow().map_or(false, |inner| inner.is_open())
+    }
+
+    /// Custom body of the showing dialog
+    pub fn body(&self) -> WidgetRef {
+        self.borrow().map(|inner| inner.body()).unwrap_or_default()
+    }
+
+    /// Returns the ID and result of a dialog that closed
+    pub fn closed(&self, actions: &Actions) -> Option<(String, DialogResult)> {
+        if let ShellDialogAction::Closed { id, result } = actions.find_widget_action(self.widget_uid()).cast() {
+            Some((id, result))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confirm_results() {
+        let dialog = Dialog::confirm("reset", "Reset?", "").with_confirm_label("Reset").destructive();
+        let ok = dialog.default_button().unwrap();
+        assert_eq!(dialog.buttons[ok].label, "Reset");
+        assert_eq!(dialog.buttons[ok].role, DialogButtonRole::Destructive);
+
+        assert!(dialog.result_for(ok, String::new()).is_confirmed());
+        assert_eq!(dialog.result_for(0, String::new()), DialogResult::Cancelled);
+    }
+
+    #[test]
+    fn test_prompt_returns_text() {
+        let dialog = Dialog::prompt("rename", "Rename", "").with_text("Editor");
+        let ok = dialog.default_button().unwrap();
+        assert_eq!(dialog.result_for(ok, "Code".to_string()), DialogResult::Text("Code".to_string()));
+    }
+
+    #[test]
+    fn test_custom_buttons() {
+        let dialog = Dialog::custom("export", "Export", None)
+            .with_button("later", "Later", DialogButtonRole::Normal)
+            .with_button("export", "Export", DialogButtonRole::Primary);
+        assert_eq!(dialog.default_button(), Some(1));
+        let later = dialog.result_for(0, String::new());
+        assert_eq!(later, DialogResult::Button("later".to_string()));
+        assert!(!later.is_confirmed());
+    }
+}
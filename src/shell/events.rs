@@ -0,0 +1,411 @@
+//! Unified shell event stream
+//!
+//! `ShellLayout` translates the low-level actions of its children
+//! (`PanelAction`, `ShellHeaderAction`, `SidebarAction`, dock splitter
+//! changes) into a single `ShellEvent` enum keyed by semantic panel IDs.
+//!
+//! ## Usage
+//!
+//! ```rust,ignore
+//! impl MatchEvent for App {
+//!     fn handle_actions(&mut self, cx: &mut Cx, actions: &Actions) {
+//!         let shell = self.ui.shell_layout(id!(body));
+//!         for event in shell.shell_events(actions) {
+//!             match event {
+//!                 ShellEvent::PanelClosed(id) => log!("closed {}", id),
+//!                 ShellEvent::ThemeChanged { dark_mode } => log!("dark={}", dark_mode),
+//!                 _ => {}
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+
+use std::path::PathBuf;
+use makepad_widgets::*;
+use crate::callbacks::SplitterId;
+use crate::grid::{LayoutState, FooterLayoutState};
+use crate::panel::PanelId;
+use crate::shell::activity_bar::SidebarSide;
+use crate::shell::dialog::DialogResult;
+use crate::shell::sidebar::SidebarSelection;
+
+/// High-level events emitted by `ShellLayout`
+#[derive(Clone, Debug, DefaultNone)]
+pub enum ShellEvent {
+    /// A panel received a click and is now the focused panel
+    PanelFocused(String),
+
+    /// A panel became visible
+    PanelOpened(String),
+
+    /// A panel was closed
+    PanelClosed(String),
+
+    /// A panel was moved to a new position
+    ///
+    /// Positions are `(row, column)` in the main grid and
+    /// `(slot, stack_index)` in the footer grid.
+    PanelMoved {
+        panel_id: String,
+        from: (usize, usize),
+        to: (usize, usize),
+    },
+
+    /// A main grid panel was maximized or restored
+    PanelMaximized { panel_id: String, maximized: bool },
+
+    /// A main grid panel was minimized to a chip or restored from it
+    PanelMinimized { panel_id: String, minimized: bool },
+
+    /// A footer panel entered or left fullscreen
+    PanelFullscreen { panel_id: String, fullscreen: bool },
+
+    /// A panel's title changed (renamed by the user or restored with a layout)
+    ///
+    /// An empty `title` means the user's title was dropped and the panel is back
+    /// to its default title.
+    PanelRenamed { panel_id: String, title: String },
+
+    /// The user pinned or unpinned a panel (panels pinned by their definition aren't reported)
+    PanelPinned { panel_id: String, pinned: bool },
+
+    /// A panel's title bar action was clicked (`checked` = new state of a toggle action)
+    PanelTitleAction { panel_id: String, action_id: String, checked: Option<bool> },
+
+    /// Dark mode was toggled
+    ThemeChanged { dark_mode: bool },
+
+    /// A theme was picked (`None` = default theme)
+    ThemeSelected { name: Option<String> },
+
+    /// A theme file failed to load
+    ///
+    /// `key` is the dotted path of the offending key (e.g. `colors.dark.accent`),
+    /// empty for I/O and syntax errors.
+    ThemeFileError { path: PathBuf, key: String, message: String },
+
+    /// Sidebar selection changed
+    SidebarSelectionChanged(Option<SidebarSelection>),
+
+    /// A sidebar switched views from its activity bar (`None` = collapsed)
+    SidebarViewChanged { side: SidebarSide, view_id: Option<String> },
+
+    /// A dock splitter was moved
+    SplitterChanged { splitter: SplitterId, position: f64 },
+
+    /// The active workspace changed
+    WorkspaceSwitched(String),
+
+    /// A workspace was deleted (after confirmation)
+    WorkspaceDeleted(String),
+
+    /// Layout was reset to defaults
+    LayoutReset,
+
+    /// The header's layout lock was turned on (true) or off
+    LayoutLockChanged(bool),
+
+    /// Layout was saved to disk
+    LayoutSaved,
+
+    /// A header toolbar item without a menu was clicked
+    ///
+    /// `checked` is the new state of a toggle item (`None` for push buttons).
+    ToolbarItemActivated { id: String, checked: Option<bool> },
+
+    /// An entry of a header toolbar item's dropdown menu was chosen
+    ToolbarMenuSelected { item_id: String, entry_id: String },
+
+    /// A menu bar item was chosen
+    ///
+    /// `checked` is the new state of a checkable item (`None` otherwise).
+    MenuSelected { id: String, checked: Option<bool> },
+
+    /// A clickable status bar item was clicked (item ID)
+    StatusItemClicked(String),
+
+    /// An action button of a notification toast was clicked
+    NotificationAction { notification_id: String, action_id: String },
+
+    /// The user closed a notification toast (notification ID)
+    NotificationDismissed(String),
+
+    /// A dialog opened with `open_dialog` closed
+    DialogClosed { id: String, result: DialogResult },
+
+    /// No event
+    None,
+}
+
+/// Compute the panel events implied by a main grid layout change
+pub fn diff_layout(old: &LayoutState, new: &LayoutState) -> Vec<ShellEvent> {
+    let mut events = Vec::new();
+
+    for panel_id in &old.visible_panels {
+        if !new.visible_panels.contains(panel_id) {
+            events.push(ShellEvent::PanelClosed(panel_id.to_string()));
+        }
+    }
+    for panel_id in &new.visible_panels {
+        if !old.visible_panels.contains(panel_id) {
+            events.push(ShellEvent::PanelOpened(panel_id.to_string()));
+        }
+    }
+
+    // Moves only make sense for panels that stayed visible
+    for panel_id in new.visible_panels.intersection(&old.visible_panels) {
+        if let (Some(from), Some(to)) = (old.find_panel_row(panel_id), new.find_panel_row(panel_id)) {
+            if from != to {
+                events.push(ShellEvent::PanelMoved {
+                    panel_id: panel_id.to_string(),
+                    from,
+                    to,
+                });
+            }
+        }
+    }
+
+    // A closed panel loses its chip implicitly; PanelClosed covers it
+    for panel_id in &old.minimized_panels {
+        if !new.is_minimized(panel_id) && new.visible_panels.contains(panel_id) {
+            events.push(ShellEvent::PanelMinimized { panel_id: panel_id.to_string(), minimized: false });
+        }
+    }
+    for panel_id in &new.minimized_panels {
+        if !old.is_minimized(panel_id) {
+            events.push(ShellEvent::PanelMinimized { panel_id: panel_id.to_string(), minimized: true });
+        }
+    }
+
+    events.extend(diff_pinned(&old.pinned_panels, &new.pinned_panels));
+
+    // Titles the user gave in place or through the context menu
+    for (panel_id, title) in &new.panel_titles {
+        if new.visible_panels.contains(panel_id) && old.panel_titles.get(panel_id) != Some(title) {
+            events.push(ShellEvent::PanelRenamed {
+                panel_id: panel_id.to_string(),
+                title: title.clone(),
+            });
+        }
+    }
+    for panel_id in old.panel_titles.keys() {
+        if new.visible_panels.contains(panel_id) && !new.panel_titles.contains_key(panel_id) {
+            events.push(ShellEvent::PanelRenamed {
+                panel_id: panel_id.to_string(),
+                title: String::new(),
+            });
+        }
+    }
+
+    if old.maximized_panel != new.maximized_panel {
+        if let Some(panel_id) = &old.maximized_panel {
+            // A closed panel loses maximize implicitly; PanelClosed covers it
+            if new.visible_panels.contains(panel_id) {
+                events.push(ShellEvent::PanelMaximized {
+                    panel_id: panel_id.to_string(),
+                    maximized: false,
+                });
+            }
+        }
+        if let Some(panel_id) = &new.maximized_panel {
+            events.push(ShellEvent::PanelMaximized {
+                panel_id: panel_id.to_string(),
+                maximized: true,
+            });
+        }
+    }
+
+    events
+}
+
+/// Compute the panel events implied by a footer layout change
+pub fn diff_footer_layout(old: &FooterLayoutState, new: &FooterLayoutState) -> Vec<ShellEvent> {
+    let mut events = Vec::new();
+
+    for (panel_id, from) in footer_positions(old) {
+        match find_footer_position(new, &panel_id) {
+            None => events.push(ShellEvent::PanelClosed(panel_id)),
+            Some(to) if to != from => events.push(ShellEvent::PanelMoved { panel_id, from, to }),
+            Some(_) => {}
+        }
+    }
+    for (panel_id, _) in footer_positions(new) {
+        if find_footer_position(old, &panel_id).is_none() {
+            events.push(ShellEvent::PanelOpened(panel_id));
+        }
+    }
+
+    events.extend(diff_pinned(&old.pinned_panels, &new.pinned_panels));
+
+    if old.fullscreen_panel != new.fullscreen_panel {
+        if let Some(panel_id) = &old.fullscreen_panel {
+            if find_footer_position(new, panel_id).is_some() {
+                events.push(ShellEvent::PanelFullscreen {
+                    panel_id: panel_id.to_string(),
+                    fullscreen: false,
+                });
+            }
+        }
+        if let Some(panel_id) = &new.fullscreen_panel {
+            events.push(ShellEvent::PanelFullscreen {
+                panel_id: panel_id.to_string(),
+                fullscreen: true,
+            });
+        }
+    }
+
+    events
+}
+
+/// `PanelPinned` events between two lists of pinned panels
+fn diff_pinned(old: &[PanelId], new: &[PanelId]) -> Vec<ShellEvent> {
+    let unpinned = old.iter().filter(|id| !new.contains(id)).map(|id| (id, false));
+    let pinned = new.iter().filter(|id| !old.contains(id)).map(|id| (id, true));
+    unpinned.chain(pinned)
+        .map(|(panel_id, pinned)| ShellEvent::PanelPinned { panel_id: panel_id.to_string(), pinned })
+        .collect()
+}
+
+/// All panels shown in visible footer slots with their (slot, stack_index) position
+fn footer_positions(state: &FooterLayoutState) -> Vec<(String, (usize, usize))> {
+    let mut positions = Vec::new();
+    for (slot_idx, slot) in state.slots.iter().enumerate() {
+        if !slot.visible {
+            continue;
+        }
+        for (stack_idx, panel_id) in slot.panel_ids.iter().enumerate() {
+            positions.push((panel_id.to_string(), (slot_idx, stack_idx)));
+        }
+    }
+    positions
+}
+
+fn find_footer_position(state: &FooterLayoutState, panel_id: &str) -> Option<(usize, usize)> {
+    footer_positions(state)
+        .into_iter()
+        .find(|(id, _)| id == panel_id)
+        .map(|(_, pos)| pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Moves in a diff, sorted since panel sets iterate in no fixed order
+    fn moves(events: &[ShellEvent]) -> Vec<(String, (usize, usize), (usize, usize))> {
+        let mut moves: Vec<_> = events.iter().filter_map(|event| match event {
+            ShellEvent::PanelMoved { panel_id, from, to } => Some((panel_id.clone(), *from, *to)),
+            _ => None,
+        }).collect();
+        moves.sort();
+        moves
+    }
+
+    #[test]
+    fn test_close_and_open_are_reported_once() {
+        let old = LayoutState::default();
+        let mut new = old.clone();
+        new.close_panel("panel_4");
+
+        let events = diff_layout(&old, &new);
+        assert!(matches!(&events[..], [ShellEvent::PanelClosed(id)] if id == "panel_4"), "{:?}", events);
+
+        // A closed panel keeps its slot, so reopening it doesn't move anything
+        let events = diff_layout(&new, &old);
+        assert!(matches!(&events[..], [ShellEvent::PanelOpened(id)] if id == "panel_4"), "{:?}", events);
+    }
+
+    #[test]
+    fn test_move_reports_every_shifted_panel() {
+        let old = LayoutState::default();
+        let mut new = old.clone();
+        new.move_panel("panel_2", 1, 0);
+
+        assert_eq!(moves(&diff_layout(&old, &new)), vec![
+            ("panel_2".to_string(), (0, 2), (1, 0)),
+            ("panel_3".to_string(), (1, 0), (1, 1)),
+            ("panel_4".to_string(), (1, 1), (1, 2)),
+            ("panel_5".to_string(), (1, 2), (1, 3)),
+        ]);
+    }
+
+    #[test]
+    fn test_maximize_and_restore() {
+        let old = LayoutState::default();
+        let mut new = old.clone();
+        new.maximized_panel = Some("panel_1".into());
+
+        let events = diff_layout(&old, &new);
+        assert!(matches!(&events[..], [ShellEvent::PanelMaximized { panel_id, maximized: true }] if panel_id == "panel_1"), "{:?}", events);
+
+        let events = diff_layout(&new, &old);
+        assert!(matches!(&events[..], [ShellEvent::PanelMaximized { panel_id, maximized: false }] if panel_id == "panel_1"), "{:?}", events);
+
+        // Closing the maximized panel restores it implicitly
+        let mut closed = new.clone();
+        closed.close_panel("panel_1");
+        let events = diff_layout(&new, &closed);
+        assert!(matches!(&events[..], [ShellEvent::PanelClosed(id)] if id == "panel_1"), "{:?}", events);
+    }
+
+    #[test]
+    fn test_rename_of_visible_panels_only() {
+        let mut old = LayoutState::default();
+        old.close_panel("panel_8");
+        let mut new = old.clone();
+        new.set_panel_title("panel_0", "Timeline");
+        new.set_panel_title("panel_8", "Hidden");
+
+        let events = diff_layout(&old, &new);
+        assert!(matches!(&events[..], [ShellEvent::PanelRenamed { panel_id, title }] if panel_id == "panel_0" && title == "Timeline"), "{:?}", events);
+        assert!(diff_layout(&new, &new.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_dropped_title_reports_the_default() {
+        let mut old = LayoutState::default();
+        old.set_panel_title("panel_0", "Timeline");
+        old.set_panel_title("panel_1", "Viewer");
+        let mut new = old.clone();
+        new.panel_titles.remove("panel_0");
+        new.close_panel("panel_1");
+        new.panel_titles.remove("panel_1");
+
+        let events = diff_layout(&old, &new);
+        assert!(matches!(&events[..], [ShellEvent::PanelClosed(closed), ShellEvent::PanelRenamed { panel_id, title }]
+            if closed == "panel_1" && panel_id == "panel_0" && title.is_empty()), "{:?}", events);
+    }
+
+    #[test]
+    fn test_footer_close_open_and_move() {
+        let old = FooterLayoutState::default();
+
+        let mut closed = old.clone();
+        closed.slots[3].visible = false;
+        let events = diff_footer_layout(&old, &closed);
+        assert!(matches!(&events[..], [ShellEvent::PanelClosed(id)] if id == "footer_panel_3"), "{:?}", events);
+        let events = diff_footer_layout(&closed, &old);
+        assert!(matches!(&events[..], [ShellEvent::PanelOpened(id)] if id == "footer_panel_3"), "{:?}", events);
+
+        // Stacking a panel onto another slot leaves its own slot empty
+        let mut stacked = old.clone();
+        let panel_id = stacked.slots[1].panel_ids.remove(0);
+        stacked.slots[0].panel_ids.push(panel_id);
+        assert_eq!(moves(&diff_footer_layout(&old, &stacked)), vec![
+            ("footer_panel_1".to_string(), (1, 0), (0, 1)),
+        ]);
+    }
+
+    #[test]
+    fn test_footer_fullscreen() {
+        let old = FooterLayoutState::default();
+        let mut new = old.clone();
+        new.fullscreen_panel = Some("footer_panel_2".into());
+
+        let events = diff_footer_layout(&old, &new);
+        assert!(matches!(&events[..], [ShellEvent::PanelFullscreen { panel_id, fullscreen: true }] if panel_id == "footer_panel_2"), "{:?}", events);
+        let events = diff_footer_layout(&new, &old);
+        assert!(matches!(&events[..], [ShellEvent::PanelFullscreen { panel_id, fullscreen: false }] if panel_id == "footer_panel_2"), "{:?}", events);
+    }
+}
@@ -1,6 +1,13 @@
 //! Shell footer widget
+//!
+//! Status bar with a status text, app status items (see `status_bar`) in a
+//! left and a right group, and a hint text.
 
 use makepad_widgets::*;
+use crate::shell::status_bar::{
+    StatusAlignment, StatusBarGroupWidgetExt, StatusBarModel, StatusItem, StatusProgress,
+};
+use crate::theme::{ShellPalette, ThemeTracker};
 
 live_design! {
     use link::theme::*;
@@ -8,6 +15,7 @@ live_design! {
     use link::widgets::*;
 
     use crate::live_design::*;
+    use crate::shell::status_bar::*;
 
     pub ShellFooter = {{ShellFooter}} {
         width: Fill
@@ -15,12 +23,9 @@ live_design! {
 
         show_bg: true
         draw_bg: {
-            instance dark_mode: 0.0
+            uniform bg_sidebar: (PALETTE_BG_SIDEBAR)
             fn pixel(self) -> vec4 {
-                // Light: slate-100, Dark: slate-800
-                let light = vec4(0.945, 0.961, 0.976, 1.0);
-                let dark = vec4(0.122, 0.161, 0.231, 1.0);
-                return mix(light, dark, self.dark_mode);
+                return self.bg_sidebar;
             }
         }
 
@@ -31,29 +36,26 @@ live_design! {
 
         status_label = <Label> {
             draw_text: {
-                instance dark_mode: 0.0
+                uniform text_muted: (PALETTE_TEXT_MUTED)
                 text_style: <FONT_REGULAR> { font_size: 11.0 }
                 fn get_color(self) -> vec4 {
-                    // Light: gray-600, Dark: slate-400
-                    let light = vec4(0.294, 0.333, 0.388, 1.0);
-                    let dark = vec4(0.580, 0.639, 0.722, 1.0);
-                    return mix(light, dark, self.dark_mode);
+                    return self.text_muted;
                 }
             }
             text: "Footer - Timeline / Status Bar"
         }
 
+        // App status items by alignment (empty until items are set)
+        left_items = <StatusBarGroup> {}
         <View> { width: Fill }
+        right_items = <StatusBarGroup> {}
 
         hint_label = <Label> {
             draw_text: {
-                instance dark_mode: 0.0
+                uniform text_faint: (PALETTE_TEXT_FAINT)
                 text_style: <FONT_REGULAR> { font_size: 10.0 }
                 fn get_color(self) -> vec4 {
-                    // Light: gray-400, Dark: slate-500
-                    let light = vec4(0.612, 0.639, 0.686, 1.0);
-                    let dark = vec4(0.392, 0.455, 0.545, 1.0);
-                    return mix(light, dark, self.dark_mode);
+                    return self.text_faint;
                 }
             }
             text: "Drag top edge to resize"
@@ -61,6 +63,14 @@ live_design! {
     }
 }
 
+/// Actions emitted by the shell footer
+#[derive(Clone, Debug, DefaultNone)]
+pub enum ShellFooterAction {
+    /// A clickable status item was clicked (item ID)
+    StatusItemClicked(String),
+    None,
+}
+
 /// Shell footer widget
 #[derive(Live, LiveHook, Widget)]
 pub struct ShellFooter {
@@ -72,14 +82,38 @@ pub struct ShellFooter {
 
     #[live]
     hint: String,
+
+    /// Hide the footer while it has no status items
+    #[live]
+    hide_when_empty: bool,
+
+    /// App status items of both groups
+    #[rust]
+    items: StatusBarModel,
+
+    #[rust]
+    theme_tracker: ThemeTracker,
 }
 
 impl Widget for ShellFooter {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
-        self.view.handle_event(cx, event, scope);
+        let actions = cx.capture_actions(|cx| {
+            self.view.handle_event(cx, event, scope);
+        });
+
+        for group in [id!(left_items), id!(right_items)] {
+            if let Some(id) = self.view.status_bar_group(group).clicked(&actions) {
+                cx.widget_action(self.widget_uid(), &scope.path, ShellFooterAction::StatusItemClicked(id));
+            }
+        }
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        // Apply the shell theme when it changed
+        if let Some(theme) = self.theme_tracker.changed() {
+            self.apply_palette_internal(cx, &theme.palette);
+        }
+
         if !self.status.is_empty() {
             self.view.label(id!(status_label)).set_text(cx, &self.status);
         }
@@ -91,6 +125,86 @@ impl Widget for ShellFooter {
     }
 }
 
+impl ShellFooter {
+    /// Add a status item, or replace the item with the same ID
+    pub fn set_status_item(&mut self, cx: &mut Cx, item: StatusItem) {
+        let alignment = item.alignment;
+        let old_alignment = self.items.get(&item.id).map(|old| old.alignment);
+        self.items.set(item);
+        if let Some(old_alignment) = old_alignment.filter(|&old| old != alignment) {
+            self.sync_group(cx, old_alignment);
+        }
+        self.sync_group(cx, alignment);
+    }
+
+    /// Remove a status item
+    ///
+    /// Returns false if no item has this ID.
+    pub fn remove_status_item(&mut self, cx: &mut Cx, id: &str) -> bool {
+        let Some(alignment) = self.items.get(id).map(|item| item.alignment) else {
+            return false;
+        };
+        self.items.remove(id);
+        self.sync_group(cx, alignment);
+        true
+    }
+
+    /// Change the text of a status item
+    ///
+    /// Returns false if no item has this ID.
+    pub fn set_status_item_text(&mut self, cx: &mut Cx, id: &str, text: &str) -> bool {
+        self.update_status_item(cx, id, |item| item.text = text.to_string())
+    }
+
+    /// Show (`Some`) or hide the progress of a status item
+    ///
+    /// Returns false if no item has this ID.
+    pub fn set_status_item_progress(&mut self, cx: &mut Cx, id: &str, progress: Option<StatusProgress>) -> bool {
+        self.update_status_item(cx, id, |item| item.progress = progress)
+    }
+
+    /// A status item by ID
+    pub fn status_item(&self, id: &str) -> Option<&StatusItem> {
+        self.items.get(id)
+    }
+
+    /// Keep progress spinners still (true) or rotating
+    pub fn set_reduce_motion(&mut self, cx: &mut Cx, reduce_motion: bool) {
+        for group in [id!(left_items), id!(right_items)] {
+            self.view.status_bar_group(group).set_reduce_motion(cx, reduce_motion);
+        }
+    }
+
+    fn update_status_item(&mut self, cx: &mut Cx, id: &str, update: impl FnOnce(&mut StatusItem)) -> bool {
+        let Some(item) = self.items.get_mut(id) else {
+            return false;
+        };
+        update(item);
+        let alignment = item.alignment;
+        self.sync_group(cx, alignment);
+        true
+    }
+
+    /// Push the items of one alignment to its group widget
+    fn sync_group(&mut self, cx: &mut Cx, alignment: StatusAlignment) {
+        let group = match alignment {
+            StatusAlignment::Left => id!(left_items),
+            StatusAlignment::Right => id!(right_items),
+        };
+        self.view.status_bar_group(group).set_items(cx, self.items.aligned(alignment));
+        if self.hide_when_empty {
+            self.view.set_visible(cx, !self.items.is_empty());
+        }
+    }
+
+    fn apply_palette_internal(&mut self, cx: &mut Cx, palette: &ShellPalette) {
+        self.view.apply_over(cx, &palette.live_nodes(&[live_id!(draw_bg)]));
+        let text = palette.live_nodes(&[live_id!(draw_text)]);
+        self.view.label(id!(status_label)).apply_over(cx, &text);
+        self.view.label(id!(hint_label)).apply_over(cx, &text);
+    }
+}
+
 impl ShellFooterRef {
     pub fn set_status(&self, cx: &mut Cx, status: &str) {
         if let Some(mut inner) = self.borrow_mut() {
@@ -106,17 +220,58 @@ impl ShellFooterRef {
         }
     }
 
+    /// Apply the built-in palettes blended by a dark mode value
     pub fn apply_dark_mode(&self, cx: &mut Cx, dark_mode: f64) {
+        self.apply_palette(cx, &ShellPalette::for_dark_mode(dark_mode));
+    }
+
+    /// Apply a palette to the footer
+    pub fn apply_palette(&self, cx: &mut Cx, palette: &ShellPalette) {
         if let Some(mut inner) = self.borrow_mut() {
-            inner.view.apply_over(cx, live! {
-                draw_bg: { dark_mode: (dark_mode) }
-            });
-            inner.view.label(id!(status_label)).apply_over(cx, live! {
-                draw_text: { dark_mode: (dark_mode) }
-            });
-            inner.view.label(id!(hint_label)).apply_over(cx, live! {
-                draw_text: { dark_mode: (dark_mode) }
-            });
+            inner.apply_palette_internal(cx, palette);
+        }
+    }
+
+    /// Add a status item, or replace the item with the same ID
+    pub fn set_status_item(&self, cx: &mut Cx, item: StatusItem) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_status_item(cx, item);
+        }
+    }
+
+    /// Remove a status item
+    pub fn remove_status_item(&self, cx: &mut Cx, id: &str) -> bool {
+        self.borrow_mut().map_or(false, |mut inner| inner.remove_status_item(cx, id))
+    }
+
+    /// Change the text of a status item
+    pub fn set_status_item_text(&self, cx: &mut Cx, id: &str, text: &str) -> bool {
+        self.borrow_mut().map_or(false, |mut inner| inner.set_status_item_text(cx, id, text))
+    }
+
+    /// Show (`Some`) or hide the progress of a status item
+    pub fn set_status_item_progress(&self, cx: &mut Cx, id: &str, progress: Option<StatusProgress>) -> bool {
+        self.borrow_mut().map_or(false, |mut inner| inner.set_status_item_progress(cx, id, progress))
+    }
+
+    /// A status item by ID
+    pub fn status_item(&self, id: &str) -> Option<StatusItem> {
+        self.borrow().and_then(|inner| inner.status_item(id).cloned())
+    }
+
+    /// Keep progress spinners still (true) or rotating
+    pub fn set_reduce_motion(&self, cx: &mut Cx, reduce_motion: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_reduce_motion(cx, reduce_motion);
+        }
+    }
+
+    /// Returns the ID of a clicked status item, if any
+    pub fn status_item_clicked(&self, actions: &Actions) -> Option<String> {
+        if let ShellFooterAction::StatusItemClicked(id) = actions.find_widget_action(self.widget_uid()).cast() {
+            Some(id)
+        } else {
+            None
         }
     }
 }
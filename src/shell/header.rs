@@ -1,6 +1,14 @@
 //! Shell header widget
+//!
+//! Hamburger, title, the app menu bar (see `menu_bar`), app toolbar items
+//! (see `toolbar`), the theme picker and the built-in reset, save and dark
+//! mode buttons.
 
 use makepad_widgets::*;
+use crate::shell::menu::MenuModel;
+use crate::shell::menu_bar::MenuBarWidgetExt;
+use crate::shell::toolbar::{HeaderToolbarAction, HeaderToolbarWidgetExt, ToolbarGroup, ToolbarItem};
+use crate::theme::{ShellPalette, ThemeTracker};
 
 live_design! {
     use link::theme::*;
@@ -8,6 +16,8 @@ live_design! {
     use link::widgets::*;
 
     use crate::live_design::*;
+    use crate::shell::menu_bar::*;
+    use crate::shell::toolbar::*;
 
     pub ShellHeader = {{ShellHeader}} {
         width: Fill
@@ -15,12 +25,9 @@ live_design! {
 
         show_bg: true
         draw_bg: {
-            instance dark_mode: 0.0
+            uniform bg_app: (PALETTE_BG_APP)
             fn pixel(self) -> vec4 {
-                // Light: white, Dark: slate-900
-                let light = vec4(1.0, 1.0, 1.0, 1.0);
-                let dark = vec4(0.059, 0.090, 0.165, 1.0);
-                return mix(light, dark, self.dark_mode);
+                return self.bg_app;
             }
         }
 
@@ -37,19 +44,15 @@ live_design! {
             text: ""
 
             draw_bg: {
-                instance dark_mode: 0.0
+                uniform text_primary: (PALETTE_TEXT_PRIMARY)
+                uniform accent: (PALETTE_ACCENT)
 
                 fn pixel(self) -> vec4 {
                     let sdf = Sdf2d::viewport(self.pos * self.rect_size);
                     let cx = self.rect_size.x * 0.5;
                     let cy = self.rect_size.y * 0.5;
 
-                    // Stroke color: dark in light mode, light in dark mode
-                    let light_stroke = vec4(0.122, 0.161, 0.216, 1.0);
-                    let dark_stroke = vec4(0.945, 0.961, 0.976, 1.0);
-                    let hover_stroke = vec4(0.231, 0.510, 0.965, 1.0);
-                    let base = mix(light_stroke, dark_stroke, self.dark_mode);
-                    let stroke = mix(base, hover_stroke, self.hover);
+                    let stroke = mix(self.text_primary, self.accent, self.hover);
                     let line_width = 1.8;
 
                     // Draw three horizontal lines (hamburger icon)
@@ -86,29 +89,87 @@ live_design! {
 
         title_label = <Label> {
             draw_text: {
-                instance dark_mode: 0.0
+                uniform text_primary: (PALETTE_TEXT_PRIMARY)
                 text_style: <FONT_SEMIBOLD> { font_size: 14.0 }
                 fn get_color(self) -> vec4 {
-                    // Light: gray-800, Dark: slate-100
-                    let light = vec4(0.122, 0.161, 0.216, 1.0);
-                    let dark = vec4(0.945, 0.961, 0.976, 1.0);
-                    return mix(light, dark, self.dark_mode);
+                    return self.text_primary;
                 }
             }
             text: "Makepad Flex App Layout Shell"
         }
 
+        // App menus (hidden until a menu model is set)
+        menu_bar = <MenuBar> { margin: { left: 12 } }
+
+        // App toolbar items by alignment group (hidden while empty)
+        toolbar_left = <HeaderToolbar> {}
         <View> { width: Fill }
+        toolbar_center = <HeaderToolbar> {}
+        <View> { width: Fill }
+        toolbar_right = <HeaderToolbar> {}
+
+        // Theme picker (hidden until themes are listed)
+        theme_picker = <DropDown> {
+            width: Fit
+            height: 24
+            visible: false
+            labels: ["Default"]
+            popup_menu_position: BelowInput
+        }
+
+        // Layout lock toggle (padlock, shackle open while unlocked)
+        lock_btn = <Button> {
+            width: 28
+            height: 28
+            margin: { left: 8 }
+            text: ""
+
+            draw_bg: {
+                uniform text_primary: (PALETTE_TEXT_PRIMARY)
+                uniform accent: (PALETTE_ACCENT)
+                // 1.0 while the layout is locked
+                instance locked: 0.0
+
+                fn pixel(self) -> vec4 {
+                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                    let cx = self.rect_size.x * 0.5;
+                    let cy = self.rect_size.y * 0.5;
+
+                    let hovered = mix(self.text_primary, self.accent, self.hover);
+                    let stroke = mix(hovered, self.accent, self.locked);
+                    let line_width = 1.5;
+
+                    // Shackle, lifted out of the body on the left while unlocked
+                    let lift = 3.0 * (1.0 - self.locked);
+                    let top = cy - 7.0 - lift;
+                    sdf.move_to(cx + 4.0, cy - 1.0);
+                    sdf.line_to(cx + 4.0, top + 2.0);
+                    sdf.line_to(cx + 2.0, top);
+                    sdf.line_to(cx - 2.0, top);
+                    sdf.line_to(cx - 4.0, top + 2.0);
+                    sdf.line_to(cx - 4.0, cy - 1.0 - lift);
+                    sdf.stroke(stroke, line_width);
+
+                    // Body
+                    sdf.box(cx - 6.0, cy - 1.0, 12.0, 8.0, 1.5);
+                    sdf.fill(stroke);
+
+                    return sdf.result;
+                }
+            }
+        }
 
         // Reset layout button (undo arrow with window)
         reset_btn = <Button> {
             width: 28
             height: 28
-            margin: { left: 8 }
+            margin: { left: 4 }
             text: ""
 
             draw_bg: {
-                instance dark_mode: 0.0
+                uniform text_primary: (PALETTE_TEXT_PRIMARY)
+                uniform accent: (PALETTE_ACCENT)
+                uniform success: (PALETTE_SUCCESS)
                 instance anim_progress: 0.0
 
                 fn pixel(self) -> vec4 {
@@ -117,13 +178,8 @@ live_design! {
                     let cy = self.rect_size.y * 0.5;
 
                     // Stroke color with animation flash
-                    let light_stroke = vec4(0.122, 0.161, 0.216, 1.0);
-                    let dark_stroke = vec4(0.945, 0.961, 0.976, 1.0);
-                    let hover_stroke = vec4(0.231, 0.510, 0.965, 1.0);
-                    let success_stroke = vec4(0.22, 0.80, 0.46, 1.0);  // green
-                    let base = mix(light_stroke, dark_stroke, self.dark_mode);
-                    let hovered = mix(base, hover_stroke, self.hover);
-                    let stroke = mix(hovered, success_stroke, self.anim_progress);
+                    let hovered = mix(self.text_primary, self.accent, self.hover);
+                    let stroke = mix(hovered, self.success, self.anim_progress);
                     let line_width = 1.4;
 
                     // Scale to fit 28x28 (original is 32x32)
@@ -172,7 +228,9 @@ live_design! {
             text: ""
 
             draw_bg: {
-                instance dark_mode: 0.0
+                uniform text_primary: (PALETTE_TEXT_PRIMARY)
+                uniform accent: (PALETTE_ACCENT)
+                uniform success: (PALETTE_SUCCESS)
                 instance anim_progress: 0.0
 
                 fn pixel(self) -> vec4 {
@@ -181,13 +239,8 @@ live_design! {
                     let cy = self.rect_size.y * 0.5;
 
                     // Stroke color with animation flash
-                    let light_stroke = vec4(0.122, 0.161, 0.216, 1.0);
-                    let dark_stroke = vec4(0.945, 0.961, 0.976, 1.0);
-                    let hover_stroke = vec4(0.231, 0.510, 0.965, 1.0);
-                    let success_stroke = vec4(0.22, 0.80, 0.46, 1.0);  // green
-                    let base = mix(light_stroke, dark_stroke, self.dark_mode);
-                    let hovered = mix(base, hover_stroke, self.hover);
-                    let stroke = mix(hovered, success_stroke, self.anim_progress);
+                    let hovered = mix(self.text_primary, self.accent, self.hover);
+                    let stroke = mix(hovered, self.success, self.anim_progress);
                     let line_width = 1.5;
 
                     // Draw download/save icon
@@ -225,7 +278,10 @@ live_design! {
             text: ""
 
             draw_bg: {
+                // Sun/moon morph progress (0.0 = sun, 1.0 = moon)
                 instance dark_mode: 0.0
+                uniform text_primary: (PALETTE_TEXT_PRIMARY)
+                uniform bg_app: (PALETTE_BG_APP)
 
                 fn pixel(self) -> vec4 {
                     let sdf = Sdf2d::viewport(self.pos * self.rect_size);
@@ -233,10 +289,7 @@ live_design! {
                     let cy = self.rect_size.y * 0.5;
                     let scale = 0.5;
 
-                    // Stroke color: dark in light mode, light in dark mode
-                    let light_stroke = vec4(0.122, 0.161, 0.216, 1.0);
-                    let dark_stroke = vec4(0.945, 0.961, 0.976, 1.0);
-                    let stroke = mix(light_stroke, dark_stroke, self.dark_mode);
+                    let stroke = self.text_primary;
                     let line_width = 1.5;
 
                     // Sun (shown in light mode)
@@ -298,10 +351,7 @@ live_design! {
                         let cut_offset_y = -3.0 * scale;
                         sdf.circle(cx + cut_offset_x, cy + cut_offset_y, cut_r + line_width);
                         // Use header bg for cutout
-                        let light_bg = vec4(1.0, 1.0, 1.0, 1.0);
-                        let dark_bg = vec4(0.059, 0.090, 0.165, 1.0);
-                        let bg = mix(light_bg, dark_bg, self.dark_mode);
-                        sdf.fill(vec4(bg.xyz, moon_alpha));
+                        sdf.fill(vec4(self.bg_app.xyz, moon_alpha));
                     }
 
                     return sdf.result;
@@ -311,6 +361,21 @@ live_design! {
     }
 }
 
+/// Theme picker label of the default theme
+const DEFAULT_THEME_LABEL: &str = "Default";
+
+/// Toolbar alignment groups in header order
+const TOOLBAR_GROUPS: [ToolbarGroup; 3] = [ToolbarGroup::Left, ToolbarGroup::Center, ToolbarGroup::Right];
+
+/// ID of the toolbar hosting an alignment group
+fn toolbar_id(group: ToolbarGroup) -> LiveId {
+    match group {
+        ToolbarGroup::Left => live_id!(toolbar_left),
+        ToolbarGroup::Center => live_id!(toolbar_center),
+        ToolbarGroup::Right => live_id!(toolbar_right),
+    }
+}
+
 /// Actions emitted by the shell header
 #[derive(Clone, Debug, DefaultNone)]
 pub enum ShellHeaderAction {
@@ -320,10 +385,26 @@ pub enum ShellHeaderAction {
     HamburgerClicked,
     ResetLayout,
     SaveLayout,
+    /// The layout lock button was clicked
+    ToggleLayoutLock,
+    /// Theme picker entry selected (0 = default theme, then theme files in order)
+    ThemeSelected(usize),
+    /// A toolbar item without a menu was clicked (`checked` = new state of a toggle item)
+    ToolbarItemActivated { id: String, checked: Option<bool> },
+    /// An entry of a toolbar item's dropdown menu was chosen
+    ToolbarMenuSelected { item_id: String, entry_id: String },
+    /// A menu bar item was chosen (`checked` = new state of a checkable item)
+    MenuSelected { id: String, checked: Option<bool> },
     None,
 }
 
+/// Duration of the click flash animation in seconds
+pub const FLASH_DURATION: f64 = 1.2;
+
 /// Animation state for button feedback
+///
+/// Drives the `anim_progress` instance of a button's `draw_bg`/`draw_icon`
+/// from 1.0 (flash color) back to 0.0.
 #[derive(Clone, Debug, Default)]
 pub struct ButtonAnimState {
     pub animating: bool,
@@ -331,6 +412,36 @@ pub struct ButtonAnimState {
     pub progress: f64,
 }
 
+impl ButtonAnimState {
+    /// Start the flash
+    pub fn start(&mut self, cx: &mut Cx) {
+        self.animating = true;
+        self.start_time = Cx::time_now();
+        self.progress = 1.0;
+        cx.new_next_frame();
+    }
+
+    /// Advance the flash on `NextFrame`
+    ///
+    /// Returns the progress to apply, or `None` when not animating.
+    pub fn update(&mut self, cx: &mut Cx) -> Option<f64> {
+        if !self.animating {
+            return None;
+        }
+        let elapsed = Cx::time_now() - self.start_time;
+        if elapsed < FLASH_DURATION {
+            // Ease out cubic
+            let t = elapsed / FLASH_DURATION;
+            self.progress = 1.0 - (t * t * t);
+            cx.new_next_frame();
+        } else {
+            self.animating = false;
+            self.progress = 0.0;
+        }
+        Some(self.progress)
+    }
+}
+
 /// Shell header widget
 #[derive(Live, LiveHook, Widget)]
 pub struct ShellHeader {
@@ -348,6 +459,13 @@ pub struct ShellHeader {
 
     #[rust]
     hamburger_hovering: bool,
+
+    /// Skip the save/reset click animations
+    #[rust]
+    reduce_motion: bool,
+
+    #[rust]
+    theme_tracker: ThemeTracker,
 }
 
 impl Widget for ShellHeader {
@@ -398,12 +516,50 @@ impl Widget for ShellHeader {
             );
         }
 
+        if let Some(index) = self.view.drop_down(id!(theme_picker)).selected(&actions) {
+            cx.widget_action(
+                self.widget_uid(),
+                &scope.path,
+                ShellHeaderAction::ThemeSelected(index),
+            );
+        }
+
+        if let Some((id, checked)) = self.view.menu_bar(id!(menu_bar)).selected(&actions) {
+            cx.widget_action(
+                self.widget_uid(),
+                &scope.path,
+                ShellHeaderAction::MenuSelected { id, checked },
+            );
+        }
+
+        for group in TOOLBAR_GROUPS {
+            for action in self.view.header_toolbar(&[toolbar_id(group)]).toolbar_actions(&actions) {
+                let action = match action {
+                    HeaderToolbarAction::Activated { id, checked } => {
+                        ShellHeaderAction::ToolbarItemActivated { id, checked }
+                    }
+                    HeaderToolbarAction::MenuSelected { item_id, entry_id } => {
+                        ShellHeaderAction::ToolbarMenuSelected { item_id, entry_id }
+                    }
+                    HeaderToolbarAction::None => continue,
+                };
+                cx.widget_action(self.widget_uid(), &scope.path, action);
+            }
+        }
+
+        if self.view.button(id!(lock_btn)).clicked(&actions) {
+            cx.widget_action(
+                self.widget_uid(),
+                &scope.path,
+                ShellHeaderAction::ToggleLayoutLock,
+            );
+        }
+
         if self.view.button(id!(reset_btn)).clicked(&actions) {
             // Start reset animation
-            self.reset_anim.animating = true;
-            self.reset_anim.start_time = Cx::time_now();
-            self.reset_anim.progress = 1.0;
-            cx.new_next_frame();
+            if !self.reduce_motion {
+                self.reset_anim.start(cx);
+            }
 
             cx.widget_action(
                 self.widget_uid(),
@@ -414,10 +570,9 @@ impl Widget for ShellHeader {
 
         if self.view.button(id!(save_btn)).clicked(&actions) {
             // Start save animation
-            self.save_anim.animating = true;
-            self.save_anim.start_time = Cx::time_now();
-            self.save_anim.progress = 1.0;
-            cx.new_next_frame();
+            if !self.reduce_motion {
+                self.save_anim.start(cx);
+            }
 
             cx.widget_action(
                 self.widget_uid(),
@@ -429,48 +584,19 @@ impl Widget for ShellHeader {
         // Update animations
         if let Event::NextFrame(_) = event {
             let mut needs_redraw = false;
-            let duration = 1.2;  // Animation duration in seconds
-
-            if self.save_anim.animating {
-                let elapsed = Cx::time_now() - self.save_anim.start_time;
-                if elapsed < duration {
-                    // Ease out cubic
-                    let t = elapsed / duration;
-                    self.save_anim.progress = 1.0 - (t * t * t);
-                    self.view.button(id!(save_btn)).apply_over(cx, live! {
-                        draw_bg: { anim_progress: (self.save_anim.progress) }
-                    });
-                    cx.new_next_frame();
-                    needs_redraw = true;
-                } else {
-                    self.save_anim.animating = false;
-                    self.save_anim.progress = 0.0;
-                    self.view.button(id!(save_btn)).apply_over(cx, live! {
-                        draw_bg: { anim_progress: 0.0 }
-                    });
-                    needs_redraw = true;
-                }
+
+            if let Some(progress) = self.save_anim.update(cx) {
+                self.view.button(id!(save_btn)).apply_over(cx, live! {
+                    draw_bg: { anim_progress: (progress) }
+                });
+                needs_redraw = true;
             }
 
-            if self.reset_anim.animating {
-                let elapsed = Cx::time_now() - self.reset_anim.start_time;
-                if elapsed < duration {
-                    // Ease out cubic
-                    let t = elapsed / duration;
-                    self.reset_anim.progress = 1.0 - (t * t * t);
-                    self.view.button(id!(reset_btn)).apply_over(cx, live! {
-                        draw_bg: { anim_progress: (self.reset_anim.progress) }
-                    });
-                    cx.new_next_frame();
-                    needs_redraw = true;
-                } else {
-                    self.reset_anim.animating = false;
-                    self.reset_anim.progress = 0.0;
-                    self.view.button(id!(reset_btn)).apply_over(cx, live! {
-                        draw_bg: { anim_progress: 0.0 }
-                    });
-                    needs_redraw = true;
-                }
+            if let Some(progress) = self.reset_anim.update(cx) {
+                self.view.button(id!(reset_btn)).apply_over(cx, live! {
+                    draw_bg: { anim_progress: (progress) }
+                });
+                needs_redraw = true;
             }
 
             if needs_redraw {
@@ -480,6 +606,15 @@ impl Widget for ShellHeader {
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        // Apply the shell theme when it changed
+        if let Some(theme) = self.theme_tracker.changed() {
+            self.apply_palette_internal(cx, &theme.palette, theme.dark_mode);
+            let title_size = theme.metrics.font_sizes.md;
+            self.view.label(id!(title_label)).apply_over(cx, live! {
+                draw_text: { text_style: { font_size: (title_size) } }
+            });
+        }
+
         if !self.title.is_empty() {
             self.view.label(id!(title_label)).set_text(cx, &self.title);
         }
@@ -488,6 +623,20 @@ impl Widget for ShellHeader {
     }
 }
 
+impl ShellHeader {
+    fn apply_palette_internal(&mut self, cx: &mut Cx, palette: &ShellPalette, dark_mode: f64) {
+        let bg = palette.live_nodes(&[live_id!(draw_bg)]);
+        self.view.apply_over(cx, &bg);
+        self.view.label(id!(title_label)).apply_over(cx, &palette.live_nodes(&[live_id!(draw_text)]));
+        for button in [id!(hamburger_btn), id!(theme_toggle), id!(lock_btn), id!(reset_btn), id!(save_btn)] {
+            self.view.button(button).apply_over(cx, &bg);
+        }
+        self.view.button(id!(theme_toggle)).apply_over(cx, live! {
+            draw_bg: { dark_mode: (dark_mode) }
+        });
+    }
+}
+
 impl ShellHeaderRef {
     pub fn set_title(&self, cx: &mut Cx, title: &str) {
         if let Some(mut inner) = self.borrow_mut() {
@@ -496,26 +645,109 @@ impl ShellHeaderRef {
         }
     }
 
-    pub fn apply_dark_mode(&self, cx: &mut Cx, dark_mode: f64) {
+    /// Show the layout lock button as locked (true) or unlocked
+    pub fn set_layout_locked(&self, cx: &mut Cx, locked: bool) {
         if let Some(mut inner) = self.borrow_mut() {
-            inner.view.apply_over(cx, live! {
-                draw_bg: { dark_mode: (dark_mode) }
-            });
-            inner.view.label(id!(title_label)).apply_over(cx, live! {
-                draw_text: { dark_mode: (dark_mode) }
-            });
-            inner.view.button(id!(hamburger_btn)).apply_over(cx, live! {
-                draw_bg: { dark_mode: (dark_mode) }
-            });
-            inner.view.button(id!(theme_toggle)).apply_over(cx, live! {
-                draw_bg: { dark_mode: (dark_mode) }
-            });
-            inner.view.button(id!(reset_btn)).apply_over(cx, live! {
-                draw_bg: { dark_mode: (dark_mode) }
-            });
-            inner.view.button(id!(save_btn)).apply_over(cx, live! {
-                draw_bg: { dark_mode: (dark_mode) }
+            let locked = if locked { 1.0 } else { 0.0 };
+            inner.view.button(id!(lock_btn)).apply_over(cx, live! {
+                draw_bg: { locked: (locked) }
             });
+            inner.view.redraw(cx);
+        }
+    }
+
+    /// Turn the save/reset click animations off (true) or on
+    pub fn set_reduce_motion(&self, cx: &mut Cx, reduce_motion: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.reduce_motion = reduce_motion;
+            if reduce_motion {
+                // Settle any running animation
+                inner.save_anim = ButtonAnimState::default();
+                inner.reset_anim = ButtonAnimState::default();
+                for button in [id!(save_btn), id!(reset_btn)] {
+                    inner.view.button(button).apply_over(cx, live! {
+                        draw_bg: { anim_progress: 0.0 }
+                    });
+                }
+                inner.view.redraw(cx);
+            }
+            for group in TOOLBAR_GROUPS {
+                inner.view.header_toolbar(&[toolbar_id(group)]).set_reduce_motion(cx, reduce_motion);
+            }
+        }
+    }
+
+    /// Replace the app toolbar items, placing each in its alignment group
+    pub fn set_toolbar_items(&self, cx: &mut Cx, items: Vec<ToolbarItem>) {
+        if let Some(inner) = self.borrow() {
+            for group in TOOLBAR_GROUPS {
+                let group_items = items.iter().filter(|item| item.group == group).cloned().collect();
+                inner.view.header_toolbar(&[toolbar_id(group)]).set_items(cx, group_items);
+            }
+        }
+    }
+
+    /// Set the checked state of a toggle toolbar item
+    pub fn set_toolbar_item_checked(&self, cx: &mut Cx, id: &str, checked: bool) -> bool {
+        self.borrow().map_or(false, |inner| {
+            TOOLBAR_GROUPS.iter().any(|&group| {
+                inner.view.header_toolbar(&[toolbar_id(group)]).set_checked(cx, id, checked)
+            })
+        })
+    }
+
+    /// Play the click flash on a toolbar item
+    pub fn flash_toolbar_item(&self, cx: &mut Cx, id: &str) -> bool {
+        self.borrow().map_or(false, |inner| {
+            TOOLBAR_GROUPS.iter().any(|&group| inner.view.header_toolbar(&[toolbar_id(group)]).flash(cx, id))
+        })
+    }
+
+    /// Replace the menu bar contents (an empty model hides the menu bar)
+    pub fn set_menu_model(&self, cx: &mut Cx, model: MenuModel) {
+        if let Some(inner) = self.borrow() {
+            inner.view.menu_bar(id!(menu_bar)).set_model(cx, model);
+        }
+    }
+
+    /// Current menu bar contents, including checked states changed by the user
+    pub fn menu_model(&self) -> MenuModel {
+        self.borrow().map(|inner| inner.view.menu_bar(id!(menu_bar)).model()).unwrap_or_default()
+    }
+
+    /// Set the checked state of a checkable menu item
+    pub fn set_menu_item_checked(&self, id: &str, checked: bool) -> bool {
+        self.borrow().map_or(false, |inner| inner.view.menu_bar(id!(menu_bar)).set_checked(id, checked))
+    }
+
+    /// Enable or disable a menu item
+    pub fn set_menu_item_enabled(&self, cx: &mut Cx, id: &str, enabled: bool) -> bool {
+        self.borrow().map_or(false, |inner| inner.view.menu_bar(id!(menu_bar)).set_enabled(cx, id, enabled))
+    }
+
+    /// Fill the theme picker with theme names and select one (`None` = default theme)
+    ///
+    /// The picker stays hidden while `names` is empty.
+    pub fn set_theme_names(&self, cx: &mut Cx, names: &[String], selected: Option<usize>) {
+        if let Some(inner) = self.borrow() {
+            let picker = inner.view.drop_down(id!(theme_picker));
+            let mut labels = vec![DEFAULT_THEME_LABEL.to_string()];
+            labels.extend(names.iter().cloned());
+            picker.set_labels(cx, labels);
+            picker.set_selected_item(cx, selected.map_or(0, |index| index + 1));
+            picker.set_visible(cx, !names.is_empty());
+        }
+    }
+
+    /// Apply the built-in palettes blended by a dark mode value
+    pub fn apply_dark_mode(&self, cx: &mut Cx, dark_mode: f64) {
+        self.apply_palette(cx, &ShellPalette::for_dark_mode(dark_mode), dark_mode);
+    }
+
+    /// Apply a palette; `dark_mode` drives the sun/moon toggle icon
+    pub fn apply_palette(&self, cx: &mut Cx, palette: &ShellPalette, dark_mode: f64) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.apply_palette_internal(cx, palette, dark_mode);
         }
     }
 }
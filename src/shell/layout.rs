@@ -1,15 +1,29 @@
 //! Shell layout widget - main container for the app shell
 
+use std::rc::Rc;
 use makepad_widgets::*;
-use crate::theme::{ShellTheme, THEME_TRANSITION_DURATION, set_global_dark_mode};
+use crate::theme::{
+    AppearanceSource, AppearanceWatcher, ShellPalette, ShellTheme, ThemeContext, ThemeDefinition,
+    ThemeLibrary, ThemeMode, THEME_TRANSITION_DURATION, check_contrast, ease_out_cubic,
+};
 use crate::shell::config::ShellConfig;
-use crate::shell::header::ShellHeaderAction;
-use crate::shell::sidebar::ShellSidebarWidgetExt;
+use crate::shell::header::{ShellHeaderAction, ShellHeaderWidgetExt};
+use crate::shell::footer::{ShellFooterAction, ShellFooterWidgetExt};
+use crate::shell::status_bar::{StatusItem, StatusProgress};
+use crate::shell::dialog::{is_input_event, Dialog, DialogResult, ShellDialogAction, ShellDialogWidgetExt};
+use crate::shell::notifications::{Notification, NotificationRecord, ShellNotificationsAction, ShellNotificationsWidgetExt};
+use crate::shell::menu::{MenuEntry, MenuModel, ShellMenuWidgetExt};
+use crate::shell::toolbar::ToolbarItem;
+use crate::shell::sidebar::{ShellSidebarWidgetExt, SidebarAction, SidebarSelection};
+use crate::shell::activity_bar::{ActivityBarWidgetExt, SidebarSide, SidebarView, ACTIVITY_BAR_WIDTH};
+use crate::shell::events::{ShellEvent, diff_layout, diff_footer_layout};
 use crate::grid::panel_grid::PanelGridWidgetExt;
 use crate::grid::footer_grid::FooterGridWidgetExt;
-use crate::grid::{LayoutState, FooterLayoutState};
-use crate::panel::PanelAction;
+use crate::grid::{DropValidator, LayoutState, FooterLayoutState, SplitterPositions};
+use crate::panel::{ClosedPanel, PanelAction, PanelId, PanelMenuCommand, PanelMenuContext, panel_menu_entries, reopen_menu_entries, reopen_menu_item};
+use crate::registry::{PanelDefinition, PanelRegistry};
 use crate::persistence::ShellPreferences;
+use crate::callbacks::SplitterId;
 
 live_design! {
     use link::theme::*;
@@ -20,7 +34,12 @@ live_design! {
 
     // Import shell components - must use crate path for cross-module visibility
     use crate::shell::header::ShellHeader;
+    use crate::shell::footer::ShellFooter;
     use crate::shell::sidebar::ShellSidebar;
+    use crate::shell::activity_bar::*;
+    use crate::shell::notifications::ShellNotifications;
+    use crate::shell::dialog::ShellDialog;
+    use crate::shell::menu::ShellMenu;
     use crate::grid::panel_grid::PanelGrid;
     use crate::grid::footer_grid::FooterGrid;
 
@@ -88,24 +107,14 @@ live_design! {
         draw_bg: {
             instance hover: 0.0
             instance pressed: 0.0
-            instance dark_mode: 0.0
+            uniform bg_overlay: (PALETTE_BG_OVERLAY)
+            uniform bg_overlay_hover: (PALETTE_BG_OVERLAY_HOVER)
+            uniform bg_overlay_pressed: (PALETTE_BG_OVERLAY_PRESSED)
 
             fn pixel(self) -> vec4 {
                 let sdf = Sdf2d::viewport(self.pos * self.rect_size);
-                // Light mode: white -> purple tint on hover -> darker on pressed
-                // Dark mode: dark purple -> lighter purple on hover -> even lighter on pressed
-                let light_normal = vec4(1.0, 1.0, 1.0, 1.0);
-                let light_hover = vec4(0.933, 0.918, 0.980, 1.0);  // purple-100
-                let light_pressed = vec4(0.882, 0.859, 0.957, 1.0);  // purple-200
-                let dark_normal = vec4(0.090, 0.075, 0.145, 1.0);  // dark purple
-                let dark_hover = vec4(0.150, 0.130, 0.210, 1.0);   // lighter dark purple
-                let dark_pressed = vec4(0.180, 0.160, 0.250, 1.0); // even lighter
-
-                let normal = mix(light_normal, dark_normal, self.dark_mode);
-                let hover_color = mix(light_hover, dark_hover, self.dark_mode);
-                let pressed_color = mix(light_pressed, dark_pressed, self.dark_mode);
-                let color = mix(normal, hover_color, self.hover);
-                let color = mix(color, pressed_color, self.pressed);
+                let color = mix(self.bg_overlay, self.bg_overlay_hover, self.hover);
+                let color = mix(color, self.bg_overlay_pressed, self.pressed);
 
                 sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 4.0);
                 sdf.fill(color);
@@ -114,23 +123,17 @@ live_design! {
         }
 
         draw_text: {
-            instance dark_mode: 0.0
+            uniform overlay_text: (PALETTE_OVERLAY_TEXT)
             text_style: <FONT_REGULAR>{ font_size: 12.0 }
             fn get_color(self) -> vec4 {
-                // Purple-tinted text
-                let light = vec4(0.345, 0.290, 0.502, 1.0);  // purple-700
-                let dark = vec4(0.847, 0.824, 0.941, 1.0);   // purple-200
-                return mix(light, dark, self.dark_mode);
+                return self.overlay_text;
             }
         }
 
         draw_icon: {
-            instance dark_mode: 0.0
+            uniform overlay_icon: (PALETTE_OVERLAY_ICON)
             fn get_color(self) -> vec4 {
-                // Purple icon color
-                let light = vec4(0.545, 0.467, 0.757, 1.0);  // purple-500
-                let dark = vec4(0.694, 0.631, 0.871, 1.0);   // purple-400
-                return mix(light, dark, self.dark_mode);
+                return self.overlay_icon;
             }
         }
 
@@ -145,12 +148,9 @@ live_design! {
 
         show_bg: true
         draw_bg: {
-            instance dark_mode: 0.0
+            uniform bg_overlay: (PALETTE_BG_OVERLAY)
             fn pixel(self) -> vec4 {
-                // Light: white, Dark: very dark purple
-                let light = vec4(1.0, 1.0, 1.0, 1.0);
-                let dark = vec4(0.067, 0.055, 0.110, 1.0);
-                return mix(light, dark, self.dark_mode);
+                return self.bg_overlay;
             }
         }
 
@@ -163,23 +163,18 @@ live_design! {
 
             show_bg: true
             draw_bg: {
-                instance dark_mode: 0.0
+                uniform bg_overlay_header: (PALETTE_BG_OVERLAY_HEADER)
                 fn pixel(self) -> vec4 {
-                    // Purple header
-                    let light = vec4(0.945, 0.929, 0.988, 1.0);  // purple-50
-                    let dark = vec4(0.110, 0.090, 0.180, 1.0);   // dark purple
-                    return mix(light, dark, self.dark_mode);
+                    return self.bg_overlay_header;
                 }
             }
 
             overlay_title = <Label> {
                 draw_text: {
-                    instance dark_mode: 0.0
+                    uniform overlay_text: (PALETTE_OVERLAY_TEXT)
                     text_style: <FONT_SEMIBOLD> { font_size: 13.0 }
                     fn get_color(self) -> vec4 {
-                        let light = vec4(0.345, 0.290, 0.502, 1.0);  // purple-700
-                        let dark = vec4(0.847, 0.824, 0.941, 1.0);   // purple-200
-                        return mix(light, dark, self.dark_mode);
+                        return self.overlay_text;
                     }
                 }
                 text: "Quick Actions"
@@ -217,11 +212,9 @@ live_design! {
                 margin: { top: 8, bottom: 8, left: 16, right: 16 }
                 show_bg: true
                 draw_bg: {
-                    instance dark_mode: 0.0
+                    uniform overlay_border: (PALETTE_OVERLAY_BORDER)
                     fn pixel(self) -> vec4 {
-                        let light = vec4(0.847, 0.824, 0.941, 1.0);  // purple-200
-                        let dark = vec4(0.200, 0.180, 0.280, 1.0);   // dark purple border
-                        return mix(light, dark, self.dark_mode);
+                        return self.overlay_border;
                     }
                 }
             }
@@ -244,12 +237,9 @@ live_design! {
 
         show_bg: true
         draw_bg: {
-            instance dark_mode: 0.0
+            uniform bg_app: (PALETTE_BG_APP)
             fn pixel(self) -> vec4 {
-                // Light: white, Dark: slate-900
-                let light = vec4(1.0, 1.0, 1.0, 1.0);
-                let dark = vec4(0.059, 0.090, 0.165, 1.0);
-                return mix(light, dark, self.dark_mode);
+                return self.bg_app;
             }
         }
 
@@ -352,11 +342,28 @@ live_design! {
                         title: "Properties"
                     }
 
+                    // Sidebar hosts: activity bar plus the views it switches between.
+                    // A sidebar tab shows its host instead of its content once views are registered.
+                    left_sidebar = <SidebarHost> {}
+                    right_sidebar = <RightSidebarHost> {}
+
                     footer_content = <FooterGrid> {
                         initial_panels: 7
                     }
                 }
             } // End dock_wrapper
+
+            // Status bar (hidden until the app adds status items)
+            status_bar = <ShellFooter> {
+                height: 24
+                padding: { left: 4, right: 4 }
+                spacing: 0
+                visible: false
+                hide_when_empty: true
+
+                status_label = { visible: false }
+                hint_label = { visible: false }
+            }
         } // End main_container
 
         // Pinned sidebar - animates width and pushes content (click behavior)
@@ -370,40 +377,47 @@ live_design! {
 
             show_bg: true
             draw_bg: {
-                instance dark_mode: 0.0
+                uniform bg_overlay: (PALETTE_BG_OVERLAY)
 
                 fn pixel(self) -> vec4 {
                     // Main background - purple tinted (same as overlay)
-                    let light_bg = vec4(0.992, 0.988, 1.0, 1.0);  // very light purple
-                    let dark_bg = vec4(0.067, 0.055, 0.110, 1.0); // very dark purple
-                    return mix(light_bg, dark_bg, self.dark_mode);
+                    return self.bg_overlay;
                 }
             }
 
             pinned_sidebar_content = <OverlaySidebarContent> {}
         }
 
-        // Overlay sidebar - appears/disappears instantly on hover (not used currently)
+        // Overlay sidebar - slides in on hover, keyboard shortcut or touch
         overlay_sidebar = <View> {
-            width: 270
+            width: 0  // Starts collapsed, animates to the configured width
             height: Fill
             margin: { top: 48.0, left: 0.0 }  // Below header, at left edge
             visible: false
+            clip_x: true
+            clip_y: true
 
             show_bg: true
             draw_bg: {
-                instance dark_mode: 0.0
+                uniform bg_overlay: (PALETTE_BG_OVERLAY)
 
                 fn pixel(self) -> vec4 {
                     // Main background - purple tinted
-                    let light_bg = vec4(0.992, 0.988, 1.0, 1.0);  // very light purple
-                    let dark_bg = vec4(0.067, 0.055, 0.110, 1.0); // very dark purple
-                    return mix(light_bg, dark_bg, self.dark_mode);
+                    return self.bg_overlay;
                 }
             }
 
             overlay_sidebar_content = <OverlaySidebarContent> {}
         }
+
+        // Toasts and the notification center, above everything else
+        notifications = <ShellNotifications> {}
+
+        // Panel title bar context menu
+        panel_menu = <ShellMenu> {}
+
+        // Modal dialogs (drawn in their own overlay above everything)
+        dialog = <ShellDialog> {}
     }
 }
 
@@ -415,10 +429,20 @@ live_design! {
 
 // Layout constants
 const APP_ID: &str = "makepad-flex-layout";
-const SIDEBAR_WIDTH: f64 = 270.0;
-const HEADER_HEIGHT: f64 = 48.0;
-const SIDEBAR_ANIM_DURATION: f64 = 0.25;  // 250ms
+const OVERLAY_BRIDGE_MARGIN: f64 = 12.0;  // Hover slack below the header
 const CLICK_DEBOUNCE_TIME: f64 = 0.3;     // 300ms
+const DEFAULT_WORKSPACE: &str = "default";
+const LEFT_SIDEBAR: &str = "left";
+const RIGHT_SIDEBAR: &str = "right";
+
+/// Status bar item that opens the notification center
+const NOTIFICATIONS_STATUS_ITEM: &str = "shell_notifications";
+
+/// IDs of the shell's own dialogs (those ending in ':' take a suffix)
+const RESET_LAYOUT_DIALOG: &str = "shell.reset_layout";
+const DELETE_WORKSPACE_DIALOG: &str = "shell.delete_workspace:";
+const CLOSE_PANEL_DIALOG: &str = "shell.close_panel:";
+const RENAME_PANEL_DIALOG: &str = "shell.rename_panel:";
 
 #[derive(Live, LiveHook, Widget)]
 pub struct ShellLayout {
@@ -431,6 +455,10 @@ pub struct ShellLayout {
     #[rust]
     theme: ShellTheme,
 
+    /// Theme provided to descendants while the shell handles events and draws
+    #[rust]
+    theme_context: Rc<ThemeContext>,
+
     #[rust]
     dark_mode_animating: bool,
 
@@ -472,57 +500,171 @@ pub struct ShellLayout {
     /// Whether overlay sidebar is showing (hover state - doesn't push content)
     #[rust]
     overlay_showing: bool,
+
+    /// Overlay opened by keyboard or touch stays open until dismissed
+    #[rust]
+    overlay_sticky: bool,
+
+    /// Delays opening the overlay while hovering the hamburger
+    #[rust]
+    overlay_hover_timer: Timer,
+
+    /// A text input (in the shell or in panel content) has key focus and owns the shortcuts
+    #[rust]
+    text_input_focused: bool,
+
+    #[rust]
+    overlay_animating: bool,
+
+    #[rust]
+    overlay_anim_start: f64,
+
+    /// The last hamburger press came from a touch device
+    #[rust]
+    hamburger_touched: bool,
+
+    /// Semantic ID of the most recently focused panel
+    #[rust]
+    focused_panel: Option<PanelId>,
+
+    /// Name of the active workspace (empty until preferences are loaded)
+    #[rust]
+    active_workspace: String,
+
+    /// Scope path of the last `handle_event`, for events raised from Ref API calls
+    #[rust]
+    scope_path: HeapLiveIdPath,
+
+    /// Saved sidebar state is restored once the dock has created the sidebars
+    #[rust]
+    restore_sidebar_state: bool,
+
+    /// Sidebar hosts are set up once the dock has created them
+    #[rust]
+    apply_sidebar_hosts: bool,
+
+    /// Activity bar views of the left sidebar host
+    #[rust]
+    left_sidebar_views: SidebarViews,
+
+    /// Activity bar views of the right sidebar host
+    #[rust]
+    right_sidebar_views: SidebarViews,
+
+    /// Theme files from the preferences directory
+    #[rust]
+    theme_library: ThemeLibrary,
+
+    /// Name of the active built-in or file-based theme (`None` = default theme)
+    #[rust]
+    active_theme: Option<String>,
+
+    /// Default theme palettes set through `set_palettes` (`None` = built-in)
+    #[rust]
+    default_theme: Option<ThemeDefinition>,
+
+    /// Polls the themes directory for hot reload
+    #[rust]
+    theme_poll_timer: Timer,
+
+    /// Light/dark/system theme mode and the platform appearance it follows
+    #[rust]
+    appearance: AppearanceWatcher,
+
+    /// Polls the platform appearance in `ThemeMode::System`
+    #[rust]
+    appearance_timer: Timer,
+
+    /// Panel definitions (titles, capabilities and context menu entries)
+    #[rust]
+    panel_registry: PanelRegistry,
+
+    /// Panel whose context menu is open
+    #[rust]
+    context_panel: Option<PanelId>,
+
+    /// Panel actions raised while translating child actions, emitted by `handle_event`
+    #[rust]
+    pending_panel_actions: Vec<PanelAction>,
+
+    /// No panel drag, close or dock resize (the header's lock toggle)
+    #[rust]
+    layout_locked: bool,
+}
+
+/// Hover zones of the overlay sidebar in absolute coordinates
+struct OverlayZones {
+    hamburger: Rect,
+    bridge: Rect,
+    overlay: Rect,
+}
+
+/// Registered views and current state of one sidebar host
+#[derive(Clone, Debug, Default)]
+struct SidebarViews {
+    views: Vec<SidebarView>,
+    active: Option<String>,
+    collapsed: bool,
 }
 
 impl Widget for ShellLayout {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
-        // Let events flow to children
-        self.view.handle_event(cx, event, scope);
-
-        // Hover logic: show overlay sidebar when hovering hamburger or overlay itself
-        // Only show overlay if sidebar is not pinned (pinned takes precedence)
-        if let Event::MouseMove(e) = event {
-            if !self.sidebar_pinned {
-                // Get hamburger button area
-                let hamburger = self.view.button(id!(main_container.header.hamburger_btn));
-                let hamburger_rect = hamburger.area().rect(cx);
-
-                // Get overlay sidebar area (fixed size even when not visible)
-                let overlay = self.view.view(id!(overlay_sidebar));
-
-                // Create a combined hover zone:
-                // - The hamburger button
-                // - A bridge zone from hamburger down to overlay
-                // - The overlay sidebar area (270x full height, starting at y=48)
-                let over_hamburger = hamburger_rect.contains(e.abs);
-
-                // Bridge zone: area below hamburger connecting to overlay
-                // Extends from hamburger's left edge to overlay width, from hamburger bottom to overlay top + some buffer
-                let bridge_zone = Rect {
-                    pos: dvec2(0.0, hamburger_rect.pos.y),
-                    size: dvec2(SIDEBAR_WIDTH, HEADER_HEIGHT + 12.0),  // Cover header area + a bit below
-                };
-                let over_bridge = self.overlay_showing && bridge_zone.contains(e.abs);
+        self.scope_path = scope.path.clone();
+
+        // Let events flow to children, then translate their actions into ShellEvents.
+        // An open dialog is modal: it gets all pointer and keyboard input.
+        let modal = self.view.shell_dialog(id!(dialog)).is_open() && is_input_event(event);
+        // Checked before the children run: an open title editor keeps its keys (Escape
+        // closes it and nothing else)
+        let keys_taken = matches!(event, Event::KeyDown(_))
+            && self.view.panel_grid(id!(center_content)).is_editing_title();
+        let theme = &self.theme_context;
+        let view = &mut self.view;
+        let actions = cx.capture_actions(|cx| {
+            ThemeContext::provide(theme, || {
+                if modal {
+                    view.shell_dialog(id!(dialog)).handle_event(cx, event, scope);
+                } else {
+                    view.handle_event(cx, event, scope);
+                }
+            });
+        });
+        let shell_events = self.handle_child_actions(cx, &actions);
+        cx.extend_actions(actions);
+        for shell_event in shell_events {
+            cx.widget_action(self.widget_uid(), &scope.path, shell_event);
+        }
+        for panel_action in std::mem::take(&mut self.pending_panel_actions) {
+            cx.widget_action(self.widget_uid(), &scope.path, panel_action);
+        }
 
-                // Overlay zone: the actual sidebar area (y starts at header height, extends down)
-                let overlay_zone = Rect {
-                    pos: dvec2(0.0, HEADER_HEIGHT),
-                    size: dvec2(SIDEBAR_WIDTH, 600.0),  // Fixed height for hover detection
-                };
-                let over_overlay = self.overlay_showing && overlay_zone.contains(e.abs);
-
-                if over_hamburger || over_bridge || over_overlay {
-                    if !self.overlay_showing {
-                        self.overlay_showing = true;
-                        overlay.set_visible(cx, true);
-                        self.apply_overlay_theme(cx);
-                        self.view.redraw(cx);
-                    }
-                } else if self.overlay_showing {
-                    self.overlay_showing = false;
-                    overlay.set_visible(cx, false);
-                    self.view.redraw(cx);
+        if !modal && !keys_taken {
+            self.handle_overlay_input(cx, event);
+        }
+        if self.overlay_hover_timer.is_event(event).is_some() {
+            self.overlay_hover_timer = Timer::empty();
+            self.set_overlay_visible(cx, true, false);
+        }
+        if self.theme_poll_timer.is_event(event).is_some() {
+            for shell_event in self.reload_themes(cx) {
+                cx.widget_action(self.widget_uid(), &scope.path, shell_event);
+            }
+        }
+        // Detection may answer later from a background thread (Event::Signal)
+        let refresh = self.appearance_timer.is_event(event).is_some() || matches!(event, Event::AppGotFocus);
+        if refresh {
+            self.appearance.refresh();
+        }
+        if refresh || matches!(event, Event::Signal) {
+            let first_answer = !self.appearance.is_known();
+            if let Some(dark) = self.appearance.poll() {
+                if first_answer {
+                    // Don't animate away from the light fallback at startup
+                    self.snap_dark_mode(cx, dark);
+                } else {
+                    self.transition_dark_mode(cx, dark);
                 }
+                cx.widget_action(self.widget_uid(), &scope.path, ShellEvent::ThemeChanged { dark_mode: dark });
             }
         }
 
@@ -534,6 +676,9 @@ impl Widget for ShellLayout {
             if self.sidebar_pin_animating {
                 self.update_sidebar_animation(cx);
             }
+            if self.overlay_animating {
+                self.update_overlay_animation(cx);
+            }
         }
     }
 
@@ -545,321 +690,1443 @@ impl Widget for ShellLayout {
             self.apply_theme(cx);
         }
 
-        self.view.draw_walk(cx, scope, walk)
+        // Descendants read the theme while the shell draws; the app's Scope is passed through
+        let theme = &self.theme_context;
+        let view = &mut self.view;
+        let step = ThemeContext::provide(theme, || view.draw_walk(cx, scope, walk));
+
+        if self.restore_sidebar_state {
+            self.restore_sidebar_state = false;
+            self.apply_sidebar_state(cx);
+        }
+        if self.apply_sidebar_hosts {
+            self.apply_sidebar_hosts = false;
+            for side in [SidebarSide::Left, SidebarSide::Right] {
+                self.apply_sidebar_host(cx, side);
+            }
+        }
+
+        step
     }
 }
 
 impl ShellLayout {
-    /// Toggle dark mode with animation
-    pub fn toggle_dark_mode(&mut self, cx: &mut Cx) {
-        self.theme.dark_mode = !self.theme.dark_mode;
-        self.dark_mode_animating = true;
-        self.dark_mode_anim_start = Cx::time_now();
-        cx.new_next_frame();
-        self.view.redraw(cx);
-    }
-
-    /// Set dark mode state (immediately, no animation)
-    pub fn set_dark_mode(&mut self, cx: &mut Cx, dark: bool) {
-        self.theme.set_dark_mode(dark);
-        self.apply_theme(cx);
-    }
+    /// Translate child widget actions into shell behavior and `ShellEvent`s
+    fn handle_child_actions(&mut self, cx: &mut Cx, actions: &Actions) -> Vec<ShellEvent> {
+        let mut events = Vec::new();
+        let dock_uid = self.view.dock(id!(main_container.dock_wrapper.dock)).widget_uid();
+
+        for action in actions.iter() {
+            match action.as_widget_action().cast::<TextInputAction>() {
+                TextInputAction::KeyFocus => self.text_input_focused = true,
+                TextInputAction::KeyFocusLost => self.text_input_focused = false,
+                _ => {}
+            }
+            match action.as_widget_action().cast::<PanelAction>() {
+                PanelAction::Focus(id) => {
+                    if let Some(panel_id) = self.find_panel(id) {
+                        if self.focused_panel != Some(panel_id) {
+                            self.focused_panel = Some(panel_id);
+                            events.push(ShellEvent::PanelFocused(panel_id.to_string()));
+                        }
+                    }
+                }
+                PanelAction::LayoutChanged(state) => {
+                    let old = self.current_layout.take().unwrap_or_default();
+                    events.extend(diff_layout(&old, &state));
+                    self.current_layout = Some(state);
+                }
+                PanelAction::FooterLayoutChanged(state) => {
+                    let old = self.current_footer_layout.take().unwrap_or_default();
+                    events.extend(diff_footer_layout(&old, &state));
+                    self.current_footer_layout = Some(state);
+                }
+                PanelAction::CloseRequested(panel_id) => self.confirm_close_panel(cx, &panel_id),
+                PanelAction::TitleAction { panel_id, action_id, checked } => {
+                    events.push(ShellEvent::PanelTitleAction { panel_id: panel_id.to_string(), action_id, checked });
+                }
+                PanelAction::ContextMenu(id, abs) => {
+                    if let Some(panel_id) = self.find_panel(id) {
+                        self.open_panel_menu(cx, panel_id, abs);
+                    }
+                }
+                _ => {}
+            }
 
-    /// Check if dark mode is enabled
-    pub fn is_dark_mode(&self) -> bool {
-        self.theme.dark_mode
-    }
+            match action.as_widget_action().cast::<ShellHeaderAction>() {
+                ShellHeaderAction::ToggleDarkMode => {
+                    self.toggle_dark_mode(cx);
+                    events.push(ShellEvent::ThemeChanged { dark_mode: self.theme.dark_mode });
+                }
+                ShellHeaderAction::HamburgerClicked => {
+                    let now = Cx::time_now();
+                    if std::mem::take(&mut self.hamburger_touched) && !self.sidebar_pinned {
+                        // Touch has no hover, so a tap toggles the overlay instead of pinning
+                        let show = !self.overlay_showing;
+                        self.set_overlay_visible(cx, show, true);
+                    } else if now - self.last_click_time > CLICK_DEBOUNCE_TIME {
+                        self.last_click_time = now;
+                        self.toggle_sidebar_expanded(cx);
+                    }
+                }
+                ShellHeaderAction::ResetLayout => {
+                    if self.config.confirm_destructive_actions {
+                        self.open_dialog(cx, Dialog::confirm(
+                            RESET_LAYOUT_DIALOG,
+                            "Reset layout?",
+                            "Panels, splitters and the footer return to their default arrangement.",
+                        ).with_confirm_label("Reset").destructive());
+                    } else {
+                        self.reset_layout(cx);
+                        events.push(ShellEvent::LayoutReset);
+                    }
+                }
+                ShellHeaderAction::SaveLayout => {
+                    self.save_layout(cx);
+                    events.push(ShellEvent::LayoutSaved);
+                }
+                ShellHeaderAction::ToggleLayoutLock => {
+                    let locked = !self.layout_locked;
+                    self.change_layout_lock(cx, locked);
+                    events.push(ShellEvent::LayoutLockChanged(locked));
+                }
+                ShellHeaderAction::ThemeSelected(index) => {
+                    // Index 0 is the default theme
+                    let name = index.checked_sub(1)
+                        .and_then(|index| self.theme_names().into_iter().nth(index));
+                    if name != self.active_theme && self.select_theme(cx, name.as_deref()) {
+                        events.push(ShellEvent::ThemeSelected { name });
+                    }
+                }
+                ShellHeaderAction::ToolbarItemActivated { id, checked } => {
+                    events.push(ShellEvent::ToolbarItemActivated { id, checked });
+                }
+                ShellHeaderAction::ToolbarMenuSelected { item_id, entry_id } => {
+                    events.push(ShellEvent::ToolbarMenuSelected { item_id, entry_id });
+                }
+                ShellHeaderAction::MenuSelected { id, checked } => {
+                    events.push(ShellEvent::MenuSelected { id, checked });
+                }
+                _ => {}
+            }
 
-    /// Update dark mode animation
-    fn update_dark_mode_animation(&mut self, cx: &mut Cx) {
-        let elapsed = Cx::time_now() - self.dark_mode_anim_start;
+            if let ShellFooterAction::StatusItemClicked(id) = action.as_widget_action().cast() {
+                if id == NOTIFICATIONS_STATUS_ITEM {
+                    let open = !self.view.shell_notifications(id!(notifications)).is_center_open();
+                    self.show_notification_center(cx, open);
+                } else {
+                    events.push(ShellEvent::StatusItemClicked(id));
+                }
+            }
 
-        if self.theme.update_animation(elapsed, THEME_TRANSITION_DURATION) {
-            // Animation still in progress
-            self.apply_theme(cx);
-            cx.new_next_frame();
-        } else {
-            // Animation complete
-            self.dark_mode_animating = false;
-            self.apply_theme(cx);
-        }
+            if let ShellDialogAction::Closed { id, result } = action.as_widget_action().cast() {
+                events.extend(self.handle_dialog_closed(cx, id, result));
+            }
 
-        self.view.redraw(cx);
-    }
+            match action.as_widget_action().cast::<ShellNotificationsAction>() {
+                ShellNotificationsAction::ActionClicked { notification_id, action_id } => {
+                    events.push(ShellEvent::NotificationAction { notification_id, action_id });
+                }
+                ShellNotificationsAction::Dismissed(id) => {
+                    events.push(ShellEvent::NotificationDismissed(id));
+                }
+                ShellNotificationsAction::CenterChanged => self.update_notifications_status_item(cx),
+                _ => {}
+            }
 
-    /// Toggle sidebar expanded state - expands sidebar and pushes dock content
-    /// Uses frame-by-frame animation (like MoFA Studio) for synced sidebar + content push
-    fn toggle_sidebar_expanded(&mut self, cx: &mut Cx) {
-        self.sidebar_pinned = !self.sidebar_pinned;
-        self.sidebar_pin_expanding = self.sidebar_pinned;
-        log!("layout.rs - toggle_sidebar_expanded: sidebar_pinned={}, expanding={}", self.sidebar_pinned, self.sidebar_pin_expanding);
+            if let Some(widget_action) = action.as_widget_action() {
+                match widget_action.cast::<SidebarAction>() {
+                    SidebarAction::SelectionChanged(selection) => {
+                        self.save_sidebar_selection(cx, widget_action.widget_uid, selection.clone());
+                        events.push(ShellEvent::SidebarSelectionChanged(selection));
+                    }
+                    SidebarAction::TreeNodeExpanded { .. } => {
+                        self.save_sidebar_expanded_nodes(cx, widget_action.widget_uid);
+                    }
+                    _ => {}
+                }
+            }
 
-        // Hide overlay sidebar when pinning (pinned takes over)
-        if self.overlay_showing {
-            self.overlay_showing = false;
-            self.view.view(id!(overlay_sidebar)).set_visible(cx, false);
+            // Only track splitters of our own dock (FooterGrid has an inner dock too)
+            if let Some(widget_action) = action.as_widget_action() {
+                if widget_action.widget_uid == dock_uid {
+                    if let DockAction::SplitPanelChanged { panel_id, align, .. } = widget_action.cast::<DockAction>() {
+                        self.handle_splitter_changed(cx, panel_id, align, &mut events);
+                    }
+                }
+            }
         }
 
-        // Start frame-by-frame animation
-        self.sidebar_pin_animating = true;
-        self.sidebar_pin_anim_start = Cx::time_now();
+        let panel_menu = self.view.shell_menu(id!(panel_menu));
+        if let Some(item_id) = panel_menu.selected(actions) {
+            let command = PanelMenuCommand::from_item_id(&item_id);
+            let context_panel = self.context_panel.take();
+            // Reopen entries name their panel; the reopen menu opens without a context panel
+            let panel_id = match &command {
+                PanelMenuCommand::Reopen(panel_id) => Some(*panel_id),
+                _ => context_panel,
+            };
+            if let Some(panel_id) = panel_id {
+                events.extend(self.run_panel_command(cx, &panel_id, &command));
+                self.pending_panel_actions.push(PanelAction::MenuCommand { panel_id, command });
+            }
+        } else if panel_menu.dismissed(actions) {
+            self.context_panel = None;
+        }
 
-        // Make pinned sidebar visible when expanding
-        let pinned = self.view.view(id!(pinned_sidebar));
-        if self.sidebar_pin_expanding {
-            pinned.set_visible(cx, true);
+        for side in [SidebarSide::Left, SidebarSide::Right] {
+            let bar = self.view.activity_bar(&[Self::sidebar_host(side), live_id!(activity_bar)]);
+            if let Some(view_id) = bar.view_clicked(actions) {
+                // Clicking the active icon collapses the sidebar
+                let state = self.sidebar_views(side);
+                let next = if !state.collapsed && state.active.as_deref() == Some(view_id.as_str()) {
+                    None
+                } else {
+                    Some(view_id)
+                };
+                events.push(self.show_sidebar_view(cx, side, next.as_deref()));
+            }
         }
 
-        // Request animation frames
-        cx.new_next_frame();
-        self.view.redraw(cx);
+        events
     }
 
-    /// Update sidebar pin animation (frame-by-frame like MoFA Studio)
-    fn update_sidebar_animation(&mut self, cx: &mut Cx) {
-        let elapsed = Cx::time_now() - self.sidebar_pin_anim_start;
-        let progress = (elapsed / SIDEBAR_ANIM_DURATION).min(1.0);
-
-        // Ease out cubic for smooth deceleration
-        let eased = 1.0 - (1.0 - progress).powi(3);
-
-        // Calculate current width based on direction
-        let current_width = if self.sidebar_pin_expanding {
-            SIDEBAR_WIDTH * eased
+    /// Record a dock splitter move and map it to a `ShellEvent`
+    fn handle_splitter_changed(&mut self, cx: &mut Cx, panel_id: LiveId, align: SplitterAlign, events: &mut Vec<ShellEvent>) {
+        let (splitter, side) = if panel_id == live_id!(main_area) {
+            (SplitterId::LeftSidebar, Some(SidebarSide::Left))
+        } else if panel_id == live_id!(right_area) {
+            (SplitterId::RightSidebar, Some(SidebarSide::Right))
+        } else if panel_id == live_id!(root) {
+            (SplitterId::Footer, None)
         } else {
-            SIDEBAR_WIDTH * (1.0 - eased)
+            return;
         };
 
-        // Apply width to pinned sidebar
-        let pinned = self.view.view(id!(pinned_sidebar));
-        pinned.apply_over(cx, live! { width: (current_width) });
+        let position = match align {
+            SplitterAlign::FromA(v) | SplitterAlign::FromB(v) => {
+                let clamped = self.clamp_splitter(cx, splitter, v);
+                if clamped != v {
+                    let align = match align {
+                        SplitterAlign::FromA(_) => SplitterAlign::FromA(clamped),
+                        _ => SplitterAlign::FromB(clamped),
+                    };
+                    self.set_dock_splitter(cx, panel_id, align);
+                }
+                clamped
+            }
+            SplitterAlign::Weighted(v) => v,
+        };
+        if self.layout_locked {
+            return;
+        }
 
-        // Apply matching margin to dock_wrapper (synced push effect)
-        let dock_wrapper = self.view.view(id!(main_container.dock_wrapper));
-        dock_wrapper.apply_over(cx, live! { margin: { left: (current_width) } });
+        if let Some(side) = side {
+            let state = self.sidebar_views_mut(side);
+            if state.collapsed {
+                if position <= ACTIVITY_BAR_WIDTH + 1.0 {
+                    // Keep the expanded width for when the sidebar is reopened
+                    return;
+                }
+                // Dragging a collapsed sidebar open shows its active view again
+                state.collapsed = false;
+                let view_id = state.active.clone();
+                self.apply_sidebar_views(cx, side);
+                events.push(ShellEvent::SidebarViewChanged { side, view_id });
+            }
+        }
 
-        log!("layout.rs - sidebar animation: progress={:.2}, width={:.1}", progress, current_width);
+        let positions = self.preferences.splitter_positions.get_or_insert_with(SplitterPositions::default);
+        match splitter {
+            SplitterId::LeftSidebar => positions.left_sidebar = position,
+            SplitterId::RightSidebar => positions.right_sidebar = position,
+            SplitterId::Footer => positions.footer = position,
+        }
 
-        if progress >= 1.0 {
-            // Animation complete
-            self.sidebar_pin_animating = false;
-            log!("layout.rs - sidebar animation complete, expanding={}", self.sidebar_pin_expanding);
+        events.push(ShellEvent::SplitterChanged { splitter, position });
+    }
 
-            // Hide sidebar when fully collapsed
-            if !self.sidebar_pin_expanding {
-                pinned.set_visible(cx, false);
+    /// Keep the grids at the minimum size of their panels (`PanelDefinition::with_min_size`)
+    ///
+    /// A sidebar or the footer can only grow into the center grid's spare space,
+    /// and the footer stays within its panels' minimum and maximum heights.
+    /// A locked layout keeps every splitter where it is.
+    fn clamp_splitter(&self, cx: &Cx, splitter: SplitterId, position: f64) -> f64 {
+        let positions = self.preferences.splitter_positions.clone().unwrap_or_default();
+        let sidebar_width = |side: SidebarSide, width: f64| {
+            if self.sidebar_views(side).collapsed { ACTIVITY_BAR_WIDTH } else { width }
+        };
+        if self.layout_locked {
+            return match splitter {
+                SplitterId::LeftSidebar => sidebar_width(SidebarSide::Left, positions.left_sidebar),
+                SplitterId::RightSidebar => sidebar_width(SidebarSide::Right, positions.right_sidebar),
+                SplitterId::Footer => positions.footer,
+            };
+        }
+
+        let grid = self.view.panel_grid(id!(center_content));
+        let center = grid.area().rect(cx).size;
+        if center.x <= 0.0 || center.y <= 0.0 {
+            return position;
+        }
+        let limits = grid.size_limits();
+
+        // Growing past `old + spare` would squeeze the grid; never force a shrink
+        let grow_limit = |old: f64, spare: f64| position.min((old + spare).max(old));
+        match splitter {
+            SplitterId::LeftSidebar => {
+                grow_limit(sidebar_width(SidebarSide::Left, positions.left_sidebar), center.x - limits.min_width)
+            }
+            SplitterId::RightSidebar => {
+                grow_limit(sidebar_width(SidebarSide::Right, positions.right_sidebar), center.x - limits.min_width)
+            }
+            SplitterId::Footer => {
+                let footer = self.view.footer_grid(id!(footer_content)).size_limits();
+                let height = grow_limit(positions.footer, center.y - limits.min_height);
+                height.min(footer.max_height.unwrap_or(f64::INFINITY)).max(footer.min_height)
             }
-        } else {
-            // Continue animation
-            cx.new_next_frame();
         }
+    }
 
-        self.view.redraw(cx);
+    /// Widget ID of the sidebar host on the given side
+    fn sidebar_host(side: SidebarSide) -> LiveId {
+        match side {
+            SidebarSide::Left => live_id!(left_sidebar),
+            SidebarSide::Right => live_id!(right_sidebar),
+        }
     }
 
-    /// Apply current theme to all widgets
-    fn apply_theme(&mut self, cx: &mut Cx) {
-        let dm = self.theme.dark_mode_anim;
+    fn sidebar_views(&self, side: SidebarSide) -> &SidebarViews {
+        match side {
+            SidebarSide::Left => &self.left_sidebar_views,
+            SidebarSide::Right => &self.right_sidebar_views,
+        }
+    }
 
-        // Update global theme state for widgets that read it during draw
-        set_global_dark_mode(dm);
+    fn sidebar_views_mut(&mut self, side: SidebarSide) -> &mut SidebarViews {
+        match side {
+            SidebarSide::Left => &mut self.left_sidebar_views,
+            SidebarSide::Right => &mut self.right_sidebar_views,
+        }
+    }
 
-        // Apply to shell background
-        self.view.apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-        });
+    /// Register the views selectable from a sidebar's activity bar
+    ///
+    /// Each view's content is the child of the host's `views` container with the
+    /// same ID. The first view is shown unless the active view is still registered.
+    /// While views are registered the sidebar tab shows its host in place of
+    /// `left_sidebar_content` / `right_sidebar_content`; an empty list brings that back.
+    pub fn set_sidebar_views(&mut self, cx: &mut Cx, side: SidebarSide, views: Vec<SidebarView>) {
+        let hosted = !views.is_empty();
+        let state = self.sidebar_views_mut(side);
+        // Without an activity bar there is nothing to expand a collapsed sidebar again
+        let expand = !hosted && std::mem::take(&mut state.collapsed);
+        let active_registered = state.active.as_ref()
+            .map_or(false, |active| views.iter().any(|view| &view.id == active));
+        if !active_registered {
+            state.active = views.first().map(|view| view.id.clone());
+        }
+        state.views = views;
 
-        // Apply to header (now inside main_container)
-        self.view.view(id!(main_container.header)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-        });
-        self.view.label(id!(main_container.header.title_label)).apply_over(cx, live! {
-            draw_text: { dark_mode: (dm) }
-        });
-        self.view.button(id!(main_container.header.hamburger_btn)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-        });
-        self.view.button(id!(main_container.header.theme_toggle)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-        });
-        self.view.button(id!(main_container.header.reset_btn)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-        });
-        self.view.button(id!(main_container.header.save_btn)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-        });
+        if self.set_sidebar_tab_kind(cx, side, hosted) {
+            // The dock creates the host on its next draw
+            self.apply_sidebar_hosts = true;
+        }
+        self.apply_sidebar_host(cx, side);
+        if expand {
+            self.set_sidebar_collapsed(cx, side, false);
+        }
+    }
 
-        // Access Dock content widgets using apply_over_and_redraw for deep propagation
-        // The Dock creates content widgets dynamically with "kind" templates
+    /// Show the host (activity bar and views) or the plain content in a sidebar's dock tab
+    ///
+    /// Returns true if the tab changed.
+    fn set_sidebar_tab_kind(&mut self, cx: &mut Cx, side: SidebarSide, hosted: bool) -> bool {
+        let (tab, kind) = match side {
+            SidebarSide::Left => (live_id!(left_panel), if hosted { live_id!(left_sidebar) } else { live_id!(left_sidebar_content) }),
+            SidebarSide::Right => (live_id!(right_panel), if hosted { live_id!(right_sidebar) } else { live_id!(right_sidebar_content) }),
+        };
+        let dock = self.view.dock(id!(main_container.dock_wrapper.dock));
+        let Some(mut state) = dock.clone_state() else { return false };
+        match state.get_mut(&tab) {
+            Some(DockItem::Tab { kind: current, .. }) if *current != kind => {
+                *current = kind;
+                dock.load_state(cx, state);
+                true
+            }
+            _ => false,
+        }
+    }
 
-        // Apply dark_mode to ALL nested widgets via recursive apply
-        self.view.view(id!(main_container.dock_wrapper)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-        });
+    /// Give a sidebar host's activity bar its views and show the active one
+    fn apply_sidebar_host(&mut self, cx: &mut Cx, side: SidebarSide) {
+        let views = self.sidebar_views(side).views.clone();
+        let bar = self.view.activity_bar(&[Self::sidebar_host(side), live_id!(activity_bar)]);
+        bar.set_visible(cx, !views.is_empty());
+        bar.set_views(cx, views);
+        self.apply_sidebar_views(cx, side);
+    }
 
-        // Apply to left sidebar content
-        self.view.widget(id!(left_sidebar_content)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-        });
-        self.view.widget(id!(left_sidebar_content.header)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-        });
-        self.view.widget(id!(left_sidebar_content.header.header_label)).apply_over(cx, live! {
-            draw_text: { dark_mode: (dm) }
-        });
+    /// Show a sidebar view, or collapse the sidebar to its activity bar with `None`
+    pub fn set_active_sidebar_view(&mut self, cx: &mut Cx, side: SidebarSide, view_id: Option<&str>) {
+        let event = self.show_sidebar_view(cx, side, view_id);
+        self.emit_events(cx, [event]);
+    }
 
-        // Apply to right sidebar content
-        self.view.widget(id!(right_sidebar_content)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-        });
-        self.view.widget(id!(right_sidebar_content.header)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-        });
-        self.view.widget(id!(right_sidebar_content.header.header_label)).apply_over(cx, live! {
-            draw_text: { dark_mode: (dm) }
-        });
+    /// Active view of a sidebar (`None` while collapsed)
+    pub fn active_sidebar_view(&self, side: SidebarSide) -> Option<&str> {
+        let state = self.sidebar_views(side);
+        if state.collapsed {
+            None
+        } else {
+            state.active.as_deref()
+        }
+    }
 
-        // Apply to center content (PanelGrid)
-        self.view.widget(id!(center_content)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-        });
+    fn show_sidebar_view(&mut self, cx: &mut Cx, side: SidebarSide, view_id: Option<&str>) -> ShellEvent {
+        let state = self.sidebar_views_mut(side);
+        let was_collapsed = state.collapsed;
+        match view_id {
+            Some(id) => {
+                state.active = Some(id.to_string());
+                state.collapsed = false;
+            }
+            None => state.collapsed = true,
+        }
+        let collapsed = state.collapsed;
 
-        // Apply to footer content
-        self.view.widget(id!(footer_content)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-        });
+        self.apply_sidebar_views(cx, side);
+        if collapsed != was_collapsed {
+            self.set_sidebar_collapsed(cx, side, collapsed);
+        }
 
-        // Apply to overlay sidebar (purple themed)
-        self.view.view(id!(overlay_sidebar)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-        });
-        self.apply_overlay_content_theme(cx, dm, id!(overlay_sidebar.overlay_sidebar_content));
+        ShellEvent::SidebarViewChanged {
+            side,
+            view_id: view_id.map(|id| id.to_string()),
+        }
+    }
 
-        // Apply to pinned sidebar (purple themed - used for click toggle)
-        self.view.view(id!(pinned_sidebar)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-        });
-        self.apply_overlay_content_theme(cx, dm, id!(pinned_sidebar.pinned_sidebar_content));
+    /// Show the active view of a host and hide the others
+    fn apply_sidebar_views(&mut self, cx: &mut Cx, side: SidebarSide) {
+        let state = self.sidebar_views(side).clone();
+        let host = Self::sidebar_host(side);
+
+        let shown = if state.collapsed { None } else { state.active.as_deref() };
+        self.view.activity_bar(&[host, live_id!(activity_bar)]).set_active(cx, shown);
+        self.view.view(&[host, live_id!(views)]).set_visible(cx, !state.collapsed);
+        for view in &state.views {
+            let visible = state.active.as_deref() == Some(view.id.as_str());
+            self.view.widget(&[host, live_id!(views), LiveId::from_str(&view.id)]).set_visible(cx, visible);
+        }
+        self.view.redraw(cx);
+    }
 
-        // Note: Dock splitters use a neutral semi-transparent color
-        // that works in both light and dark modes (can't dynamically theme them)
+    /// Shrink a sidebar to its activity bar, or restore its last width
+    fn set_sidebar_collapsed(&mut self, cx: &mut Cx, side: SidebarSide, collapsed: bool) {
+        let positions = self.preferences.splitter_positions.clone().unwrap_or_default();
+        let (splitter, align) = match side {
+            SidebarSide::Left => (
+                live_id!(main_area),
+                SplitterAlign::FromA(if collapsed { ACTIVITY_BAR_WIDTH } else { positions.left_sidebar }),
+            ),
+            SidebarSide::Right => (
+                live_id!(right_area),
+                SplitterAlign::FromB(if collapsed { ACTIVITY_BAR_WIDTH } else { positions.right_sidebar }),
+            ),
+        };
+        self.set_dock_splitter(cx, splitter, align);
     }
 
-    /// Apply theme to overlay sidebar only (called when showing overlay)
-    fn apply_overlay_theme(&mut self, cx: &mut Cx) {
-        let dm = self.theme.dark_mode_anim;
+    /// Move one of the dock's splitters
+    ///
+    /// The Dock has no splitter setter, so its state is cloned, updated and loaded back.
+    fn set_dock_splitter(&mut self, cx: &mut Cx, splitter: LiveId, align: SplitterAlign) {
+        let dock = self.view.dock(id!(main_container.dock_wrapper.dock));
+        if let Some(mut state) = dock.clone_state() {
+            if let Some(DockItem::Splitter { align: current, .. }) = state.get_mut(&splitter) {
+                *current = align;
+                dock.load_state(cx, state);
+            }
+        }
+    }
 
-        // Apply to overlay container
-        self.view.view(id!(overlay_sidebar)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-        });
+    /// Preference keys and widget paths of the left and right sidebars
+    fn sidebar_paths() -> [(&'static str, &'static [LiveId]); 2] {
+        [
+            (LEFT_SIDEBAR, id!(left_sidebar_content)),
+            (RIGHT_SIDEBAR, id!(right_sidebar_content)),
+        ]
+    }
 
-        // Apply to overlay content
-        self.apply_overlay_content_theme(cx, dm, id!(overlay_sidebar.overlay_sidebar_content));
+    /// Preference key and widget path of the sidebar with the given uid
+    fn sidebar_key(&self, sidebar_uid: WidgetUid) -> Option<(&'static str, &'static [LiveId])> {
+        Self::sidebar_paths()
+            .into_iter()
+            .find(|(_, path)| self.view.shell_sidebar(path).widget_uid() == sidebar_uid)
+    }
 
-        // Apply to pinned sidebar too
-        self.view.view(id!(pinned_sidebar)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-        });
-        self.apply_overlay_content_theme(cx, dm, id!(pinned_sidebar.pinned_sidebar_content));
+    /// Persist the selection of the left or right sidebar
+    fn save_sidebar_selection(&mut self, cx: &mut Cx, sidebar_uid: WidgetUid, selection: Option<SidebarSelection>) {
+        let Some((key, _)) = self.sidebar_key(sidebar_uid) else { return };
+
+        match selection {
+            Some(selection) => self.preferences.sidebar_selections.insert(key.to_string(), selection),
+            None => self.preferences.sidebar_selections.remove(key),
+        };
+        if let Err(e) = self.preferences.save(APP_ID) {
+            self.report_error(cx, "Failed to save sidebar selection", &e.to_string());
+        }
+    }
+
+    /// Persist the expanded tree nodes of the left or right sidebar
+    fn save_sidebar_expanded_nodes(&mut self, cx: &mut Cx, sidebar_uid: WidgetUid) {
+        let Some((key, path)) = self.sidebar_key(sidebar_uid) else { return };
+
+        let expanded = self.view.shell_sidebar(path).expanded_tree_paths();
+        self.preferences.sidebar_expanded_nodes.insert(key.to_string(), expanded);
+        if let Err(e) = self.preferences.save(APP_ID) {
+            self.report_error(cx, "Failed to save sidebar tree state", &e.to_string());
+        }
+    }
+
+    /// Apply saved selections and expanded tree nodes to the left and right sidebars
+    fn apply_sidebar_state(&mut self, cx: &mut Cx) {
+        for (key, path) in Self::sidebar_paths() {
+            let sidebar = self.view.shell_sidebar(path);
+            if let Some(expanded) = self.preferences.sidebar_expanded_nodes.get(key) {
+                sidebar.set_expanded_tree_paths(cx, expanded.clone());
+            }
+            if let Some(selection) = self.preferences.sidebar_selections.get(key) {
+                sidebar.set_selection(cx, Some(selection.clone()));
+            }
+        }
+    }
+
+    /// Resolve a panel ID to its semantic ID if the main or footer grid holds it
+    fn find_panel(&self, id: PanelId) -> Option<PanelId> {
+        let layout = self.view.panel_grid(id!(center_content)).layout_state()
+            .or_else(|| self.current_layout.clone())
+            .unwrap_or_default();
+        let footer_layout = self.current_footer_layout.clone().unwrap_or_default();
+
+        layout.row_assignments.iter().flatten()
+            .chain(footer_layout.slots.iter().flat_map(|slot| slot.panel_ids.iter()))
+            .any(|panel_id| *panel_id == id)
+            .then_some(id)
+    }
+
+    /// Switch to another named workspace
+    ///
+    /// The current main grid layout is stored under the active workspace and the
+    /// target workspace's layout (or the default layout for a new workspace) is applied.
+    pub fn switch_workspace(&mut self, cx: &mut Cx, name: &str) {
+        if self.active_workspace == name {
+            return;
+        }
+
+        let layout = self.current_layout.clone().unwrap_or_default();
+        self.preferences.workspaces.insert(self.active_workspace.clone(), layout);
+
+        let next = self.preferences.workspaces.get(name).cloned().unwrap_or_default();
+        self.current_layout = Some(next.clone());
+        self.view.panel_grid(id!(center_content)).set_layout_state(cx, next);
+
+        self.active_workspace = name.to_string();
+        self.preferences.active_workspace = Some(name.to_string());
+        self.emit_events(cx, [ShellEvent::WorkspaceSwitched(name.to_string())]);
+        self.view.redraw(cx);
+    }
+
+    /// Name of the active workspace
+    pub fn active_workspace(&self) -> &str {
+        &self.active_workspace
+    }
+
+    /// Delete a saved workspace, asking first unless confirmations are off
+    ///
+    /// Deleting the active workspace switches to the default one. Returns
+    /// false for the default workspace and for unknown names.
+    pub fn delete_workspace(&mut self, cx: &mut Cx, name: &str) -> bool {
+        if name == DEFAULT_WORKSPACE
+            || (name != self.active_workspace && !self.preferences.workspaces.contains_key(name))
+        {
+            return false;
+        }
+        if self.config.confirm_destructive_actions {
+            self.open_dialog(cx, Dialog::confirm(
+                format!("{}{}", DELETE_WORKSPACE_DIALOG, name),
+                format!("Delete workspace \"{}\"?", name),
+                "Its saved layout will be lost.",
+            ).with_confirm_label("Delete").destructive());
+        } else {
+            let event = self.remove_workspace(cx, name);
+            self.emit_events(cx, [event]);
+        }
+        true
+    }
+
+    fn remove_workspace(&mut self, cx: &mut Cx, name: &str) -> ShellEvent {
+        if self.active_workspace == name {
+            self.switch_workspace(cx, DEFAULT_WORKSPACE);
+        }
+        self.preferences.workspaces.remove(name);
+        if let Err(e) = self.preferences.save(APP_ID) {
+            self.report_error(cx, "Failed to save workspaces", &e.to_string());
+        }
+        ShellEvent::WorkspaceDeleted(name.to_string())
+    }
+
+    /// Replace the app items of the header toolbar
+    pub fn set_toolbar_items(&mut self, cx: &mut Cx, items: Vec<ToolbarItem>) {
+        self.view.shell_header(id!(main_container.header)).set_toolbar_items(cx, items);
+    }
+
+    /// Set the checked state of a toggle toolbar item
+    ///
+    /// Returns false if no toggle item has this ID.
+    pub fn set_toolbar_item_checked(&mut self, cx: &mut Cx, id: &str, checked: bool) -> bool {
+        self.view.shell_header(id!(main_container.header)).set_toolbar_item_checked(cx, id, checked)
+    }
+
+    /// Play the click flash on a toolbar item (e.g. to confirm a background save)
+    ///
+    /// Returns false if no item has this ID.
+    pub fn flash_toolbar_item(&mut self, cx: &mut Cx, id: &str) -> bool {
+        self.view.shell_header(id!(main_container.header)).flash_toolbar_item(cx, id)
+    }
+
+    /// Add a status bar item, or replace the item with the same ID
+    ///
+    /// The status bar shows while it has items.
+    pub fn set_status_item(&mut self, cx: &mut Cx, item: StatusItem) {
+        self.view.shell_footer(id!(main_container.status_bar)).set_status_item(cx, item);
+    }
+
+    /// Remove a status bar item
+    ///
+    /// Returns false if no item has this ID.
+    pub fn remove_status_item(&mut self, cx: &mut Cx, id: &str) -> bool {
+        self.view.shell_footer(id!(main_container.status_bar)).remove_status_item(cx, id)
+    }
+
+    /// Change the text of a status bar item
+    ///
+    /// Returns false if no item has this ID.
+    pub fn set_status_item_text(&mut self, cx: &mut Cx, id: &str, text: &str) -> bool {
+        self.view.shell_footer(id!(main_container.status_bar)).set_status_item_text(cx, id, text)
+    }
+
+    /// Show (`Some`) or hide the progress spinner of a status bar item
+    ///
+    /// Returns false if no item has this ID.
+    pub fn set_status_item_progress(&mut self, cx: &mut Cx, id: &str, progress: Option<StatusProgress>) -> bool {
+        self.view.shell_footer(id!(main_container.status_bar)).set_status_item_progress(cx, id, progress)
+    }
+
+    /// Show a notification toast, replacing the one with the same ID
+    ///
+    /// Returns the notification ID (assigned if the notification has none).
+    /// The notification is also kept in the notification center, which
+    /// opens from a status bar item.
+    pub fn notify(&mut self, cx: &mut Cx, notification: Notification) -> String {
+        let id = self.view.shell_notifications(id!(notifications))
+            .notify(cx, notification)
+            .unwrap_or_default();
+        self.update_notifications_status_item(cx);
+        id
+    }
+
+    /// Set the progress of a progress notification (`None` = busy)
+    ///
+    /// Returns false if no notification has this ID.
+    pub fn set_notification_progress(&mut self, cx: &mut Cx, id: &str, progress: Option<f64>) -> bool {
+        self.view.shell_notifications(id!(notifications)).set_progress(cx, id, progress)
+    }
+
+    /// Close a notification toast (it stays in the notification center)
+    ///
+    /// Returns false if no toast has this ID.
+    pub fn dismiss_notification(&mut self, cx: &mut Cx, id: &str) -> bool {
+        self.view.shell_notifications(id!(notifications)).dismiss(cx, id)
+    }
+
+    /// Open or close the notification center
+    pub fn show_notification_center(&mut self, cx: &mut Cx, show: bool) {
+        self.view.shell_notifications(id!(notifications)).set_center_open(cx, show);
+        self.update_notifications_status_item(cx);
+    }
+
+    /// Notification history, oldest first
+    pub fn notification_history(&self) -> Vec<NotificationRecord> {
+        self.view.shell_notifications(id!(notifications)).history()
+    }
+
+    /// Forget the notification history
+    pub fn clear_notifications(&mut self, cx: &mut Cx) {
+        self.view.shell_notifications(id!(notifications)).clear_history(cx);
+        self.update_notifications_status_item(cx);
+    }
+
+    /// Show a modal dialog, or queue it behind the one showing
+    ///
+    /// The result arrives as `ShellEvent::DialogClosed` with the dialog's ID.
+    pub fn open_dialog(&mut self, cx: &mut Cx, dialog: Dialog) {
+        self.view.shell_dialog(id!(dialog)).open(cx, dialog);
+    }
+
+    /// Cancel the showing dialog
+    pub fn close_dialog(&mut self, cx: &mut Cx) {
+        self.view.shell_dialog(id!(dialog)).close(cx);
+    }
+
+    /// Whether a dialog is showing
+    pub fn is_dialog_open(&self) -> bool {
+        self.view.shell_dialog(id!(dialog)).is_open()
+    }
+
+    /// Body widget of the showing custom dialog, to read or fill its fields
+    pub fn dialog_body(&self) -> WidgetRef {
+        self.view.shell_dialog(id!(dialog)).body()
+    }
+
+    /// Run the shell's own confirmations; other dialogs are reported to the app
+    fn handle_dialog_closed(&mut self, cx: &mut Cx, id: String, result: DialogResult) -> Vec<ShellEvent> {
+        let mut events = Vec::new();
+        if id == RESET_LAYOUT_DIALOG {
+            if result.is_confirmed() {
+                self.reset_layout(cx);
+                events.push(ShellEvent::LayoutReset);
+            }
+        } else if let Some(name) = id.strip_prefix(DELETE_WORKSPACE_DIALOG) {
+            if result.is_confirmed() {
+                events.push(self.remove_workspace(cx, name));
+            }
+        } else if let Some(panel_id) = id.strip_prefix(CLOSE_PANEL_DIALOG) {
+            if result.is_confirmed() {
+                events.extend(self.close_panel(cx, panel_id));
+            }
+        } else if let Some(panel_id) = id.strip_prefix(RENAME_PANEL_DIALOG) {
+            if let DialogResult::Text(title) = result {
+                events.extend(self.rename_panel(cx, panel_id, title.trim()));
+            }
+        } else {
+            events.push(ShellEvent::DialogClosed { id, result });
+        }
+        events
+    }
+
+    /// Close a panel in the main grid or the footer, returning the layout events
+    fn close_panel(&mut self, cx: &mut Cx, panel_id: &str) -> Vec<ShellEvent> {
+        if self.view.panel_grid(id!(center_content)).close_panel(cx, panel_id) {
+            self.sync_layout()
+        } else if self.view.footer_grid(id!(footer_content)).close_panel(cx, panel_id) {
+            self.sync_footer_layout()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Ask before closing a panel that needs confirmation
+    fn confirm_close_panel(&mut self, cx: &mut Cx, panel_id: &str) {
+        let title = self.panel_title(panel_id);
+        self.open_dialog(cx, Dialog::confirm(
+            format!("{}{}", CLOSE_PANEL_DIALOG, panel_id),
+            format!("Close {}?", title),
+            "The panel can be reopened later.",
+        ).with_confirm_label("Close"));
+    }
+
+    /// Display title of a panel: the user's title, then the registered one, then the ID
+    fn panel_title(&self, panel_id: &str) -> String {
+        self.current_layout.as_ref()
+            .and_then(|layout| layout.panel_titles.get(panel_id).cloned())
+            .or_else(|| self.panel_registry.get(panel_id).map(|def| def.title.clone()))
+            .unwrap_or_else(|| panel_id.to_string())
+    }
+
+    /// Set the panel definitions: titles, title bar actions and context menu entries
+    pub fn set_panel_registry(&mut self, cx: &mut Cx, registry: PanelRegistry) {
+        let definitions: Vec<PanelDefinition> = registry.panels().cloned().collect();
+        self.view.panel_grid(id!(center_content)).set_panel_definitions(cx, &definitions);
+        self.view.footer_grid(id!(footer_content)).set_panel_definitions(cx, &definitions);
+        self.panel_registry = registry;
+    }
+
+    /// Set the checked state of a panel's toggle title action in the main grid or footer
+    pub fn set_panel_title_action_checked(&mut self, cx: &mut Cx, panel_id: &str, action_id: &str, checked: bool) -> bool {
+        self.view.panel_grid(id!(center_content)).set_title_action_checked(cx, panel_id, action_id, checked)
+            || self.view.footer_grid(id!(footer_content)).set_title_action_checked(cx, panel_id, action_id, checked)
+    }
+
+    /// Panel definitions set through `set_panel_registry`
+    pub fn panel_registry(&self) -> &PanelRegistry {
+        &self.panel_registry
+    }
+
+    /// Show a panel's context menu at an absolute position
+    fn open_panel_menu(&mut self, cx: &mut Cx, panel_id: PanelId, abs: DVec2) {
+        let layout = self.view.panel_grid(id!(center_content)).layout_state().unwrap_or_default();
+        let footer_layout = self.view.footer_grid(id!(footer_content)).layout_state().unwrap_or_default();
+        let footer_panels: Vec<PanelId> = footer_layout.slots.iter()
+            .filter(|slot| slot.visible)
+            .flat_map(|slot| slot.panel_ids.iter().copied())
+            .collect();
+
+        let context = if footer_panels.iter().any(|id| *id == panel_id) {
+            PanelMenuContext {
+                in_footer: true,
+                maximized: footer_layout.fullscreen_panel.map_or(false, |id| id == panel_id),
+                has_others: footer_panels.len() > 1,
+                pinned: self.view.footer_grid(id!(footer_content)).is_panel_pinned(&panel_id),
+                layout_locked: self.layout_locked,
+            }
+        } else {
+            PanelMenuContext {
+                in_footer: false,
+                maximized: layout.maximized_panel.map_or(false, |id| id == panel_id),
+                has_others: layout.visible_count() > 1,
+                pinned: self.view.panel_grid(id!(center_content)).is_panel_pinned(&panel_id),
+                layout_locked: self.layout_locked,
+            }
+        };
+
+        let mut entries = panel_menu_entries(self.panel_registry.get(&panel_id), context);
+        entries.push(MenuEntry::Separator);
+        entries.push(reopen_menu_item(&self.closed_panels()).into());
+        let menu = self.view.shell_menu(id!(panel_menu));
+        menu.open(cx, entries, abs, Rect::default());
+        self.context_panel = Some(panel_id);
+    }
+
+    /// Closed panels of the main grid, then of the footer, with their titles and decorations
+    fn closed_panels(&self) -> Vec<ClosedPanel> {
+        let grid = self.view.panel_grid(id!(center_content));
+        let footer = self.view.footer_grid(id!(footer_content));
+        let main_panels = grid.closed_panels().into_iter()
+            .map(|id| (id, grid.panel_decoration(&id)));
+        let footer_panels = footer.closed_panels().into_iter()
+            .map(|id| (id, footer.panel_decoration(&id)));
+        main_panels.chain(footer_panels)
+            .map(|(id, decoration)| ClosedPanel { id, title: self.panel_title(&id), decoration })
+            .collect()
+    }
+
+    /// Show the closed panels in a menu at an absolute position; choosing one reopens it
+    pub fn open_reopen_menu(&mut self, cx: &mut Cx, abs: DVec2) {
+        let entries = reopen_menu_entries(&self.closed_panels());
+        self.view.shell_menu(id!(panel_menu)).open(cx, entries, abs, Rect::default());
+        self.context_panel = None;
+    }
+
+    /// Reopen a closed panel in the main grid or the footer, returning the layout events
+    fn reopen_panel(&mut self, cx: &mut Cx, panel_id: &str) -> Vec<ShellEvent> {
+        if self.view.panel_grid(id!(center_content)).reopen_panel(cx, panel_id) {
+            self.sync_layout()
+        } else if self.view.footer_grid(id!(footer_content)).reopen_panel(cx, panel_id) {
+            self.sync_footer_layout()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Perform the built-in part of a context menu command
+    ///
+    /// Moving to the footer, popping out, resetting and custom entries are left
+    /// to the app, which receives every command as `PanelAction::MenuCommand`.
+    fn run_panel_command(&mut self, cx: &mut Cx, panel_id: &str, command: &PanelMenuCommand) -> Vec<ShellEvent> {
+        match command {
+            PanelMenuCommand::Close => {
+                if self.config.confirm_close_panels.iter().any(|id| id == panel_id) {
+                    self.confirm_close_panel(cx, panel_id);
+                    Vec::new()
+                } else {
+                    self.close_panel(cx, panel_id)
+                }
+            }
+            PanelMenuCommand::CloseOthers => {
+                let layout = self.view.panel_grid(id!(center_content)).layout_state().unwrap_or_default();
+                let footer_layout = self.view.footer_grid(id!(footer_content)).layout_state().unwrap_or_default();
+                let in_footer = footer_layout.slots.iter().any(|slot| slot.panel_ids.iter().any(|id| id == panel_id));
+                let others: Vec<PanelId> = if in_footer {
+                    footer_layout.slots.iter()
+                        .filter(|slot| slot.visible)
+                        .flat_map(|slot| slot.panel_ids.iter().copied())
+                        .collect()
+                } else {
+                    layout.row_assignments.iter().flatten()
+                        .filter(|id| layout.visible_panels.contains(*id))
+                        .copied()
+                        .collect()
+                };
+                // Panels that ask before closing and pinned panels stay open
+                let mut events = Vec::new();
+                for id in others {
+                    if id != panel_id && !self.config.confirm_close_panels.iter().any(|confirm| id == *confirm) && !self.is_panel_pinned(&id) {
+                        events.extend(self.close_panel(cx, &id));
+                    }
+                }
+                events
+            }
+            PanelMenuCommand::Pin => {
+                let pinned = !self.is_panel_pinned(panel_id);
+                self.set_panel_pinned(cx, panel_id, pinned)
+            }
+            PanelMenuCommand::Maximize => {
+                if self.view.panel_grid(id!(center_content)).toggle_maximize(cx, panel_id) {
+                    self.sync_layout()
+                } else if self.view.footer_grid(id!(footer_content)).toggle_fullscreen(cx, panel_id) {
+                    self.sync_footer_layout()
+                } else {
+                    Vec::new()
+                }
+            }
+            PanelMenuCommand::Rename => {
+                let title = self.panel_title(panel_id);
+                self.open_dialog(cx, Dialog::prompt(
+                    format!("{}{}", RENAME_PANEL_DIALOG, panel_id),
+                    "Rename panel",
+                    "",
+                ).with_text(title).with_placeholder("Panel title").with_confirm_label("Rename"));
+                Vec::new()
+            }
+            PanelMenuCommand::Reopen(_) => self.reopen_panel(cx, panel_id),
+            PanelMenuCommand::MoveToFooter
+            | PanelMenuCommand::PopOut
+            | PanelMenuCommand::Reset
+            | PanelMenuCommand::Custom(_) => Vec::new(),
+        }
+    }
+
+    /// Whether a panel of the main grid or the footer is pinned
+    fn is_panel_pinned(&self, panel_id: &str) -> bool {
+        self.view.panel_grid(id!(center_content)).is_panel_pinned(panel_id)
+            || self.view.footer_grid(id!(footer_content)).is_panel_pinned(panel_id)
+    }
+
+    /// Pin or unpin a panel in the main grid or the footer, returning the layout events
+    fn set_panel_pinned(&mut self, cx: &mut Cx, panel_id: &str, pinned: bool) -> Vec<ShellEvent> {
+        if self.view.panel_grid(id!(center_content)).set_panel_pinned(cx, panel_id, pinned) {
+            self.sync_layout()
+        } else if self.view.footer_grid(id!(footer_content)).set_panel_pinned(cx, panel_id, pinned) {
+            self.sync_footer_layout()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Lock or unlock panel drag and close and the dock splitters
+    fn apply_layout_lock(&mut self, cx: &mut Cx, locked: bool) {
+        self.layout_locked = locked;
+        self.view.panel_grid(id!(center_content)).set_layout_locked(cx, locked);
+        self.view.footer_grid(id!(footer_content)).set_layout_locked(cx, locked);
+        self.view.shell_header(id!(main_container.header)).set_layout_locked(cx, locked);
+    }
+
+    /// Lock or unlock the layout and save the choice; false if it is unchanged
+    fn change_layout_lock(&mut self, cx: &mut Cx, locked: bool) -> bool {
+        if self.layout_locked == locked {
+            return false;
+        }
+        self.apply_layout_lock(cx, locked);
+        self.preferences.layout_locked = Some(locked);
+        if let Err(e) = self.preferences.save(APP_ID) {
+            self.report_error(cx, "Failed to save layout lock", &e.to_string());
+        }
+        true
+    }
+
+    /// Lock (true) or unlock the layout: no panel drag or close, no dock resize
+    ///
+    /// The choice is saved to preferences right away.
+    pub fn set_layout_locked(&mut self, cx: &mut Cx, locked: bool) {
+        if self.change_layout_lock(cx, locked) {
+            self.emit_events(cx, [ShellEvent::LayoutLockChanged(locked)]);
+        }
+    }
+
+    /// Whether the layout is locked
+    pub fn is_layout_locked(&self) -> bool {
+        self.layout_locked
+    }
+
+    /// Set (or clear with None) the app hook that can forbid drops in the main grid and footer
+    pub fn set_drop_validator(&mut self, validator: Option<DropValidator>) {
+        self.view.panel_grid(id!(center_content)).set_drop_validator(validator.clone());
+        self.view.footer_grid(id!(footer_content)).set_drop_validator(validator);
+    }
+
+    /// Give a main grid panel a new title (an empty title is ignored)
+    fn rename_panel(&mut self, cx: &mut Cx, panel_id: &str, title: &str) -> Vec<ShellEvent> {
+        if title.is_empty() || !self.view.panel_grid(id!(center_content)).rename_panel(cx, panel_id, title) {
+            return Vec::new();
+        }
+        // The layout diff reports PanelRenamed
+        self.sync_layout()
+    }
+
+    /// Pick up a main grid change made through its Ref, returning the layout events
+    fn sync_layout(&mut self) -> Vec<ShellEvent> {
+        let Some(state) = self.view.panel_grid(id!(center_content)).layout_state() else { return Vec::new() };
+        let old = self.current_layout.replace(state.clone()).unwrap_or_default();
+        diff_layout(&old, &state)
+    }
+
+    /// Pick up a footer change made through its Ref, returning the layout events
+    fn sync_footer_layout(&mut self) -> Vec<ShellEvent> {
+        let Some(state) = self.view.footer_grid(id!(footer_content)).layout_state() else { return Vec::new() };
+        let old = self.current_footer_layout.replace(state.clone()).unwrap_or_default();
+        diff_footer_layout(&old, &state)
+    }
+
+    /// Emit shell events right away, including from Ref API calls made outside `handle_event`
+    ///
+    /// Actions raised while the app handles actions reach it in the next round of
+    /// `handle_actions`, without waiting for another input event.
+    fn emit_events(&self, cx: &mut Cx, events: impl IntoIterator<Item = ShellEvent>) {
+        let uid = self.widget_uid();
+        for event in events {
+            cx.widget_action(uid, &self.scope_path, event);
+        }
+    }
+
+    /// Log a shell-internal error and show it as an error notification
+    fn report_error(&mut self, cx: &mut Cx, title: &str, message: &str) {
+        log!("{}: {}", title, message);
+        self.notify(cx, Notification::error(title).with_id(title).with_message(message));
+    }
+
+    /// Show the notification center item (with the unread count) once there are notifications
+    fn update_notifications_status_item(&mut self, cx: &mut Cx) {
+        let notifications = self.view.shell_notifications(id!(notifications));
+        if notifications.history().is_empty() {
+            self.remove_status_item(cx, NOTIFICATIONS_STATUS_ITEM);
+            return;
+        }
+        let text = match notifications.unread_count() {
+            0 => "Notifications".to_string(),
+            unread => format!("Notifications ({})", unread),
+        };
+        self.set_status_item(cx, StatusItem::new(NOTIFICATIONS_STATUS_ITEM, text)
+            .with_tooltip("Show notifications")
+            .align_right()
+            .with_priority(i32::MIN)
+            .clickable());
+    }
+
+    /// Replace the header menu bar contents (an empty model hides the menu bar)
+    pub fn set_menu_model(&mut self, cx: &mut Cx, model: MenuModel) {
+        self.view.shell_header(id!(main_container.header)).set_menu_model(cx, model);
+    }
+
+    /// Current menu bar contents, including checked states changed by the user
+    pub fn menu_model(&self) -> MenuModel {
+        self.view.shell_header(id!(main_container.header)).menu_model()
+    }
+
+    /// Set the checked state of a checkable menu item
+    ///
+    /// Returns false if no checkable item has this ID.
+    pub fn set_menu_item_checked(&mut self, id: &str, checked: bool) -> bool {
+        self.view.shell_header(id!(main_container.header)).set_menu_item_checked(id, checked)
+    }
+
+    /// Enable or disable a menu item
+    ///
+    /// Returns false if no item has this ID.
+    pub fn set_menu_item_enabled(&mut self, cx: &mut Cx, id: &str, enabled: bool) -> bool {
+        self.view.shell_header(id!(main_container.header)).set_menu_item_enabled(cx, id, enabled)
+    }
+
+    /// Toggle dark mode with animation (immediately with reduce motion)
+    ///
+    /// Switches the theme mode to Light or Dark, so the platform appearance
+    /// is no longer followed.
+    pub fn toggle_dark_mode(&mut self, cx: &mut Cx) {
+        let dark = !self.theme.dark_mode;
+        self.set_theme_mode(cx, ThemeMode::from_dark(dark));
+    }
+
+    /// Set dark mode state (immediately, no animation)
+    ///
+    /// Switches the theme mode to Light or Dark.
+    pub fn set_dark_mode(&mut self, cx: &mut Cx, dark: bool) {
+        self.appearance.set_mode(ThemeMode::from_dark(dark));
+        self.update_appearance_timer(cx);
+        self.dark_mode_animating = false;
+        self.theme.set_dark_mode(dark);
+        self.apply_theme(cx);
+    }
+
+    /// Switch between light, dark and following the platform appearance
+    ///
+    /// Animates to the resulting mode (immediately with reduce motion).
+    pub fn set_theme_mode(&mut self, cx: &mut Cx, mode: ThemeMode) {
+        let dark = self.appearance.set_mode(mode);
+        self.update_appearance_timer(cx);
+        self.transition_dark_mode(cx, dark);
+    }
+
+    /// Current theme mode
+    pub fn theme_mode(&self) -> ThemeMode {
+        self.appearance.mode()
+    }
+
+    /// Replace the platform appearance source followed in `ThemeMode::System`
+    pub fn set_appearance_source(&mut self, cx: &mut Cx, source: impl AppearanceSource + 'static) {
+        let dark = self.appearance.set_source(source);
+        self.transition_dark_mode(cx, dark);
+    }
+
+    /// Animate to light or dark (immediately with reduce motion)
+    fn transition_dark_mode(&mut self, cx: &mut Cx, dark: bool) {
+        if dark == self.theme.dark_mode {
+            return;
+        }
+        if self.config.reduce_motion {
+            self.snap_dark_mode(cx, dark);
+            return;
+        }
+        self.theme.dark_mode = dark;
+        self.dark_mode_animating = true;
+        self.dark_mode_anim_start = Cx::time_now();
+        cx.new_next_frame();
+        self.view.redraw(cx);
+    }
+
+    /// Switch to light or dark without animating
+    fn snap_dark_mode(&mut self, cx: &mut Cx, dark: bool) {
+        self.dark_mode_animating = false;
+        self.theme.set_dark_mode(dark);
+        self.apply_theme(cx);
+        self.view.redraw(cx);
+    }
+
+    /// Poll the platform appearance only while following it
+    fn update_appearance_timer(&mut self, cx: &mut Cx) {
+        cx.stop_timer(self.appearance_timer);
+        self.appearance_timer = Timer::empty();
+        if self.appearance.mode() == ThemeMode::System && self.config.appearance_poll_interval > 0.0 {
+            self.appearance_timer = cx.start_interval(self.config.appearance_poll_interval);
+        }
+    }
+
+    /// Check if dark mode is enabled
+    pub fn is_dark_mode(&self) -> bool {
+        self.theme.dark_mode
+    }
+
+    /// Replace the light and dark palettes of the default theme (e.g. with a brand theme)
+    ///
+    /// A selected named theme stays applied until the default theme is picked.
+    pub fn set_palettes(&mut self, cx: &mut Cx, light: ShellPalette, dark: ShellPalette) {
+        let mut definition = self.default_theme();
+        definition.light = light;
+        definition.dark = dark;
+        self.default_theme = Some(definition.clone());
+        if self.active_theme.is_none() {
+            self.apply_theme_definition(cx, &definition);
+        }
+    }
+
+    /// Update dark mode animation
+    fn update_dark_mode_animation(&mut self, cx: &mut Cx) {
+        let elapsed = Cx::time_now() - self.dark_mode_anim_start;
+
+        if self.theme.update_animation(elapsed, THEME_TRANSITION_DURATION) {
+            // Animation still in progress
+            self.apply_theme(cx);
+            cx.new_next_frame();
+        } else {
+            // Animation complete
+            self.dark_mode_animating = false;
+            self.apply_theme(cx);
+        }
+
+        self.view.redraw(cx);
+    }
+
+    /// Hover zones for the overlay sidebar, derived from the current widget rects
+    fn overlay_zones(&self, cx: &Cx) -> OverlayZones {
+        let shell = self.view.area().rect(cx);
+        let header = self.view.widget(id!(main_container.header)).area().rect(cx);
+        let hamburger = self.view.button(id!(main_container.header.hamburger_btn)).area().rect(cx);
+        let top = header.pos.y + header.size.y;
+        let width = self.config.overlay_sidebar_width;
+
+        OverlayZones {
+            hamburger,
+            // Bridge from the hamburger down into the overlay so it stays open while moving
+            bridge: Rect {
+                pos: dvec2(shell.pos.x, hamburger.pos.y),
+                size: dvec2(width, top - hamburger.pos.y + OVERLAY_BRIDGE_MARGIN),
+            },
+            overlay: Rect {
+                pos: dvec2(shell.pos.x, top),
+                size: dvec2(width, (shell.pos.y + shell.size.y - top).max(0.0)),
+            },
+        }
+    }
+
+    /// Open/close the overlay sidebar from hover, keyboard and touch input
+    fn handle_overlay_input(&mut self, cx: &mut Cx, event: &Event) {
+        // Pinned sidebar takes precedence over the overlay
+        if self.sidebar_pinned {
+            return;
+        }
+
+        match event {
+            Event::MouseMove(e) => {
+                let zones = self.overlay_zones(cx);
+                let over_hamburger = zones.hamburger.contains(e.abs);
+                let over_overlay = self.overlay_showing
+                    && (zones.bridge.contains(e.abs) || zones.overlay.contains(e.abs));
+
+                if over_hamburger && !self.overlay_showing {
+                    if self.config.overlay_hover_delay <= 0.0 {
+                        self.set_overlay_visible(cx, true, false);
+                    } else if self.overlay_hover_timer.is_empty() {
+                        self.overlay_hover_timer = cx.start_timeout(self.config.overlay_hover_delay);
+                    }
+                } else if !over_hamburger && !over_overlay {
+                    cx.stop_timer(self.overlay_hover_timer);
+                    self.overlay_hover_timer = Timer::empty();
+                    if self.overlay_showing && !self.overlay_sticky {
+                        self.set_overlay_visible(cx, false, false);
+                    }
+                }
+            }
+            // Keys belong to a focused text input (Escape clears or leaves it there)
+            Event::KeyDown(ke) if !self.text_input_focused => {
+                let modifier = ke.modifiers.control || ke.modifiers.logo;
+                if ke.key_code == KeyCode::Escape && self.overlay_showing {
+                    self.set_overlay_visible(cx, false, false);
+                } else if self.config.enable_overlay_shortcut && modifier && ke.key_code == KeyCode::KeyB {
+                    let show = !self.overlay_showing;
+                    self.set_overlay_visible(cx, show, true);
+                }
+            }
+            Event::FingerDown(fe) => {
+                let zones = self.overlay_zones(cx);
+                if zones.hamburger.contains(fe.abs) {
+                    self.hamburger_touched = fe.device.is_touch();
+                } else if self.overlay_showing && self.overlay_sticky && !zones.overlay.contains(fe.abs) {
+                    // Tap/click outside dismisses an overlay opened without hover
+                    self.set_overlay_visible(cx, false, false);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Show or hide the overlay sidebar with the configured animation
+    ///
+    /// A `sticky` overlay (opened by keyboard or touch) ignores the pointer leaving it
+    /// and is closed with Escape, the shortcut, or a click outside.
+    pub fn set_overlay_visible(&mut self, cx: &mut Cx, visible: bool, sticky: bool) {
+        cx.stop_timer(self.overlay_hover_timer);
+        self.overlay_hover_timer = Timer::empty();
+
+        if visible && self.sidebar_pinned {
+            return;
+        }
+        self.overlay_sticky = visible && sticky;
+        if visible == self.overlay_showing {
+            return;
+        }
+        self.overlay_showing = visible;
+
+        if visible {
+            self.apply_sidebar_offsets(cx);
+            self.view.view(id!(overlay_sidebar)).set_visible(cx, true);
+            self.apply_overlay_theme(cx);
+        }
+
+        self.overlay_animating = true;
+        self.overlay_anim_start = Cx::time_now();
+        cx.new_next_frame();
+        self.view.redraw(cx);
+    }
+
+    /// Sidebar open or close animation duration (0 with reduce motion)
+    fn sidebar_duration(&self, opening: bool) -> f64 {
+        if self.config.reduce_motion {
+            0.0
+        } else if opening {
+            self.config.sidebar_open_duration
+        } else {
+            self.config.sidebar_close_duration
+        }
+    }
+
+    /// Update overlay open/close animation
+    fn update_overlay_animation(&mut self, cx: &mut Cx) {
+        let duration = self.sidebar_duration(self.overlay_showing);
+        let progress = if duration > 0.0 {
+            ((Cx::time_now() - self.overlay_anim_start) / duration).min(1.0)
+        } else {
+            1.0
+        };
+        let eased = ease_out_cubic(progress);
+        let width = if self.overlay_showing {
+            self.config.overlay_sidebar_width * eased
+        } else {
+            self.config.overlay_sidebar_width * (1.0 - eased)
+        };
+
+        let overlay = self.view.view(id!(overlay_sidebar));
+        overlay.apply_over(cx, live! { width: (width) });
+
+        if progress >= 1.0 {
+            self.overlay_animating = false;
+            if !self.overlay_showing {
+                overlay.set_visible(cx, false);
+            }
+        } else {
+            cx.new_next_frame();
+        }
+        self.view.redraw(cx);
+    }
+
+    /// Position the overlay and pinned sidebars directly below the header
+    fn apply_sidebar_offsets(&mut self, cx: &mut Cx) {
+        let shell = self.view.area().rect(cx);
+        let header = self.view.widget(id!(main_container.header)).area().rect(cx);
+        let top = (header.pos.y + header.size.y - shell.pos.y).max(0.0);
+
+        self.view.view(id!(overlay_sidebar)).apply_over(cx, live! { margin: { top: (top) } });
+        self.view.view(id!(pinned_sidebar)).apply_over(cx, live! { margin: { top: (top) } });
+    }
+
+    /// Toggle sidebar expanded state - expands sidebar and pushes dock content
+    /// Uses frame-by-frame animation (like MoFA Studio) for synced sidebar + content push
+    fn toggle_sidebar_expanded(&mut self, cx: &mut Cx) {
+        self.sidebar_pinned = !self.sidebar_pinned;
+        self.sidebar_pin_expanding = self.sidebar_pinned;
+        log!("layout.rs - toggle_sidebar_expanded: sidebar_pinned={}, expanding={}", self.sidebar_pinned, self.sidebar_pin_expanding);
+
+        // Hide overlay sidebar when pinning (pinned takes over)
+        if self.overlay_showing {
+            self.overlay_showing = false;
+            self.overlay_sticky = false;
+            self.overlay_animating = false;
+            self.view.view(id!(overlay_sidebar)).set_visible(cx, false);
+        }
+        self.apply_sidebar_offsets(cx);
+
+        // Start frame-by-frame animation
+        self.sidebar_pin_animating = true;
+        self.sidebar_pin_anim_start = Cx::time_now();
+
+        // Make pinned sidebar visible when expanding
+        let pinned = self.view.view(id!(pinned_sidebar));
+        if self.sidebar_pin_expanding {
+            pinned.set_visible(cx, true);
+        }
+
+        // Request animation frames
+        cx.new_next_frame();
+        self.view.redraw(cx);
+    }
+
+    /// Update sidebar pin animation (frame-by-frame like MoFA Studio)
+    fn update_sidebar_animation(&mut self, cx: &mut Cx) {
+        let elapsed = Cx::time_now() - self.sidebar_pin_anim_start;
+        let duration = self.sidebar_duration(self.sidebar_pin_expanding);
+        let progress = if duration > 0.0 { (elapsed / duration).min(1.0) } else { 1.0 };
+
+        // Ease out cubic for smooth deceleration
+        let eased = ease_out_cubic(progress);
+
+        // Calculate current width based on direction
+        let current_width = if self.sidebar_pin_expanding {
+            self.config.overlay_sidebar_width * eased
+        } else {
+            self.config.overlay_sidebar_width * (1.0 - eased)
+        };
+
+        // Apply width to pinned sidebar
+        let pinned = self.view.view(id!(pinned_sidebar));
+        pinned.apply_over(cx, live! { width: (current_width) });
+
+        // Apply matching margin to dock_wrapper (synced push effect)
+        let dock_wrapper = self.view.view(id!(main_container.dock_wrapper));
+        dock_wrapper.apply_over(cx, live! { margin: { left: (current_width) } });
+
+        log!("layout.rs - sidebar animation: progress={:.2}, width={:.1}", progress, current_width);
+
+        if progress >= 1.0 {
+            // Animation complete
+            self.sidebar_pin_animating = false;
+            log!("layout.rs - sidebar animation complete, expanding={}", self.sidebar_pin_expanding);
+
+            // Hide sidebar when fully collapsed
+            if !self.sidebar_pin_expanding {
+                pinned.set_visible(cx, false);
+            }
+        } else {
+            // Continue animation
+            cx.new_next_frame();
+        }
+
+        self.view.redraw(cx);
+    }
+
+    /// Apply current theme to the shell's own views and pass it down the widget tree
+    fn apply_theme(&mut self, cx: &mut Cx) {
+        // Header, sidebars, grids, panels and app content read the new context
+        // when they redraw
+        let generation = self.theme_context.generation + 1;
+        self.theme_context = Rc::new(self.theme.context(generation));
+
+        let bg = self.theme_context.palette.live_nodes(&[live_id!(draw_bg)]);
+
+        // Apply to shell background and the view around the dock
+        self.view.apply_over(cx, &bg);
+        self.view.view(id!(main_container.dock_wrapper)).apply_over(cx, &bg);
+
+        // Apply to overlay and pinned sidebars (purple themed)
+        self.apply_overlay_theme(cx);
+
+        // Dock contents keep their own draw lists, so redraw everything
+        cx.redraw_all();
+
+        // Note: Dock splitters use a neutral semi-transparent color
+        // that works in both light and dark modes (can't dynamically theme them)
     }
 
-    /// Apply theme to overlay content (overlay_sidebar)
-    fn apply_overlay_content_theme(&mut self, cx: &mut Cx, dm: f64, _content_id: &[LiveId]) {
-        // We apply to both overlay and pinned sidebar contents
-        // The content_id parameter is no longer used - we explicitly access both
-
-        // Apply to overlay_sidebar content
-        self.view.view(id!(overlay_sidebar.overlay_sidebar_content)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-        });
-        self.view.view(id!(overlay_sidebar.overlay_sidebar_content.overlay_header)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-        });
-        self.view.label(id!(overlay_sidebar.overlay_sidebar_content.overlay_header.overlay_title)).apply_over(cx, live! {
-            draw_text: { dark_mode: (dm) }
-        });
-        self.view.view(id!(overlay_sidebar.overlay_sidebar_content.menu_items.overlay_separator)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-        });
+    /// Apply theme to overlay and pinned sidebars (called when showing overlay)
+    fn apply_overlay_theme(&mut self, cx: &mut Cx) {
+        let palette = self.theme_context.palette.clone();
+        let bg = palette.live_nodes(&[live_id!(draw_bg)]);
 
-        // Menu buttons in overlay_sidebar
-        self.view.button(id!(overlay_sidebar.overlay_sidebar_content.menu_items.btn_new_project)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-            draw_text: { dark_mode: (dm) }
-            draw_icon: { dark_mode: (dm) }
-        });
-        self.view.button(id!(overlay_sidebar.overlay_sidebar_content.menu_items.btn_open_recent)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-            draw_text: { dark_mode: (dm) }
-            draw_icon: { dark_mode: (dm) }
-        });
-        self.view.button(id!(overlay_sidebar.overlay_sidebar_content.menu_items.btn_import_file)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-            draw_text: { dark_mode: (dm) }
-            draw_icon: { dark_mode: (dm) }
-        });
-        self.view.button(id!(overlay_sidebar.overlay_sidebar_content.menu_items.btn_export_data)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-            draw_text: { dark_mode: (dm) }
-            draw_icon: { dark_mode: (dm) }
-        });
-        self.view.button(id!(overlay_sidebar.overlay_sidebar_content.menu_items.btn_preferences)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-            draw_text: { dark_mode: (dm) }
-            draw_icon: { dark_mode: (dm) }
-        });
-        self.view.button(id!(overlay_sidebar.overlay_sidebar_content.menu_items.btn_help)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-            draw_text: { dark_mode: (dm) }
-            draw_icon: { dark_mode: (dm) }
-        });
+        self.view.view(id!(overlay_sidebar)).apply_over(cx, &bg);
+        self.apply_overlay_content_theme(cx, &palette, id!(overlay_sidebar.overlay_sidebar_content));
 
-        // Apply to pinned_sidebar content (same structure)
-        self.view.view(id!(pinned_sidebar.pinned_sidebar_content)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-        });
-        self.view.view(id!(pinned_sidebar.pinned_sidebar_content.overlay_header)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-        });
-        self.view.label(id!(pinned_sidebar.pinned_sidebar_content.overlay_header.overlay_title)).apply_over(cx, live! {
-            draw_text: { dark_mode: (dm) }
-        });
-        self.view.view(id!(pinned_sidebar.pinned_sidebar_content.menu_items.overlay_separator)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-        });
+        self.view.view(id!(pinned_sidebar)).apply_over(cx, &bg);
+        self.apply_overlay_content_theme(cx, &palette, id!(pinned_sidebar.pinned_sidebar_content));
+    }
 
-        // Menu buttons in pinned_sidebar
-        self.view.button(id!(pinned_sidebar.pinned_sidebar_content.menu_items.btn_new_project)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-            draw_text: { dark_mode: (dm) }
-            draw_icon: { dark_mode: (dm) }
-        });
-        self.view.button(id!(pinned_sidebar.pinned_sidebar_content.menu_items.btn_open_recent)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-            draw_text: { dark_mode: (dm) }
-            draw_icon: { dark_mode: (dm) }
-        });
-        self.view.button(id!(pinned_sidebar.pinned_sidebar_content.menu_items.btn_import_file)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-            draw_text: { dark_mode: (dm) }
-            draw_icon: { dark_mode: (dm) }
-        });
-        self.view.button(id!(pinned_sidebar.pinned_sidebar_content.menu_items.btn_export_data)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-            draw_text: { dark_mode: (dm) }
-            draw_icon: { dark_mode: (dm) }
-        });
-        self.view.button(id!(pinned_sidebar.pinned_sidebar_content.menu_items.btn_preferences)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-            draw_text: { dark_mode: (dm) }
-            draw_icon: { dark_mode: (dm) }
-        });
-        self.view.button(id!(pinned_sidebar.pinned_sidebar_content.menu_items.btn_help)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dm) }
-            draw_text: { dark_mode: (dm) }
-            draw_icon: { dark_mode: (dm) }
-        });
+    /// Apply theme to an `OverlaySidebarContent` instance
+    fn apply_overlay_content_theme(&mut self, cx: &mut Cx, palette: &ShellPalette, content_id: &[LiveId]) {
+        let content = self.view.view(content_id);
+        let bg = palette.live_nodes(&[live_id!(draw_bg)]);
+
+        content.apply_over(cx, &bg);
+        content.view(id!(overlay_header)).apply_over(cx, &bg);
+        content.label(id!(overlay_header.overlay_title)).apply_over(cx, &palette.live_nodes(&[live_id!(draw_text)]));
+        content.view(id!(menu_items.overlay_separator)).apply_over(cx, &bg);
+
+        let button = palette.live_nodes(&[live_id!(draw_bg), live_id!(draw_text), live_id!(draw_icon)]);
+        for id in [
+            id!(menu_items.btn_new_project),
+            id!(menu_items.btn_open_recent),
+            id!(menu_items.btn_import_file),
+            id!(menu_items.btn_export_data),
+            id!(menu_items.btn_preferences),
+            id!(menu_items.btn_help),
+        ] {
+            content.button(id).apply_over(cx, &button);
+        }
     }
 
     /// Reset layout to default state
@@ -876,11 +2143,14 @@ impl ShellLayout {
     /// Load preferences from disk and apply
     fn load_preferences(&mut self, cx: &mut Cx) {
         self.preferences = ShellPreferences::load(APP_ID);
+        self.active_workspace = self.preferences.active_workspace.clone()
+            .unwrap_or_else(|| DEFAULT_WORKSPACE.to_string());
 
-        // Apply dark mode preference
-        if self.preferences.dark_mode {
-            self.theme.set_dark_mode(true);
-        }
+        // Apply the theme mode, asking the platform in System mode
+        let mode = self.preferences.theme_mode.unwrap_or(self.config.theme_mode);
+        let dark = self.appearance.set_mode(mode);
+        self.theme.set_dark_mode(dark);
+        self.update_appearance_timer(cx);
 
         // Apply saved layout to PanelGrid and track it
         if let Some(ref layout) = self.preferences.layout {
@@ -893,6 +2163,142 @@ impl ShellLayout {
             self.current_footer_layout = Some(footer_layout.clone());
             self.view.footer_grid(id!(footer_content)).set_layout_state(cx, footer_layout.clone());
         }
+
+        // Restore the layout lock, falling back to the configured one
+        let locked = self.preferences.layout_locked.unwrap_or(self.config.layout_locked);
+        self.apply_layout_lock(cx, locked);
+
+        // Sidebars don't exist until the dock draws them
+        self.restore_sidebar_state = !self.preferences.sidebar_selections.is_empty()
+            || !self.preferences.sidebar_expanded_nodes.is_empty();
+
+        // Load theme files and restore the selected theme
+        self.theme_library = ThemeLibrary::new(ShellPreferences::themes_dir(APP_ID));
+        let events = self.reload_themes(cx);
+        self.emit_events(cx, events);
+        if let Some(name) = self.preferences.theme_name.clone() {
+            if !self.activate_theme(cx, Some(&name)) {
+                let message = format!("Saved theme '{}' not found in {}", name, self.theme_library.dir().display());
+                log!("{}", message);
+                self.notify(cx, Notification::warning("Theme not found").with_message(message));
+            }
+        }
+        if self.config.theme_poll_interval > 0.0 {
+            self.theme_poll_timer = cx.start_interval(self.config.theme_poll_interval);
+        }
+    }
+
+    /// Rescan the themes directory and hot reload the active theme if its file changed
+    ///
+    /// Returns a `ThemeFileError` event for every changed file that failed to load.
+    /// An active theme whose file became invalid or was removed stays applied.
+    fn reload_themes(&mut self, cx: &mut Cx) -> Vec<ShellEvent> {
+        let changed = self.theme_library.refresh();
+        if changed.is_empty() {
+            return Vec::new();
+        }
+
+        let mut events = Vec::new();
+        let mut notices = Vec::new();
+        for path in changed {
+            match self.theme_library.entry(&path).map(|entry| &entry.theme) {
+                Some(Ok(theme)) => {
+                    // Contrast problems are worth a warning but don't reject the theme
+                    for (mode, palette) in [("light", &theme.light), ("dark", &theme.dark)] {
+                        for issue in check_contrast(palette) {
+                            log!("Theme file {} ({}): low contrast, {}", path.display(), mode, issue);
+                        }
+                    }
+                }
+                Some(Err(error)) => {
+                    log!("Invalid theme file {}: {}", path.display(), error);
+                    notices.push(Notification::warning("Invalid theme file")
+                        .with_id(format!("theme_file:{}", path.display()))
+                        .with_message(format!("{}: {}", path.display(), error)));
+                    events.push(ShellEvent::ThemeFileError {
+                        key: error.key.clone(),
+                        message: error.message.clone(),
+                        path,
+                    });
+                }
+                None => {}
+            }
+        }
+        for notice in notices {
+            self.notify(cx, notice);
+        }
+
+        if let Some(definition) = self.active_theme.as_deref().and_then(|name| self.find_theme(name)) {
+            self.apply_theme_definition(cx, &definition);
+        }
+        self.update_theme_picker(cx);
+
+        events
+    }
+
+    /// Switch to a built-in or file-based theme by name (`None` = default theme)
+    ///
+    /// Returns false if no theme has that name. The choice is saved
+    /// to preferences right away.
+    pub fn select_theme(&mut self, cx: &mut Cx, name: Option<&str>) -> bool {
+        if !self.activate_theme(cx, name) {
+            return false;
+        }
+        self.preferences.theme_name = self.active_theme.clone();
+        if let Err(e) = self.preferences.save(APP_ID) {
+            self.report_error(cx, "Failed to save theme selection", &e.to_string());
+        }
+        true
+    }
+
+    fn activate_theme(&mut self, cx: &mut Cx, name: Option<&str>) -> bool {
+        let definition = match name {
+            Some(name) => match self.find_theme(name) {
+                Some(definition) => definition,
+                None => return false,
+            },
+            None => self.default_theme(),
+        };
+
+        self.active_theme = name.map(str::to_string);
+        self.apply_theme_definition(cx, &definition);
+        self.update_theme_picker(cx);
+        true
+    }
+
+    /// Names of the built-in themes followed by the loaded theme files
+    fn theme_names(&self) -> Vec<String> {
+        ThemeDefinition::builtin_themes()
+            .into_iter()
+            .map(|theme| theme.name)
+            .chain(self.theme_library.theme_names())
+            .collect()
+    }
+
+    /// Find a built-in or file-based theme by name
+    fn find_theme(&self, name: &str) -> Option<ThemeDefinition> {
+        ThemeDefinition::builtin_themes()
+            .into_iter()
+            .find(|theme| theme.name == name)
+            .or_else(|| self.theme_library.find(name).cloned())
+    }
+
+    /// The theme used when no named theme is selected
+    fn default_theme(&self) -> ThemeDefinition {
+        self.default_theme.clone().unwrap_or_else(|| ThemeDefinition::builtin(""))
+    }
+
+    fn apply_theme_definition(&mut self, cx: &mut Cx, definition: &ThemeDefinition) {
+        self.theme.set_definition(definition);
+        self.apply_theme(cx);
+        self.view.redraw(cx);
+    }
+
+    fn update_theme_picker(&mut self, cx: &mut Cx) {
+        let names = self.theme_names();
+        let selected = self.active_theme.as_ref()
+            .and_then(|active| names.iter().position(|name| name == active));
+        self.view.shell_header(id!(main_container.header)).set_theme_names(cx, &names, selected);
     }
 
     /// Save current layout to disk
@@ -912,12 +2318,19 @@ impl ShellLayout {
             self.preferences.footer_layout = Some(FooterLayoutState::default());
         }
 
-        // Save dark mode preference
+        // Keep the active workspace entry in sync with the saved layout
+        if let Some(layout) = &self.preferences.layout {
+            self.preferences.workspaces.insert(self.active_workspace.clone(), layout.clone());
+        }
+        self.preferences.active_workspace = Some(self.active_workspace.clone());
+
+        // Save theme mode preference
         self.preferences.dark_mode = self.theme.dark_mode;
+        self.preferences.theme_mode = Some(self.appearance.mode());
 
         // Persist to disk
         if let Err(e) = self.preferences.save(APP_ID) {
-            log!("Failed to save layout: {}", e);
+            self.report_error(cx, "Failed to save layout", &e.to_string());
         }
 
         self.view.redraw(cx);
@@ -928,6 +2341,52 @@ impl ShellLayout {
         &self.config
     }
 
+    /// Replace the shell configuration
+    pub fn set_config(&mut self, cx: &mut Cx, config: ShellConfig) {
+        self.config = config;
+
+        // Resize a settled pinned sidebar to the new width
+        if self.sidebar_pinned && !self.sidebar_pin_animating {
+            let width = self.config.overlay_sidebar_width;
+            self.view.view(id!(pinned_sidebar)).apply_over(cx, live! { width: (width) });
+            self.view.view(id!(main_container.dock_wrapper)).apply_over(cx, live! { margin: { left: (width) } });
+        }
+
+        // Resize and reposition an open overlay (a running animation picks up the new width)
+        if self.overlay_showing {
+            self.apply_sidebar_offsets(cx);
+            if !self.overlay_animating {
+                let width = self.config.overlay_sidebar_width;
+                self.view.view(id!(overlay_sidebar)).apply_over(cx, live! { width: (width) });
+            }
+        }
+
+        self.view.shell_header(id!(main_container.header)).set_reduce_motion(cx, self.config.reduce_motion);
+        self.view.shell_footer(id!(main_container.status_bar)).set_reduce_motion(cx, self.config.reduce_motion);
+        self.view.shell_notifications(id!(notifications)).set_reduce_motion(cx, self.config.reduce_motion);
+        self.view.panel_grid(id!(center_content)).set_reduce_motion(cx, self.config.reduce_motion);
+        self.view.panel_grid(id!(center_content)).set_confirm_close_panels(self.config.confirm_close_panels.clone());
+        self.view.footer_grid(id!(footer_content)).set_confirm_close_panels(self.config.confirm_close_panels.clone());
+        if self.config.reduce_motion && self.dark_mode_animating {
+            // Jump to the end of a running theme transition
+            self.dark_mode_animating = false;
+            let dark = self.theme.dark_mode;
+            self.theme.set_dark_mode(dark);
+            self.apply_theme(cx);
+        }
+        if self.initialized {
+            self.update_appearance_timer(cx);
+        }
+        self.view.redraw(cx);
+    }
+
+    /// Turn the theme, sidebar, header button and panel move animations off (true) or on
+    pub fn set_reduce_motion(&mut self, cx: &mut Cx, reduce_motion: bool) {
+        let mut config = self.config.clone();
+        config.reduce_motion = reduce_motion;
+        self.set_config(cx, config);
+    }
+
     /// Get the current theme
     pub fn theme(&self) -> &ShellTheme {
         &self.theme
@@ -954,6 +2413,65 @@ impl ShellLayoutRef {
         self.borrow().map(|inner| inner.is_dark_mode()).unwrap_or(false)
     }
 
+    /// Switch between light, dark and following the platform appearance
+    pub fn set_theme_mode(&self, cx: &mut Cx, mode: ThemeMode) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_theme_mode(cx, mode);
+        }
+    }
+
+    /// Current theme mode
+    pub fn theme_mode(&self) -> ThemeMode {
+        self.borrow().map(|inner| inner.theme_mode()).unwrap_or_default()
+    }
+
+    /// Replace the platform appearance source followed in `ThemeMode::System`
+    pub fn set_appearance_source(&self, cx: &mut Cx, source: impl AppearanceSource + 'static) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_appearance_source(cx, source);
+        }
+    }
+
+    /// Replace the light and dark palettes of the default theme (e.g. with a brand theme)
+    pub fn set_palettes(&self, cx: &mut Cx, light: ShellPalette, dark: ShellPalette) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_palettes(cx, light, dark);
+        }
+    }
+
+    /// Switch to a built-in or file-based theme by name (`None` = default theme)
+    ///
+    /// Returns false if no theme has that name.
+    pub fn select_theme(&self, cx: &mut Cx, name: Option<&str>) -> bool {
+        if let Some(mut inner) = self.borrow_mut() {
+            return inner.select_theme(cx, name);
+        }
+        false
+    }
+
+    /// Name of the active built-in or file-based theme (`None` = default theme)
+    pub fn active_theme(&self) -> Option<String> {
+        self.borrow().and_then(|inner| inner.active_theme.clone())
+    }
+
+    /// Names of the built-in themes and loaded theme files, in picker order
+    pub fn theme_names(&self) -> Vec<String> {
+        self.borrow().map(|inner| inner.theme_names()).unwrap_or_default()
+    }
+
+    /// Rescan the themes directory now instead of waiting for the next poll
+    pub fn reload_themes(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            let events = inner.reload_themes(cx);
+            inner.emit_events(cx, events);
+        }
+    }
+
+    /// The palette currently applied (blended during theme transitions)
+    pub fn palette(&self) -> ShellPalette {
+        self.borrow().map(|inner| inner.theme.palette()).unwrap_or_default()
+    }
+
     /// Apply dark mode value directly
     pub fn apply_dark_mode(&self, cx: &mut Cx, dark_mode: f64) {
         if let Some(mut inner) = self.borrow_mut() {
@@ -961,4 +2479,373 @@ impl ShellLayoutRef {
             inner.apply_theme(cx);
         }
     }
+
+    /// Switch to another named workspace
+    pub fn switch_workspace(&self, cx: &mut Cx, name: &str) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.switch_workspace(cx, name);
+        }
+    }
+
+    /// Name of the active workspace
+    pub fn active_workspace(&self) -> Option<String> {
+        self.borrow().map(|inner| inner.active_workspace().to_string())
+    }
+
+    /// Delete a saved workspace, asking first unless confirmations are off
+    pub fn delete_workspace(&self, cx: &mut Cx, name: &str) -> bool {
+        self.borrow_mut().map_or(false, |mut inner| inner.delete_workspace(cx, name))
+    }
+
+    /// Replace the shell configuration
+    pub fn set_config(&self, cx: &mut Cx, config: ShellConfig) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_config(cx, config);
+        }
+    }
+
+    /// Turn the theme, sidebar, header button and panel move animations off (true) or on
+    pub fn set_reduce_motion(&self, cx: &mut Cx, reduce_motion: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_reduce_motion(cx, reduce_motion);
+        }
+    }
+
+    /// Show or hide the overlay sidebar (stays open until dismissed)
+    pub fn show_overlay_sidebar(&self, cx: &mut Cx, visible: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_overlay_visible(cx, visible, true);
+        }
+    }
+
+    /// Register the views selectable from a sidebar's activity bar
+    pub fn set_sidebar_views(&self, cx: &mut Cx, side: SidebarSide, views: Vec<SidebarView>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_sidebar_views(cx, side, views);
+        }
+    }
+
+    /// Show a sidebar view, or collapse the sidebar with `None`
+    pub fn set_active_sidebar_view(&self, cx: &mut Cx, side: SidebarSide, view_id: Option<&str>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_active_sidebar_view(cx, side, view_id);
+        }
+    }
+
+    /// Active view of a sidebar (`None` while collapsed or without views)
+    pub fn active_sidebar_view(&self, side: SidebarSide) -> Option<String> {
+        self.borrow().and_then(|inner| inner.active_sidebar_view(side).map(|id| id.to_string()))
+    }
+
+    /// Replace the app items of the header toolbar
+    pub fn set_toolbar_items(&self, cx: &mut Cx, items: Vec<ToolbarItem>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_toolbar_items(cx, items);
+        }
+    }
+
+    /// Set the checked state of a toggle toolbar item
+    pub fn set_toolbar_item_checked(&self, cx: &mut Cx, id: &str, checked: bool) -> bool {
+        self.borrow_mut().map_or(false, |mut inner| inner.set_toolbar_item_checked(cx, id, checked))
+    }
+
+    /// Play the click flash on a toolbar item
+    pub fn flash_toolbar_item(&self, cx: &mut Cx, id: &str) -> bool {
+        self.borrow_mut().map_or(false, |mut inner| inner.flash_toolbar_item(cx, id))
+    }
+
+    /// Add a status bar item, or replace the item with the same ID
+    pub fn set_status_item(&self, cx: &mut Cx, item: StatusItem) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_status_item(cx, item);
+        }
+    }
+
+    /// Remove a status bar item
+    pub fn remove_status_item(&self, cx: &mut Cx, id: &str) -> bool {
+        self.borrow_mut().map_or(false, |mut inner| inner.remove_status_item(cx, id))
+    }
+
+    /// Change the text of a status bar item
+    pub fn set_status_item_text(&self, cx: &mut Cx, id: &str, text: &str) -> bool {
+        self.borrow_mut().map_or(false, |mut inner| inner.set_status_item_text(cx, id, text))
+    }
+
+    /// Show (`Some`) or hide the progress spinner of a status bar item
+    pub fn set_status_item_progress(&self, cx: &mut Cx, id: &str, progress: Option<StatusProgress>) -> bool {
+        self.borrow_mut().map_or(false, |mut inner| inner.set_status_item_progress(cx, id, progress))
+    }
+
+    /// Show a notification toast, replacing the one with the same ID
+    pub fn notify(&self, cx: &mut Cx, notification: Notification) -> Option<String> {
+        self.borrow_mut().map(|mut inner| inner.notify(cx, notification))
+    }
+
+    /// Set the progress of a progress notification (`None` = busy)
+    pub fn set_notification_progress(&self, cx: &mut Cx, id: &str, progress: Option<f64>) -> bool {
+        self.borrow_mut().map_or(false, |mut inner| inner.set_notification_progress(cx, id, progress))
+    }
+
+    /// Close a notification toast
+    pub fn dismiss_notification(&self, cx: &mut Cx, id: &str) -> bool {
+        self.borrow_mut().map_or(false, |mut inner| inner.dismiss_notification(cx, id))
+    }
+
+    /// Open or close the notification center
+    pub fn show_notification_center(&self, cx: &mut Cx, show: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.show_notification_center(cx, show);
+        }
+    }
+
+    /// Notification history, oldest first
+    pub fn notification_history(&self) -> Vec<NotificationRecord> {
+        self.borrow().map(|inner| inner.notification_history()).unwrap_or_default()
+    }
+
+    /// Forget the notification history
+    pub fn clear_notifications(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.clear_notifications(cx);
+        }
+    }
+
+    /// Show a modal dialog, or queue it behind the one showing
+    pub fn open_dialog(&self, cx: &mut Cx, dialog: Dialog) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.open_dialog(cx, dialog);
+        }
+    }
+
+    /// Cancel the showing dialog
+    pub fn close_dialog(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.close_dialog(cx);
+        }
+    }
+
+    /// Whether a dialog is showing
+    pub fn is_dialog_open(&self) -> bool {
+        self.borrow().map_or(false, |inner| inner.is_dialog_open())
+    }
+
+    /// Body widget of the showing custom dialog
+    pub fn dialog_body(&self) -> WidgetRef {
+        self.borrow().map(|inner| inner.dialog_body()).unwrap_or_default()
+    }
+
+    /// Replace the header menu bar contents
+    pub fn set_menu_model(&self, cx: &mut Cx, model: MenuModel) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_menu_model(cx, model);
+        }
+    }
+
+    /// Current menu bar contents
+    pub fn menu_model(&self) -> MenuModel {
+        self.borrow().map(|inner| inner.menu_model()).unwrap_or_default()
+    }
+
+    /// Set the checked state of a checkable menu item
+    pub fn set_menu_item_checked(&self, id: &str, checked: bool) -> bool {
+        self.borrow_mut().map_or(false, |mut inner| inner.set_menu_item_checked(id, checked))
+    }
+
+    /// Enable or disable a menu item
+    pub fn set_menu_item_enabled(&self, cx: &mut Cx, id: &str, enabled: bool) -> bool {
+        self.borrow_mut().map_or(false, |mut inner| inner.set_menu_item_enabled(cx, id, enabled))
+    }
+
+    /// Set the panel definitions: titles, title bar actions and context menu entries
+    pub fn set_panel_registry(&self, cx: &mut Cx, registry: PanelRegistry) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_panel_registry(cx, registry);
+        }
+    }
+
+    /// Set the checked state of a panel's toggle title action
+    pub fn set_panel_title_action_checked(&self, cx: &mut Cx, panel_id: &str, action_id: &str, checked: bool) -> bool {
+        self.borrow_mut().map_or(false, |mut inner| inner.set_panel_title_action_checked(cx, panel_id, action_id, checked))
+    }
+
+    /// Pin or unpin a panel; false if it is unknown, pinned by its definition or unchanged
+    pub fn set_panel_pinned(&self, cx: &mut Cx, panel_id: &str, pinned: bool) -> bool {
+        let Some(mut inner) = self.borrow_mut() else { return false };
+        let events = inner.set_panel_pinned(cx, panel_id, pinned);
+        let changed = !events.is_empty();
+        inner.emit_events(cx, events);
+        changed
+    }
+
+    /// Whether a panel is pinned by the user or by its definition
+    pub fn is_panel_pinned(&self, panel_id: &str) -> bool {
+        self.borrow().map_or(false, |inner| inner.is_panel_pinned(panel_id))
+    }
+
+    /// Show a closed panel again; false if it isn't closed (or the footer is full)
+    pub fn reopen_panel(&self, cx: &mut Cx, panel_id: &str) -> bool {
+        let Some(mut inner) = self.borrow_mut() else { return false };
+        let events = inner.reopen_panel(cx, panel_id);
+        let changed = !events.is_empty();
+        inner.emit_events(cx, events);
+        changed
+    }
+
+    /// Closed panels of the main grid and the footer
+    pub fn closed_panels(&self) -> Vec<PanelId> {
+        self.borrow().map(|inner| inner.closed_panels().into_iter().map(|panel| panel.id).collect()).unwrap_or_default()
+    }
+
+    /// Show the "Reopen Panel" menu at an absolute position (e.g. below a toolbar button)
+    pub fn open_reopen_menu(&self, cx: &mut Cx, abs: DVec2) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.open_reopen_menu(cx, abs);
+        }
+    }
+
+    /// Lock (true) or unlock the layout: no panel drag or close, no dock resize
+    pub fn set_layout_locked(&self, cx: &mut Cx, locked: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_layout_locked(cx, locked);
+        }
+    }
+
+    /// Whether the layout is locked
+    pub fn is_layout_locked(&self) -> bool {
+        self.borrow().map_or(false, |inner| inner.is_layout_locked())
+    }
+
+    /// Set (or clear with None) the app hook that can forbid drops in the main grid and footer
+    pub fn set_drop_validator(&self, validator: Option<DropValidator>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_drop_validator(validator);
+        }
+    }
+
+    /// Returns the panel ID and command of a chosen panel context menu entry
+    pub fn panel_menu_command(&self, actions: &Actions) -> Option<(PanelId, PanelMenuCommand)> {
+        actions
+            .filter_widget_actions(self.widget_uid())
+            .into_iter()
+            .flatten()
+            .find_map(|action| match action.cast::<PanelAction>() {
+                PanelAction::MenuCommand { panel_id, command } => Some((panel_id, command)),
+                _ => None,
+            })
+    }
+
+    /// Get all `ShellEvent`s emitted by this shell in the given actions
+    pub fn shell_events(&self, actions: &Actions) -> Vec<ShellEvent> {
+        actions
+            .filter_widget_actions(self.widget_uid())
+            .into_iter()
+            .flatten()
+            .map(|action| action.cast::<ShellEvent>())
+            .filter(|event| !matches!(event, ShellEvent::None))
+            .collect()
+    }
+
+    /// Returns the ID of the panel that gained focus, if any
+    pub fn panel_focused(&self, actions: &Actions) -> Option<String> {
+        self.shell_events(actions).into_iter().find_map(|event| match event {
+            ShellEvent::PanelFocused(id) => Some(id),
+            _ => None,
+        })
+    }
+
+    /// Returns the ID of a panel that was opened, if any
+    pub fn panel_opened(&self, actions: &Actions) -> Option<String> {
+        self.shell_events(actions).into_iter().find_map(|event| match event {
+            ShellEvent::PanelOpened(id) => Some(id),
+            _ => None,
+        })
+    }
+
+    /// Returns the ID of a panel that was closed, if any
+    pub fn panel_closed(&self, actions: &Actions) -> Option<String> {
+        self.shell_events(actions).into_iter().find_map(|event| match event {
+            ShellEvent::PanelClosed(id) => Some(id),
+            _ => None,
+        })
+    }
+
+    /// Returns the new dark mode state if the theme was toggled
+    pub fn theme_changed(&self, actions: &Actions) -> Option<bool> {
+        self.shell_events(actions).into_iter().find_map(|event| match event {
+            ShellEvent::ThemeChanged { dark_mode } => Some(dark_mode),
+            _ => None,
+        })
+    }
+
+    /// Returns the new sidebar selection if it changed
+    pub fn sidebar_selection_changed(&self, actions: &Actions) -> Option<Option<SidebarSelection>> {
+        self.shell_events(actions).into_iter().find_map(|event| match event {
+            ShellEvent::SidebarSelectionChanged(selection) => Some(selection),
+            _ => None,
+        })
+    }
+
+    /// Returns the side and new view if a sidebar view changed (`None` view = collapsed)
+    pub fn sidebar_view_changed(&self, actions: &Actions) -> Option<(SidebarSide, Option<String>)> {
+        self.shell_events(actions).into_iter().find_map(|event| match event {
+            ShellEvent::SidebarViewChanged { side, view_id } => Some((side, view_id)),
+            _ => None,
+        })
+    }
+
+    /// Returns the ID and new checked state of an activated toolbar item
+    pub fn toolbar_item_activated(&self, actions: &Actions) -> Option<(String, Option<bool>)> {
+        self.shell_events(actions).into_iter().find_map(|event| match event {
+            ShellEvent::ToolbarItemActivated { id, checked } => Some((id, checked)),
+            _ => None,
+        })
+    }
+
+    /// Returns the ID of a clicked status bar item, if any
+    pub fn status_item_clicked(&self, actions: &Actions) -> Option<String> {
+        self.shell_events(actions).into_iter().find_map(|event| match event {
+            ShellEvent::StatusItemClicked(id) => Some(id),
+            _ => None,
+        })
+    }
+
+    /// Returns the panel ID, action ID and new checked state of a clicked title bar action
+    pub fn panel_title_action(&self, actions: &Actions) -> Option<(String, String, Option<bool>)> {
+        self.shell_events(actions).into_iter().find_map(|event| match event {
+            ShellEvent::PanelTitleAction { panel_id, action_id, checked } => Some((panel_id, action_id, checked)),
+            _ => None,
+        })
+    }
+
+    /// Returns the ID and result of a dialog that closed
+    pub fn dialog_closed(&self, actions: &Actions) -> Option<(String, DialogResult)> {
+        self.shell_events(actions).into_iter().find_map(|event| match event {
+            ShellEvent::DialogClosed { id, result } => Some((id, result)),
+            _ => None,
+        })
+    }
+
+    /// Returns the notification ID and action ID of a clicked toast action button
+    pub fn notification_action(&self, actions: &Actions) -> Option<(String, String)> {
+        self.shell_events(actions).into_iter().find_map(|event| match event {
+            ShellEvent::NotificationAction { notification_id, action_id } => Some((notification_id, action_id)),
+            _ => None,
+        })
+    }
+
+    /// Returns the ID and new checked state of a chosen menu bar item
+    pub fn menu_selected(&self, actions: &Actions) -> Option<(String, Option<bool>)> {
+        self.shell_events(actions).into_iter().find_map(|event| match event {
+            ShellEvent::MenuSelected { id, checked } => Some((id, checked)),
+            _ => None,
+        })
+    }
+
+    /// Returns the name of the workspace that was switched to, if any
+    pub fn workspace_switched(&self, actions: &Actions) -> Option<String> {
+        self.shell_events(actions).into_iter().find_map(|event| match event {
+            ShellEvent::WorkspaceSwitched(name) => Some(name),
+            _ => None,
+        })
+    }
 }
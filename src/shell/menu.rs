@@ -0,0 +1,882 @@
+//! Menu model and popup menus
+//!
+//! A `MenuModel` is a serializable list of top-level menus, each a
+//! `MenuItem` whose `submenu` holds its entries. Entries can nest further
+//! submenus, separators, checkable items, disabled items, shortcut hints and
+//! a badge or "modified" dot after the label.
+//!
+//! `ShellMenu` shows a list of entries in an overlay at an absolute position
+//! (e.g. below a menu bar title or a toolbar button), cascading submenus to
+//! the right. It reports the chosen item and closes when an item is chosen,
+//! on Escape and on a click outside of it.
+//!
+//! ## Keyboard
+//!
+//! | Key | Action |
+//! |-----|--------|
+//! | Up / Down, Home / End | Move between enabled items |
+//! | Right | Open the submenu (or move to the next menu bar menu) |
+//! | Left | Close the submenu (or move to the previous menu bar menu) |
+//! | Enter / Space | Choose the item or open its submenu |
+//! | Escape | Close the innermost menu |
+
+use makepad_widgets::*;
+use serde::{Deserialize, Serialize};
+use crate::shell::sidebar_menu::svg_icon_value;
+use crate::theme::current_palette;
+
+live_design! {
+    use link::theme::*;
+    use link::shaders::*;
+    use link::widgets::*;
+
+    use crate::live_design::*;
+
+    // Item row: check mark, icon, label, modified dot, badge, shortcut hint and submenu arrow
+    pub ShellMenuRow = <View> {
+        width: Fill, height: Fit
+        padding: { top: 5, bottom: 5, left: 6, right: 8 }
+        flow: Right
+        align: { y: 0.5 }
+        spacing: 6
+
+        show_bg: true
+        draw_bg: {
+            instance highlighted: 0.0
+            uniform bg_hover: (PALETTE_BG_HOVER)
+
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 4.0);
+                sdf.fill(vec4(self.bg_hover.xyz, self.highlighted));
+                return sdf.result;
+            }
+        }
+
+        check = <View> {
+            width: 12, height: 12
+            show_bg: true
+            draw_bg: {
+                instance checked: 0.0
+                instance disabled: 0.0
+                uniform text_primary: (PALETTE_TEXT_PRIMARY)
+                uniform text_faint: (PALETTE_TEXT_FAINT)
+
+                fn pixel(self) -> vec4 {
+                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                    sdf.move_to(2.0, 6.5);
+                    sdf.line_to(5.0, 9.5);
+                    sdf.line_to(10.0, 3.0);
+                    sdf.stroke(mix(self.text_primary, self.text_faint, self.disabled), 1.5);
+                    return sdf.result * self.checked;
+                }
+            }
+        }
+
+        icon = <Icon> {
+            visible: false
+            icon_walk: { width: 14, height: 14 }
+            draw_icon: {
+                uniform icon: (PALETTE_ICON)
+                fn get_color(self) -> vec4 {
+                    return self.icon;
+                }
+            }
+        }
+
+        label = <Label> {
+            width: Fill
+            draw_text: {
+                instance disabled: 0.0
+                uniform text_primary: (PALETTE_TEXT_PRIMARY)
+                uniform text_faint: (PALETTE_TEXT_FAINT)
+                text_style: <FONT_REGULAR>{ font_size: 10.0 }
+                fn get_color(self) -> vec4 {
+                    return mix(self.text_primary, self.text_faint, self.disabled);
+                }
+            }
+            text: ""
+        }
+
+        dirty_dot = <View> {
+            width: 6, height: 6
+            visible: false
+
+            show_bg: true
+            draw_bg: {
+                uniform text_secondary: (PALETTE_TEXT_SECONDARY)
+                fn pixel(self) -> vec4 {
+                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                    sdf.circle(self.rect_size.x * 0.5, self.rect_size.y * 0.5, self.rect_size.x * 0.5);
+                    sdf.fill(self.text_secondary);
+                    return sdf.result;
+                }
+            }
+        }
+
+        badge = <View> {
+            width: Fit, height: Fit
+            padding: { left: 5, right: 5, top: 1, bottom: 1 }
+            visible: false
+
+            show_bg: true
+            draw_bg: {
+                uniform accent: (PALETTE_ACCENT)
+                fn pixel(self) -> vec4 {
+                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                    sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, self.rect_size.y * 0.5);
+                    sdf.fill(self.accent);
+                    return sdf.result;
+                }
+            }
+
+            badge_label = <Label> {
+                draw_text: {
+                    uniform on_accent: (PALETTE_ON_ACCENT)
+                    text_style: <FONT_SEMIBOLD> { font_size: 8.0 }
+                    fn get_color(self) -> vec4 {
+                        return self.on_accent;
+                    }
+                }
+                text: ""
+            }
+        }
+
+        shortcut = <Label> {
+            draw_text: {
+                uniform text_muted: (PALETTE_TEXT_MUTED)
+                text_style: <FONT_REGULAR>{ font_size: 9.0 }
+                fn get_color(self) -> vec4 {
+                    return self.text_muted;
+                }
+            }
+            text: ""
+        }
+
+        arrow = <View> {
+            width: 8, height: 10
+            show_bg: true
+            draw_bg: {
+                instance has_submenu: 0.0
+                uniform text_muted: (PALETTE_TEXT_MUTED)
+
+                fn pixel(self) -> vec4 {
+                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                    sdf.move_to(2.0, 1.0);
+                    sdf.line_to(6.0, 5.0);
+                    sdf.line_to(2.0, 9.0);
+                    sdf.stroke(self.text_muted, 1.2);
+                    return sdf.result * self.has_submenu;
+                }
+            }
+        }
+    }
+
+    pub ShellMenuSeparator = <View> {
+        width: Fill, height: 9
+        show_bg: true
+        draw_bg: {
+            uniform border: (PALETTE_BORDER)
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.rect(4.0, 4.0, self.rect_size.x - 8.0, 1.0);
+                sdf.fill(self.border);
+                return sdf.result;
+            }
+        }
+    }
+
+    pub ShellMenu = {{ShellMenu}} {
+        width: 220, height: Fit
+        flow: Down
+        padding: 4
+
+        draw_bg: {
+            uniform bg_panel: (PALETTE_BG_PANEL)
+            uniform border: (PALETTE_BORDER)
+
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 6.0);
+                sdf.fill_keep(self.bg_panel);
+                sdf.stroke(self.border, 1.0);
+                return sdf.result;
+            }
+        }
+
+        row_template: <ShellMenuRow> {}
+        separator_template: <ShellMenuSeparator> {}
+    }
+}
+
+/// A menu item
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MenuItem {
+    /// Item ID reported when the item is chosen
+    pub id: String,
+
+    /// Display label
+    pub label: String,
+
+    /// SVG icon path
+    #[serde(default)]
+    pub icon: Option<String>,
+
+    /// Shortcut hint shown on the right (e.g. "Ctrl+S"); display only
+    #[serde(default)]
+    pub shortcut: Option<String>,
+
+    /// Count or short text shown after the label (e.g. "3" errors)
+    #[serde(default)]
+    pub badge: Option<String>,
+
+    /// Show a "modified" dot after the label
+    #[serde(default)]
+    pub dirty: bool,
+
+    /// Checked state of a checkable item (`None` = not checkable)
+    #[serde(default)]
+    pub checked: Option<bool>,
+
+    /// Whether the item can be chosen
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Submenu entries (empty = the item is chosen directly)
+    #[serde(default)]
+    pub submenu: Vec<MenuEntry>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl MenuItem {
+    /// Create a new menu item
+    pub fn new(id: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            label: label.into(),
+            icon: None,
+            shortcut: None,
+            badge: None,
+            dirty: false,
+            checked: None,
+            enabled: true,
+            submenu: Vec::new(),
+        }
+    }
+
+    /// Set the SVG icon path
+    pub fn with_icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Set the shortcut hint
+    pub fn with_shortcut(mut self, shortcut: impl Into<String>) -> Self {
+        self.shortcut = Some(shortcut.into());
+        self
+    }
+
+    /// Set the badge shown after the label
+    pub fn with_badge(mut self, badge: impl Into<String>) -> Self {
+        self.badge = Some(badge.into());
+        self
+    }
+
+    /// Show or hide the "modified" dot
+    pub fn with_dirty(mut self, dirty: bool) -> Self {
+        self.dirty = dirty;
+        self
+    }
+
+    /// Make the item checkable with an initial state
+    pub fn checkable(mut self, checked: bool) -> Self {
+        self.checked = Some(checked);
+        self
+    }
+
+    /// Disable the item
+    pub fn disabled(mut self) -> Self {
+        self.enabled = false;
+        self
+    }
+
+    /// Set the submenu entries
+    pub fn with_submenu(mut self, submenu: Vec<MenuEntry>) -> Self {
+        self.submenu = submenu;
+        self
+    }
+}
+
+/// An entry of a menu: an item or a separator line
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MenuEntry {
+    Item(MenuItem),
+    Separator,
+}
+
+impl MenuEntry {
+    /// The item of an item entry
+    pub fn item(&self) -> Option<&MenuItem> {
+        match self {
+            MenuEntry::Item(item) => Some(item),
+            MenuEntry::Separator => None,
+        }
+    }
+
+    /// Whether the keyboard cursor can rest on this entry
+    pub fn is_selectable(&self) -> bool {
+        self.item().map_or(false, |item| item.enabled)
+    }
+}
+
+impl From<MenuItem> for MenuEntry {
+    fn from(item: MenuItem) -> Self {
+        MenuEntry::Item(item)
+    }
+}
+
+/// Find an item by ID in a list of entries and their submenus
+pub fn find_item<'a>(entries: &'a [MenuEntry], id: &str) -> Option<&'a MenuItem> {
+    entries.iter().filter_map(MenuEntry::item).find_map(|item| {
+        if item.id == id {
+            Some(item)
+        } else {
+            find_item(&item.submenu, id)
+        }
+    })
+}
+
+/// Find an item by ID in a list of entries and their submenus
+pub fn find_item_mut<'a>(entries: &'a mut [MenuEntry], id: &str) -> Option<&'a mut MenuItem> {
+    for entry in entries {
+        if let MenuEntry::Item(item) = entry {
+            if item.id == id {
+                return Some(item);
+            }
+            if let Some(found) = find_item_mut(&mut item.submenu, id) {
+                return Some(found);
+            }
+        }
+    }
+    None
+}
+
+/// Index of the next selectable entry after (or before) `from`, wrapping around
+///
+/// With `from` = `None` this is the first (or last) selectable entry.
+pub fn next_selectable(entries: &[MenuEntry], from: Option<usize>, forward: bool) -> Option<usize> {
+    let len = entries.len();
+    (1..=len)
+        .map(|step| match (from, forward) {
+            (Some(from), true) => (from + step) % len,
+            (Some(from), false) => (from + len * 2 - step) % len,
+            (None, true) => step - 1,
+            (None, false) => len - step,
+        })
+        .find(|&index| entries[index].is_selectable())
+}
+
+/// Serializable menu bar contents: top-level menus with their entries
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct MenuModel {
+    /// Top-level menus; one without a submenu is chosen directly
+    pub menus: Vec<MenuItem>,
+}
+
+impl MenuModel {
+    /// Create a model from top-level menus
+    pub fn new(menus: Vec<MenuItem>) -> Self {
+        Self { menus }
+    }
+
+    /// Find a menu or item by ID
+    pub fn find(&self, id: &str) -> Option<&MenuItem> {
+        self.menus.iter().find_map(|menu| {
+            if menu.id == id {
+                Some(menu)
+            } else {
+                find_item(&menu.submenu, id)
+            }
+        })
+    }
+
+    /// Find a menu or item by ID
+    pub fn find_mut(&mut self, id: &str) -> Option<&mut MenuItem> {
+        for menu in &mut self.menus {
+            if menu.id == id {
+                return Some(menu);
+            }
+            if let Some(found) = find_item_mut(&mut menu.submenu, id) {
+                return Some(found);
+            }
+        }
+        None
+    }
+
+    /// Set the checked state of a checkable item
+    ///
+    /// Returns false if no checkable item has this ID.
+    pub fn set_checked(&mut self, id: &str, checked: bool) -> bool {
+        match self.find_mut(id) {
+            Some(item) if item.checked.is_some() => {
+                item.checked = Some(checked);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Enable or disable an item
+    ///
+    /// Returns false if no item has this ID.
+    pub fn set_enabled(&mut self, id: &str, enabled: bool) -> bool {
+        self.find_mut(id).map(|item| item.enabled = enabled).is_some()
+    }
+}
+
+/// Actions emitted by `ShellMenu`
+#[derive(Clone, Debug, DefaultNone)]
+pub enum ShellMenuAction {
+    /// An item was chosen (item ID)
+    Selected(String),
+
+    /// The menu closed without a choice (Escape or click outside)
+    Dismissed,
+
+    /// Left was pressed in the root menu (previous menu bar menu)
+    NavigateLeft,
+
+    /// Right was pressed on an item without a submenu (next menu bar menu)
+    NavigateRight,
+
+    None,
+}
+
+/// One open menu panel: the root menu or a submenu
+struct MenuLevel {
+    entries: Vec<MenuEntry>,
+    rows: Vec<WidgetRef>,
+    position: DVec2,
+    cursor: Option<usize>,
+    area: Area,
+}
+
+/// Popup menu with cascading submenus, drawn in an overlay
+#[derive(Live, LiveHook, Widget)]
+pub struct ShellMenu {
+    #[live]
+    draw_list: DrawList2d,
+
+    #[redraw]
+    #[live]
+    draw_bg: DrawQuad,
+
+    #[walk]
+    walk: Walk,
+
+    #[layout]
+    layout: Layout,
+
+    #[live]
+    row_template: Option<LivePtr>,
+
+    #[live]
+    separator_template: Option<LivePtr>,
+
+    /// Open menu panels, root first (empty = closed)
+    #[rust]
+    levels: Vec<MenuLevel>,
+
+    /// Clicks inside this rect (usually the opening button) don't dismiss the menu
+    #[rust]
+    anchor: Rect,
+
+    /// Actions raised while handling input, emitted at the end of `handle_event`
+    #[rust]
+    pending_actions: Vec<ShellMenuAction>,
+}
+
+impl Widget for ShellMenu {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if self.levels.is_empty() {
+            return;
+        }
+
+        // Pointer: hovering moves the cursor (and opens submenus), a click chooses
+        let mut hovered = None;
+        let mut clicked = None;
+        for (depth, level) in self.levels.iter().enumerate() {
+            for (index, row) in level.rows.iter().enumerate() {
+                if !level.entries[index].is_selectable() {
+                    continue;
+                }
+                match event.hits(cx, row.area()) {
+                    Hit::FingerHoverIn(_) => hovered = Some((depth, index)),
+                    Hit::FingerUp(fe) if fe.is_over => clicked = Some((depth, index)),
+                    _ => {}
+                }
+            }
+        }
+        if let Some((depth, index)) = hovered {
+            self.move_cursor(cx, depth, Some(index));
+            self.open_submenu(cx, false);
+        }
+        if let Some((depth, index)) = clicked {
+            self.move_cursor(cx, depth, Some(index));
+            self.activate(cx, false);
+        }
+
+        match event {
+            Event::KeyDown(ke) => self.handle_key(cx, ke),
+            Event::MouseDown(e) => {
+                let inside = self.anchor.contains(e.abs)
+                    || self.levels.iter().any(|level| level.area.rect(cx).contains(e.abs));
+                if !inside {
+                    self.close(cx);
+                    self.pending_actions.push(ShellMenuAction::Dismissed);
+                }
+            }
+            _ => {}
+        }
+
+        let uid = self.widget_uid();
+        for action in std::mem::take(&mut self.pending_actions) {
+            cx.widget_action(uid, &scope.path, action);
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, _walk: Walk) -> DrawStep {
+        if self.levels.is_empty() {
+            return DrawStep::done();
+        }
+        let palette = current_palette();
+        self.draw_bg.apply_over(cx, &palette.uniform_nodes());
+        let bg = palette.live_nodes(&[live_id!(draw_bg)]);
+        let text = palette.live_nodes(&[live_id!(draw_text)]);
+        let icon = palette.live_nodes(&[live_id!(draw_icon)]);
+
+        // Draw above everything else, independent of the parent's layout
+        self.draw_list.begin_overlay_reuse(cx);
+        cx.begin_pass_sized_turtle(Layout::flow_down());
+        for level in &mut self.levels {
+            let walk = Walk { abs_pos: Some(level.position), ..self.walk };
+            self.draw_bg.begin(cx, walk, self.layout);
+            for row in &level.rows {
+                row.apply_over(cx, &bg);
+                row.view(id!(check)).apply_over(cx, &bg);
+                row.view(id!(arrow)).apply_over(cx, &bg);
+                row.widget(id!(icon)).apply_over(cx, &icon);
+                row.label(id!(label)).apply_over(cx, &text);
+                row.view(id!(dirty_dot)).apply_over(cx, &bg);
+                row.view(id!(badge)).apply_over(cx, &bg);
+                row.label(id!(badge.badge_label)).apply_over(cx, &text);
+                row.label(id!(shortcut)).apply_over(cx, &text);
+                row.draw_all(cx, scope);
+            }
+            self.draw_bg.end(cx);
+            level.area = self.draw_bg.area();
+        }
+        cx.end_pass_sized_turtle();
+        self.draw_list.end(cx);
+
+        DrawStep::done()
+    }
+}
+
+impl ShellMenu {
+    /// Show `entries` with the menu's top-left corner at `position`
+    ///
+    /// Clicks inside `anchor` (usually the button that opened the menu) are
+    /// left to the opener, so it can close the menu on a second click.
+    pub fn open(&mut self, cx: &mut Cx, entries: Vec<MenuEntry>, position: DVec2, anchor: Rect) {
+        self.levels.clear();
+        self.anchor = anchor;
+        self.push_level(cx, entries, position);
+    }
+
+    /// Put the keyboard cursor on the first enabled entry of the root menu
+    pub fn select_first(&mut self, cx: &mut Cx) {
+        if let Some(root) = self.levels.first() {
+            let first = next_selectable(&root.entries, None, true);
+            self.move_cursor(cx, 0, first);
+        }
+    }
+
+    /// Hide the menu
+    pub fn close(&mut self, cx: &mut Cx) {
+        if !self.levels.is_empty() {
+            self.levels.clear();
+            self.draw_list.redraw(cx);
+        }
+    }
+
+    /// Whether the menu is showing
+    pub fn is_open(&self) -> bool {
+        !self.levels.is_empty()
+    }
+
+    fn push_level(&mut self, cx: &mut Cx, entries: Vec<MenuEntry>, position: DVec2) {
+        let rows = entries.iter().map(|entry| match entry {
+            MenuEntry::Separator => WidgetRef::new_from_ptr(cx, self.separator_template),
+            MenuEntry::Item(item) => {
+                let row = WidgetRef::new_from_ptr(cx, self.row_template);
+                row.label(id!(label)).set_text(cx, &item.label);
+                row.label(id!(shortcut)).set_text(cx, item.shortcut.as_deref().unwrap_or(""));
+                row.view(id!(dirty_dot)).set_visible(cx, item.dirty);
+                row.view(id!(badge)).set_visible(cx, item.badge.is_some());
+                row.label(id!(badge.badge_label)).set_text(cx, item.badge.as_deref().unwrap_or(""));
+                if let Some(icon) = &item.icon {
+                    let icon = svg_icon_value(icon);
+                    let icon_widget = row.widget(id!(icon));
+                    icon_widget.apply_over(cx, live! {
+                        draw_icon: { svg_file: (icon) }
+                    });
+                    icon_widget.set_visible(cx, true);
+                }
+                let checked = if item.checked == Some(true) { 1.0 } else { 0.0 };
+                let disabled = if item.enabled { 0.0 } else { 1.0 };
+                let has_submenu = if item.submenu.is_empty() { 0.0 } else { 1.0 };
+                row.view(id!(check)).apply_over(cx, live! {
+                    draw_bg: { checked: (checked), disabled: (disabled) }
+                });
+                row.label(id!(label)).apply_over(cx, live! {
+                    draw_text: { disabled: (disabled) }
+                });
+                row.view(id!(arrow)).apply_over(cx, live! {
+                    draw_bg: { has_submenu: (has_submenu) }
+                });
+                row
+            }
+        }).collect();
+
+        self.levels.push(MenuLevel {
+            entries,
+            rows,
+            position,
+            cursor: None,
+            area: Area::Empty,
+        });
+        self.draw_list.redraw(cx);
+    }
+
+    /// Move the cursor of a level, closing the submenus below it
+    fn move_cursor(&mut self, cx: &mut Cx, depth: usize, cursor: Option<usize>) {
+        if self.levels.len() > depth + 1 && self.levels[depth].cursor == cursor {
+            return;
+        }
+        self.levels.truncate(depth + 1);
+        let level = &mut self.levels[depth];
+        level.cursor = cursor;
+        for (index, row) in level.rows.iter().enumerate() {
+            let highlighted = if Some(index) == cursor { 1.0 } else { 0.0 };
+            row.apply_over(cx, live! {
+                draw_bg: { highlighted: (highlighted) }
+            });
+        }
+        self.draw_list.redraw(cx);
+    }
+
+    /// Open the submenu of the innermost cursor item, if it has one
+    ///
+    /// Returns false if the item has no submenu.
+    fn open_submenu(&mut self, cx: &mut Cx, select_first: bool) -> bool {
+        let depth = self.levels.len() - 1;
+        let level = &self.levels[depth];
+        let Some(index) = level.cursor else { return false };
+        let Some(item) = level.entries[index].item() else { return false };
+        if item.submenu.is_empty() || !item.enabled {
+            return false;
+        }
+
+        let menu = level.area.rect(cx);
+        let row = level.rows[index].area().rect(cx);
+        let position = dvec2(menu.pos.x + menu.size.x - 2.0, row.pos.y - self.layout.padding.top);
+        let submenu = item.submenu.clone();
+        self.push_level(cx, submenu, position);
+        if select_first {
+            let first = next_selectable(&self.levels[depth + 1].entries, None, true);
+            self.move_cursor(cx, depth + 1, first);
+        }
+        true
+    }
+
+    /// Choose the innermost cursor item, or open its submenu
+    fn activate(&mut self, cx: &mut Cx, from_keyboard: bool) {
+        let Some(level) = self.levels.last() else { return };
+        let Some(item) = level.cursor.and_then(|index| level.entries[index].item()) else { return };
+        if !item.enabled {
+            return;
+        }
+        if !item.submenu.is_empty() {
+            self.open_submenu(cx, from_keyboard);
+            return;
+        }
+        let id = item.id.clone();
+        self.close(cx);
+        self.pending_actions.push(ShellMenuAction::Selected(id));
+    }
+
+    fn handle_key(&mut self, cx: &mut Cx, ke: &KeyEvent) {
+        let depth = self.levels.len() - 1;
+        let level = &self.levels[depth];
+        match ke.key_code {
+            KeyCode::ArrowDown | KeyCode::ArrowUp => {
+                let next = next_selectable(&level.entries, level.cursor, ke.key_code == KeyCode::ArrowDown);
+                self.move_cursor(cx, depth, next);
+            }
+            KeyCode::Home => {
+                let first = next_selectable(&level.entries, None, true);
+                self.move_cursor(cx, depth, first);
+            }
+            KeyCode::End => {
+                let last = next_selectable(&level.entries, None, false);
+                self.move_cursor(cx, depth, last);
+            }
+            KeyCode::ArrowRight => {
+                if !self.open_submenu(cx, true) {
+                    self.pending_actions.push(ShellMenuAction::NavigateRight);
+                }
+            }
+            KeyCode::ArrowLeft => {
+                if depth > 0 {
+                    self.levels.truncate(depth);
+                    self.draw_list.redraw(cx);
+                } else {
+                    self.pending_actions.push(ShellMenuAction::NavigateLeft);
+                }
+            }
+            KeyCode::ReturnKey | KeyCode::Space => self.activate(cx, true),
+            KeyCode::Escape => {
+                if depth > 0 {
+                    self.levels.truncate(depth);
+                    self.draw_list.redraw(cx);
+                } else {
+                    self.close(cx);
+                    self.pending_actions.push(ShellMenuAction::Dismissed);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl ShellMenuRef {
+    /// Show `entries` with the menu's top-left corner at `position`
+    pub fn open(&self, cx: &mut Cx, entries: Vec<MenuEntry>, position: DVec2, anchor: Rect) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.open(cx, entries, position, anchor);
+        }
+    }
+
+    /// Put the keyboard cursor on the first enabled entry
+    pub fn select_first(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.select_first(cx);
+        }
+    }
+
+    /// Hide the menu
+    pub fn close(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.close(cx);
+        }
+    }
+
+    /// Whether the menu is showing
+    pub fn is_open(&self) -> bool {
+        self.borrow().map_or(false, |inner| inner.is_open())
+    }
+
+    /// Returns the chosen item ID, if any
+    pub fn selected(&self, actions: &Actions) -> Option<String> {
+        if let ShellMenuAction::Selected(id) = actions.find_widget_action(self.widget_uid()).cast() {
+            Some(id)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the menu closed without a choice
+    pub fn dismissed(&self, actions: &Actions) -> bool {
+        matches!(actions.find_widget_action(self.widget_uid()).cast(), ShellMenuAction::Dismissed)
+    }
+
+    /// Returns `Some(true)` for Right and `Some(false)` for Left pressed past the menu's edge
+    pub fn navigated(&self, actions: &Actions) -> Option<bool> {
+        match actions.find_widget_action(self.widget_uid()).cast() {
+            ShellMenuAction::NavigateRight => Some(true),
+            ShellMenuAction::NavigateLeft => Some(false),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> Vec<MenuEntry> {
+        vec![
+            MenuItem::new("new", "New").with_shortcut("Ctrl+N").into(),
+            MenuEntry::Separator,
+            MenuItem::new("save", "Save").disabled().into(),
+            MenuItem::new("recent", "Open Recent")
+                .with_submenu(vec![MenuItem::new("recent_1", "notes.md").into()])
+                .into(),
+        ]
+    }
+
+    #[test]
+    fn test_next_selectable_skips_separators_and_disabled() {
+        let entries = entries();
+        assert_eq!(next_selectable(&entries, None, true), Some(0));
+        assert_eq!(next_selectable(&entries, None, false), Some(3));
+        assert_eq!(next_selectable(&entries, Some(0), true), Some(3));
+        assert_eq!(next_selectable(&entries, Some(3), true), Some(0));
+        assert_eq!(next_selectable(&entries, Some(0), false), Some(3));
+        assert_eq!(next_selectable(&[MenuEntry::Separator], None, true), None);
+    }
+
+    #[test]
+    fn test_model_find_and_update() {
+        let mut model = MenuModel::new(vec![
+            MenuItem::new("file", "File").with_submenu(entries()),
+            MenuItem::new("view", "View").with_submenu(vec![
+                MenuItem::new("word_wrap", "Word Wrap").checkable(false).into(),
+            ]),
+        ]);
+
+        assert_eq!(model.find("recent_1").map(|item| item.label.as_str()), Some("notes.md"));
+        assert!(model.set_checked("word_wrap", true));
+        assert_eq!(model.find("word_wrap").and_then(|item| item.checked), Some(true));
+        // Only checkable items can be checked
+        assert!(!model.set_checked("new", true));
+        assert!(model.set_enabled("save", true));
+        assert!(!model.set_enabled("missing", true));
+    }
+
+    #[test]
+    fn test_model_serde() {
+        let model = MenuModel::new(vec![MenuItem::new("file", "File").with_submenu(entries())]);
+        let json = serde_json::to_string(&model).unwrap();
+        assert_eq!(serde_json::from_str::<MenuModel>(&json).unwrap(), model);
+
+        // Omitted fields use defaults
+        let model: MenuModel = serde_json::from_str(r#"{
+            "menus": [{ "id": "edit", "label": "Edit", "submenu": [
+                { "type": "item", "id": "undo", "label": "Undo", "shortcut": "Ctrl+Z" },
+                { "type": "separator" }
+            ]}]
+        }"#).unwrap();
+        let undo = model.find("undo").unwrap();
+        assert!(undo.enabled);
+        assert_eq!(undo.checked, None);
+        assert_eq!(undo.badge, None);
+        assert!(!undo.dirty);
+        assert_eq!(model.menus[0].submenu[1], MenuEntry::Separator);
+    }
+}
@@ -0,0 +1,384 @@
+//! Application menu bar
+//!
+//! `MenuBar` shows the top-level menus of a `MenuModel` as a row of titles in
+//! the header. Clicking a title opens its entries in a `ShellMenu`; while a
+//! menu is open, hovering another title switches to it. A top-level menu
+//! without entries is chosen directly. Choosing a checkable item flips its
+//! state in the model before it is reported.
+//!
+//! ## Keyboard
+//!
+//! | Key | Action |
+//! |-----|--------|
+//! | F10 | Focus the first menu title (again to leave) |
+//! | Left / Right | Move between titles, or between open menus |
+//! | Down / Enter / Space | Open the focused menu |
+//! | Escape | Leave the menu bar |
+//!
+//! Inside an open menu the `ShellMenu` keys apply.
+
+use makepad_widgets::*;
+use crate::shell::menu::{MenuModel, ShellMenuWidgetRefExt};
+use crate::theme::current_palette;
+
+live_design! {
+    use link::theme::*;
+    use link::shaders::*;
+    use link::widgets::*;
+
+    use crate::live_design::*;
+    use crate::shell::menu::*;
+
+    pub MenuBarButton = <Button> {
+        width: Fit, height: 26
+        padding: { left: 8, right: 8 }
+        margin: 0
+        align: { x: 0.5, y: 0.5 }
+        text: ""
+
+        draw_bg: {
+            instance hover: 0.0
+            instance pressed: 0.0
+            instance active: 0.0
+            uniform bg_hover: (PALETTE_BG_HOVER)
+            uniform bg_selected: (PALETTE_BG_SELECTED)
+
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                let color = mix(self.bg_hover, self.bg_selected, self.active);
+                sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 4.0);
+                sdf.fill(vec4(color.xyz, max(max(self.hover, self.pressed), self.active)));
+                return sdf.result;
+            }
+        }
+
+        draw_text: {
+            instance disabled: 0.0
+            uniform text_primary: (PALETTE_TEXT_PRIMARY)
+            uniform text_faint: (PALETTE_TEXT_FAINT)
+            text_style: <FONT_REGULAR>{ font_size: 10.0 }
+            fn get_color(self) -> vec4 {
+                return mix(self.text_primary, self.text_faint, self.disabled);
+            }
+        }
+    }
+
+    pub MenuBar = {{MenuBar}} {
+        width: Fit, height: Fit
+        flow: Right
+        spacing: 2
+        align: { y: 0.5 }
+        visible: false
+
+        button_template: <MenuBarButton> {}
+        menu_template: <ShellMenu> {}
+    }
+}
+
+/// Actions emitted by `MenuBar`
+#[derive(Clone, Debug, DefaultNone)]
+pub enum MenuBarAction {
+    /// An item was chosen (`checked` = new state of a checkable item)
+    Selected { id: String, checked: Option<bool> },
+
+    None,
+}
+
+/// Row of top-level menu titles
+#[derive(Live, LiveHook, Widget)]
+pub struct MenuBar {
+    #[redraw]
+    #[rust]
+    area: Area,
+
+    #[walk]
+    walk: Walk,
+
+    #[layout]
+    layout: Layout,
+
+    #[live]
+    button_template: Option<LivePtr>,
+
+    #[live]
+    menu_template: Option<LivePtr>,
+
+    #[live(true)]
+    visible: bool,
+
+    #[rust]
+    model: MenuModel,
+
+    /// One title button per top-level menu, in model order
+    #[rust]
+    buttons: Vec<WidgetRef>,
+
+    /// Dropdown shared by all menus (created with the first model)
+    #[rust]
+    menu: WidgetRef,
+
+    /// Index of the menu whose dropdown is open
+    #[rust]
+    open_index: Option<usize>,
+
+    /// Index of the title with keyboard focus
+    #[rust]
+    focused: Option<usize>,
+}
+
+impl Widget for MenuBar {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if !self.visible {
+            return;
+        }
+        let uid = self.widget_uid();
+        // Keys go to the dropdown while it is open
+        let menu_was_open = self.open_index.is_some();
+        let actions = cx.capture_actions(|cx| {
+            for button in &self.buttons {
+                button.handle_event(cx, event, scope);
+            }
+            self.menu.handle_event(cx, event, scope);
+        });
+
+        let menu = self.menu.as_shell_menu();
+        if let Some(id) = menu.selected(&actions) {
+            self.set_open(cx, None, None);
+            let action = self.select(&id);
+            cx.widget_action(uid, &scope.path, action);
+        }
+        if menu.dismissed(&actions) {
+            self.set_open(cx, None, None);
+        }
+        if let (Some(forward), Some(index)) = (menu.navigated(&actions), self.open_index) {
+            let next = self.step(index, forward);
+            self.open_menu(cx, next, true);
+        }
+
+        // Switch menus by hovering while one is open
+        if let Some(open) = self.open_index {
+            let hovered = self.buttons.iter()
+                .position(|button| matches!(event.hits(cx, button.area()), Hit::FingerHoverIn(_)));
+            if let Some(index) = hovered.filter(|&index| index != open) {
+                self.open_menu(cx, index, false);
+            }
+        }
+
+        let clicked = self.buttons.iter().position(|button| button.as_button().clicked(&actions));
+        if let Some(index) = clicked {
+            if self.open_index == Some(index) {
+                self.set_open(cx, None, None);
+            } else if let Some(action) = self.activate(cx, index, false) {
+                cx.widget_action(uid, &scope.path, action);
+            }
+        }
+
+        if let Event::KeyDown(ke) = event {
+            if !menu_was_open {
+                if let Some(action) = self.handle_key(cx, ke) {
+                    cx.widget_action(uid, &scope.path, action);
+                }
+            }
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if !self.visible {
+            return DrawStep::done();
+        }
+        let palette = current_palette();
+        let button_nodes = palette.live_nodes(&[live_id!(draw_bg), live_id!(draw_text)]);
+
+        cx.begin_turtle(walk, self.layout);
+        for button in &self.buttons {
+            button.apply_over(cx, &button_nodes);
+            button.draw_all(cx, scope);
+        }
+        cx.end_turtle_with_area(&mut self.area);
+
+        // Draws into its own overlay
+        self.menu.draw_all(cx, scope);
+
+        DrawStep::done()
+    }
+
+    fn visible(&self) -> bool {
+        self.visible
+    }
+
+    fn set_visible(&mut self, cx: &mut Cx, visible: bool) {
+        if self.visible != visible {
+            self.visible = visible;
+            self.redraw(cx);
+        }
+    }
+}
+
+impl MenuBar {
+    /// Replace the menu model (the bar hides while it has no menus)
+    pub fn set_model(&mut self, cx: &mut Cx, model: MenuModel) {
+        if self.menu.is_empty() {
+            self.menu = WidgetRef::new_from_ptr(cx, self.menu_template);
+        }
+        self.set_open(cx, None, None);
+
+        self.buttons = model.menus.iter().map(|item| {
+            let button = WidgetRef::new_from_ptr(cx, self.button_template);
+            button.set_text(cx, &item.label);
+            button
+        }).collect();
+
+        self.visible = !model.menus.is_empty();
+        self.model = model;
+        self.apply_states(cx);
+    }
+
+    /// Current menu model, including checked states changed by the user
+    pub fn model(&self) -> &MenuModel {
+        &self.model
+    }
+
+    /// Set the checked state of a checkable item
+    ///
+    /// Returns false if no checkable item has this ID.
+    pub fn set_checked(&mut self, id: &str, checked: bool) -> bool {
+        self.model.set_checked(id, checked)
+    }
+
+    /// Enable or disable an item or top-level menu
+    ///
+    /// Returns false if no item has this ID.
+    pub fn set_enabled(&mut self, cx: &mut Cx, id: &str, enabled: bool) -> bool {
+        if !self.model.set_enabled(id, enabled) {
+            return false;
+        }
+        self.apply_states(cx);
+        true
+    }
+
+    /// Open the menu at `index`, or choose it directly if it has no entries
+    fn activate(&mut self, cx: &mut Cx, index: usize, from_keyboard: bool) -> Option<MenuBarAction> {
+        let menu = &self.model.menus[index];
+        if !menu.enabled {
+            return None;
+        }
+        if menu.submenu.is_empty() {
+            let id = menu.id.clone();
+            self.set_open(cx, None, None);
+            return Some(self.select(&id));
+        }
+        self.open_menu(cx, index, from_keyboard);
+        None
+    }
+
+    /// Show the dropdown of the menu at `index` (closes it for a menu without entries)
+    fn open_menu(&mut self, cx: &mut Cx, index: usize, select_first: bool) {
+        let item = &self.model.menus[index];
+        let dropdown = self.menu.as_shell_menu();
+        if item.submenu.is_empty() || !item.enabled {
+            dropdown.close(cx);
+            self.set_open(cx, None, Some(index));
+            return;
+        }
+
+        let rect = self.buttons[index].area().rect(cx);
+        let position = dvec2(rect.pos.x, rect.pos.y + rect.size.y + 2.0);
+        dropdown.open(cx, item.submenu.clone(), position, rect);
+        if select_first {
+            dropdown.select_first(cx);
+        }
+        self.set_open(cx, Some(index), Some(index));
+    }
+
+    /// Report a chosen item, flipping it first if it is checkable
+    fn select(&mut self, id: &str) -> MenuBarAction {
+        let checked = self.model.find_mut(id).and_then(|item| {
+            item.checked = item.checked.map(|checked| !checked);
+            item.checked
+        });
+        MenuBarAction::Selected { id: id.to_string(), checked }
+    }
+
+    /// Index of the adjacent top-level menu, wrapping around
+    fn step(&self, index: usize, forward: bool) -> usize {
+        let len = self.model.menus.len();
+        if forward { (index + 1) % len } else { (index + len - 1) % len }
+    }
+
+    fn handle_key(&mut self, cx: &mut Cx, ke: &KeyEvent) -> Option<MenuBarAction> {
+        if ke.key_code == KeyCode::F10 {
+            let focused = if self.focused.is_none() && !self.buttons.is_empty() { Some(0) } else { None };
+            self.set_open(cx, None, focused);
+            return None;
+        }
+        let index = self.focused?;
+        match ke.key_code {
+            KeyCode::ArrowLeft | KeyCode::ArrowRight => {
+                let next = self.step(index, ke.key_code == KeyCode::ArrowRight);
+                self.set_open(cx, None, Some(next));
+                None
+            }
+            KeyCode::ArrowDown | KeyCode::ReturnKey | KeyCode::Space => self.activate(cx, index, true),
+            KeyCode::Escape => {
+                self.set_open(cx, None, None);
+                None
+            }
+            _ => None,
+        }
+    }
+
+    fn set_open(&mut self, cx: &mut Cx, open_index: Option<usize>, focused: Option<usize>) {
+        if open_index.is_none() && self.open_index.is_some() {
+            self.menu.as_shell_menu().close(cx);
+        }
+        self.open_index = open_index;
+        self.focused = focused;
+        self.apply_states(cx);
+    }
+
+    /// Highlight the open or focused title and dim disabled ones
+    fn apply_states(&mut self, cx: &mut Cx) {
+        for (index, (button, item)) in self.buttons.iter().zip(&self.model.menus).enumerate() {
+            let active = if self.open_index == Some(index) || self.focused == Some(index) { 1.0 } else { 0.0 };
+            let disabled = if item.enabled { 0.0 } else { 1.0 };
+            button.apply_over(cx, live! {
+                draw_bg: { active: (active) }
+                draw_text: { disabled: (disabled) }
+            });
+        }
+        self.redraw(cx);
+    }
+}
+
+impl MenuBarRef {
+    /// Replace the menu model
+    pub fn set_model(&self, cx: &mut Cx, model: MenuModel) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_model(cx, model);
+        }
+    }
+
+    /// Current menu model
+    pub fn model(&self) -> MenuModel {
+        self.borrow().map(|inner| inner.model().clone()).unwrap_or_default()
+    }
+
+    /// Set the checked state of a checkable item
+    pub fn set_checked(&self, id: &str, checked: bool) -> bool {
+        self.borrow_mut().map_or(false, |mut inner| inner.set_checked(id, checked))
+    }
+
+    /// Enable or disable an item or top-level menu
+    pub fn set_enabled(&self, cx: &mut Cx, id: &str, enabled: bool) -> bool {
+        self.borrow_mut().map_or(false, |mut inner| inner.set_enabled(cx, id, enabled))
+    }
+
+    /// Returns the chosen item ID and the new state of a checkable item
+    pub fn selected(&self, actions: &Actions) -> Option<(String, Option<bool>)> {
+        if let MenuBarAction::Selected { id, checked } = actions.find_widget_action(self.widget_uid()).cast() {
+            Some((id, checked))
+        } else {
+            None
+        }
+    }
+}
@@ -3,28 +3,63 @@
 //! This module provides the core layout widgets:
 //! - `ShellLayout` - Main container with header, footer, sidebars, and content
 //! - `ShellHeader` - Top header bar
+//! - `MenuBar` - Application menu bar fed from a `MenuModel`
+//! - `HeaderToolbar` - Data-driven header toolbar items
+//! - `ShellMenu` - Popup menu with cascading submenus shown in an overlay
 //! - `ShellFooter` - Bottom footer/status bar
+//! - `StatusBarGroup` - App status items of one status bar alignment
+//! - `ShellNotifications` - Notification toasts and the notification center
+//! - `ShellDialog` - Modal confirm, prompt and custom dialogs
 //! - `ShellSidebar` - Left and right sidebars
-//! - `SidebarMenuItem` - Menu items with hover effects
+//! - `SidebarMenu` - Data-driven sidebar menu items with hover effects
+//! - `SidebarTree` - Hierarchical sidebar navigation
+//! - `ActivityBar` - Icon bar switching between registered sidebar views
 //! - `ShellConfig` - Configuration options
+//! - `ShellEvent` - Unified event stream emitted by `ShellLayout`
 
 pub mod config;
+pub mod events;
 pub mod header;
+pub mod menu;
+pub mod menu_bar;
+pub mod toolbar;
+pub mod status_bar;
 pub mod footer;
+pub mod notifications;
+pub mod dialog;
 pub mod sidebar;
 pub mod sidebar_menu;
+pub mod sidebar_tree;
+pub mod activity_bar;
 pub mod layout;
 
 // Re-export live_design functions
 pub use header::live_design as header_live_design;
+pub use menu::live_design as menu_live_design;
+pub use menu_bar::live_design as menu_bar_live_design;
+pub use toolbar::live_design as toolbar_live_design;
+pub use status_bar::live_design as status_bar_live_design;
 pub use footer::live_design as footer_live_design;
+pub use notifications::live_design as notifications_live_design;
+pub use dialog::live_design as dialog_live_design;
 pub use sidebar::live_design as sidebar_live_design;
 pub use sidebar_menu::live_design as sidebar_menu_live_design;
+pub use sidebar_tree::live_design as sidebar_tree_live_design;
+pub use activity_bar::live_design as activity_bar_live_design;
 pub use layout::live_design as layout_live_design;
 
 pub use config::{ShellConfig, ShellConfigBuilder};
+pub use events::ShellEvent;
 pub use header::{ShellHeader, ShellHeaderRef};
+pub use menu::{MenuEntry, MenuItem, MenuModel, ShellMenu, ShellMenuRef};
+pub use menu_bar::{MenuBar, MenuBarRef};
+pub use toolbar::{HeaderToolbar, HeaderToolbarRef, ToolbarGroup, ToolbarItem};
+pub use status_bar::{StatusAlignment, StatusBarGroup, StatusBarGroupRef, StatusItem, StatusProgress};
 pub use footer::{ShellFooter, ShellFooterRef};
-pub use sidebar::{ShellSidebar, ShellSidebarRef};
-// sidebar_menu exports live_design templates (SidebarMenuButton, ShowMoreContainer, MoreAppsSection)
+pub use notifications::{Notification, NotificationKind, NotificationRecord, ShellNotifications, ShellNotificationsRef};
+pub use dialog::{Dialog, DialogButtonRole, DialogResult, ShellDialog, ShellDialogRef};
+pub use sidebar::{ShellSidebar, ShellSidebarRef, ShellSidebarWidgetRefExt, SidebarSelection};
+pub use sidebar_menu::{SidebarItem, SidebarMenu, SidebarMenuRef};
+pub use sidebar_tree::{SidebarTree, SidebarTreeRef, SidebarTreeNode};
+pub use activity_bar::{ActivityBar, ActivityBarRef, SidebarSide, SidebarView};
 pub use layout::{ShellLayout, ShellLayoutRef};
@@ -0,0 +1,1139 @@
+//! Notification toasts and the notification center
+//!
+//! `ShellNotifications` stacks toasts in the bottom-right corner of the
+//! shell. A `Notification` has a kind (info, warning, error or progress),
+//! a title and message, up to three action buttons and an optional
+//! auto-dismiss timeout. Notifications are addressed by ID: notifying again
+//! with the same ID updates the toast in place, e.g. to advance a progress
+//! bar or turn it into a "done" message.
+//!
+//! Every notification is also kept in a history shown by the notification
+//! center panel, which the shell opens from its status bar item.
+//!
+//! ## Usage
+//!
+//! ```rust,ignore
+//! let id = shell.notify(cx, Notification::progress("Indexing").with_id("index"));
+//! shell.set_notification_progress(cx, &id, Some(0.5));
+//! shell.notify(cx, Notification::info("Indexing done").with_id("index"));
+//!
+//! shell.notify(cx, Notification::error("Upload failed")
+//!     .with_message("Connection reset")
+//!     .with_action("retry", "Retry"));
+//! ```
+
+use makepad_widgets::*;
+use crate::theme::current_palette;
+
+live_design! {
+    use link::theme::*;
+    use link::shaders::*;
+    use link::widgets::*;
+
+    use crate::live_design::*;
+
+    pub ShellToastButton = <Button> {
+        width: Fit, height: 24
+        padding: { left: 10, right: 10 }
+        margin: 0
+        visible: false
+        text: ""
+
+        draw_bg: {
+            instance hover: 0.0
+            instance pressed: 0.0
+            uniform bg_hover: (PALETTE_BG_HOVER)
+            uniform bg_pressed: (PALETTE_BG_PRESSED)
+            uniform border: (PALETTE_BORDER)
+
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                let color = mix(self.bg_hover, self.bg_pressed, self.pressed);
+                sdf.box(0.5, 0.5, self.rect_size.x - 1.0, self.rect_size.y - 1.0, 4.0);
+                sdf.fill_keep(vec4(color.xyz, max(self.hover, self.pressed)));
+                sdf.stroke(self.border, 1.0);
+                return sdf.result;
+            }
+        }
+
+        draw_text: {
+            uniform accent: (PALETTE_ACCENT)
+            text_style: <FONT_MEDIUM>{ font_size: 9.5 }
+            fn get_color(self) -> vec4 {
+                return self.accent;
+            }
+        }
+    }
+
+    pub ShellToastClose = <Button> {
+        width: 18, height: 18
+        padding: 0
+        margin: 0
+        text: ""
+
+        draw_bg: {
+            instance hover: 0.0
+            instance pressed: 0.0
+            uniform text_muted: (PALETTE_TEXT_MUTED)
+            uniform text_primary: (PALETTE_TEXT_PRIMARY)
+
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                let color = mix(self.text_muted, self.text_primary, self.hover);
+                sdf.move_to(5.0, 5.0);
+                sdf.line_to(self.rect_size.x - 5.0, self.rect_size.y - 5.0);
+                sdf.move_to(self.rect_size.x - 5.0, 5.0);
+                sdf.line_to(5.0, self.rect_size.y - 5.0);
+                sdf.stroke(color, 1.2);
+                return sdf.result;
+            }
+        }
+    }
+
+    pub ShellToast = <View> {
+        width: 340, height: Fit
+        flow: Down
+        padding: { top: 10, bottom: 10, left: 16, right: 10 }
+        spacing: 6
+
+        show_bg: true
+        draw_bg: {
+            // 0 = info, 1 = warning, 2 = error, 3 = progress
+            instance kind: 0.0
+            uniform bg_panel: (PALETTE_BG_PANEL)
+            uniform border: (PALETTE_BORDER)
+            uniform accent: (PALETTE_ACCENT)
+            uniform warning: (PALETTE_WARNING)
+            uniform danger: (PALETTE_DANGER)
+
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 6.0);
+                sdf.fill_keep(self.bg_panel);
+                sdf.stroke(self.border, 1.0);
+
+                // Kind stripe on the left edge
+                let stripe = mix(self.accent, self.warning, clamp(self.kind, 0.0, 1.0));
+                let stripe = mix(stripe, self.danger, step(1.5, self.kind) * step(self.kind, 2.5));
+                sdf.box(1.0, 1.0, 4.0, self.rect_size.y - 2.0, 2.0);
+                sdf.fill(mix(stripe, self.accent, step(2.5, self.kind)));
+                return sdf.result;
+            }
+        }
+
+        header = <View> {
+            width: Fill, height: Fit
+            flow: Right
+            align: { y: 0.5 }
+            spacing: 8
+
+            title = <Label> {
+                width: Fill
+                draw_text: {
+                    uniform text_primary: (PALETTE_TEXT_PRIMARY)
+                    text_style: <FONT_SEMIBOLD>{ font_size: 10.5 }
+                    wrap: Word
+                    fn get_color(self) -> vec4 {
+                        return self.text_primary;
+                    }
+                }
+                text: ""
+            }
+
+            close_btn = <ShellToastClose> {}
+        }
+
+        message = <Label> {
+            width: Fill
+            draw_text: {
+                uniform text_secondary: (PALETTE_TEXT_SECONDARY)
+                text_style: <FONT_REGULAR>{ font_size: 10.0 }
+                wrap: Word
+                fn get_color(self) -> vec4 {
+                    return self.text_secondary;
+                }
+            }
+            text: ""
+        }
+
+        // Progress bar: filled to `progress`, or a sliding segment while `busy`
+        progress_bar = <View> {
+            width: Fill, height: 4
+            visible: false
+            show_bg: true
+            draw_bg: {
+                instance progress: 0.0
+                instance busy: 0.0
+                instance phase: 0.0
+                uniform bg_hover: (PALETTE_BG_HOVER)
+                uniform accent: (PALETTE_ACCENT)
+
+                fn pixel(self) -> vec4 {
+                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                    sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 2.0);
+                    sdf.fill(self.bg_hover);
+                    let start = mix(0.0, self.phase * 1.3 - 0.3, self.busy);
+                    let end = mix(self.progress, self.phase * 1.3, self.busy);
+                    let x = self.pos.x;
+                    let filled = step(start, x) * step(x, end);
+                    return mix(sdf.result, self.accent, filled);
+                }
+            }
+        }
+
+        actions = <View> {
+            width: Fill, height: Fit
+            flow: Right
+            align: { x: 1.0 }
+            spacing: 6
+            visible: false
+
+            action_0 = <ShellToastButton> {}
+            action_1 = <ShellToastButton> {}
+            action_2 = <ShellToastButton> {}
+        }
+    }
+
+    pub ShellNotificationCenterHeader = <View> {
+        width: Fill, height: Fit
+        flow: Right
+        align: { y: 0.5 }
+        padding: { left: 8, right: 4, top: 4, bottom: 8 }
+        spacing: 6
+
+        title = <Label> {
+            width: Fill
+            draw_text: {
+                uniform text_primary: (PALETTE_TEXT_PRIMARY)
+                text_style: <FONT_SEMIBOLD>{ font_size: 11.0 }
+                fn get_color(self) -> vec4 {
+                    return self.text_primary;
+                }
+            }
+            text: "Notifications"
+        }
+
+        clear_btn = <ShellToastButton> { visible: true, text: "Clear" }
+        close_btn = <ShellToastClose> {}
+    }
+
+    pub ShellNotificationCenterRow = <View> {
+        width: Fill, height: Fit
+        flow: Right
+        padding: { left: 8, right: 8, top: 6, bottom: 6 }
+        spacing: 8
+
+        dot = <View> {
+            width: 8, height: 8
+            margin: { top: 4 }
+            show_bg: true
+            draw_bg: {
+                instance kind: 0.0
+                uniform accent: (PALETTE_ACCENT)
+                uniform warning: (PALETTE_WARNING)
+                uniform danger: (PALETTE_DANGER)
+                fn pixel(self) -> vec4 {
+                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                    let color = mix(self.accent, self.warning, clamp(self.kind, 0.0, 1.0));
+                    let color = mix(color, self.danger, step(1.5, self.kind) * step(self.kind, 2.5));
+                    sdf.circle(4.0, 4.0, 4.0);
+                    sdf.fill(mix(color, self.accent, step(2.5, self.kind)));
+                    return sdf.result;
+                }
+            }
+        }
+
+        text = <View> {
+            width: Fill, height: Fit
+            flow: Down
+            spacing: 2
+
+            title = <Label> {
+                width: Fill
+                draw_text: {
+                    uniform text_primary: (PALETTE_TEXT_PRIMARY)
+                    text_style: <FONT_MEDIUM>{ font_size: 10.0 }
+                    wrap: Word
+                    fn get_color(self) -> vec4 {
+                        return self.text_primary;
+                    }
+                }
+                text: ""
+            }
+
+            message = <Label> {
+                width: Fill
+                draw_text: {
+                    uniform text_muted: (PALETTE_TEXT_MUTED)
+                    text_style: <FONT_REGULAR>{ font_size: 9.5 }
+                    wrap: Word
+                    fn get_color(self) -> vec4 {
+                        return self.text_muted;
+                    }
+                }
+                text: ""
+            }
+        }
+
+        age = <Label> {
+            draw_text: {
+                uniform text_faint: (PALETTE_TEXT_FAINT)
+                text_style: <FONT_REGULAR>{ font_size: 9.0 }
+                fn get_color(self) -> vec4 {
+                    return self.text_faint;
+                }
+            }
+            text: ""
+        }
+    }
+
+    pub ShellNotificationCenterEmpty = <View> {
+        width: Fill, height: Fit
+        padding: 12
+        align: { x: 0.5 }
+
+        <Label> {
+            draw_text: {
+                uniform text_faint: (PALETTE_TEXT_FAINT)
+                text_style: <FONT_REGULAR>{ font_size: 10.0 }
+                fn get_color(self) -> vec4 {
+                    return self.text_faint;
+                }
+            }
+            text: "No notifications"
+        }
+    }
+
+    pub ShellNotifications = {{ShellNotifications}} {
+        width: Fill, height: Fill
+        flow: Down
+        align: { x: 1.0, y: 1.0 }
+        padding: { right: 16, bottom: 36 }
+        spacing: 8
+
+        center_walk: { width: 360, height: Fit }
+        center_layout: { flow: Down, padding: 6, spacing: 0 }
+
+        draw_bg: {
+            uniform bg_panel: (PALETTE_BG_PANEL)
+            uniform border: (PALETTE_BORDER)
+
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(1.0, 1.0, self.rect_size.x - 2.0, self.rect_size.y - 2.0, 6.0);
+                sdf.fill_keep(self.bg_panel);
+                sdf.stroke(self.border, 1.0);
+                return sdf.result;
+            }
+        }
+
+        toast_template: <ShellToast> {}
+        center_header_template: <ShellNotificationCenterHeader> {}
+        center_row_template: <ShellNotificationCenterRow> {}
+        center_empty_template: <ShellNotificationCenterEmpty> {}
+    }
+}
+
+/// Most toasts shown at once; older ones are dropped (they stay in the history)
+pub const MAX_TOASTS: usize = 4;
+
+/// Most notifications kept in the history
+pub const MAX_HISTORY: usize = 100;
+
+/// Most action buttons shown on a toast
+pub const MAX_TOAST_ACTIONS: usize = 3;
+
+/// Most recent notifications listed in the notification center
+const MAX_CENTER_ROWS: usize = 8;
+
+/// Seconds a busy progress bar takes to slide across
+const BUSY_CYCLE: f64 = 1.5;
+
+/// Kind of a notification (sets the stripe color and the default timeout)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NotificationKind {
+    #[default]
+    Info,
+    Warning,
+    Error,
+    /// A running task with a progress bar
+    Progress,
+}
+
+impl NotificationKind {
+    /// Seconds until the toast closes by itself (`None` = until dismissed)
+    pub fn default_timeout(self) -> Option<f64> {
+        match self {
+            NotificationKind::Info => Some(5.0),
+            NotificationKind::Warning => Some(8.0),
+            NotificationKind::Error | NotificationKind::Progress => None,
+        }
+    }
+
+    /// Value of the `kind` shader instance
+    fn shader_value(self) -> f64 {
+        match self {
+            NotificationKind::Info => 0.0,
+            NotificationKind::Warning => 1.0,
+            NotificationKind::Error => 2.0,
+            NotificationKind::Progress => 3.0,
+        }
+    }
+}
+
+/// An action button on a toast
+#[derive(Clone, Debug, PartialEq)]
+pub struct NotificationButton {
+    /// Action ID reported when clicked
+    pub id: String,
+
+    /// Button label
+    pub label: String,
+}
+
+/// A notification shown as a toast and kept in the history
+#[derive(Clone, Debug, PartialEq)]
+pub struct Notification {
+    /// Notification ID (empty = assigned by `notify`)
+    pub id: String,
+
+    pub kind: NotificationKind,
+
+    /// Bold first line
+    pub title: String,
+
+    /// Optional details below the title
+    pub message: String,
+
+    /// Action buttons (at most `MAX_TOAST_ACTIONS` are shown)
+    pub actions: Vec<NotificationButton>,
+
+    /// Seconds until the toast closes by itself (`None` = until dismissed)
+    pub timeout: Option<f64>,
+
+    /// Progress fraction 0.0 - 1.0 of a progress notification (`None` = busy)
+    pub progress: Option<f64>,
+}
+
+impl Notification {
+    /// Create a notification with the kind's default timeout
+    pub fn new(kind: NotificationKind, title: impl Into<String>) -> Self {
+        Self {
+            id: String::new(),
+            kind,
+            title: title.into(),
+            message: String::new(),
+            actions: Vec::new(),
+            timeout: kind.default_timeout(),
+            progress: None,
+        }
+    }
+
+    /// Informational notification (closes after 5 seconds)
+    pub fn info(title: impl Into<String>) -> Self {
+        Self::new(NotificationKind::Info, title)
+    }
+
+    /// Warning (closes after 8 seconds)
+    pub fn warning(title: impl Into<String>) -> Self {
+        Self::new(NotificationKind::Warning, title)
+    }
+
+    /// Error (stays until dismissed)
+    pub fn error(title: impl Into<String>) -> Self {
+        Self::new(NotificationKind::Error, title)
+    }
+
+    /// Running task with a busy progress bar (stays until dismissed or replaced)
+    pub fn progress(title: impl Into<String>) -> Self {
+        Self::new(NotificationKind::Progress, title)
+    }
+
+    /// Set the ID, to update or dismiss the notification later
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    /// Set the details text
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    /// Add an action button
+    pub fn with_action(mut self, id: impl Into<String>, label: impl Into<String>) -> Self {
+        self.actions.push(NotificationButton { id: id.into(), label: label.into() });
+        self
+    }
+
+    /// Close the toast after `seconds`
+    pub fn with_timeout(mut self, seconds: f64) -> Self {
+        self.timeout = Some(seconds);
+        self
+    }
+
+    /// Keep the toast until it is dismissed
+    pub fn sticky(mut self) -> Self {
+        self.timeout = None;
+        self
+    }
+
+    /// Set the progress fraction (0.0 - 1.0)
+    pub fn with_progress(mut self, fraction: f64) -> Self {
+        self.progress = Some(fraction.clamp(0.0, 1.0));
+        self
+    }
+}
+
+/// A notification in the history
+#[derive(Clone, Debug, PartialEq)]
+pub struct NotificationRecord {
+    pub notification: Notification,
+
+    /// `Cx::time_now()` when it was (last) notified
+    pub time: f64,
+
+    /// Seen in the notification center
+    pub read: bool,
+}
+
+#[derive(Clone, Debug)]
+struct Toast {
+    notification: Notification,
+    expires: Option<f64>,
+}
+
+/// Visible toasts and the notification history
+#[derive(Clone, Debug, Default)]
+pub struct NotificationCenter {
+    /// Oldest first
+    history: Vec<NotificationRecord>,
+    /// Oldest first
+    toasts: Vec<Toast>,
+    next_id: u64,
+}
+
+impl NotificationCenter {
+    /// Show a notification, replacing the one with the same ID
+    ///
+    /// Returns the notification ID (assigned if empty).
+    pub fn notify(&mut self, mut notification: Notification, now: f64) -> String {
+        if notification.id.is_empty() {
+            self.next_id += 1;
+            notification.id = format!("notification_{}", self.next_id);
+        }
+        let id = notification.id.clone();
+
+        self.history.retain(|record| record.notification.id != id);
+        self.history.push(NotificationRecord { notification: notification.clone(), time: now, read: false });
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0);
+        }
+
+        let toast = Toast { expires: notification.timeout.map(|timeout| now + timeout), notification };
+        match self.toasts.iter_mut().find(|toast| toast.notification.id == id) {
+            Some(existing) => *existing = toast,
+            None => {
+                self.toasts.push(toast);
+                if self.toasts.len() > MAX_TOASTS {
+                    self.toasts.remove(0);
+                }
+            }
+        }
+        id
+    }
+
+    /// Set the progress of a notification (`None` = busy)
+    ///
+    /// Returns false if no notification has this ID.
+    pub fn set_progress(&mut self, id: &str, progress: Option<f64>) -> bool {
+        let progress = progress.map(|fraction| fraction.clamp(0.0, 1.0));
+        let mut found = false;
+        let toasts = self.toasts.iter_mut().map(|toast| &mut toast.notification);
+        let records = self.history.iter_mut().map(|record| &mut record.notification);
+        for notification in toasts.chain(records).filter(|notification| notification.id == id) {
+            notification.progress = progress;
+            found = true;
+        }
+        found
+    }
+
+    /// Close a toast (it stays in the history)
+    ///
+    /// Returns false if no toast has this ID.
+    pub fn dismiss(&mut self, id: &str) -> bool {
+        let len = self.toasts.len();
+        self.toasts.retain(|toast| toast.notification.id != id);
+        self.toasts.len() != len
+    }
+
+    /// Close the toasts whose timeout elapsed, returning their IDs
+    pub fn expire(&mut self, now: f64) -> Vec<String> {
+        let (expired, kept) = std::mem::take(&mut self.toasts)
+            .into_iter()
+            .partition(|toast| toast.expires.map_or(false, |expires| expires <= now));
+        self.toasts = kept;
+        expired.into_iter().map(|toast: Toast| toast.notification.id).collect()
+    }
+
+    /// Earliest time a toast expires
+    pub fn next_expiry(&self) -> Option<f64> {
+        self.toasts.iter().filter_map(|toast| toast.expires).reduce(f64::min)
+    }
+
+    /// Visible toasts, oldest first
+    pub fn toasts(&self) -> impl Iterator<Item = &Notification> {
+        self.toasts.iter().map(|toast| &toast.notification)
+    }
+
+    /// Notification history, oldest first
+    pub fn history(&self) -> &[NotificationRecord] {
+        &self.history
+    }
+
+    /// Notifications not yet seen in the notification center
+    pub fn unread_count(&self) -> usize {
+        self.history.iter().filter(|record| !record.read).count()
+    }
+
+    /// Mark the whole history as seen
+    pub fn mark_all_read(&mut self) {
+        for record in &mut self.history {
+            record.read = true;
+        }
+    }
+
+    /// Forget the history (visible toasts stay)
+    pub fn clear_history(&mut self) {
+        self.history.clear();
+    }
+}
+
+/// Short relative time for the notification center ("now", "5 min", "2 h", "3 d")
+pub fn format_age(seconds: f64) -> String {
+    let minutes = (seconds / 60.0).floor() as u64;
+    match minutes {
+        0 => "now".to_string(),
+        1..=59 => format!("{} min", minutes),
+        60..=1439 => format!("{} h", minutes / 60),
+        _ => format!("{} d", minutes / 1440),
+    }
+}
+
+/// ID of the toast action button at `index`
+fn action_button_id(index: usize) -> LiveId {
+    match index {
+        0 => live_id!(action_0),
+        1 => live_id!(action_1),
+        _ => live_id!(action_2),
+    }
+}
+
+/// Actions emitted by `ShellNotifications`
+#[derive(Clone, Debug, DefaultNone)]
+pub enum ShellNotificationsAction {
+    /// An action button of a toast was clicked (the toast closes)
+    ActionClicked { notification_id: String, action_id: String },
+
+    /// The user closed a toast
+    Dismissed(String),
+
+    /// The notification center was closed or its history cleared
+    CenterChanged,
+
+    None,
+}
+
+/// Toast stack and notification center overlay
+#[derive(Live, LiveHook, Widget)]
+pub struct ShellNotifications {
+    #[redraw]
+    #[rust]
+    area: Area,
+
+    /// Notification center background
+    #[live]
+    draw_bg: DrawQuad,
+
+    #[walk]
+    walk: Walk,
+
+    #[layout]
+    layout: Layout,
+
+    #[live]
+    center_walk: Walk,
+
+    #[live]
+    center_layout: Layout,
+
+    #[live]
+    toast_template: Option<LivePtr>,
+
+    #[live]
+    center_header_template: Option<LivePtr>,
+
+    #[live]
+    center_row_template: Option<LivePtr>,
+
+    #[live]
+    center_empty_template: Option<LivePtr>,
+
+    #[rust]
+    model: NotificationCenter,
+
+    /// Toast widgets by notification ID
+    #[rust]
+    toasts: ComponentMap<LiveId, WidgetRef>,
+
+    /// Notification center rows by notification ID
+    #[rust]
+    rows: ComponentMap<LiveId, WidgetRef>,
+
+    #[rust]
+    center_header: WidgetRef,
+
+    #[rust]
+    center_empty: WidgetRef,
+
+    #[rust]
+    center_open: bool,
+
+    /// Fires when the next toast expires
+    #[rust]
+    timer: Timer,
+
+    /// Drives busy progress bars
+    #[rust]
+    next_frame: NextFrame,
+
+    /// Keep busy progress bars still
+    #[rust]
+    reduce_motion: bool,
+}
+
+impl Widget for ShellNotifications {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        let uid = self.widget_uid();
+
+        if self.timer.is_event(event).is_some() {
+            self.timer = Timer::empty();
+            if !self.model.expire(Cx::time_now()).is_empty() {
+                self.sync_toasts(cx);
+            }
+            self.schedule_expiry(cx);
+        }
+
+        if let Some(ne) = self.next_frame.is_event(event) {
+            let phase = (ne.time % BUSY_CYCLE) / BUSY_CYCLE;
+            for notification in self.model.toasts().filter(|n| is_busy(n)) {
+                if let Some(toast) = self.toasts.get(&LiveId::from_str(&notification.id)) {
+                    toast.view(id!(progress_bar)).apply_over(cx, live! {
+                        draw_bg: { phase: (phase) }
+                    });
+                }
+            }
+            self.redraw(cx);
+            self.update_busy_animation(cx);
+        }
+
+        if self.center_open {
+            let actions = cx.capture_actions(|cx| self.center_header.handle_event(cx, event, scope));
+            if self.center_header.button(id!(clear_btn)).clicked(&actions) {
+                self.clear_history(cx);
+                cx.widget_action(uid, &scope.path, ShellNotificationsAction::CenterChanged);
+            }
+            let escape = matches!(event, Event::KeyDown(ke) if ke.key_code == KeyCode::Escape);
+            if self.center_header.button(id!(close_btn)).clicked(&actions) || escape {
+                self.set_center_open(cx, false);
+                cx.widget_action(uid, &scope.path, ShellNotificationsAction::CenterChanged);
+            }
+            return;
+        }
+
+        let actions = cx.capture_actions(|cx| {
+            for toast in self.toasts.values() {
+                toast.handle_event(cx, event, scope);
+            }
+        });
+        let mut emitted = Vec::new();
+        for notification in self.model.toasts() {
+            let Some(toast) = self.toasts.get(&LiveId::from_str(&notification.id)) else {
+                continue;
+            };
+            if toast.button(id!(close_btn)).clicked(&actions) {
+                emitted.push(ShellNotificationsAction::Dismissed(notification.id.clone()));
+            }
+            for (index, action) in notification.actions.iter().take(MAX_TOAST_ACTIONS).enumerate() {
+                if toast.button(&[action_button_id(index)]).clicked(&actions) {
+                    emitted.push(ShellNotificationsAction::ActionClicked {
+                        notification_id: notification.id.clone(),
+                        action_id: action.id.clone(),
+                    });
+                }
+            }
+        }
+        for action in emitted {
+            let id = match &action {
+                ShellNotificationsAction::Dismissed(id) => id,
+                ShellNotificationsAction::ActionClicked { notification_id, .. } => notification_id,
+                _ => continue,
+            };
+            self.dismiss(cx, &id.clone());
+            cx.widget_action(uid, &scope.path, action);
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        let palette = current_palette();
+        let bg = palette.live_nodes(&[live_id!(draw_bg)]);
+        let text = palette.live_nodes(&[live_id!(draw_text)]);
+        let button = palette.live_nodes(&[live_id!(draw_bg), live_id!(draw_text)]);
+
+        cx.begin_turtle(walk, self.layout);
+        if self.center_open {
+            self.draw_bg.apply_over(cx, &palette.uniform_nodes());
+            self.draw_bg.begin(cx, self.center_walk, self.center_layout);
+
+            self.center_header.label(id!(title)).apply_over(cx, &text);
+            self.center_header.button(id!(clear_btn)).apply_over(cx, &button);
+            self.center_header.button(id!(close_btn)).apply_over(cx, &bg);
+            self.center_header.draw_all(cx, scope);
+
+            let history = self.model.history();
+            if history.is_empty() {
+                self.center_empty.apply_over(cx, &text);
+                self.center_empty.draw_all(cx, scope);
+            }
+            let now = Cx::time_now();
+            for record in history.iter().rev().take(MAX_CENTER_ROWS) {
+                if let Some(row) = self.rows.get(&LiveId::from_str(&record.notification.id)) {
+                    row.label(id!(age)).set_text(cx, &format_age(now - record.time));
+                    row.view(id!(dot)).apply_over(cx, &bg);
+                    for label in [id!(title), id!(message), id!(age)] {
+                        row.label(label).apply_over(cx, &text);
+                    }
+                    row.draw_all(cx, scope);
+                }
+            }
+
+            self.draw_bg.end(cx);
+        } else {
+            for notification in self.model.toasts() {
+                if let Some(toast) = self.toasts.get(&LiveId::from_str(&notification.id)) {
+                    toast.apply_over(cx, &bg);
+                    toast.view(id!(progress_bar)).apply_over(cx, &bg);
+                    toast.button(id!(close_btn)).apply_over(cx, &bg);
+                    for label in [id!(title), id!(message)] {
+                        toast.label(label).apply_over(cx, &text);
+                    }
+                    for index in 0..MAX_TOAST_ACTIONS {
+                        toast.button(&[action_button_id(index)]).apply_over(cx, &button);
+                    }
+                    toast.draw_all(cx, scope);
+                }
+            }
+        }
+        cx.end_turtle_with_area(&mut self.area);
+
+        DrawStep::done()
+    }
+}
+
+impl ShellNotifications {
+    /// Show a notification, replacing the one with the same ID
+    ///
+    /// Returns the notification ID (assigned if empty).
+    pub fn notify(&mut self, cx: &mut Cx, notification: Notification) -> String {
+        let id = self.model.notify(notification, Cx::time_now());
+        self.sync_toasts(cx);
+        self.sync_rows(cx);
+        self.schedule_expiry(cx);
+        id
+    }
+
+    /// Set the progress of a notification (`None` = busy)
+    ///
+    /// Returns false if no notification has this ID.
+    pub fn set_progress(&mut self, cx: &mut Cx, id: &str, progress: Option<f64>) -> bool {
+        if !self.model.set_progress(id, progress) {
+            return false;
+        }
+        self.sync_toasts(cx);
+        true
+    }
+
+    /// Close a toast (it stays in the history)
+    ///
+    /// Returns false if no toast has this ID.
+    pub fn dismiss(&mut self, cx: &mut Cx, id: &str) -> bool {
+        if !self.model.dismiss(id) {
+            return false;
+        }
+        self.sync_toasts(cx);
+        self.schedule_expiry(cx);
+        true
+    }
+
+    /// Open or close the notification center (opening marks the history as read)
+    pub fn set_center_open(&mut self, cx: &mut Cx, open: bool) {
+        if open {
+            if self.center_header.is_empty() {
+                self.center_header = WidgetRef::new_from_ptr(cx, self.center_header_template);
+                self.center_empty = WidgetRef::new_from_ptr(cx, self.center_empty_template);
+            }
+            self.model.mark_all_read();
+        }
+        self.center_open = open;
+        self.redraw(cx);
+    }
+
+    /// Whether the notification center is open
+    pub fn is_center_open(&self) -> bool {
+        self.center_open
+    }
+
+    /// Notification history, oldest first
+    pub fn history(&self) -> &[NotificationRecord] {
+        self.model.history()
+    }
+
+    /// Notifications not yet seen in the notification center
+    pub fn unread_count(&self) -> usize {
+        self.model.unread_count()
+    }
+
+    /// Forget the history (visible toasts stay)
+    pub fn clear_history(&mut self, cx: &mut Cx) {
+        self.model.clear_history();
+        self.sync_rows(cx);
+    }
+
+    /// Keep busy progress bars still (true) or moving
+    pub fn set_reduce_motion(&mut self, cx: &mut Cx, reduce_motion: bool) {
+        self.reduce_motion = reduce_motion;
+        self.update_busy_animation(cx);
+    }
+
+    /// Create and fill the toast widgets of the visible toasts
+    fn sync_toasts(&mut self, cx: &mut Cx) {
+        let template = self.toast_template;
+        let visible: Vec<LiveId> = self.model.toasts().map(|n| LiveId::from_str(&n.id)).collect();
+        self.toasts.retain(|id, _| visible.contains(id));
+
+        for notification in self.model.toasts() {
+            let toast = self.toasts.get_or_insert(cx, LiveId::from_str(&notification.id), |cx| {
+                WidgetRef::new_from_ptr(cx, template)
+            });
+            let kind = notification.kind.shader_value();
+            toast.apply_over(cx, live! {
+                draw_bg: { kind: (kind) }
+            });
+            toast.label(id!(title)).set_text(cx, &notification.title);
+            let message = toast.label(id!(message));
+            message.set_text(cx, &notification.message);
+            message.set_visible(cx, !notification.message.is_empty());
+
+            let progress_bar = toast.view(id!(progress_bar));
+            progress_bar.set_visible(cx, notification.kind == NotificationKind::Progress);
+            let busy = if is_busy(notification) { 1.0 } else { 0.0 };
+            let progress = notification.progress.unwrap_or(0.0);
+            progress_bar.apply_over(cx, live! {
+                draw_bg: { busy: (busy), progress: (progress), phase: 0.5 }
+            });
+
+            toast.view(id!(actions)).set_visible(cx, !notification.actions.is_empty());
+            for index in 0..MAX_TOAST_ACTIONS {
+                let button = toast.button(&[action_button_id(index)]);
+                match notification.actions.get(index) {
+                    Some(action) => {
+                        button.set_text(cx, &action.label);
+                        button.set_visible(cx, true);
+                    }
+                    None => button.set_visible(cx, false),
+                }
+            }
+        }
+        self.update_busy_animation(cx);
+        self.redraw(cx);
+    }
+
+    /// Create and fill the notification center rows
+    fn sync_rows(&mut self, cx: &mut Cx) {
+        let template = self.center_row_template;
+        let listed: Vec<LiveId> = self.model.history().iter()
+            .rev()
+            .take(MAX_CENTER_ROWS)
+            .map(|record| LiveId::from_str(&record.notification.id))
+            .collect();
+        self.rows.retain(|id, _| listed.contains(id));
+
+        for record in self.model.history().iter().rev().take(MAX_CENTER_ROWS) {
+            let notification = &record.notification;
+            let row = self.rows.get_or_insert(cx, LiveId::from_str(&notification.id), |cx| {
+                WidgetRef::new_from_ptr(cx, template)
+            });
+            let kind = notification.kind.shader_value();
+            row.view(id!(dot)).apply_over(cx, live! {
+                draw_bg: { kind: (kind) }
+            });
+            row.label(id!(title)).set_text(cx, &notification.title);
+            let message = row.label(id!(message));
+            message.set_text(cx, &notification.message);
+            message.set_visible(cx, !notification.message.is_empty());
+        }
+        self.redraw(cx);
+    }
+
+    /// Restart the timer for the next toast to expire
+    fn schedule_expiry(&mut self, cx: &mut Cx) {
+        cx.stop_timer(self.timer);
+        self.timer = match self.model.next_expiry() {
+            Some(expires) => cx.start_timeout((expires - Cx::time_now()).max(0.01)),
+            None => Timer::empty(),
+        };
+    }
+
+    /// Request the next frame while a busy progress bar should move
+    fn update_busy_animation(&mut self, cx: &mut Cx) {
+        if !self.reduce_motion && self.model.toasts().any(is_busy) {
+            self.next_frame = cx.new_next_frame();
+        }
+    }
+}
+
+/// A progress notification without a known fraction
+fn is_busy(notification: &Notification) -> bool {
+    notification.kind == NotificationKind::Progress && notification.progress.is_none()
+}
+
+impl ShellNotificationsRef {
+    /// Show a notification, replacing the one with the same ID
+    pub fn notify(&self, cx: &mut Cx, notification: Notification) -> Option<String> {
+        self.borrow_mut().map(|mut inner| inner.notify(cx, notification))
+    }
+
+    /// Set the progress of a notification (`None` = busy)
+    pub fn set_progress(&self, cx: &mut Cx, id: &str, progress: Option<f64>) -> bool {
+        self.borrow_mut().map_or(false, |mut inner| inner.set_progress(cx, id, progress))
+    }
+
+    /// Close a toast
+    pub fn dismiss(&self, cx: &mut Cx, id: &str) -> bool {
+        self.borrow_mut().map_or(false, |mut inner| inner.dismiss(cx, id))
+    }
+
+    /// Open or close the notification center
+    pub fn set_center_open(&self, cx: &mut Cx, open: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_center_open(cx, open);
+        }
+    }
+
+    /// Whether the notification center is open
+    pub fn is_center_open(&self) -> bool {
+        self.borrow().map_or(false, |inner| inner.is_center_open())
+    }
+
+    /// Notification history, oldest first
+    pub fn history(&self) -> Vec<NotificationRecord> {
+        self.borrow().map(|inner| inner.history().to_vec()).unwrap_or_default()
+    }
+
+    /// Notifications not yet seen in the notification center
+    pub fn unread_count(&self) -> usize {
+        self.borrow().map_or(0, |inner| inner.unread_count())
+    }
+
+    /// Forget the history
+    pub fn clear_history(&self, cx: &mut Cx) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.clear_history(cx);
+        }
+    }
+
+    /// Keep busy progress bars still (true) or moving
+    pub fn set_reduce_motion(&self, cx: &mut Cx, reduce_motion: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_reduce_motion(cx, reduce_motion);
+        }
+    }
+
+    /// All notification actions in this action batch
+    pub fn notification_actions(&self, actions: &Actions) -> Vec<ShellNotificationsAction> {
+        actions.filter_widget_actions(self.widget_uid())
+            .into_iter()
+            .flatten()
+            .map(|action| action.cast::<ShellNotificationsAction>())
+            .filter(|action| !matches!(action, ShellNotificationsAction::None))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notify_assigns_ids_and_replaces() {
+        let mut center = NotificationCenter::default();
+        let first = center.notify(Notification::info("Saved"), 0.0);
+        let second = center.notify(Notification::info("Saved"), 0.0);
+        assert_ne!(first, second);
+
+        center.notify(Notification::progress("Indexing").with_id("index"), 1.0);
+        center.notify(Notification::info("Indexed").with_id("index"), 2.0);
+        assert_eq!(center.toasts().count(), 3);
+        assert_eq!(center.history().len(), 3);
+        assert_eq!(center.history().last().unwrap().notification.title, "Indexed");
+    }
+
+    #[test]
+    fn test_toasts_expire_by_kind() {
+        let mut center = NotificationCenter::default();
+        center.notify(Notification::info("Info").with_id("info"), 0.0);
+        center.notify(Notification::warning("Warning").with_id("warning"), 0.0);
+        center.notify(Notification::error("Error").with_id("error"), 0.0);
+
+        assert_eq!(center.next_expiry(), Some(5.0));
+        assert_eq!(center.expire(5.0), ["info"]);
+        assert_eq!(center.expire(10.0), ["warning"]);
+        assert_eq!(center.next_expiry(), None);
+        // Errors stay until dismissed, and the history keeps everything
+        assert!(center.dismiss("error"));
+        assert_eq!(center.toasts().count(), 0);
+        assert_eq!(center.history().len(), 3);
+    }
+
+    #[test]
+    fn test_toast_stack_is_capped() {
+        let mut center = NotificationCenter::default();
+        for index in 0..MAX_TOASTS + 2 {
+            center.notify(Notification::error(format!("Error {}", index)), 0.0);
+        }
+        assert_eq!(center.toasts().count(), MAX_TOASTS);
+        assert_eq!(center.toasts().next().unwrap().title, "Error 2");
+    }
+
+    #[test]
+    fn test_progress_and_unread() {
+        let mut center = NotificationCenter::default();
+        center.notify(Notification::progress("Sync").with_id("sync"), 0.0);
+        assert!(center.set_progress("sync", Some(1.5)));
+        assert_eq!(center.toasts().next().unwrap().progress, Some(1.0));
+        assert!(!center.set_progress("missing", None));
+
+        assert_eq!(center.unread_count(), 1);
+        center.mark_all_read();
+        assert_eq!(center.unread_count(), 0);
+    }
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(30.0), "now");
+        assert_eq!(format_age(300.0), "5 min");
+        assert_eq!(format_age(7200.0), "2 h");
+        assert_eq!(format_age(3.0 * 86400.0), "3 d");
+    }
+}
@@ -1,13 +1,16 @@
 //! Shell sidebar widget with app menu
 //!
 //! Provides a sidebar with:
-//! - App selection menu items with hover effects
-//! - Show More/Less expandable section
-//! - Selection state tracking
-//! - Dark mode support
+//! - Data-driven menu items (`SidebarItem`) with icons, badges and sections
+//! - Optional hierarchical tree (`SidebarTreeNode`) with lazy children
+//! - Selection state tracking by semantic item ID
+//! - Palette-driven theming (light/dark and custom palettes)
 
 use makepad_widgets::*;
-use crate::theme::get_global_dark_mode;
+use serde::{Deserialize, Serialize};
+use crate::shell::sidebar_menu::{retained_selection, SidebarItem, SidebarMenuWidgetExt};
+use crate::shell::sidebar_tree::{SidebarTreeAction, SidebarTreeNode, SidebarTreeWidgetExt};
+use crate::theme::{ShellPalette, ThemeListener, ThemeTracker};
 
 live_design! {
     use link::theme::*;
@@ -16,6 +19,7 @@ live_design! {
 
     use crate::live_design::*;
     use crate::shell::sidebar_menu::*;
+    use crate::shell::sidebar_tree::*;
 
     // Sidebar header component
     pub ShellSidebarHeader = <View> {
@@ -26,24 +30,18 @@ live_design! {
 
         show_bg: true
         draw_bg: {
-            instance dark_mode: 0.0
+            uniform bg_sidebar: (PALETTE_BG_SIDEBAR)
             fn pixel(self) -> vec4 {
-                // Light: slate-100, Dark: slate-800
-                let light = vec4(0.945, 0.961, 0.976, 1.0);
-                let dark = vec4(0.122, 0.161, 0.231, 1.0);
-                return mix(light, dark, self.dark_mode);
+                return self.bg_sidebar;
             }
         }
 
         header_label = <Label> {
             draw_text: {
-                instance dark_mode: 0.0
+                uniform text_secondary: (PALETTE_TEXT_SECONDARY)
                 text_style: <FONT_SEMIBOLD> { font_size: 12.0 }
                 fn get_color(self) -> vec4 {
-                    // Light: gray-700, Dark: slate-200
-                    let light = vec4(0.247, 0.282, 0.333, 1.0);
-                    let dark = vec4(0.886, 0.910, 0.941, 1.0);
-                    return mix(light, dark, self.dark_mode);
+                    return self.text_secondary;
                 }
             }
             text: "Apps"
@@ -57,11 +55,9 @@ live_design! {
         margin: { top: 8, bottom: 8, left: 8, right: 8 }
         show_bg: true
         draw_bg: {
-            instance dark_mode: 0.0
+            uniform border: (PALETTE_BORDER)
             fn pixel(self) -> vec4 {
-                let light = vec4(0.886, 0.910, 0.941, 1.0);  // slate-200
-                let dark = vec4(0.192, 0.231, 0.302, 1.0);   // slate-700
-                return mix(light, dark, self.dark_mode);
+                return self.border;
             }
         }
     }
@@ -74,92 +70,23 @@ live_design! {
 
         show_bg: true
         draw_bg: {
-            instance dark_mode: 0.0
+            uniform bg_list: (PALETTE_BG_LIST)
             fn pixel(self) -> vec4 {
-                // Light: slate-50, Dark: slate-900
-                let light = vec4(0.973, 0.980, 0.988, 1.0);
-                let dark = vec4(0.059, 0.090, 0.165, 1.0);
-                return mix(light, dark, self.dark_mode);
+                return self.bg_list;
             }
         }
 
         header = <ShellSidebarHeader> {}
 
-        // Main menu section
-        menu_section = <View> {
-            width: Fill
-            height: Fit
-            flow: Down
+        // Main menu section (items are set at runtime via `ShellSidebarRef::set_items`)
+        menu_section = <SidebarMenu> {
             padding: { left: 8, right: 8, top: 4, bottom: 4 }
-            spacing: 2
-
-            // Primary apps with icons
-            app_btn_0 = <SidebarMenuButton> {
-                text: "Dashboard"
-                draw_icon: { svg_file: dep("crate://makepad-widgets/resources/icons/icon_layout.svg") }
-            }
-            app_btn_1 = <SidebarMenuButton> {
-                text: "Editor"
-                draw_icon: { svg_file: dep("crate://makepad-widgets/resources/icons/icon_file.svg") }
-            }
-            app_btn_2 = <SidebarMenuButton> {
-                text: "Terminal"
-                draw_icon: { svg_file: dep("crate://makepad-widgets/resources/icons/icon_text.svg") }
-            }
-            app_btn_3 = <SidebarMenuButton> {
-                text: "Explorer"
-                draw_icon: { svg_file: dep("crate://makepad-widgets/resources/icons/icon_folder.svg") }
-            }
-
-            // Show More button (using Button for reliable click detection)
-            show_more_btn = <Button> {
-                width: Fill, height: Fit
-                padding: {top: 8, bottom: 8, left: 12, right: 12}
-                align: {x: 0.0, y: 0.5}
-                draw_bg: {
-                    instance dark_mode: 0.0
-                    fn pixel(self) -> vec4 {
-                        let light = vec4(0.973, 0.980, 0.988, 1.0); // slate-50
-                        let dark = vec4(0.059, 0.090, 0.165, 1.0);  // slate-900
-                        return mix(light, dark, self.dark_mode);
-                    }
-                }
-                draw_text: {
-                    instance dark_mode: 0.0
-                    text_style: <FONT_REGULAR> { font_size: 10.0 }
-                    fn get_color(self) -> vec4 {
-                        let light = vec4(0.392, 0.455, 0.545, 1.0); // slate-500
-                        let dark = vec4(0.580, 0.639, 0.722, 1.0);  // slate-400
-                        return mix(light, dark, self.dark_mode);
-                    }
-                }
-                text: "Show More >"
-            }
-
-            // Collapsible section for additional apps
-            more_apps_section = <View> {
-                width: Fill, height: Fit
-                flow: Down
-                spacing: 2
-                visible: false
+        }
 
-                app_btn_4 = <SidebarMenuButton> {
-                    text: "Database"
-                    draw_icon: { svg_file: dep("crate://makepad-widgets/resources/icons/icon_widget.svg") }
-                }
-                app_btn_5 = <SidebarMenuButton> {
-                    text: "Network"
-                    draw_icon: { svg_file: dep("crate://makepad-widgets/resources/icons/icon_vector.svg") }
-                }
-                app_btn_6 = <SidebarMenuButton> {
-                    text: "Metrics"
-                    draw_icon: { svg_file: dep("crate://makepad-widgets/resources/icons/icon_draw.svg") }
-                }
-                app_btn_7 = <SidebarMenuButton> {
-                    text: "Logs"
-                    draw_icon: { svg_file: dep("crate://makepad-widgets/resources/icons/icon_text.svg") }
-                }
-            }
+        // Hierarchical navigation (shown once nodes are set via `ShellSidebarRef::set_tree`)
+        tree_section = <SidebarTree> {
+            visible: false
+            padding: { left: 8, right: 8, top: 4, bottom: 4 }
         }
 
         // Separator
@@ -187,12 +114,30 @@ live_design! {
 }
 
 /// Selection state for the sidebar
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum SidebarSelection {
-    App(usize),
+    /// A menu item, by semantic ID
+    Item(String),
+    /// A tree node, by full path of node IDs
+    TreeItem(Vec<String>),
     Settings,
 }
 
+/// Default menu items shown until the app calls `set_items`
+pub fn default_sidebar_items() -> Vec<SidebarItem> {
+    const ICONS: &str = "crate://makepad-widgets/resources/icons";
+    vec![
+        SidebarItem::new("dashboard", "Dashboard").with_icon(format!("{}/icon_layout.svg", ICONS)),
+        SidebarItem::new("editor", "Editor").with_icon(format!("{}/icon_file.svg", ICONS)),
+        SidebarItem::new("terminal", "Terminal").with_icon(format!("{}/icon_text.svg", ICONS)),
+        SidebarItem::new("explorer", "Explorer").with_icon(format!("{}/icon_folder.svg", ICONS)),
+        SidebarItem::new("database", "Database").with_icon(format!("{}/icon_widget.svg", ICONS)).with_section("More"),
+        SidebarItem::new("network", "Network").with_icon(format!("{}/icon_vector.svg", ICONS)).with_section("More"),
+        SidebarItem::new("metrics", "Metrics").with_icon(format!("{}/icon_draw.svg", ICONS)).with_section("More"),
+        SidebarItem::new("logs", "Logs").with_icon(format!("{}/icon_text.svg", ICONS)).with_section("More"),
+    ]
+}
+
 /// Shell sidebar widget with app menu
 #[derive(Live, LiveHook, Widget)]
 pub struct ShellSidebar {
@@ -205,56 +150,80 @@ pub struct ShellSidebar {
     #[rust]
     selection: Option<SidebarSelection>,
 
+    /// Whether menu items have been set (by the app or from defaults)
+    #[rust]
+    items_initialized: bool,
+
+    /// Actions raised outside of event handling, emitted on the next event
     #[rust]
-    more_apps_visible: bool,
+    pending_actions: Vec<SidebarAction>,
+
+    #[rust]
+    theme_tracker: ThemeTracker,
+}
+
+impl ThemeListener for ShellSidebar {
+    fn theme_tracker(&mut self) -> &mut ThemeTracker {
+        &mut self.theme_tracker
+    }
+
+    fn on_dark_mode_change(&mut self, _cx: &mut Cx, _dark_mode: f64) {}
+
+    fn on_palette_change(&mut self, cx: &mut Cx, palette: &ShellPalette) {
+        self.apply_palette_internal(cx, palette);
+    }
 }
 
 impl Widget for ShellSidebar {
     fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
-        // Capture actions from child widgets (buttons)
+        for action in std::mem::take(&mut self.pending_actions) {
+            cx.widget_action(self.widget_uid(), &scope.path, action);
+        }
+
+        // Capture actions from child widgets (menu and buttons)
         let actions = cx.capture_actions(|cx| {
             self.view.handle_event(cx, event, scope);
         });
 
-        // Handle Show More/Less click
-        if self.view.button(id!(menu_section.show_more_btn)).clicked(&actions) {
-            self.toggle_more_apps(cx);
-        }
-
-        // Handle app button clicks
-        if self.view.button(id!(menu_section.app_btn_0)).clicked(&actions) {
-            self.handle_selection(cx, SidebarSelection::App(0), scope);
-        }
-        if self.view.button(id!(menu_section.app_btn_1)).clicked(&actions) {
-            self.handle_selection(cx, SidebarSelection::App(1), scope);
-        }
-        if self.view.button(id!(menu_section.app_btn_2)).clicked(&actions) {
-            self.handle_selection(cx, SidebarSelection::App(2), scope);
-        }
-        if self.view.button(id!(menu_section.app_btn_3)).clicked(&actions) {
-            self.handle_selection(cx, SidebarSelection::App(3), scope);
-        }
-        if self.view.button(id!(menu_section.more_apps_section.app_btn_4)).clicked(&actions) {
-            self.handle_selection(cx, SidebarSelection::App(4), scope);
-        }
-        if self.view.button(id!(menu_section.more_apps_section.app_btn_5)).clicked(&actions) {
-            self.handle_selection(cx, SidebarSelection::App(5), scope);
-        }
-        if self.view.button(id!(menu_section.more_apps_section.app_btn_6)).clicked(&actions) {
-            self.handle_selection(cx, SidebarSelection::App(6), scope);
-        }
-        if self.view.button(id!(menu_section.more_apps_section.app_btn_7)).clicked(&actions) {
-            self.handle_selection(cx, SidebarSelection::App(7), scope);
+        if let Some(item_id) = self.view.sidebar_menu(id!(menu_section)).item_clicked(&actions) {
+            self.handle_selection(cx, SidebarSelection::Item(item_id), scope);
         }
         if self.view.button(id!(bottom_section.settings_btn)).clicked(&actions) {
             self.handle_selection(cx, SidebarSelection::Settings, scope);
         }
+
+        // Re-emit tree actions as sidebar actions so parents only track the sidebar
+        for action in self.view.sidebar_tree(id!(tree_section)).tree_actions(&actions) {
+            match action {
+                SidebarTreeAction::Selected(path) => {
+                    self.handle_selection(cx, SidebarSelection::TreeItem(path), scope);
+                }
+                SidebarTreeAction::Expanded { path, expanded } => {
+                    cx.widget_action(
+                        self.widget_uid(),
+                        &scope.path,
+                        SidebarAction::TreeNodeExpanded { path, expanded },
+                    );
+                }
+                SidebarTreeAction::LoadChildren(path) => {
+                    cx.widget_action(
+                        self.widget_uid(),
+                        &scope.path,
+                        SidebarAction::LoadChildren(path),
+                    );
+                }
+                SidebarTreeAction::None => {}
+            }
+        }
     }
 
     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
-        // Apply global theme on every draw
-        let dm = get_global_dark_mode();
-        self.apply_dark_mode_internal(cx, dm);
+        if !self.items_initialized {
+            self.set_items(cx, default_sidebar_items());
+        }
+
+        // Apply the shell theme when it changed
+        self.sync_theme(cx);
 
         if !self.title.is_empty() {
             self.view.label(id!(header.header_label)).set_text(cx, &self.title);
@@ -265,31 +234,22 @@ impl Widget for ShellSidebar {
 }
 
 impl ShellSidebar {
-    fn toggle_more_apps(&mut self, cx: &mut Cx) {
-        self.more_apps_visible = !self.more_apps_visible;
-
-        // Toggle visibility
-        self.view.view(id!(menu_section.more_apps_section))
-            .set_visible(cx, self.more_apps_visible);
-
-        // Update button text
-        if self.more_apps_visible {
-            self.view.button(id!(menu_section.show_more_btn))
-                .set_text(cx, "Show Less ^");
-        } else {
-            self.view.button(id!(menu_section.show_more_btn))
-                .set_text(cx, "Show More >");
+    fn set_items(&mut self, cx: &mut Cx, items: Vec<SidebarItem>) {
+        self.items_initialized = true;
+
+        // A selected item that is gone is deselected, and the parent forgets it too
+        if let Some(SidebarSelection::Item(id)) = &self.selection {
+            if retained_selection(Some(id.clone()), &items).is_none() {
+                self.selection = None;
+                self.pending_actions.push(SidebarAction::SelectionChanged(None));
+                cx.new_next_frame();
+            }
         }
-
-        self.view.redraw(cx);
+        self.view.sidebar_menu(id!(menu_section)).set_items(cx, items);
     }
 
     fn handle_selection(&mut self, cx: &mut Cx, selection: SidebarSelection, scope: &mut Scope) {
-        // Clear all selections first
-        self.clear_all_selections(cx);
-
-        // Apply selection
-        self.apply_selection(cx, &selection);
+        self.apply_selection(cx, Some(&selection));
         self.selection = Some(selection.clone());
 
         // Emit action to parent
@@ -302,111 +262,39 @@ impl ShellSidebar {
         self.view.redraw(cx);
     }
 
-    fn clear_all_selections(&mut self, cx: &mut Cx) {
-        // Clear all app buttons
-        self.view.button(id!(menu_section.app_btn_0)).apply_over(cx, live!{ draw_bg: { selected: 0.0 } });
-        self.view.button(id!(menu_section.app_btn_1)).apply_over(cx, live!{ draw_bg: { selected: 0.0 } });
-        self.view.button(id!(menu_section.app_btn_2)).apply_over(cx, live!{ draw_bg: { selected: 0.0 } });
-        self.view.button(id!(menu_section.app_btn_3)).apply_over(cx, live!{ draw_bg: { selected: 0.0 } });
-        self.view.button(id!(menu_section.more_apps_section.app_btn_4)).apply_over(cx, live!{ draw_bg: { selected: 0.0 } });
-        self.view.button(id!(menu_section.more_apps_section.app_btn_5)).apply_over(cx, live!{ draw_bg: { selected: 0.0 } });
-        self.view.button(id!(menu_section.more_apps_section.app_btn_6)).apply_over(cx, live!{ draw_bg: { selected: 0.0 } });
-        self.view.button(id!(menu_section.more_apps_section.app_btn_7)).apply_over(cx, live!{ draw_bg: { selected: 0.0 } });
-        self.view.button(id!(bottom_section.settings_btn)).apply_over(cx, live!{ draw_bg: { selected: 0.0 } });
-    }
-
-    fn apply_selection(&mut self, cx: &mut Cx, selection: &SidebarSelection) {
-        match selection {
-            SidebarSelection::App(0) => {
-                self.view.button(id!(menu_section.app_btn_0)).apply_over(cx, live!{ draw_bg: { selected: 1.0 } });
-            }
-            SidebarSelection::App(1) => {
-                self.view.button(id!(menu_section.app_btn_1)).apply_over(cx, live!{ draw_bg: { selected: 1.0 } });
-            }
-            SidebarSelection::App(2) => {
-                self.view.button(id!(menu_section.app_btn_2)).apply_over(cx, live!{ draw_bg: { selected: 1.0 } });
-            }
-            SidebarSelection::App(3) => {
-                self.view.button(id!(menu_section.app_btn_3)).apply_over(cx, live!{ draw_bg: { selected: 1.0 } });
-            }
-            SidebarSelection::App(4) => {
-                self.view.button(id!(menu_section.more_apps_section.app_btn_4)).apply_over(cx, live!{ draw_bg: { selected: 1.0 } });
-            }
-            SidebarSelection::App(5) => {
-                self.view.button(id!(menu_section.more_apps_section.app_btn_5)).apply_over(cx, live!{ draw_bg: { selected: 1.0 } });
-            }
-            SidebarSelection::App(6) => {
-                self.view.button(id!(menu_section.more_apps_section.app_btn_6)).apply_over(cx, live!{ draw_bg: { selected: 1.0 } });
-            }
-            SidebarSelection::App(7) => {
-                self.view.button(id!(menu_section.more_apps_section.app_btn_7)).apply_over(cx, live!{ draw_bg: { selected: 1.0 } });
-            }
-            SidebarSelection::Settings => {
-                self.view.button(id!(bottom_section.settings_btn)).apply_over(cx, live!{ draw_bg: { selected: 1.0 } });
-            }
-            _ => {}
-        }
+    fn apply_selection(&mut self, cx: &mut Cx, selection: Option<&SidebarSelection>) {
+        let item_id = match selection {
+            Some(SidebarSelection::Item(id)) => Some(id.as_str()),
+            _ => None,
+        };
+        self.view.sidebar_menu(id!(menu_section)).select(cx, item_id);
+
+        let tree_path = match selection {
+            Some(SidebarSelection::TreeItem(path)) => Some(path.as_slice()),
+            _ => None,
+        };
+        self.view.sidebar_tree(id!(tree_section)).select(cx, tree_path);
+
+        let settings = if selection == Some(&SidebarSelection::Settings) { 1.0 } else { 0.0 };
+        self.view.button(id!(bottom_section.settings_btn)).apply_over(cx, live!{ draw_bg: { selected: (settings) } });
     }
 
-    pub fn apply_dark_mode_internal(&mut self, cx: &mut Cx, dark_mode: f64) {
-        // Background
-        self.view.apply_over(cx, live! {
-            draw_bg: { dark_mode: (dark_mode) }
-        });
+    pub fn apply_palette_internal(&mut self, cx: &mut Cx, palette: &ShellPalette) {
+        let bg = palette.live_nodes(&[live_id!(draw_bg)]);
 
-        // Header
-        self.view.view(id!(header)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dark_mode) }
-        });
-        self.view.label(id!(header.header_label)).apply_over(cx, live! {
-            draw_text: { dark_mode: (dark_mode) }
-        });
+        // Background, header and separator
+        self.view.apply_over(cx, &bg);
+        self.view.view(id!(header)).apply_over(cx, &bg);
+        self.view.label(id!(header.header_label)).apply_over(cx, &palette.live_nodes(&[live_id!(draw_text)]));
+        self.view.view(id!(separator)).apply_over(cx, &bg);
 
-        // Separator
-        self.view.view(id!(separator)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dark_mode) }
-        });
-
-        // Menu buttons in menu_section
-        let menu_button_ids = [
-            id!(menu_section.app_btn_0), id!(menu_section.app_btn_1),
-            id!(menu_section.app_btn_2), id!(menu_section.app_btn_3),
-        ];
-        for btn_id in &menu_button_ids {
-            self.view.button(*btn_id).apply_over(cx, live! {
-                draw_bg: { dark_mode: (dark_mode) }
-                draw_text: { dark_mode: (dark_mode) }
-                draw_icon: { dark_mode: (dark_mode) }
-            });
-        }
-
-        // Show More button
-        self.view.button(id!(menu_section.show_more_btn)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dark_mode) }
-            draw_text: { dark_mode: (dark_mode) }
-        });
-
-        // More apps section buttons
-        let more_button_ids = [
-            id!(menu_section.more_apps_section.app_btn_4),
-            id!(menu_section.more_apps_section.app_btn_5),
-            id!(menu_section.more_apps_section.app_btn_6),
-            id!(menu_section.more_apps_section.app_btn_7),
-        ];
-        for btn_id in &more_button_ids {
-            self.view.button(*btn_id).apply_over(cx, live! {
-                draw_bg: { dark_mode: (dark_mode) }
-                draw_text: { dark_mode: (dark_mode) }
-                draw_icon: { dark_mode: (dark_mode) }
-            });
-        }
+        // Menu items read the scope palette when drawn
 
         // Settings button
-        self.view.button(id!(bottom_section.settings_btn)).apply_over(cx, live! {
-            draw_bg: { dark_mode: (dark_mode) }
-            draw_text: { dark_mode: (dark_mode) }
-            draw_icon: { dark_mode: (dark_mode) }
-        });
+        self.view.button(id!(bottom_section.settings_btn)).apply_over(
+            cx,
+            &palette.live_nodes(&[live_id!(draw_bg), live_id!(draw_text), live_id!(draw_icon)]),
+        );
     }
 }
 
@@ -418,9 +306,75 @@ impl ShellSidebarRef {
         }
     }
 
+    /// Replace the menu items
+    ///
+    /// The current selection is kept if its item ID is still present; otherwise it is
+    /// cleared and reported as `SidebarAction::SelectionChanged(None)`.
+    pub fn set_items(&self, cx: &mut Cx, items: Vec<SidebarItem>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_items(cx, items);
+        }
+    }
+
+    /// Set the tree nodes (an empty list hides the tree)
+    pub fn set_tree(&self, cx: &mut Cx, nodes: Vec<SidebarTreeNode>) {
+        if let Some(inner) = self.borrow() {
+            inner.view.widget(id!(tree_section)).set_visible(cx, !nodes.is_empty());
+            inner.view.sidebar_tree(id!(tree_section)).set_nodes(cx, nodes);
+        }
+    }
+
+    /// Provide the children of a lazily loaded tree node
+    ///
+    /// Call this in response to `SidebarAction::LoadChildren(path)`.
+    pub fn set_tree_children(&self, cx: &mut Cx, path: &[String], children: Vec<SidebarTreeNode>) {
+        if let Some(inner) = self.borrow() {
+            inner.view.sidebar_tree(id!(tree_section)).set_children(cx, path, children);
+        }
+    }
+
+    /// Paths of all expanded tree nodes
+    pub fn expanded_tree_paths(&self) -> Vec<Vec<String>> {
+        self.borrow()
+            .map(|inner| inner.view.sidebar_tree(id!(tree_section)).expanded_paths())
+            .unwrap_or_default()
+    }
+
+    /// Replace the set of expanded tree node paths
+    pub fn set_expanded_tree_paths(&self, cx: &mut Cx, paths: Vec<Vec<String>>) {
+        if let Some(inner) = self.borrow() {
+            inner.view.sidebar_tree(id!(tree_section)).set_expanded_paths(cx, paths);
+        }
+    }
+
+    /// Paths of lazy tree nodes whose children were requested in this action batch
+    pub fn load_children_requested(&self, actions: &Actions) -> Vec<Vec<String>> {
+        actions.filter_widget_actions(self.widget_uid())
+            .into_iter()
+            .flatten()
+            .filter_map(|action| match action.cast::<SidebarAction>() {
+                SidebarAction::LoadChildren(path) => Some(path),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Update the badge of a single menu item
+    pub fn set_badge(&self, cx: &mut Cx, item_id: &str, badge: Option<&str>) {
+        if let Some(inner) = self.borrow() {
+            inner.view.sidebar_menu(id!(menu_section)).set_badge(cx, item_id, badge);
+        }
+    }
+
+    /// Apply the built-in palettes blended by a dark mode value
     pub fn apply_dark_mode(&self, cx: &mut Cx, dark_mode: f64) {
+        self.apply_palette(cx, &ShellPalette::for_dark_mode(dark_mode));
+    }
+
+    /// Apply a palette to the sidebar
+    pub fn apply_palette(&self, cx: &mut Cx, palette: &ShellPalette) {
         if let Some(mut inner) = self.borrow_mut() {
-            inner.apply_dark_mode_internal(cx, dark_mode);
+            inner.apply_palette_internal(cx, palette);
         }
     }
 
@@ -430,13 +384,22 @@ impl ShellSidebarRef {
 
     pub fn set_selection(&self, cx: &mut Cx, selection: Option<SidebarSelection>) {
         if let Some(mut inner) = self.borrow_mut() {
-            inner.clear_all_selections(cx);
-            if let Some(ref sel) = selection {
-                inner.apply_selection(cx, sel);
-            }
+            inner.apply_selection(cx, selection.as_ref());
             inner.selection = selection;
+            inner.view.redraw(cx);
         }
     }
+
+    /// Returns the new selection if this sidebar's selection changed
+    pub fn selection_changed(&self, actions: &Actions) -> Option<Option<SidebarSelection>> {
+        actions.filter_widget_actions(self.widget_uid())
+            .into_iter()
+            .flatten()
+            .find_map(|action| match action.cast::<SidebarAction>() {
+                SidebarAction::SelectionChanged(selection) => Some(selection),
+                _ => None,
+            })
+    }
 }
 
 // ============================================================================
@@ -446,6 +409,10 @@ impl ShellSidebarRef {
 #[derive(Clone, Debug, DefaultNone)]
 pub enum SidebarAction {
     SelectionChanged(Option<SidebarSelection>),
+    /// A tree node was expanded or collapsed (full path)
+    TreeNodeExpanded { path: Vec<String>, expanded: bool },
+    /// A lazy tree node was expanded; respond with `ShellSidebarRef::set_tree_children`
+    LoadChildren(Vec<String>),
     None,
 }
 
@@ -1,6 +1,11 @@
 //! Sidebar menu widgets with hover effects and icons
+//!
+//! `SidebarMenu` renders any number of `SidebarItem`s from a template,
+//! grouped under optional section headers.
 
+use std::rc::Rc;
 use makepad_widgets::*;
+use crate::theme::current_palette;
 
 live_design! {
     use link::theme::*;
@@ -44,29 +49,16 @@ live_design! {
             instance hover: 0.0
             instance pressed: 0.0
             instance selected: 0.0
-            instance dark_mode: 0.0
+            uniform bg_list: (PALETTE_BG_LIST)
+            uniform bg_hover: (PALETTE_BG_HOVER)
+            uniform bg_selected: (PALETTE_BG_SELECTED)
+            uniform bg_pressed: (PALETTE_BG_PRESSED)
 
             fn pixel(self) -> vec4 {
                 let sdf = Sdf2d::viewport(self.pos * self.rect_size);
-                // Light mode colors
-                let normal_light = vec4(0.973, 0.980, 0.988, 1.0);
-                let hover_light = vec4(0.886, 0.910, 0.941, 1.0);
-                let selected_light = vec4(0.859, 0.914, 0.988, 1.0);
-                let pressed_light = vec4(0.792, 0.835, 0.890, 1.0);
-                // Dark mode colors
-                let normal_dark = vec4(0.059, 0.090, 0.165, 1.0);
-                let hover_dark = vec4(0.122, 0.161, 0.231, 1.0);
-                let selected_dark = vec4(0.165, 0.220, 0.310, 1.0);
-                let pressed_dark = vec4(0.200, 0.255, 0.350, 1.0);
-                // Mix based on dark_mode
-                let normal = mix(normal_light, normal_dark, self.dark_mode);
-                let hover_color = mix(hover_light, hover_dark, self.dark_mode);
-                let selected_color = mix(selected_light, selected_dark, self.dark_mode);
-                let pressed_color = mix(pressed_light, pressed_dark, self.dark_mode);
-                // Apply state
-                let color = mix(normal, hover_color, self.hover);
-                let color = mix(color, selected_color, self.selected);
-                let color = mix(color, pressed_color, self.pressed);
+                let color = mix(self.bg_list, self.bg_hover, self.hover);
+                let color = mix(color, self.bg_selected, self.selected);
+                let color = mix(color, self.bg_pressed, self.pressed);
                 sdf.box(2.0, 2.0, self.rect_size.x - 4.0, self.rect_size.y - 4.0, 6.0);
                 sdf.fill(color);
                 return sdf.result;
@@ -74,24 +66,377 @@ live_design! {
         }
 
         draw_text: {
-            instance dark_mode: 0.0
+            uniform text_secondary: (PALETTE_TEXT_SECONDARY)
             text_style: <FONT_REGULAR>{ font_size: 11.0 }
             fn get_color(self) -> vec4 {
-                let light = vec4(0.247, 0.282, 0.333, 1.0);
-                let dark = vec4(0.886, 0.910, 0.941, 1.0);
-                return mix(light, dark, self.dark_mode);
+                return self.text_secondary;
             }
         }
 
         draw_icon: {
-            instance dark_mode: 0.0
+            uniform text_muted: (PALETTE_TEXT_MUTED)
             fn get_color(self) -> vec4 {
-                let light = vec4(0.392, 0.455, 0.545, 1.0);
-                let dark = vec4(0.580, 0.639, 0.722, 1.0);
-                return mix(light, dark, self.dark_mode);
+                return self.text_muted;
             }
         }
 
         text: "Menu Item"
     }
+
+    // Menu row: button with an optional badge on the right
+    pub SidebarMenuItem = <View> {
+        width: Fill, height: Fit
+        flow: Overlay
+        align: { x: 1.0, y: 0.5 }
+
+        button = <SidebarMenuButton> {}
+
+        badge = <View> {
+            width: Fit, height: Fit
+            margin: { right: 12 }
+            padding: { left: 6, right: 6, top: 1, bottom: 1 }
+            visible: false
+
+            show_bg: true
+            draw_bg: {
+                uniform accent: (PALETTE_ACCENT)
+                fn pixel(self) -> vec4 {
+                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                    sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, self.rect_size.y * 0.5);
+                    sdf.fill(self.accent);
+                    return sdf.result;
+                }
+            }
+
+            badge_label = <Label> {
+                draw_text: {
+                    uniform on_accent: (PALETTE_ON_ACCENT)
+                    text_style: <FONT_SEMIBOLD> { font_size: 9.0 }
+                    fn get_color(self) -> vec4 {
+                        return self.on_accent;
+                    }
+                }
+                text: ""
+            }
+        }
+    }
+
+    // Section header shown above a group of items
+    pub SidebarSectionHeader = <View> {
+        width: Fill, height: Fit
+        padding: { left: 12, right: 12, top: 10, bottom: 4 }
+
+        section_label = <Label> {
+            draw_text: {
+                uniform text_muted: (PALETTE_TEXT_MUTED)
+                text_style: <FONT_SEMIBOLD> { font_size: 9.0 }
+                fn get_color(self) -> vec4 {
+                    return self.text_muted;
+                }
+            }
+            text: ""
+        }
+    }
+
+    pub SidebarMenu = {{SidebarMenu}} {
+        width: Fill, height: Fit
+        flow: Down
+        spacing: 2
+
+        item_template: <SidebarMenuItem> {}
+        section_template: <SidebarSectionHeader> {}
+    }
+}
+
+/// A single entry in a `SidebarMenu`
+#[derive(Clone, Debug, PartialEq)]
+pub struct SidebarItem {
+    /// Semantic ID reported back on selection (e.g. "dashboard")
+    pub id: String,
+
+    /// Display label
+    pub label: String,
+
+    /// SVG icon path (e.g. "crate://self/resources/icons/dashboard.svg")
+    pub icon: Option<String>,
+
+    /// Optional badge text shown on the right (e.g. an unread count)
+    pub badge: Option<String>,
+
+    /// Optional section name; items with the same section are grouped under a header
+    pub section: Option<String>,
+}
+
+impl SidebarItem {
+    /// Create a new item with an ID and label
+    pub fn new(id: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            label: label.into(),
+            icon: None,
+            badge: None,
+            section: None,
+        }
+    }
+
+    /// Set the SVG icon path
+    pub fn with_icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Set the badge text
+    pub fn with_badge(mut self, badge: impl Into<String>) -> Self {
+        self.badge = Some(badge.into());
+        self
+    }
+
+    /// Set the section name
+    pub fn with_section(mut self, section: impl Into<String>) -> Self {
+        self.section = Some(section.into());
+        self
+    }
+}
+
+/// Build a live value that points a `draw_icon.svg_file` at a runtime path
+pub fn svg_icon_value(path: &str) -> LiveValue {
+    LiveValue::Dependency(Rc::new(path.to_string()))
+}
+
+/// Actions emitted by `SidebarMenu`
+#[derive(Clone, Debug, DefaultNone)]
+pub enum SidebarMenuAction {
+    /// An item was clicked (semantic item ID)
+    ItemClicked(String),
+    None,
+}
+
+/// Template-driven list of sidebar menu items
+#[derive(Live, LiveHook, Widget)]
+pub struct SidebarMenu {
+    #[redraw]
+    #[rust]
+    area: Area,
+
+    #[walk]
+    walk: Walk,
+
+    #[layout]
+    layout: Layout,
+
+    #[live]
+    item_template: Option<LivePtr>,
+
+    #[live]
+    section_template: Option<LivePtr>,
+
+    /// Items in display order (grouped by section)
+    #[rust]
+    items: Vec<SidebarItem>,
+
+    #[rust]
+    item_widgets: ComponentMap<LiveId, WidgetRef>,
+
+    #[rust]
+    section_widgets: ComponentMap<LiveId, WidgetRef>,
+
+    /// Currently selected item ID
+    #[rust]
+    selected: Option<String>,
+}
+
+impl Widget for SidebarMenu {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        let actions = cx.capture_actions(|cx| {
+            for widget in self.item_widgets.values_mut() {
+                widget.handle_event(cx, event, scope);
+            }
+        });
+
+        let clicked = self.items.iter().find_map(|item| {
+            let widget = self.item_widgets.get(&LiveId::from_str(&item.id))?;
+            widget.button(id!(button)).clicked(&actions).then(|| item.id.clone())
+        });
+
+        if let Some(item_id) = clicked {
+            self.select(cx, Some(&item_id));
+            cx.widget_action(
+                self.widget_uid(),
+                &scope.path,
+                SidebarMenuAction::ItemClicked(item_id),
+            );
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        let palette = current_palette();
+        let text = palette.live_nodes(&[live_id!(draw_text)]);
+        let button = palette.live_nodes(&[live_id!(draw_bg), live_id!(draw_text), live_id!(draw_icon)]);
+        let badge = palette.live_nodes(&[live_id!(draw_bg)]);
+
+        cx.begin_turtle(walk, self.layout);
+
+        let mut current_section: Option<String> = None;
+        for item in &self.items {
+            if item.section != current_section {
+                current_section = item.section.clone();
+                if let Some(section) = &item.section {
+                    if let Some(header) = self.section_widgets.get(&LiveId::from_str(section)) {
+                        header.label(id!(section_label)).apply_over(cx, &text);
+                        header.draw_all(cx, scope);
+                    }
+                }
+            }
+
+            if let Some(widget) = self.item_widgets.get(&LiveId::from_str(&item.id)) {
+                widget.button(id!(button)).apply_over(cx, &button);
+                widget.view(id!(badge)).apply_over(cx, &badge);
+                widget.label(id!(badge.badge_label)).apply_over(cx, &text);
+                widget.draw_all(cx, scope);
+            }
+        }
+
+        cx.end_turtle_with_area(&mut self.area);
+        DrawStep::done()
+    }
+}
+
+impl SidebarMenu {
+    /// Replace all items, creating one widget per item from the template
+    pub fn set_items(&mut self, cx: &mut Cx, items: Vec<SidebarItem>) {
+        // Group items by section, keeping sections in order of first appearance
+        let mut sections: Vec<Option<String>> = Vec::new();
+        for item in &items {
+            if !sections.contains(&item.section) {
+                sections.push(item.section.clone());
+            }
+        }
+        let mut grouped = items;
+        grouped.sort_by_key(|item| sections.iter().position(|s| *s == item.section));
+
+        self.item_widgets.clear();
+        self.section_widgets.clear();
+
+        let item_template = self.item_template;
+        let section_template = self.section_template;
+
+        for item in &grouped {
+            let widget = self.item_widgets.get_or_insert(cx, LiveId::from_str(&item.id), |cx| {
+                WidgetRef::new_from_ptr(cx, item_template)
+            });
+
+            let button = widget.button(id!(button));
+            button.set_text(cx, &item.label);
+            if let Some(icon) = &item.icon {
+                let icon = svg_icon_value(icon);
+                button.apply_over(cx, live! {
+                    draw_icon: { svg_file: (icon) }
+                });
+            }
+
+            let badge = widget.view(id!(badge));
+            badge.set_visible(cx, item.badge.is_some());
+            if let Some(text) = &item.badge {
+                widget.label(id!(badge.badge_label)).set_text(cx, text);
+            }
+
+            if let Some(section) = &item.section {
+                let header = self.section_widgets.get_or_insert(cx, LiveId::from_str(section), |cx| {
+                    WidgetRef::new_from_ptr(cx, section_template)
+                });
+                header.label(id!(section_label)).set_text(cx, section);
+            }
+        }
+
+        self.items = grouped;
+
+        // Re-apply selection to the freshly created widgets
+        let selected = retained_selection(self.selected.take(), &self.items);
+        self.select(cx, selected.as_deref());
+        self.redraw(cx);
+    }
+
+    /// Mark an item as selected (or clear the selection with `None`)
+    pub fn select(&mut self, cx: &mut Cx, item_id: Option<&str>) {
+        for item in &self.items {
+            if let Some(widget) = self.item_widgets.get(&LiveId::from_str(&item.id)) {
+                let selected = if Some(item.id.as_str()) == item_id { 1.0 } else { 0.0 };
+                widget.button(id!(button)).apply_over(cx, live! {
+                    draw_bg: { selected: (selected) }
+                });
+            }
+        }
+        self.selected = item_id.map(|id| id.to_string());
+        self.redraw(cx);
+    }
+
+    /// Update the badge of a single item without rebuilding the menu
+    pub fn set_badge(&mut self, cx: &mut Cx, item_id: &str, badge: Option<&str>) {
+        if let Some(item) = self.items.iter_mut().find(|item| item.id == item_id) {
+            item.badge = badge.map(|b| b.to_string());
+        }
+        if let Some(widget) = self.item_widgets.get(&LiveId::from_str(item_id)) {
+            widget.view(id!(badge)).set_visible(cx, badge.is_some());
+            if let Some(text) = badge {
+                widget.label(id!(badge.badge_label)).set_text(cx, text);
+            }
+        }
+        self.redraw(cx);
+    }
+
+    /// Get the current items
+    pub fn items(&self) -> &[SidebarItem] {
+        &self.items
+    }
+}
+
+/// The selection left after replacing the items: cleared when no item has its ID
+pub fn retained_selection(selected: Option<String>, items: &[SidebarItem]) -> Option<String> {
+    selected.filter(|id| items.iter().any(|item| item.id == *id))
+}
+
+impl SidebarMenuRef {
+    /// Replace all items
+    pub fn set_items(&self, cx: &mut Cx, items: Vec<SidebarItem>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_items(cx, items);
+        }
+    }
+
+    /// Mark an item as selected (or clear with `None`)
+    pub fn select(&self, cx: &mut Cx, item_id: Option<&str>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.select(cx, item_id);
+        }
+    }
+
+    /// Update the badge of a single item
+    pub fn set_badge(&self, cx: &mut Cx, item_id: &str, badge: Option<&str>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_badge(cx, item_id, badge);
+        }
+    }
+
+    /// Returns the ID of the clicked item, if any
+    pub fn item_clicked(&self, actions: &Actions) -> Option<String> {
+        if let SidebarMenuAction::ItemClicked(id) = actions.find_widget_action(self.widget_uid()).cast() {
+            Some(id)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selection_of_a_removed_item_is_cleared() {
+        let items = vec![SidebarItem::new("inbox", "Inbox"), SidebarItem::new("sent", "Sent")];
+
+        assert_eq!(retained_selection(Some("sent".to_string()), &items), Some("sent".to_string()));
+        assert_eq!(retained_selection(Some("trash".to_string()), &items), None);
+        assert_eq!(retained_selection(None, &items), None);
+    }
 }
@@ -0,0 +1,715 @@
+//! Hierarchical tree navigation for the sidebar
+//!
+//! `SidebarTree` renders nested `SidebarTreeNode`s (e.g. projects → datasets → runs)
+//! with expand/collapse, keyboard navigation and lazily loaded children.
+//! Nodes are addressed by their path: the list of node IDs from the root.
+
+use std::collections::HashSet;
+use makepad_widgets::*;
+use crate::shell::sidebar_menu::svg_icon_value;
+use crate::theme::current_palette;
+
+live_design! {
+    use link::theme::*;
+    use link::shaders::*;
+    use link::widgets::*;
+
+    use crate::live_design::*;
+    use crate::shell::sidebar_menu::*;
+
+    // Expand/collapse chevron (points right when collapsed, down when expanded)
+    pub SidebarTreeChevron = <View> {
+        width: 12, height: 12
+        show_bg: true
+        draw_bg: {
+            instance expanded: 0.0
+            instance expandable: 0.0
+            uniform text_muted: (PALETTE_TEXT_MUTED)
+
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                let c = self.rect_size * 0.5;
+                sdf.rotate(self.expanded * 1.5708, c.x, c.y);
+                sdf.move_to(c.x - 2.0, c.y - 4.0);
+                sdf.line_to(c.x + 2.0, c.y);
+                sdf.line_to(c.x - 2.0, c.y + 4.0);
+                sdf.stroke(self.text_muted, 1.5);
+                return sdf.result * self.expandable;
+            }
+        }
+    }
+
+    // Tree row: menu button with a chevron overlaid on the left
+    pub SidebarTreeRow = <View> {
+        width: Fill, height: Fit
+        flow: Overlay
+        align: { x: 0.0, y: 0.5 }
+
+        button = <SidebarMenuButton> {
+            padding: { top: 6, bottom: 6, left: 24, right: 12 }
+            icon_walk: { width: 14, height: 14, margin: { right: 8 } }
+
+            draw_bg: {
+                instance focused: 0.0
+                uniform accent: (PALETTE_ACCENT)
+
+                fn pixel(self) -> vec4 {
+                    let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                    let color = mix(self.bg_list, self.bg_hover, max(self.hover, self.pressed));
+                    let color = mix(color, self.bg_selected, self.selected);
+
+                    sdf.box(2.0, 1.0, self.rect_size.x - 4.0, self.rect_size.y - 2.0, 4.0);
+                    sdf.fill_keep(color);
+                    sdf.stroke(self.accent * self.focused, 1.0);
+                    return sdf.result;
+                }
+            }
+        }
+
+        chevron = <SidebarTreeChevron> {
+            margin: { left: 8 }
+        }
+    }
+
+    pub SidebarTree = {{SidebarTree}} {
+        width: Fill, height: Fit
+        flow: Down
+        spacing: 1
+
+        indent: 14.0
+        row_template: <SidebarTreeRow> {}
+        loading_text: "Loading..."
+    }
+}
+
+/// A node in the sidebar tree
+#[derive(Clone, Debug, PartialEq)]
+pub struct SidebarTreeNode {
+    /// ID, unique among its siblings
+    pub id: String,
+
+    /// Display label
+    pub label: String,
+
+    /// SVG icon path
+    pub icon: Option<String>,
+
+    /// Child nodes
+    pub children: Vec<SidebarTreeNode>,
+
+    /// Whether children are loaded on demand (via `SidebarAction::LoadChildren`)
+    pub lazy: bool,
+}
+
+impl SidebarTreeNode {
+    /// Create a new leaf node
+    pub fn new(id: impl Into<String>, label: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            label: label.into(),
+            icon: None,
+            children: Vec::new(),
+            lazy: false,
+        }
+    }
+
+    /// Set the SVG icon path
+    pub fn with_icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Set the child nodes
+    pub fn with_children(mut self, children: Vec<SidebarTreeNode>) -> Self {
+        self.children = children;
+        self
+    }
+
+    /// Mark the node as having children that are loaded when first expanded
+    pub fn with_lazy_children(mut self) -> Self {
+        self.lazy = true;
+        self
+    }
+
+    /// Whether the node can be expanded
+    pub fn is_expandable(&self) -> bool {
+        self.lazy || !self.children.is_empty()
+    }
+
+    /// Whether the node's children still need to be loaded
+    pub fn needs_load(&self) -> bool {
+        self.lazy && self.children.is_empty()
+    }
+}
+
+/// Find a node by path
+fn find_node_mut<'a>(nodes: &'a mut [SidebarTreeNode], path: &[String]) -> Option<&'a mut SidebarTreeNode> {
+    let (first, rest) = path.split_first()?;
+    let node = nodes.iter_mut().find(|node| &node.id == first)?;
+    if rest.is_empty() {
+        Some(node)
+    } else {
+        find_node_mut(&mut node.children, rest)
+    }
+}
+
+/// A visible row in the flattened tree
+#[derive(Clone, Debug)]
+struct TreeRow {
+    path: Vec<String>,
+    depth: usize,
+    label: String,
+    icon: Option<String>,
+    expandable: bool,
+    expanded: bool,
+    /// "Loading..." placeholder shown under a node whose children are loading
+    placeholder: bool,
+}
+
+impl TreeRow {
+    fn key(&self) -> LiveId {
+        let key = self.path.join("/");
+        if self.placeholder {
+            LiveId::from_str(&format!("{}#loading", key))
+        } else {
+            LiveId::from_str(&key)
+        }
+    }
+}
+
+/// Flatten the visible part of the tree into rows
+fn flatten_rows(
+    nodes: &[SidebarTreeNode],
+    expanded: &HashSet<Vec<String>>,
+    parent: &[String],
+    rows: &mut Vec<TreeRow>,
+) {
+    for node in nodes {
+        let mut path = parent.to_vec();
+        path.push(node.id.clone());
+        let is_expanded = node.is_expandable() && expanded.contains(&path);
+
+        rows.push(TreeRow {
+            path: path.clone(),
+            depth: parent.len(),
+            label: node.label.clone(),
+            icon: node.icon.clone(),
+            expandable: node.is_expandable(),
+            expanded: is_expanded,
+            placeholder: false,
+        });
+
+        if is_expanded {
+            if node.needs_load() {
+                rows.push(TreeRow {
+                    path: path.clone(),
+                    depth: parent.len() + 1,
+                    label: String::new(),
+                    icon: None,
+                    expandable: false,
+                    expanded: false,
+                    placeholder: true,
+                });
+            } else {
+                flatten_rows(&node.children, expanded, &path, rows);
+            }
+        }
+    }
+}
+
+/// What a navigation key does to the tree
+#[derive(Clone, Debug, PartialEq)]
+enum KeyMove {
+    /// Move the keyboard cursor
+    Cursor(Option<usize>),
+    /// Expand or collapse a node
+    Expand(Vec<String>, bool),
+    /// Select (and toggle) the row
+    Activate(usize),
+    None,
+}
+
+/// Map a key press to its effect, given the visible rows and the keyboard cursor
+///
+/// Up/Down/Home/End move the cursor over nodes (skipping "Loading..." rows),
+/// Right expands or steps into a node, Left collapses or steps out to the parent.
+fn key_move(rows: &[TreeRow], cursor: Option<usize>, key: KeyCode) -> KeyMove {
+    if rows.is_empty() {
+        return KeyMove::None;
+    }
+    let last = rows.len() - 1;
+    let current = cursor.unwrap_or(0).min(last);
+    let row = &rows[current];
+
+    match key {
+        KeyCode::ArrowDown => {
+            let next = (current + 1..=last).find(|&i| !rows[i].placeholder);
+            KeyMove::Cursor(next.or(Some(current)))
+        }
+        KeyCode::ArrowUp => {
+            let prev = (0..current).rev().find(|&i| !rows[i].placeholder);
+            KeyMove::Cursor(prev.or(Some(current)))
+        }
+        KeyCode::Home => KeyMove::Cursor(Some(0)),
+        KeyCode::End => KeyMove::Cursor((0..=last).rev().find(|&i| !rows[i].placeholder)),
+        KeyCode::ArrowRight => {
+            if row.expandable && !row.expanded {
+                KeyMove::Expand(row.path.clone(), true)
+            } else if row.expanded && current < last && !rows[current + 1].placeholder {
+                KeyMove::Cursor(Some(current + 1))
+            } else {
+                KeyMove::None
+            }
+        }
+        KeyCode::ArrowLeft => {
+            if row.expanded {
+                KeyMove::Expand(row.path.clone(), false)
+            } else if row.path.len() > 1 {
+                let parent = &row.path[..row.path.len() - 1];
+                KeyMove::Cursor(rows.iter().position(|r| !r.placeholder && r.path == parent))
+            } else {
+                KeyMove::None
+            }
+        }
+        KeyCode::ReturnKey | KeyCode::Space => KeyMove::Activate(current),
+        _ => KeyMove::None,
+    }
+}
+
+/// Actions emitted by `SidebarTree`
+#[derive(Clone, Debug, DefaultNone)]
+pub enum SidebarTreeAction {
+    /// A node was selected (full path)
+    Selected(Vec<String>),
+
+    /// A node was expanded or collapsed
+    Expanded { path: Vec<String>, expanded: bool },
+
+    /// A lazy node was expanded and needs its children
+    LoadChildren(Vec<String>),
+
+    None,
+}
+
+/// Tree of expandable sidebar nodes
+#[derive(Live, LiveHook, Widget)]
+pub struct SidebarTree {
+    #[redraw]
+    #[rust]
+    area: Area,
+
+    #[walk]
+    walk: Walk,
+
+    #[layout]
+    layout: Layout,
+
+    #[live]
+    row_template: Option<LivePtr>,
+
+    /// Horizontal indent per tree level
+    #[live]
+    indent: f64,
+
+    #[live]
+    loading_text: String,
+
+    #[live(true)]
+    visible: bool,
+
+    #[rust]
+    nodes: Vec<SidebarTreeNode>,
+
+    /// Paths of expanded nodes (kept even if the node isn't loaded yet)
+    #[rust]
+    expanded: HashSet<Vec<String>>,
+
+    /// Visible rows in display order
+    #[rust]
+    rows: Vec<TreeRow>,
+
+    #[rust]
+    row_widgets: ComponentMap<LiveId, WidgetRef>,
+
+    #[rust]
+    selected: Option<Vec<String>>,
+
+    /// Keyboard cursor (index into `rows`)
+    #[rust]
+    cursor: Option<usize>,
+
+    /// Lazy nodes whose `LoadChildren` was already requested
+    #[rust]
+    requested_loads: HashSet<Vec<String>>,
+
+    /// Actions raised outside of event handling, emitted on the next `handle_event`
+    #[rust]
+    pending_actions: Vec<SidebarTreeAction>,
+}
+
+impl Widget for SidebarTree {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        // Load requests queued while hidden (e.g. restored expanded nodes) go out anyway
+        let uid = self.widget_uid();
+        for action in std::mem::take(&mut self.pending_actions) {
+            cx.widget_action(uid, &scope.path, action);
+        }
+        if !self.visible {
+            return;
+        }
+        let actions = cx.capture_actions(|cx| {
+            for widget in self.row_widgets.values_mut() {
+                widget.handle_event(cx, event, scope);
+            }
+        });
+
+        let clicked = self.rows.iter().position(|row| {
+            !row.placeholder && self.row_widgets.get(&row.key())
+                .map_or(false, |widget| widget.button(id!(button)).clicked(&actions))
+        });
+        if let Some(index) = clicked {
+            cx.set_key_focus(self.area);
+            self.set_cursor(cx, Some(index));
+            self.activate(cx, index);
+        }
+
+        if let Hit::KeyDown(ke) = event.hits(cx, self.area) {
+            self.handle_key(cx, &ke);
+        }
+
+        for action in std::mem::take(&mut self.pending_actions) {
+            cx.widget_action(uid, &scope.path, action);
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if !self.visible {
+            return DrawStep::done();
+        }
+        let palette = current_palette();
+        let button = palette.live_nodes(&[live_id!(draw_bg), live_id!(draw_text), live_id!(draw_icon)]);
+        let chevron = palette.live_nodes(&[live_id!(draw_bg)]);
+
+        cx.begin_turtle(walk, self.layout);
+
+        for row in &self.rows {
+            if let Some(widget) = self.row_widgets.get(&row.key()) {
+                widget.button(id!(button)).apply_over(cx, &button);
+                widget.view(id!(chevron)).apply_over(cx, &chevron);
+                widget.draw_all(cx, scope);
+            }
+        }
+
+        cx.end_turtle_with_area(&mut self.area);
+        DrawStep::done()
+    }
+
+    fn visible(&self) -> bool {
+        self.visible
+    }
+
+    fn set_visible(&mut self, cx: &mut Cx, visible: bool) {
+        if self.visible != visible {
+            self.visible = visible;
+            self.redraw(cx);
+        }
+    }
+}
+
+impl SidebarTree {
+    /// Replace the tree
+    pub fn set_nodes(&mut self, cx: &mut Cx, nodes: Vec<SidebarTreeNode>) {
+        self.nodes = nodes;
+        self.requested_loads.clear();
+        self.rebuild_rows(cx);
+    }
+
+    /// Provide the children of a lazily loaded node
+    pub fn set_children(&mut self, cx: &mut Cx, path: &[String], children: Vec<SidebarTreeNode>) {
+        if let Some(node) = find_node_mut(&mut self.nodes, path) {
+            node.children = children;
+            node.lazy = false;
+        }
+        self.requested_loads.remove(path);
+        self.rebuild_rows(cx);
+    }
+
+    /// Replace the set of expanded node paths (e.g. restored from preferences)
+    pub fn set_expanded_paths(&mut self, cx: &mut Cx, paths: Vec<Vec<String>>) {
+        self.expanded = paths.into_iter().collect();
+        self.rebuild_rows(cx);
+    }
+
+    /// Paths of all expanded nodes
+    pub fn expanded_paths(&self) -> Vec<Vec<String>> {
+        let mut paths: Vec<_> = self.expanded.iter().cloned().collect();
+        paths.sort();
+        paths
+    }
+
+    /// Mark a node as selected (or clear with `None`)
+    pub fn select(&mut self, cx: &mut Cx, path: Option<&[String]>) {
+        self.selected = path.map(|p| p.to_vec());
+        self.apply_row_state(cx);
+    }
+
+    /// Expand or collapse a node
+    pub fn set_expanded(&mut self, cx: &mut Cx, path: &[String], expanded: bool) {
+        let changed = if expanded {
+            self.expanded.insert(path.to_vec())
+        } else {
+            self.expanded.remove(path)
+        };
+        if changed {
+            self.pending_actions.push(SidebarTreeAction::Expanded {
+                path: path.to_vec(),
+                expanded,
+            });
+            self.rebuild_rows(cx);
+        }
+    }
+
+    /// Select the row and toggle it if it has children
+    fn activate(&mut self, cx: &mut Cx, index: usize) {
+        let Some(row) = self.rows.get(index).cloned() else { return };
+        if row.placeholder {
+            return;
+        }
+
+        if self.selected.as_ref() != Some(&row.path) {
+            self.select(cx, Some(&row.path));
+            self.pending_actions.push(SidebarTreeAction::Selected(row.path.clone()));
+        }
+        if row.expandable {
+            self.set_expanded(cx, &row.path, !row.expanded);
+        }
+    }
+
+    fn handle_key(&mut self, cx: &mut Cx, ke: &KeyEvent) {
+        match key_move(&self.rows, self.cursor, ke.key_code) {
+            KeyMove::Cursor(cursor) => self.set_cursor(cx, cursor),
+            KeyMove::Expand(path, expanded) => self.set_expanded(cx, &path, expanded),
+            KeyMove::Activate(index) => self.activate(cx, index),
+            KeyMove::None => {}
+        }
+    }
+
+    fn set_cursor(&mut self, cx: &mut Cx, cursor: Option<usize>) {
+        self.cursor = cursor;
+        self.apply_row_state(cx);
+    }
+
+    /// Recompute visible rows and (re)create their widgets
+    fn rebuild_rows(&mut self, cx: &mut Cx) {
+        // Keep the cursor on the same node across rebuilds
+        let cursor_path = self.cursor
+            .and_then(|i| self.rows.get(i))
+            .map(|row| row.path.clone());
+
+        let mut rows = Vec::new();
+        flatten_rows(&self.nodes, &self.expanded, &[], &mut rows);
+
+        // Expanded lazy nodes without children need a load request
+        for row in rows.iter().filter(|row| row.placeholder) {
+            if self.requested_loads.insert(row.path.clone()) {
+                self.pending_actions.push(SidebarTreeAction::LoadChildren(row.path.clone()));
+                cx.new_next_frame();
+            }
+        }
+
+        let template = self.row_template;
+        let mut widgets = ComponentMap::default();
+        for row in &rows {
+            let key = row.key();
+            let widget = match self.row_widgets.remove(&key) {
+                Some(widget) => widget,
+                None => WidgetRef::new_from_ptr(cx, template),
+            };
+
+            let button = widget.button(id!(button));
+            if row.placeholder {
+                button.set_text(cx, &self.loading_text);
+            } else {
+                button.set_text(cx, &row.label);
+            }
+            if let Some(icon) = &row.icon {
+                let icon = svg_icon_value(icon);
+                button.apply_over(cx, live! {
+                    draw_icon: { svg_file: (icon) }
+                });
+            }
+
+            let depth = row.depth as f64 * self.indent;
+            let padding_left = 24.0 + depth;
+            let chevron_left = 8.0 + depth;
+            let expandable = if row.expandable { 1.0 } else { 0.0 };
+            let expanded = if row.expanded { 1.0 } else { 0.0 };
+            button.apply_over(cx, live! {
+                padding: { left: (padding_left) }
+            });
+            widget.view(id!(chevron)).apply_over(cx, live! {
+                margin: { left: (chevron_left) }
+                draw_bg: { expandable: (expandable), expanded: (expanded) }
+            });
+
+            widgets.insert(key, widget);
+        }
+
+        self.row_widgets = widgets;
+        self.rows = rows;
+        self.cursor = cursor_path.and_then(|path| {
+            self.rows.iter().position(|row| !row.placeholder && row.path == path)
+        });
+        self.apply_row_state(cx);
+    }
+
+    /// Apply selection and keyboard cursor highlights
+    fn apply_row_state(&mut self, cx: &mut Cx) {
+        for (index, row) in self.rows.iter().enumerate() {
+            if let Some(widget) = self.row_widgets.get(&row.key()) {
+                let selected = if !row.placeholder && self.selected.as_ref() == Some(&row.path) { 1.0 } else { 0.0 };
+                let focused = if self.cursor == Some(index) { 1.0 } else { 0.0 };
+                widget.button(id!(button)).apply_over(cx, live! {
+                    draw_bg: { selected: (selected), focused: (focused) }
+                });
+            }
+        }
+        self.redraw(cx);
+    }
+}
+
+impl SidebarTreeRef {
+    /// Replace the tree
+    pub fn set_nodes(&self, cx: &mut Cx, nodes: Vec<SidebarTreeNode>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_nodes(cx, nodes);
+        }
+    }
+
+    /// Provide the children of a lazily loaded node
+    pub fn set_children(&self, cx: &mut Cx, path: &[String], children: Vec<SidebarTreeNode>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_children(cx, path, children);
+        }
+    }
+
+    /// Replace the set of expanded node paths
+    pub fn set_expanded_paths(&self, cx: &mut Cx, paths: Vec<Vec<String>>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_expanded_paths(cx, paths);
+        }
+    }
+
+    /// Paths of all expanded nodes
+    pub fn expanded_paths(&self) -> Vec<Vec<String>> {
+        self.borrow().map(|inner| inner.expanded_paths()).unwrap_or_default()
+    }
+
+    /// Mark a node as selected (or clear with `None`)
+    pub fn select(&self, cx: &mut Cx, path: Option<&[String]>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.select(cx, path);
+        }
+    }
+
+    /// All tree actions in this action batch
+    pub fn tree_actions(&self, actions: &Actions) -> Vec<SidebarTreeAction> {
+        actions.filter_widget_actions(self.widget_uid())
+            .into_iter()
+            .flatten()
+            .map(|action| action.cast::<SidebarTreeAction>())
+            .filter(|action| !matches!(action, SidebarTreeAction::None))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    /// projects → (alpha → run_1, beta (lazy)), archive
+    fn tree() -> Vec<SidebarTreeNode> {
+        vec![
+            SidebarTreeNode::new("projects", "Projects").with_children(vec![
+                SidebarTreeNode::new("alpha", "Alpha").with_children(vec![
+                    SidebarTreeNode::new("run_1", "Run 1"),
+                ]),
+                SidebarTreeNode::new("beta", "Beta").with_lazy_children(),
+            ]),
+            SidebarTreeNode::new("archive", "Archive"),
+        ]
+    }
+
+    fn rows(expanded: &[&[&str]]) -> Vec<TreeRow> {
+        let expanded = expanded.iter().map(|ids| path(ids)).collect();
+        let mut rows = Vec::new();
+        flatten_rows(&tree(), &expanded, &[], &mut rows);
+        rows
+    }
+
+    #[test]
+    fn test_flatten_shows_children_of_expanded_nodes_only() {
+        let collapsed = rows(&[]);
+        assert_eq!(collapsed.iter().map(|row| row.path.clone()).collect::<Vec<_>>(), vec![
+            path(&["projects"]),
+            path(&["archive"]),
+        ]);
+
+        // An expanded node under a collapsed parent stays hidden
+        assert_eq!(rows(&[&["projects", "alpha"]]).len(), 2);
+
+        let expanded = rows(&[&["projects"], &["projects", "alpha"]]);
+        assert_eq!(expanded.iter().map(|row| (row.path.clone(), row.depth)).collect::<Vec<_>>(), vec![
+            (path(&["projects"]), 0),
+            (path(&["projects", "alpha"]), 1),
+            (path(&["projects", "alpha", "run_1"]), 2),
+            (path(&["projects", "beta"]), 1),
+            (path(&["archive"]), 0),
+        ]);
+        assert!(expanded[0].expanded && expanded[1].expanded);
+        assert!(!expanded[2].expandable);
+        assert!(expanded[3].expandable && !expanded[3].expanded);
+    }
+
+    #[test]
+    fn test_expanded_lazy_node_shows_a_loading_row() {
+        let rows = rows(&[&["projects"], &["projects", "beta"]]);
+        let loading = &rows[3];
+        assert!(loading.placeholder);
+        assert_eq!((loading.path.clone(), loading.depth), (path(&["projects", "beta"]), 2));
+        assert_ne!(loading.key(), rows[2].key());
+    }
+
+    #[test]
+    fn test_arrow_home_and_end_skip_loading_rows() {
+        // projects, alpha, beta, (loading), archive
+        let rows = rows(&[&["projects"], &["projects", "beta"]]);
+        assert!(rows[3].placeholder);
+
+        assert_eq!(key_move(&rows, Some(2), KeyCode::ArrowDown), KeyMove::Cursor(Some(4)));
+        assert_eq!(key_move(&rows, Some(4), KeyCode::ArrowUp), KeyMove::Cursor(Some(2)));
+        assert_eq!(key_move(&rows, Some(4), KeyCode::ArrowDown), KeyMove::Cursor(Some(4)), "stays on the last row");
+        assert_eq!(key_move(&rows, None, KeyCode::ArrowUp), KeyMove::Cursor(Some(0)));
+        assert_eq!(key_move(&rows, Some(4), KeyCode::Home), KeyMove::Cursor(Some(0)));
+        assert_eq!(key_move(&rows, Some(0), KeyCode::End), KeyMove::Cursor(Some(4)));
+        assert_eq!(key_move(&[], Some(0), KeyCode::ArrowDown), KeyMove::None);
+    }
+
+    #[test]
+    fn test_left_and_right_expand_collapse_and_step() {
+        let collapsed = rows(&[]);
+        assert_eq!(key_move(&collapsed, Some(0), KeyCode::ArrowRight), KeyMove::Expand(path(&["projects"]), true));
+        assert_eq!(key_move(&collapsed, Some(1), KeyCode::ArrowRight), KeyMove::None, "leaf");
+        assert_eq!(key_move(&collapsed, Some(0), KeyCode::ArrowLeft), KeyMove::None, "collapsed root");
+
+        let expanded = rows(&[&["projects"]]);
+        assert_eq!(key_move(&expanded, Some(0), KeyCode::ArrowRight), KeyMove::Cursor(Some(1)), "steps into the first child");
+        assert_eq!(key_move(&expanded, Some(0), KeyCode::ArrowLeft), KeyMove::Expand(path(&["projects"]), false));
+        assert_eq!(key_move(&expanded, Some(2), KeyCode::ArrowLeft), KeyMove::Cursor(Some(0)), "steps out to the parent");
+        assert_eq!(key_move(&expanded, Some(2), KeyCode::ReturnKey), KeyMove::Activate(2));
+    }
+}
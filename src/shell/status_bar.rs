@@ -0,0 +1,484 @@
+//! Status bar items
+//!
+//! Apps put `StatusItem`s into the `ShellFooter` status bar by ID, so each
+//! subsystem can own and update its own segments. Items sit in the left or
+//! right group, ordered by priority, and can show an icon, a tooltip, a
+//! progress spinner with an optional percentage and report clicks.
+//! `ShellFooter` hosts one `StatusBarGroup` per alignment.
+
+use makepad_widgets::*;
+use crate::shell::sidebar_menu::svg_icon_value;
+use crate::theme::current_palette;
+
+live_design! {
+    use link::theme::*;
+    use link::shaders::*;
+    use link::widgets::*;
+
+    use crate::live_design::*;
+
+    pub StatusBarItem = <View> {
+        width: Fit, height: Fill
+        padding: { left: 6, right: 6 }
+        flow: Right
+        align: { y: 0.5 }
+        spacing: 4
+
+        show_bg: true
+        draw_bg: {
+            instance hover: 0.0
+            uniform bg_hover: (PALETTE_BG_HOVER)
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                sdf.box(0.0, 2.0, self.rect_size.x, self.rect_size.y - 4.0, 3.0);
+                sdf.fill(vec4(self.bg_hover.xyz, self.hover));
+                return sdf.result;
+            }
+        }
+
+        // Rotating arc shown while the item has progress
+        spinner = <View> {
+            width: 10, height: 10
+            visible: false
+            show_bg: true
+            draw_bg: {
+                instance angle: 0.0
+                uniform accent: (PALETTE_ACCENT)
+                fn pixel(self) -> vec4 {
+                    let p = self.pos * 2.0 - 1.0;
+                    let d = length(p);
+                    let ring = smoothstep(0.5, 0.6, d) * (1.0 - smoothstep(0.85, 0.95, d));
+                    let tail = fract((atan(p.y, p.x) - self.angle) / 6.2831853);
+                    let alpha = self.accent.w * ring * tail;
+                    return vec4(self.accent.xyz * alpha, alpha);
+                }
+            }
+        }
+
+        icon = <Icon> {
+            visible: false
+            icon_walk: { width: 12, height: 12 }
+            draw_icon: {
+                uniform text_muted: (PALETTE_TEXT_MUTED)
+                fn get_color(self) -> vec4 {
+                    return self.text_muted;
+                }
+            }
+        }
+
+        label = <Label> {
+            draw_text: {
+                uniform text_muted: (PALETTE_TEXT_MUTED)
+                text_style: <FONT_REGULAR> { font_size: 10.0 }
+                fn get_color(self) -> vec4 {
+                    return self.text_muted;
+                }
+            }
+            text: ""
+        }
+    }
+
+    pub StatusBarGroup = {{StatusBarGroup}} {
+        width: Fit, height: Fill
+        flow: Right
+        spacing: 2
+        align: { y: 0.5 }
+
+        item_template: <StatusBarItem> {}
+        tooltip_template: <Tooltip> {}
+    }
+}
+
+/// Which end of the status bar an item sits at
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum StatusAlignment {
+    #[default]
+    Left,
+    Right,
+}
+
+/// Progress shown by a status item
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StatusProgress {
+    /// Spinner only (unknown duration)
+    Busy,
+    /// Spinner and percentage (fraction 0.0 - 1.0)
+    Fraction(f64),
+}
+
+/// A segment of the status bar
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatusItem {
+    /// Item ID, used to update the item and reported on click
+    pub id: String,
+
+    /// Display text
+    pub text: String,
+
+    /// SVG icon path
+    pub icon: Option<String>,
+
+    /// Text shown while hovering
+    pub tooltip: Option<String>,
+
+    /// Left or right group
+    pub alignment: StatusAlignment,
+
+    /// Higher priorities sit further left within their group
+    pub priority: i32,
+
+    /// Report clicks (and show a hover highlight)
+    pub clickable: bool,
+
+    /// Spinner and optional percentage
+    pub progress: Option<StatusProgress>,
+}
+
+impl StatusItem {
+    /// Create a new left-aligned item
+    pub fn new(id: impl Into<String>, text: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            text: text.into(),
+            icon: None,
+            tooltip: None,
+            alignment: StatusAlignment::default(),
+            priority: 0,
+            clickable: false,
+            progress: None,
+        }
+    }
+
+    /// Set the SVG icon path
+    pub fn with_icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Set the hover tooltip
+    pub fn with_tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Place the item in the right group
+    pub fn align_right(mut self) -> Self {
+        self.alignment = StatusAlignment::Right;
+        self
+    }
+
+    /// Set the priority (higher = further left)
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Report clicks as `ShellEvent::StatusItemClicked`
+    pub fn clickable(mut self) -> Self {
+        self.clickable = true;
+        self
+    }
+
+    /// Show a progress spinner
+    pub fn with_progress(mut self, progress: StatusProgress) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Text as displayed, with the percentage of a known progress appended
+    pub fn display_text(&self) -> String {
+        match self.progress {
+            Some(StatusProgress::Fraction(fraction)) => {
+                let percent = (fraction.clamp(0.0, 1.0) * 100.0).round();
+                if self.text.is_empty() {
+                    format!("{}%", percent)
+                } else {
+                    format!("{} {}%", self.text, percent)
+                }
+            }
+            _ => self.text.clone(),
+        }
+    }
+}
+
+/// Status items keyed by ID
+#[derive(Clone, Debug, Default)]
+pub struct StatusBarModel {
+    items: Vec<StatusItem>,
+}
+
+impl StatusBarModel {
+    /// Add an item, or replace the item with the same ID in place
+    pub fn set(&mut self, item: StatusItem) {
+        match self.items.iter_mut().find(|existing| existing.id == item.id) {
+            Some(existing) => *existing = item,
+            None => self.items.push(item),
+        }
+    }
+
+    /// Remove an item, returning false if no item has this ID
+    pub fn remove(&mut self, id: &str) -> bool {
+        let len = self.items.len();
+        self.items.retain(|item| item.id != id);
+        self.items.len() != len
+    }
+
+    /// Item by ID
+    pub fn get(&self, id: &str) -> Option<&StatusItem> {
+        self.items.iter().find(|item| item.id == id)
+    }
+
+    /// Item by ID
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut StatusItem> {
+        self.items.iter_mut().find(|item| item.id == id)
+    }
+
+    /// Whether there are no items
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Items of one group in display order: by priority, highest first, then
+    /// in the order they were added
+    pub fn aligned(&self, alignment: StatusAlignment) -> Vec<StatusItem> {
+        let mut items: Vec<StatusItem> = self.items.iter()
+            .filter(|item| item.alignment == alignment)
+            .cloned()
+            .collect();
+        items.sort_by(|a, b| b.priority.cmp(&a.priority));
+        items
+    }
+}
+
+/// Actions emitted by `StatusBarGroup`
+#[derive(Clone, Debug, DefaultNone)]
+pub enum StatusBarGroupAction {
+    /// A clickable item was clicked (item ID)
+    Clicked(String),
+    None,
+}
+
+/// Row of status items for one alignment group
+#[derive(Live, LiveHook, Widget)]
+pub struct StatusBarGroup {
+    #[redraw]
+    #[rust]
+    area: Area,
+
+    #[walk]
+    walk: Walk,
+
+    #[layout]
+    layout: Layout,
+
+    #[live]
+    item_template: Option<LivePtr>,
+
+    #[live]
+    tooltip_template: Option<LivePtr>,
+
+    #[rust]
+    items: Vec<StatusItem>,
+
+    #[rust]
+    item_widgets: ComponentMap<LiveId, WidgetRef>,
+
+    #[rust]
+    tooltip: WidgetRef,
+
+    /// Drives the progress spinners
+    #[rust]
+    next_frame: NextFrame,
+
+    /// Keep spinners still
+    #[rust]
+    reduce_motion: bool,
+}
+
+impl Widget for StatusBarGroup {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        let uid = self.widget_uid();
+        for item in &self.items {
+            let Some(widget) = self.item_widgets.get(&LiveId::from_str(&item.id)) else {
+                continue;
+            };
+            match event.hits(cx, widget.area()) {
+                Hit::FingerHoverIn(_) => {
+                    if item.clickable {
+                        cx.set_cursor(MouseCursor::Hand);
+                        widget.apply_over(cx, live! { draw_bg: { hover: 1.0 } });
+                        widget.redraw(cx);
+                    }
+                    if let Some(tooltip) = &item.tooltip {
+                        let rect = widget.area().rect(cx);
+                        // The status bar is at the bottom of the window, so show above
+                        let pos = dvec2(rect.pos.x, rect.pos.y - 28.0);
+                        self.tooltip.as_tooltip().show_with_options(cx, pos, tooltip);
+                    }
+                }
+                Hit::FingerHoverOut(_) => {
+                    if item.clickable {
+                        widget.apply_over(cx, live! { draw_bg: { hover: 0.0 } });
+                        widget.redraw(cx);
+                    }
+                    self.tooltip.as_tooltip().hide(cx);
+                }
+                Hit::FingerUp(fe) if fe.is_over && item.clickable => {
+                    cx.widget_action(uid, &scope.path, StatusBarGroupAction::Clicked(item.id.clone()));
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(ne) = self.next_frame.is_event(event) {
+            let angle = (ne.time * 5.0) % std::f64::consts::TAU;
+            for item in self.items.iter().filter(|item| item.progress.is_some()) {
+                if let Some(widget) = self.item_widgets.get(&LiveId::from_str(&item.id)) {
+                    widget.view(id!(spinner)).apply_over(cx, live! {
+                        draw_bg: { angle: (angle) }
+                    });
+                }
+            }
+            self.redraw(cx);
+            self.update_spinner(cx);
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if self.items.is_empty() {
+            return DrawStep::done();
+        }
+        let palette = current_palette();
+        let bg = palette.live_nodes(&[live_id!(draw_bg)]);
+        let text = palette.live_nodes(&[live_id!(draw_text)]);
+        let icon = palette.live_nodes(&[live_id!(draw_icon)]);
+
+        cx.begin_turtle(walk, self.layout);
+        for item in &self.items {
+            if let Some(widget) = self.item_widgets.get(&LiveId::from_str(&item.id)) {
+                widget.apply_over(cx, &bg);
+                widget.view(id!(spinner)).apply_over(cx, &bg);
+                widget.widget(id!(icon)).apply_over(cx, &icon);
+                widget.label(id!(label)).apply_over(cx, &text);
+                widget.draw_all(cx, scope);
+            }
+        }
+        cx.end_turtle_with_area(&mut self.area);
+
+        // Draws into its own overlay
+        self.tooltip.draw_all(cx, scope);
+
+        DrawStep::done()
+    }
+}
+
+impl StatusBarGroup {
+    /// Replace the items (in display order)
+    pub fn set_items(&mut self, cx: &mut Cx, items: Vec<StatusItem>) {
+        if self.tooltip.is_empty() {
+            self.tooltip = WidgetRef::new_from_ptr(cx, self.tooltip_template);
+        }
+        let template = self.item_template;
+        self.item_widgets.retain(|id, _| items.iter().any(|item| LiveId::from_str(&item.id) == *id));
+        for item in &items {
+            let widget = self.item_widgets.get_or_insert(cx, LiveId::from_str(&item.id), |cx| {
+                WidgetRef::new_from_ptr(cx, template)
+            });
+            widget.label(id!(label)).set_text(cx, &item.display_text());
+            widget.view(id!(spinner)).set_visible(cx, item.progress.is_some());
+            let icon_widget = widget.widget(id!(icon));
+            if let Some(icon) = &item.icon {
+                let icon = svg_icon_value(icon);
+                icon_widget.apply_over(cx, live! {
+                    draw_icon: { svg_file: (icon) }
+                });
+            }
+            icon_widget.set_visible(cx, item.icon.is_some());
+            if !item.clickable {
+                widget.apply_over(cx, live! { draw_bg: { hover: 0.0 } });
+            }
+        }
+        self.items = items;
+        self.update_spinner(cx);
+        self.redraw(cx);
+    }
+
+    /// Keep spinners still (true) or rotating
+    pub fn set_reduce_motion(&mut self, cx: &mut Cx, reduce_motion: bool) {
+        self.reduce_motion = reduce_motion;
+        self.update_spinner(cx);
+    }
+
+    /// Request the next frame while any spinner should rotate
+    fn update_spinner(&mut self, cx: &mut Cx) {
+        if !self.reduce_motion && self.items.iter().any(|item| item.progress.is_some()) {
+            self.next_frame = cx.new_next_frame();
+        }
+    }
+}
+
+impl StatusBarGroupRef {
+    /// Replace the items (in display order)
+    pub fn set_items(&self, cx: &mut Cx, items: Vec<StatusItem>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_items(cx, items);
+        }
+    }
+
+    /// Keep spinners still (true) or rotating
+    pub fn set_reduce_motion(&self, cx: &mut Cx, reduce_motion: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_reduce_motion(cx, reduce_motion);
+        }
+    }
+
+    /// Returns the ID of a clicked item, if any
+    pub fn clicked(&self, actions: &Actions) -> Option<String> {
+        if let StatusBarGroupAction::Clicked(id) = actions.find_widget_action(self.widget_uid()).cast() {
+            Some(id)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aligned_orders_by_priority_then_insertion() {
+        let mut model = StatusBarModel::default();
+        model.set(StatusItem::new("branch", "main"));
+        model.set(StatusItem::new("errors", "0 errors").with_priority(10));
+        model.set(StatusItem::new("warnings", "0 warnings"));
+        model.set(StatusItem::new("encoding", "UTF-8").align_right());
+
+        let left: Vec<_> = model.aligned(StatusAlignment::Left).into_iter().map(|item| item.id).collect();
+        assert_eq!(left, ["errors", "branch", "warnings"]);
+        assert_eq!(model.aligned(StatusAlignment::Right).len(), 1);
+    }
+
+    #[test]
+    fn test_set_replaces_by_id() {
+        let mut model = StatusBarModel::default();
+        model.set(StatusItem::new("a", "one"));
+        model.set(StatusItem::new("b", "two"));
+        model.set(StatusItem::new("a", "three"));
+
+        let left: Vec<_> = model.aligned(StatusAlignment::Left).into_iter().map(|item| item.text).collect();
+        assert_eq!(left, ["three", "two"]);
+        assert!(model.remove("a"));
+        assert!(!model.remove("a"));
+    }
+
+    #[test]
+    fn test_display_text_with_progress() {
+        let item = StatusItem::new("index", "Indexing");
+        assert_eq!(item.display_text(), "Indexing");
+        assert_eq!(item.clone().with_progress(StatusProgress::Busy).display_text(), "Indexing");
+        assert_eq!(item.with_progress(StatusProgress::Fraction(0.424)).display_text(), "Indexing 42%");
+        let item = StatusItem::new("sync", "").with_progress(StatusProgress::Fraction(1.5));
+        assert_eq!(item.display_text(), "100%");
+    }
+}
@@ -0,0 +1,505 @@
+//! Data-driven header toolbar
+//!
+//! Apps register `ToolbarItem`s with an ID, icon, tooltip, optional toggle
+//! state and an alignment group. `ShellHeader` hosts one `HeaderToolbar` per
+//! group and reports clicks by item ID. Items with a menu open a `ShellMenu`
+//! below the button instead, and any item can play the click flash used by
+//! the built-in save and reset buttons.
+
+use std::collections::HashMap;
+use makepad_widgets::*;
+use crate::shell::header::ButtonAnimState;
+use crate::shell::menu::{MenuEntry, ShellMenuWidgetRefExt};
+use crate::shell::sidebar_menu::svg_icon_value;
+use crate::theme::current_palette;
+
+live_design! {
+    use link::theme::*;
+    use link::shaders::*;
+    use link::widgets::*;
+
+    use crate::live_design::*;
+    use crate::shell::menu::*;
+
+    pub HeaderToolbarButton = <Button> {
+        width: Fit, height: 28
+        padding: { left: 6, right: 6 }
+        margin: 0
+        align: { x: 0.5, y: 0.5 }
+        icon_walk: { width: 16, height: 16, margin: 0 }
+        text: ""
+
+        draw_bg: {
+            instance hover: 0.0
+            instance pressed: 0.0
+            instance active: 0.0
+            uniform bg_hover: (PALETTE_BG_HOVER)
+            uniform bg_selected: (PALETTE_BG_SELECTED)
+
+            fn pixel(self) -> vec4 {
+                let sdf = Sdf2d::viewport(self.pos * self.rect_size);
+                let color = mix(self.bg_hover, self.bg_selected, self.active);
+                sdf.box(0.0, 0.0, self.rect_size.x, self.rect_size.y, 4.0);
+                sdf.fill(vec4(color.xyz, max(max(self.hover, self.pressed), self.active)));
+                return sdf.result;
+            }
+        }
+
+        draw_text: {
+            uniform text_primary: (PALETTE_TEXT_PRIMARY)
+            text_style: <FONT_MEDIUM>{ font_size: 10.0 }
+            fn get_color(self) -> vec4 {
+                return self.text_primary;
+            }
+        }
+
+        draw_icon: {
+            instance hover: 0.0
+            instance active: 0.0
+            instance anim_progress: 0.0
+            uniform text_primary: (PALETTE_TEXT_PRIMARY)
+            uniform accent: (PALETTE_ACCENT)
+            uniform success: (PALETTE_SUCCESS)
+            fn get_color(self) -> vec4 {
+                let color = mix(self.text_primary, self.accent, max(self.hover, self.active));
+                return mix(color, self.success, self.anim_progress);
+            }
+        }
+    }
+
+    pub HeaderToolbar = {{HeaderToolbar}} {
+        width: Fit, height: Fit
+        flow: Right
+        spacing: 4
+        align: { y: 0.5 }
+        visible: false
+
+        button_template: <HeaderToolbarButton> {}
+        menu_template: <ShellMenu> {}
+        tooltip_template: <Tooltip> {}
+    }
+}
+
+/// Where a toolbar item sits in the header
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ToolbarGroup {
+    /// After the title
+    Left,
+    /// Centered between the title and the right group
+    Center,
+    /// Before the built-in theme and layout buttons
+    #[default]
+    Right,
+}
+
+/// A button in the header toolbar
+#[derive(Clone, Debug, PartialEq)]
+pub struct ToolbarItem {
+    /// Item ID reported on activation
+    pub id: String,
+
+    /// SVG icon path
+    pub icon: Option<String>,
+
+    /// Text shown next to (or instead of) the icon
+    pub label: Option<String>,
+
+    /// Text shown while hovering
+    pub tooltip: Option<String>,
+
+    /// Checked state of a toggle item (`None` = push button)
+    pub checked: Option<bool>,
+
+    /// Alignment group
+    pub group: ToolbarGroup,
+
+    /// Dropdown menu opened by the item (empty = no menu)
+    pub menu: Vec<MenuEntry>,
+
+    /// Play the click flash when clicked
+    pub flash_on_click: bool,
+}
+
+impl ToolbarItem {
+    /// Create a new push button item in the right group
+    pub fn new(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            icon: None,
+            label: None,
+            tooltip: None,
+            checked: None,
+            group: ToolbarGroup::default(),
+            menu: Vec::new(),
+            flash_on_click: false,
+        }
+    }
+
+    /// Set the SVG icon path
+    pub fn with_icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Set the button text
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Set the hover tooltip
+    pub fn with_tooltip(mut self, tooltip: impl Into<String>) -> Self {
+        self.tooltip = Some(tooltip.into());
+        self
+    }
+
+    /// Make the item a toggle with an initial checked state
+    pub fn toggle(mut self, checked: bool) -> Self {
+        self.checked = Some(checked);
+        self
+    }
+
+    /// Set the alignment group
+    pub fn in_group(mut self, group: ToolbarGroup) -> Self {
+        self.group = group;
+        self
+    }
+
+    /// Open a dropdown menu instead of activating
+    pub fn with_menu(mut self, menu: Vec<MenuEntry>) -> Self {
+        self.menu = menu;
+        self
+    }
+
+    /// Play the click flash when clicked
+    pub fn flash_on_click(mut self) -> Self {
+        self.flash_on_click = true;
+        self
+    }
+}
+
+/// Actions emitted by `HeaderToolbar`
+#[derive(Clone, Debug, DefaultNone)]
+pub enum HeaderToolbarAction {
+    /// An item without a menu was clicked (`checked` = new state of a toggle item)
+    Activated { id: String, checked: Option<bool> },
+
+    /// An entry of an item's dropdown menu was chosen
+    MenuSelected { item_id: String, entry_id: String },
+
+    None,
+}
+
+/// Row of toolbar buttons for one alignment group
+#[derive(Live, LiveHook, Widget)]
+pub struct HeaderToolbar {
+    #[redraw]
+    #[rust]
+    area: Area,
+
+    #[walk]
+    walk: Walk,
+
+    #[layout]
+    layout: Layout,
+
+    #[live]
+    button_template: Option<LivePtr>,
+
+    #[live]
+    menu_template: Option<LivePtr>,
+
+    #[live]
+    tooltip_template: Option<LivePtr>,
+
+    #[live(true)]
+    visible: bool,
+
+    #[rust]
+    items: Vec<ToolbarItem>,
+
+    #[rust]
+    buttons: ComponentMap<LiveId, WidgetRef>,
+
+    /// Running click flashes by item ID
+    #[rust]
+    flashes: HashMap<String, ButtonAnimState>,
+
+    /// Dropdown menu shared by all items (created with the first item)
+    #[rust]
+    menu: WidgetRef,
+
+    /// ID of the item whose menu is open
+    #[rust]
+    menu_owner: Option<String>,
+
+    #[rust]
+    tooltip: WidgetRef,
+
+    /// Skip the click flash
+    #[rust]
+    reduce_motion: bool,
+}
+
+impl Widget for HeaderToolbar {
+    fn handle_event(&mut self, cx: &mut Cx, event: &Event, scope: &mut Scope) {
+        if !self.visible {
+            return;
+        }
+        let uid = self.widget_uid();
+        let actions = cx.capture_actions(|cx| {
+            for button in self.buttons.values_mut() {
+                button.handle_event(cx, event, scope);
+            }
+            self.menu.handle_event(cx, event, scope);
+        });
+
+        // Tooltips
+        for item in &self.items {
+            let (Some(tooltip), Some(button)) = (&item.tooltip, self.buttons.get(&LiveId::from_str(&item.id))) else {
+                continue;
+            };
+            match event.hits(cx, button.area()) {
+                Hit::FingerHoverIn(_) => {
+                    let rect = button.area().rect(cx);
+                    let pos = dvec2(rect.pos.x, rect.pos.y + rect.size.y + 4.0);
+                    self.tooltip.as_tooltip().show_with_options(cx, pos, tooltip);
+                }
+                Hit::FingerHoverOut(_) => self.tooltip.as_tooltip().hide(cx),
+                _ => {}
+            }
+        }
+
+        let clicked = self.items.iter()
+            .position(|item| {
+                self.buttons.get(&LiveId::from_str(&item.id))
+                    .map_or(false, |button| button.as_button().clicked(&actions))
+            });
+        if let Some(index) = clicked {
+            self.tooltip.as_tooltip().hide(cx);
+            if let Some(action) = self.activate(cx, index) {
+                cx.widget_action(uid, &scope.path, action);
+            }
+        }
+
+        let menu = self.menu.as_shell_menu();
+        if let Some(entry_id) = menu.selected(&actions) {
+            if let Some(item_id) = self.menu_owner.take() {
+                cx.widget_action(uid, &scope.path, HeaderToolbarAction::MenuSelected { item_id, entry_id });
+            }
+        }
+        if menu.dismissed(&actions) {
+            self.menu_owner = None;
+        }
+
+        if matches!(event, Event::NextFrame(_)) && !self.flashes.is_empty() {
+            let mut finished = Vec::new();
+            for (id, flash) in self.flashes.iter_mut() {
+                if let Some(progress) = flash.update(cx) {
+                    if let Some(button) = self.buttons.get(&LiveId::from_str(id)) {
+                        button.apply_over(cx, live! {
+                            draw_icon: { anim_progress: (progress) }
+                        });
+                    }
+                }
+                if !flash.animating {
+                    finished.push(id.clone());
+                }
+            }
+            for id in finished {
+                self.flashes.remove(&id);
+            }
+            self.redraw(cx);
+        }
+    }
+
+    fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+        if !self.visible {
+            return DrawStep::done();
+        }
+        let palette = current_palette();
+        let button_nodes = palette.live_nodes(&[live_id!(draw_bg), live_id!(draw_text), live_id!(draw_icon)]);
+
+        cx.begin_turtle(walk, self.layout);
+        for item in &self.items {
+            if let Some(button) = self.buttons.get(&LiveId::from_str(&item.id)) {
+                button.apply_over(cx, &button_nodes);
+                button.draw_all(cx, scope);
+            }
+        }
+        cx.end_turtle_with_area(&mut self.area);
+
+        // Both draw into their own overlay
+        self.menu.draw_all(cx, scope);
+        self.tooltip.draw_all(cx, scope);
+
+        DrawStep::done()
+    }
+
+    fn visible(&self) -> bool {
+        self.visible
+    }
+
+    fn set_visible(&mut self, cx: &mut Cx, visible: bool) {
+        if self.visible != visible {
+            self.visible = visible;
+            self.redraw(cx);
+        }
+    }
+}
+
+impl HeaderToolbar {
+    /// Replace the toolbar items (the toolbar hides while empty)
+    pub fn set_items(&mut self, cx: &mut Cx, items: Vec<ToolbarItem>) {
+        self.buttons.clear();
+        self.flashes.clear();
+        self.close_menu(cx);
+        if self.menu.is_empty() {
+            self.menu = WidgetRef::new_from_ptr(cx, self.menu_template);
+            self.tooltip = WidgetRef::new_from_ptr(cx, self.tooltip_template);
+        }
+
+        let template = self.button_template;
+        for item in &items {
+            let button = self.buttons.get_or_insert(cx, LiveId::from_str(&item.id), |cx| {
+                WidgetRef::new_from_ptr(cx, template)
+            });
+            button.set_text(cx, item.label.as_deref().unwrap_or(""));
+            if let Some(icon) = &item.icon {
+                let icon = svg_icon_value(icon);
+                button.apply_over(cx, live! {
+                    draw_icon: { svg_file: (icon) }
+                });
+            }
+        }
+
+        self.visible = !items.is_empty();
+        self.items = items;
+        self.apply_checked(cx);
+    }
+
+    /// Set the checked state of a toggle item
+    ///
+    /// Returns false if no toggle item has this ID.
+    pub fn set_checked(&mut self, cx: &mut Cx, id: &str, checked: bool) -> bool {
+        let Some(item) = self.items.iter_mut().find(|item| item.id == id && item.checked.is_some()) else {
+            return false;
+        };
+        item.checked = Some(checked);
+        self.apply_checked(cx);
+        true
+    }
+
+    /// Play the click flash on an item
+    ///
+    /// Returns false if no item has this ID.
+    pub fn flash(&mut self, cx: &mut Cx, id: &str) -> bool {
+        if !self.items.iter().any(|item| item.id == id) {
+            return false;
+        }
+        if !self.reduce_motion {
+            self.flashes.entry(id.to_string()).or_default().start(cx);
+        }
+        true
+    }
+
+    /// Turn the click flash off (true) or on
+    pub fn set_reduce_motion(&mut self, cx: &mut Cx, reduce_motion: bool) {
+        self.reduce_motion = reduce_motion;
+        if reduce_motion {
+            // Settle any running flash
+            for id in self.flashes.drain().map(|(id, _)| id) {
+                if let Some(button) = self.buttons.get(&LiveId::from_str(&id)) {
+                    button.apply_over(cx, live! {
+                        draw_icon: { anim_progress: 0.0 }
+                    });
+                }
+            }
+            self.redraw(cx);
+        }
+    }
+
+    /// Handle a click on the item at `index`
+    fn activate(&mut self, cx: &mut Cx, index: usize) -> Option<HeaderToolbarAction> {
+        let item = self.items[index].clone();
+        if item.flash_on_click {
+            self.flash(cx, &item.id);
+        }
+
+        if !item.menu.is_empty() {
+            // A second click on the same item closes its menu
+            let reopen = self.menu_owner.as_deref() != Some(item.id.as_str());
+            self.close_menu(cx);
+            if reopen {
+                if let Some(button) = self.buttons.get(&LiveId::from_str(&item.id)) {
+                    let rect = button.area().rect(cx);
+                    let position = dvec2(rect.pos.x, rect.pos.y + rect.size.y + 4.0);
+                    self.menu.as_shell_menu().open(cx, item.menu.clone(), position, rect);
+                    self.menu_owner = Some(item.id);
+                }
+            }
+            return None;
+        }
+
+        let checked = item.checked.map(|checked| !checked);
+        if let Some(checked) = checked {
+            self.set_checked(cx, &item.id, checked);
+        }
+        Some(HeaderToolbarAction::Activated { id: item.id, checked })
+    }
+
+    fn close_menu(&mut self, cx: &mut Cx) {
+        self.menu_owner = None;
+        self.menu.as_shell_menu().close(cx);
+    }
+
+    /// Highlight checked toggle items
+    fn apply_checked(&mut self, cx: &mut Cx) {
+        for item in &self.items {
+            if let Some(button) = self.buttons.get(&LiveId::from_str(&item.id)) {
+                let active = if item.checked == Some(true) { 1.0 } else { 0.0 };
+                button.apply_over(cx, live! {
+                    draw_bg: { active: (active) }
+                    draw_icon: { active: (active) }
+                });
+            }
+        }
+        self.redraw(cx);
+    }
+}
+
+impl HeaderToolbarRef {
+    /// Replace the toolbar items
+    pub fn set_items(&self, cx: &mut Cx, items: Vec<ToolbarItem>) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_items(cx, items);
+        }
+    }
+
+    /// Set the checked state of a toggle item
+    pub fn set_checked(&self, cx: &mut Cx, id: &str, checked: bool) -> bool {
+        self.borrow_mut().map_or(false, |mut inner| inner.set_checked(cx, id, checked))
+    }
+
+    /// Play the click flash on an item
+    pub fn flash(&self, cx: &mut Cx, id: &str) -> bool {
+        self.borrow_mut().map_or(false, |mut inner| inner.flash(cx, id))
+    }
+
+    /// Turn the click flash off (true) or on
+    pub fn set_reduce_motion(&self, cx: &mut Cx, reduce_motion: bool) {
+        if let Some(mut inner) = self.borrow_mut() {
+            inner.set_reduce_motion(cx, reduce_motion);
+        }
+    }
+
+    /// All toolbar actions in this action batch
+    pub fn toolbar_actions(&self, actions: &Actions) -> Vec<HeaderToolbarAction> {
+        actions.filter_widget_actions(self.widget_uid())
+            .into_iter()
+            .flatten()
+            .map(|action| action.cast::<HeaderToolbarAction>())
+            .filter(|action| !matches!(action, HeaderToolbarAction::None))
+            .collect()
+    }
+}
@@ -0,0 +1,309 @@
+//! Light, dark or system theme mode
+//!
+//! In `ThemeMode::System` the shell follows the platform appearance. It asks
+//! an `AppearanceSource` to refresh at startup and again whenever the app
+//! regains focus or the poll interval elapses, and animates to the new mode
+//! when the appearance changed. The source is injectable, so tests (or
+//! platforms the default detection doesn't cover) can provide their own.
+//!
+//! Platform detection can block (a D-Bus round trip on Linux), so
+//! `SystemAppearance` detects on a background thread and wakes the UI with a
+//! signal once the answer is in; the shell polls again on `Event::Signal`.
+//!
+//! ## Usage
+//!
+//! ```rust,ignore
+//! let mut watcher = AppearanceWatcher::new(SystemAppearance::default());
+//! let dark = watcher.set_mode(ThemeMode::System);
+//!
+//! // Later, e.g. on a timer
+//! watcher.refresh();
+//!
+//! // Once the source answered (`Event::Signal` for `SystemAppearance`)
+//! if let Some(dark) = watcher.poll() {
+//!     log!("system switched to {}", if dark { "dark" } else { "light" });
+//! }
+//! ```
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::Arc;
+use makepad_widgets::SignalToUI;
+use serde::{Deserialize, Serialize};
+
+/// Theme mode preference
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThemeMode {
+    /// Always light
+    Light,
+
+    /// Always dark
+    Dark,
+
+    /// Follow the platform appearance
+    #[default]
+    System,
+}
+
+impl ThemeMode {
+    /// Whether this mode is dark, given the platform appearance
+    ///
+    /// `System` falls back to light when the appearance is unknown.
+    pub fn is_dark(self, system_dark: Option<bool>) -> bool {
+        match self {
+            ThemeMode::Light => false,
+            ThemeMode::Dark => true,
+            ThemeMode::System => system_dark.unwrap_or(false),
+        }
+    }
+
+    /// The explicit mode for a dark mode flag
+    pub fn from_dark(dark: bool) -> Self {
+        if dark { ThemeMode::Dark } else { ThemeMode::Light }
+    }
+}
+
+/// Source of the platform light/dark appearance
+pub trait AppearanceSource {
+    /// Whether the platform prefers a dark appearance (`None` if unknown)
+    ///
+    /// Called on the UI thread, so this must return the latest known answer
+    /// without blocking.
+    fn prefers_dark(&self) -> Option<bool>;
+
+    /// Start checking the platform appearance again
+    ///
+    /// Sources that can't answer immediately detect in the background and
+    /// post `SignalToUI::set_ui_signal()` when `prefers_dark` has a new answer.
+    fn refresh(&self) {}
+}
+
+/// Reads the OS appearance setting (macOS, Windows, Linux desktops)
+///
+/// Detection runs on a background thread; `prefers_dark` returns the result
+/// of the last finished detection.
+#[derive(Clone, Debug, Default)]
+pub struct SystemAppearance {
+    state: Arc<DetectState>,
+}
+
+/// Result of the last detection, shared with the detection thread
+#[derive(Debug, Default)]
+struct DetectState {
+    /// 0 = unknown, 1 = light, 2 = dark
+    dark: AtomicU8,
+    detecting: AtomicBool,
+}
+
+impl AppearanceSource for SystemAppearance {
+    fn prefers_dark(&self) -> Option<bool> {
+        match self.state.dark.load(Ordering::Acquire) {
+            1 => Some(false),
+            2 => Some(true),
+            _ => None,
+        }
+    }
+
+    fn refresh(&self) {
+        // One detection at a time; a slow desktop portal shouldn't pile up threads
+        if self.state.detecting.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let state = self.state.clone();
+        std::thread::spawn(move || {
+            let dark = match dark_light::detect() {
+                dark_light::Mode::Dark => 2,
+                dark_light::Mode::Light => 1,
+                dark_light::Mode::Default => 0,
+            };
+            let changed = state.dark.swap(dark, Ordering::AcqRel) != dark;
+            state.detecting.store(false, Ordering::Release);
+            if changed {
+                SignalToUI::set_ui_signal();
+            }
+        });
+    }
+}
+
+/// Resolves a theme mode against an appearance source and reports
+/// appearance changes while in `ThemeMode::System`
+pub struct AppearanceWatcher {
+    source: Box<dyn AppearanceSource>,
+    mode: ThemeMode,
+    system_dark: Option<bool>,
+}
+
+impl Default for AppearanceWatcher {
+    fn default() -> Self {
+        Self::new(SystemAppearance::default())
+    }
+}
+
+impl fmt::Debug for AppearanceWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AppearanceWatcher")
+            .field("mode", &self.mode)
+            .field("system_dark", &self.system_dark)
+            .finish()
+    }
+}
+
+impl AppearanceWatcher {
+    /// Create a watcher in `ThemeMode::System` (call `set_mode` to query the source)
+    pub fn new(source: impl AppearanceSource + 'static) -> Self {
+        Self {
+            source: Box::new(source),
+            mode: ThemeMode::System,
+            system_dark: None,
+        }
+    }
+
+    /// Replace the appearance source
+    ///
+    /// Returns whether the mode is now dark.
+    pub fn set_source(&mut self, source: impl AppearanceSource + 'static) -> bool {
+        self.source = Box::new(source);
+        self.set_mode(self.mode)
+    }
+
+    /// Current theme mode
+    pub fn mode(&self) -> ThemeMode {
+        self.mode
+    }
+
+    /// Switch the theme mode, querying the source for `ThemeMode::System`
+    ///
+    /// Returns whether the new mode is dark, going by the source's latest
+    /// answer; a background refresh is started as well.
+    pub fn set_mode(&mut self, mode: ThemeMode) -> bool {
+        self.mode = mode;
+        if mode == ThemeMode::System {
+            self.source.refresh();
+            self.system_dark = self.source.prefers_dark();
+        }
+        self.is_dark()
+    }
+
+    /// Whether the current mode is dark
+    pub fn is_dark(&self) -> bool {
+        self.mode.is_dark(self.system_dark)
+    }
+
+    /// Whether the source has answered at least once in `ThemeMode::System`
+    pub fn is_known(&self) -> bool {
+        self.system_dark.is_some()
+    }
+
+    /// Ask the source to check the platform appearance again in `ThemeMode::System`
+    ///
+    /// The answer is picked up by the next `poll`.
+    pub fn refresh(&self) {
+        if self.mode == ThemeMode::System {
+            self.source.refresh();
+        }
+    }
+
+    /// Read the source's latest answer in `ThemeMode::System`
+    ///
+    /// Returns the new dark mode state if the platform appearance flipped it.
+    pub fn poll(&mut self) -> Option<bool> {
+        if self.mode != ThemeMode::System {
+            return None;
+        }
+        let was_dark = self.is_dark();
+        self.system_dark = self.source.prefers_dark();
+        let dark = self.is_dark();
+        (dark != was_dark).then_some(dark)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    /// Appearance the test can change after handing it to a watcher
+    #[derive(Clone, Default)]
+    struct FakeAppearance(Rc<Cell<Option<bool>>>, Rc<Cell<usize>>);
+
+    impl AppearanceSource for FakeAppearance {
+        fn prefers_dark(&self) -> Option<bool> {
+            self.0.get()
+        }
+
+        fn refresh(&self) {
+            self.1.set(self.1.get() + 1);
+        }
+    }
+
+    #[test]
+    fn test_explicit_modes_ignore_source() {
+        let source = FakeAppearance::default();
+        source.0.set(Some(true));
+        let mut watcher = AppearanceWatcher::new(source.clone());
+
+        assert!(!watcher.set_mode(ThemeMode::Light));
+        assert!(watcher.set_mode(ThemeMode::Dark));
+
+        source.0.set(Some(false));
+        assert_eq!(watcher.poll(), None);
+        assert!(watcher.is_dark());
+    }
+
+    #[test]
+    fn test_system_mode_follows_source() {
+        let source = FakeAppearance::default();
+        source.0.set(Some(true));
+        let mut watcher = AppearanceWatcher::new(source.clone());
+        assert!(watcher.set_mode(ThemeMode::System));
+
+        // No change, no event
+        assert_eq!(watcher.poll(), None);
+
+        source.0.set(Some(false));
+        assert_eq!(watcher.poll(), Some(false));
+        assert_eq!(watcher.poll(), None);
+
+        source.0.set(Some(true));
+        assert_eq!(watcher.poll(), Some(true));
+    }
+
+    #[test]
+    fn test_unknown_appearance_is_light() {
+        let source = FakeAppearance::default();
+        let mut watcher = AppearanceWatcher::new(source.clone());
+        assert!(!watcher.set_mode(ThemeMode::System));
+
+        // Unknown -> light is not a change
+        source.0.set(Some(false));
+        assert_eq!(watcher.poll(), None);
+    }
+
+    #[test]
+    fn test_refresh_only_in_system_mode() {
+        let source = FakeAppearance::default();
+        let mut watcher = AppearanceWatcher::new(source.clone());
+
+        watcher.set_mode(ThemeMode::Dark);
+        watcher.refresh();
+        assert_eq!(source.1.get(), 0);
+
+        watcher.set_mode(ThemeMode::System);
+        assert_eq!(source.1.get(), 1);
+        assert!(!watcher.is_known());
+
+        watcher.refresh();
+        assert_eq!(source.1.get(), 2);
+        source.0.set(Some(true));
+        assert_eq!(watcher.poll(), Some(true));
+        assert!(watcher.is_known());
+    }
+
+    #[test]
+    fn test_theme_mode_serde() {
+        let json = serde_json::to_string(&ThemeMode::System).unwrap();
+        assert_eq!(json, "\"System\"");
+        assert_eq!(serde_json::from_str::<ThemeMode>("\"Dark\"").unwrap(), ThemeMode::Dark);
+    }
+}
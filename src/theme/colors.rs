@@ -1,7 +1,12 @@
-//! Color palette for the app shell
+//! Color constants from before `ShellPalette`
 //!
-//! Tailwind-inspired color system with light/dark variants.
-//! Colors are defined as live_design constants for use in shaders.
+//! Deprecated: the shell's colors now come from `ShellPalette`, applied to
+//! shader uniforms. The semantic constants below hold the values of the
+//! matching `ShellPalette::light()` / `ShellPalette::dark()` fields and the
+//! Tailwind scales are unchanged; all of them will be removed in the next
+//! release.
+
+#![allow(deprecated)]
 
 use makepad_widgets::*;
 
@@ -9,129 +14,190 @@ use makepad_widgets::*;
 // SEMANTIC COLORS - LIGHT MODE
 // ============================================================================
 
-/// Main app background (light)
-pub const BG_APP: Vec4 = vec4(0.96, 0.97, 0.98, 1.0);           // #f5f7fa
+/// Main app background (light): `ShellPalette::light().bg_app`
+#[deprecated(note = "use `ShellPalette::light().bg_app`")]
+pub const BG_APP: Vec4 = vec4(1.0, 1.0, 1.0, 1.0);
 
-/// Header background (light)
-pub const BG_HEADER: Vec4 = vec4(0.25, 0.50, 0.75, 1.0);        // #4080c0
+/// Header background (light): `ShellPalette::light().bg_app`
+#[deprecated(note = "use `ShellPalette::light().bg_app`")]
+pub const BG_HEADER: Vec4 = vec4(1.0, 1.0, 1.0, 1.0);
 
-/// Sidebar background (light)
-pub const BG_SIDEBAR: Vec4 = vec4(0.50, 0.63, 0.82, 1.0);       // #80a0d0
+/// Sidebar background (light): `ShellPalette::light().bg_sidebar`
+#[deprecated(note = "use `ShellPalette::light().bg_sidebar`")]
+pub const BG_SIDEBAR: Vec4 = vec4(0.945, 0.961, 0.976, 1.0);
 
-/// Footer background (light)
-pub const BG_FOOTER: Vec4 = vec4(0.38, 0.63, 0.38, 1.0);        // #60a060
+/// Footer background (light): `ShellPalette::light().bg_sidebar`
+#[deprecated(note = "use `ShellPalette::light().bg_sidebar`")]
+pub const BG_FOOTER: Vec4 = vec4(0.945, 0.961, 0.976, 1.0);
 
-/// Content area background (light)
-pub const BG_CONTENT: Vec4 = vec4(0.91, 0.91, 0.94, 1.0);       // #e8e8f0
+/// Content area background (light): `ShellPalette::light().bg_canvas`
+#[deprecated(note = "use `ShellPalette::light().bg_canvas`")]
+pub const BG_CONTENT: Vec4 = vec4(0.886, 0.910, 0.941, 1.0);
 
-/// Panel/card background (light)
-pub const BG_PANEL: Vec4 = vec4(1.0, 1.0, 1.0, 1.0);            // #ffffff
+/// Panel/card background (light): `ShellPalette::light().bg_panel`
+#[deprecated(note = "use `ShellPalette::light().bg_panel`")]
+pub const BG_PANEL: Vec4 = vec4(1.0, 1.0, 1.0, 1.0);
 
-/// Primary text (light)
-pub const TEXT_PRIMARY: Vec4 = vec4(0.13, 0.13, 0.13, 1.0);     // #202020
+/// Primary text (light): `ShellPalette::light().text_primary`
+#[deprecated(note = "use `ShellPalette::light().text_primary`")]
+pub const TEXT_PRIMARY: Vec4 = vec4(0.122, 0.161, 0.216, 1.0);
 
-/// Secondary/dim text (light)
-pub const TEXT_SECONDARY: Vec4 = vec4(0.38, 0.38, 0.38, 1.0);   // #606060
+/// Secondary/dim text (light): `ShellPalette::light().text_secondary`
+#[deprecated(note = "use `ShellPalette::light().text_secondary`")]
+pub const TEXT_SECONDARY: Vec4 = vec4(0.247, 0.282, 0.333, 1.0);
 
-/// Accent color (light)
-pub const ACCENT: Vec4 = vec4(0.13, 0.38, 0.63, 1.0);           // #2060a0
+/// Accent color (light): `ShellPalette::light().accent`
+#[deprecated(note = "use `ShellPalette::light().accent`")]
+pub const ACCENT: Vec4 = vec4(0.231, 0.510, 0.965, 1.0);
 
-/// Border color (light)
-pub const BORDER: Vec4 = vec4(0.63, 0.63, 0.69, 1.0);           // #a0a0b0
+/// Border color (light): `ShellPalette::light().border`
+#[deprecated(note = "use `ShellPalette::light().border`")]
+pub const BORDER: Vec4 = vec4(0.886, 0.910, 0.941, 1.0);
 
 // ============================================================================
 // SEMANTIC COLORS - DARK MODE
 // ============================================================================
 
-/// Main app background (dark)
-pub const BG_APP_DARK: Vec4 = vec4(0.06, 0.09, 0.16, 1.0);      // #0f172a
+/// Main app background (dark): `ShellPalette::dark().bg_app`
+#[deprecated(note = "use `ShellPalette::dark().bg_app`")]
+pub const BG_APP_DARK: Vec4 = vec4(0.059, 0.090, 0.165, 1.0);
 
-/// Header background (dark)
-pub const BG_HEADER_DARK: Vec4 = vec4(0.15, 0.25, 0.38, 1.0);   // #264060
+/// Header background (dark): `ShellPalette::dark().bg_app`
+#[deprecated(note = "use `ShellPalette::dark().bg_app`")]
+pub const BG_HEADER_DARK: Vec4 = vec4(0.059, 0.090, 0.165, 1.0);
 
-/// Sidebar background (dark)
-pub const BG_SIDEBAR_DARK: Vec4 = vec4(0.12, 0.15, 0.20, 1.0);  // #1e2633
+/// Sidebar background (dark): `ShellPalette::dark().bg_sidebar`
+#[deprecated(note = "use `ShellPalette::dark().bg_sidebar`")]
+pub const BG_SIDEBAR_DARK: Vec4 = vec4(0.122, 0.161, 0.231, 1.0);
 
-/// Footer background (dark)
-pub const BG_FOOTER_DARK: Vec4 = vec4(0.15, 0.25, 0.15, 1.0);   // #264026
+/// Footer background (dark): `ShellPalette::dark().bg_sidebar`
+#[deprecated(note = "use `ShellPalette::dark().bg_sidebar`")]
+pub const BG_FOOTER_DARK: Vec4 = vec4(0.122, 0.161, 0.231, 1.0);
 
-/// Content area background (dark)
-pub const BG_CONTENT_DARK: Vec4 = vec4(0.10, 0.10, 0.12, 1.0);  // #1a1a1f
+/// Content area background (dark): `ShellPalette::dark().bg_canvas`
+#[deprecated(note = "use `ShellPalette::dark().bg_canvas`")]
+pub const BG_CONTENT_DARK: Vec4 = vec4(0.059, 0.090, 0.165, 1.0);
 
-/// Panel/card background (dark)
-pub const BG_PANEL_DARK: Vec4 = vec4(0.12, 0.16, 0.23, 1.0);    // #1f293b
+/// Panel/card background (dark): `ShellPalette::dark().bg_panel`
+#[deprecated(note = "use `ShellPalette::dark().bg_panel`")]
+pub const BG_PANEL_DARK: Vec4 = vec4(0.122, 0.161, 0.231, 1.0);
 
-/// Primary text (dark)
-pub const TEXT_PRIMARY_DARK: Vec4 = vec4(0.95, 0.96, 0.98, 1.0); // #f1f5f9
+/// Primary text (dark): `ShellPalette::dark().text_primary`
+#[deprecated(note = "use `ShellPalette::dark().text_primary`")]
+pub const TEXT_PRIMARY_DARK: Vec4 = vec4(0.945, 0.961, 0.976, 1.0);
 
-/// Secondary/dim text (dark)
-pub const TEXT_SECONDARY_DARK: Vec4 = vec4(0.58, 0.64, 0.72, 1.0); // #94a3b8
+/// Secondary/dim text (dark): `ShellPalette::dark().text_secondary`
+#[deprecated(note = "use `ShellPalette::dark().text_secondary`")]
+pub const TEXT_SECONDARY_DARK: Vec4 = vec4(0.886, 0.910, 0.941, 1.0);
 
-/// Accent color (dark)
-pub const ACCENT_DARK: Vec4 = vec4(0.38, 0.65, 0.98, 1.0);      // #60a5fa
+/// Accent color (dark): `ShellPalette::dark().accent`
+#[deprecated(note = "use `ShellPalette::dark().accent`")]
+pub const ACCENT_DARK: Vec4 = vec4(0.376, 0.647, 0.980, 1.0);
 
-/// Border color (dark)
-pub const BORDER_DARK: Vec4 = vec4(0.30, 0.30, 0.35, 1.0);      // #4d4d59
+/// Border color (dark): `ShellPalette::dark().border`
+#[deprecated(note = "use `ShellPalette::dark().border`")]
+pub const BORDER_DARK: Vec4 = vec4(0.200, 0.255, 0.333, 1.0);
 
 // ============================================================================
 // TAILWIND SLATE PALETTE
 // ============================================================================
 
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const SLATE_50: Vec4 = vec4(0.97, 0.98, 0.99, 1.0);         // #f8fafc
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const SLATE_100: Vec4 = vec4(0.95, 0.96, 0.98, 1.0);        // #f1f5f9
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const SLATE_200: Vec4 = vec4(0.89, 0.91, 0.94, 1.0);        // #e2e8f0
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const SLATE_300: Vec4 = vec4(0.80, 0.84, 0.89, 1.0);        // #cbd5e1
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const SLATE_400: Vec4 = vec4(0.58, 0.64, 0.72, 1.0);        // #94a3b8
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const SLATE_500: Vec4 = vec4(0.39, 0.45, 0.55, 1.0);        // #64748b
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const SLATE_600: Vec4 = vec4(0.28, 0.33, 0.41, 1.0);        // #475569
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const SLATE_700: Vec4 = vec4(0.20, 0.25, 0.33, 1.0);        // #334155
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const SLATE_800: Vec4 = vec4(0.12, 0.16, 0.23, 1.0);        // #1e293b
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const SLATE_900: Vec4 = vec4(0.06, 0.09, 0.16, 1.0);        // #0f172a
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const SLATE_950: Vec4 = vec4(0.01, 0.03, 0.06, 1.0);        // #020617
 
 // ============================================================================
 // TAILWIND BLUE PALETTE
 // ============================================================================
 
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const BLUE_50: Vec4 = vec4(0.94, 0.96, 1.0, 1.0);           // #eff6ff
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const BLUE_100: Vec4 = vec4(0.86, 0.92, 0.99, 1.0);         // #dbeafe
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const BLUE_200: Vec4 = vec4(0.74, 0.85, 0.98, 1.0);         // #bfdbfe
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const BLUE_300: Vec4 = vec4(0.58, 0.75, 0.96, 1.0);         // #93c5fd
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const BLUE_400: Vec4 = vec4(0.38, 0.65, 0.98, 1.0);         // #60a5fa
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const BLUE_500: Vec4 = vec4(0.23, 0.51, 0.96, 1.0);         // #3b82f6
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const BLUE_600: Vec4 = vec4(0.15, 0.39, 0.92, 1.0);         // #2563eb
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const BLUE_700: Vec4 = vec4(0.11, 0.31, 0.85, 1.0);         // #1d4ed8
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const BLUE_800: Vec4 = vec4(0.12, 0.25, 0.69, 1.0);         // #1e40af
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const BLUE_900: Vec4 = vec4(0.12, 0.23, 0.55, 1.0);         // #1e3a8a
 
 // ============================================================================
 // TAILWIND GREEN PALETTE
 // ============================================================================
 
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const GREEN_50: Vec4 = vec4(0.94, 0.99, 0.96, 1.0);         // #f0fdf4
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const GREEN_100: Vec4 = vec4(0.86, 0.98, 0.91, 1.0);        // #dcfce7
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const GREEN_200: Vec4 = vec4(0.73, 0.95, 0.82, 1.0);        // #bbf7d0
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const GREEN_300: Vec4 = vec4(0.52, 0.90, 0.68, 1.0);        // #86efac
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const GREEN_400: Vec4 = vec4(0.29, 0.82, 0.55, 1.0);        // #4ade80
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const GREEN_500: Vec4 = vec4(0.13, 0.72, 0.44, 1.0);        // #22c55e
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const GREEN_600: Vec4 = vec4(0.09, 0.60, 0.36, 1.0);        // #16a34a
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const GREEN_700: Vec4 = vec4(0.08, 0.49, 0.31, 1.0);        // #15803d
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const GREEN_800: Vec4 = vec4(0.09, 0.39, 0.27, 1.0);        // #166534
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const GREEN_900: Vec4 = vec4(0.08, 0.32, 0.23, 1.0);        // #14532d
 
 // ============================================================================
 // TAILWIND RED PALETTE
 // ============================================================================
 
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const RED_50: Vec4 = vec4(0.99, 0.95, 0.95, 1.0);           // #fef2f2
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const RED_100: Vec4 = vec4(0.99, 0.89, 0.89, 1.0);          // #fee2e2
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const RED_200: Vec4 = vec4(0.99, 0.79, 0.79, 1.0);          // #fecaca
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const RED_300: Vec4 = vec4(0.99, 0.65, 0.65, 1.0);          // #fca5a5
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const RED_400: Vec4 = vec4(0.97, 0.44, 0.44, 1.0);          // #f87171
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const RED_500: Vec4 = vec4(0.94, 0.27, 0.27, 1.0);          // #ef4444
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const RED_600: Vec4 = vec4(0.86, 0.15, 0.15, 1.0);          // #dc2626
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const RED_700: Vec4 = vec4(0.73, 0.11, 0.11, 1.0);          // #b91c1c
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const RED_800: Vec4 = vec4(0.60, 0.11, 0.11, 1.0);          // #991b1b
+#[deprecated(note = "use `ShellPalette` instead")]
 pub const RED_900: Vec4 = vec4(0.50, 0.13, 0.13, 1.0);          // #7f1d1d
 
 // ============================================================================
@@ -139,6 +205,7 @@ pub const RED_900: Vec4 = vec4(0.50, 0.13, 0.13, 1.0);          // #7f1d1d
 // ============================================================================
 
 /// Default panel color palette (light, professional colors)
+#[deprecated(note = "panels take their colors from `ShellPalette`")]
 pub fn panel_colors() -> [Vec4; 9] {
     [
         vec4(1.0, 1.0, 1.0, 1.0),       // White
@@ -154,6 +221,7 @@ pub fn panel_colors() -> [Vec4; 9] {
 }
 
 /// Dark mode panel color palette (slightly brighter)
+#[deprecated(note = "panels take their colors from `ShellPalette`")]
 pub fn panel_colors_dark() -> [Vec4; 9] {
     [
         vec4(0.90, 0.35, 0.35, 1.0),   // Red
@@ -167,3 +235,27 @@ pub fn panel_colors_dark() -> [Vec4; 9] {
         vec4(0.50, 0.90, 0.50, 1.0),   // Light green
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::ShellPalette;
+
+    #[test]
+    fn test_semantic_constants_match_the_palettes() {
+        let (light, dark) = (ShellPalette::light(), ShellPalette::dark());
+        let pairs = [
+            (BG_APP, light.bg_app), (BG_HEADER, light.bg_app), (BG_SIDEBAR, light.bg_sidebar),
+            (BG_FOOTER, light.bg_sidebar), (BG_CONTENT, light.bg_canvas), (BG_PANEL, light.bg_panel),
+            (TEXT_PRIMARY, light.text_primary), (TEXT_SECONDARY, light.text_secondary),
+            (ACCENT, light.accent), (BORDER, light.border),
+            (BG_APP_DARK, dark.bg_app), (BG_HEADER_DARK, dark.bg_app), (BG_SIDEBAR_DARK, dark.bg_sidebar),
+            (BG_FOOTER_DARK, dark.bg_sidebar), (BG_CONTENT_DARK, dark.bg_canvas), (BG_PANEL_DARK, dark.bg_panel),
+            (TEXT_PRIMARY_DARK, dark.text_primary), (TEXT_SECONDARY_DARK, dark.text_secondary),
+            (ACCENT_DARK, dark.accent), (BORDER_DARK, dark.border),
+        ];
+        for (constant, field) in pairs {
+            assert_eq!(constant, field);
+        }
+    }
+}
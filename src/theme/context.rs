@@ -0,0 +1,159 @@
+//! Theme propagation from a shell to its subtree
+//!
+//! `ShellLayout` provides its current theme as a `ThemeContext` to its
+//! subtree, for both event handling and drawing. Widgets read it with
+//! `ThemeContext::current` while the shell's pass runs, so the `Scope`
+//! props and data an app hands to the shell reach its content untouched.
+//!
+//! The context is not carried in the `Scope`: it lives on a per-thread stack
+//! that `ShellLayout` pushes for the duration of its `handle_event` and
+//! `draw_walk`. It is only visible to code running synchronously inside those
+//! calls, so it can't be read from a later frame, another thread, or a
+//! widget the shell doesn't reach through its own pass.
+//! Every shell has its own context (nested shells shadow the outer one), so
+//! two shells in one process can show different themes, and any widget
+//! inside a shell (including app content) can read the theme without being
+//! known to the shell.
+//!
+//! On every theme change the shell bumps the context `generation` and
+//! redraws. Widgets that only need to react to changes keep a
+//! `ThemeTracker` (or implement `ThemeListener`) and compare generations.
+//!
+//! ## Usage
+//!
+//! ```rust,ignore
+//! fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+//!     if let Some(theme) = self.theme_tracker.changed() {
+//!         self.view.apply_over(cx, &theme.palette.live_nodes(&[live_id!(draw_bg)]));
+//!     }
+//!     self.view.draw_walk(cx, scope, walk)
+//! }
+//! ```
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use super::palette::ShellPalette;
+use super::styles::ThemeMetrics;
+
+thread_local! {
+    /// Contexts of the shells whose event or draw pass is running, innermost last
+    static ACTIVE_THEMES: RefCell<Vec<Rc<ThemeContext>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Pops the context pushed by `ThemeContext::provide`, even if the pass panics
+struct ProvideGuard;
+
+impl Drop for ProvideGuard {
+    fn drop(&mut self) {
+        ACTIVE_THEMES.with_borrow_mut(|themes| themes.pop());
+    }
+}
+
+/// Theme state of one shell, provided to its descendants
+#[derive(Clone, Debug, Default)]
+pub struct ThemeContext {
+    /// Dark mode animation value (0.0 = light, 1.0 = dark)
+    pub dark_mode: f64,
+
+    /// Palette blended for the current dark mode value
+    pub palette: ShellPalette,
+
+    /// Font sizes, spacing and radii
+    pub metrics: ThemeMetrics,
+
+    /// Incremented by the shell on every theme change
+    pub generation: u64,
+}
+
+impl ThemeContext {
+    /// Run `f` with `theme` as the context of the enclosing shell
+    ///
+    /// `ShellLayout` wraps its event handling and drawing in this.
+    pub fn provide<R>(theme: &Rc<ThemeContext>, f: impl FnOnce() -> R) -> R {
+        ACTIVE_THEMES.with_borrow_mut(|themes| themes.push(theme.clone()));
+        let _guard = ProvideGuard;
+        f()
+    }
+
+    /// The theme context of the innermost shell whose event or draw pass is running, if any
+    pub fn current() -> Option<Rc<ThemeContext>> {
+        ACTIVE_THEMES.with_borrow(|themes| themes.last().cloned())
+    }
+}
+
+/// The palette of the enclosing shell, or the built-in light palette outside one
+pub fn current_palette() -> ShellPalette {
+    ThemeContext::current()
+        .map(|theme| theme.palette.clone())
+        .unwrap_or_default()
+}
+
+/// The dark mode value of the enclosing shell (0.0 outside one)
+pub fn current_dark_mode() -> f64 {
+    ThemeContext::current().map_or(0.0, |theme| theme.dark_mode)
+}
+
+/// Remembers which theme generation a widget last applied
+#[derive(Clone, Debug, Default)]
+pub struct ThemeTracker {
+    generation: Option<u64>,
+}
+
+impl ThemeTracker {
+    /// The current theme if it changed since the last call (always on the first call)
+    ///
+    /// Returns `None` outside a shell; live_design defaults are the light palette.
+    pub fn changed(&mut self) -> Option<Rc<ThemeContext>> {
+        let theme = ThemeContext::current()?;
+        if self.generation == Some(theme.generation) {
+            return None;
+        }
+        self.generation = Some(theme.generation);
+        Some(theme)
+    }
+
+    /// Apply the theme again on the next `changed` call
+    pub fn invalidate(&mut self) {
+        self.generation = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(generation: u64) -> Rc<ThemeContext> {
+        Rc::new(ThemeContext { generation, ..Default::default() })
+    }
+
+    #[test]
+    fn test_tracker_reports_each_generation_once() {
+        let mut tracker = ThemeTracker::default();
+
+        // Outside a shell there's nothing to apply
+        assert!(tracker.changed().is_none());
+
+        ThemeContext::provide(&context(1), || {
+            assert_eq!(tracker.changed().map(|theme| theme.generation), Some(1));
+            assert!(tracker.changed().is_none());
+        });
+        ThemeContext::provide(&context(2), || {
+            assert_eq!(tracker.changed().map(|theme| theme.generation), Some(2));
+            assert!(tracker.changed().is_none());
+
+            tracker.invalidate();
+            assert_eq!(tracker.changed().map(|theme| theme.generation), Some(2));
+        });
+    }
+
+    #[test]
+    fn test_innermost_shell_wins() {
+        ThemeContext::provide(&context(1), || {
+            ThemeContext::provide(&context(7), || {
+                assert_eq!(ThemeContext::current().map(|theme| theme.generation), Some(7));
+            });
+            assert_eq!(ThemeContext::current().map(|theme| theme.generation), Some(1));
+        });
+        assert!(ThemeContext::current().is_none());
+    }
+}
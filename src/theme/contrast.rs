@@ -0,0 +1,175 @@
+//! WCAG contrast checks for palettes
+//!
+//! Every pair of palette roles the shell draws on top of each other is
+//! listed in `CONTRAST_PAIRS` with the WCAG 2.1 AA minimum it must meet:
+//! 4.5:1 for text, 3:1 for icons and other UI graphics.
+//!
+//! ## Usage
+//!
+//! ```rust,ignore
+//! for issue in check_contrast(&palette) {
+//!     log!("{}", issue); // "text_muted on bg_list: 3.21:1 (needs 4.5:1)"
+//! }
+//! ```
+
+use std::fmt;
+use makepad_widgets::*;
+use super::palette::ShellPalette;
+
+/// Minimum contrast ratio for normal text (WCAG 2.1 AA, 1.4.3)
+pub const WCAG_AA_TEXT: f64 = 4.5;
+
+/// Minimum contrast ratio for icons and UI graphics (WCAG 2.1 AA, 1.4.11)
+pub const WCAG_AA_NON_TEXT: f64 = 3.0;
+
+/// Foreground/background palette roles the shell draws together, with the
+/// contrast ratio each pair requires
+pub const CONTRAST_PAIRS: &[(&str, &str, f64)] = &[
+    // Titles and labels on every surface they appear on
+    ("text_primary", "bg_app", WCAG_AA_TEXT),
+    ("text_primary", "bg_panel", WCAG_AA_TEXT),
+    ("text_primary", "bg_sidebar", WCAG_AA_TEXT),
+    ("text_primary", "bg_list", WCAG_AA_TEXT),
+    ("text_primary", "bg_title_bar", WCAG_AA_TEXT),
+    ("text_primary", "bg_hover", WCAG_AA_TEXT),
+    ("text_primary", "bg_selected", WCAG_AA_TEXT),
+    ("text_primary", "bg_pressed", WCAG_AA_TEXT),
+    ("text_secondary", "bg_app", WCAG_AA_TEXT),
+    ("text_secondary", "bg_panel", WCAG_AA_TEXT),
+    ("text_secondary", "bg_sidebar", WCAG_AA_TEXT),
+    ("text_secondary", "bg_list", WCAG_AA_TEXT),
+    ("text_secondary", "bg_title_bar", WCAG_AA_TEXT),
+    ("text_secondary", "bg_hover", WCAG_AA_TEXT),
+    ("text_secondary", "bg_selected", WCAG_AA_TEXT),
+    ("text_secondary", "bg_pressed", WCAG_AA_TEXT),
+    // Section headers and status text
+    ("text_muted", "bg_app", WCAG_AA_TEXT),
+    ("text_muted", "bg_panel", WCAG_AA_TEXT),
+    ("text_muted", "bg_sidebar", WCAG_AA_TEXT),
+    ("text_muted", "bg_list", WCAG_AA_TEXT),
+    ("text_muted", "bg_title_bar", WCAG_AA_TEXT),
+    // Badge text
+    ("on_accent", "accent", WCAG_AA_TEXT),
+    // Overlay sidebar
+    ("overlay_text", "bg_overlay", WCAG_AA_TEXT),
+    ("overlay_text", "bg_overlay_header", WCAG_AA_TEXT),
+    ("overlay_text", "bg_overlay_hover", WCAG_AA_TEXT),
+    ("overlay_text", "bg_overlay_pressed", WCAG_AA_TEXT),
+    ("overlay_icon", "bg_overlay", WCAG_AA_NON_TEXT),
+    ("overlay_icon", "bg_overlay_hover", WCAG_AA_NON_TEXT),
+    // Drag handles, idle icons and indicators
+    ("text_faint", "bg_panel", WCAG_AA_NON_TEXT),
+    ("text_faint", "bg_sidebar", WCAG_AA_NON_TEXT),
+    ("text_faint", "bg_list", WCAG_AA_NON_TEXT),
+    ("text_faint", "bg_title_bar", WCAG_AA_NON_TEXT),
+    ("icon", "bg_panel", WCAG_AA_NON_TEXT),
+    ("icon", "bg_title_bar", WCAG_AA_NON_TEXT),
+    ("accent", "bg_app", WCAG_AA_NON_TEXT),
+    ("accent", "bg_panel", WCAG_AA_NON_TEXT),
+    ("accent", "bg_list", WCAG_AA_NON_TEXT),
+    ("danger", "bg_title_bar", WCAG_AA_NON_TEXT),
+];
+
+/// A palette pair that doesn't meet its required contrast
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContrastIssue {
+    /// Foreground color role
+    pub foreground: &'static str,
+
+    /// Background color role
+    pub background: &'static str,
+
+    /// Measured contrast ratio
+    pub ratio: f64,
+
+    /// Required contrast ratio
+    pub required: f64,
+}
+
+impl fmt::Display for ContrastIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} on {}: {:.2}:1 (needs {}:1)",
+            self.foreground, self.background, self.ratio, self.required
+        )
+    }
+}
+
+/// Relative luminance of an sRGB color (alpha is ignored)
+pub fn relative_luminance(color: Vec4) -> f64 {
+    let channel = |c: f32| {
+        let c = c.clamp(0.0, 1.0) as f64;
+        if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+    };
+    0.2126 * channel(color.x) + 0.7152 * channel(color.y) + 0.0722 * channel(color.z)
+}
+
+/// Contrast ratio (1.0 to 21.0) of a foreground drawn over a background
+///
+/// A translucent foreground is composited over the background first.
+pub fn contrast_ratio(foreground: Vec4, background: Vec4) -> f64 {
+    let alpha = foreground.w.clamp(0.0, 1.0);
+    let foreground = vec4(
+        background.x + (foreground.x - background.x) * alpha,
+        background.y + (foreground.y - background.y) * alpha,
+        background.z + (foreground.z - background.z) * alpha,
+        1.0,
+    );
+    let a = relative_luminance(foreground);
+    let b = relative_luminance(background);
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// Check every pair in `CONTRAST_PAIRS` against its WCAG AA minimum
+pub fn check_contrast(palette: &ShellPalette) -> Vec<ContrastIssue> {
+    CONTRAST_PAIRS
+        .iter()
+        .filter_map(|&(foreground, background, required)| {
+            let ratio = contrast_ratio(palette.color(foreground)?, palette.color(background)?);
+            (ratio < required).then_some(ContrastIssue { foreground, background, ratio, required })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_contrast_ratio() {
+        let black = vec4(0.0, 0.0, 0.0, 1.0);
+        let white = vec4(1.0, 1.0, 1.0, 1.0);
+
+        assert!((contrast_ratio(black, white) - 21.0).abs() < 1e-9);
+        assert!((contrast_ratio(white, black) - 21.0).abs() < 1e-9);
+        assert!((contrast_ratio(white, white) - 1.0).abs() < 1e-9);
+        // A fully transparent foreground disappears into the background
+        assert!((contrast_ratio(vec4(0.0, 0.0, 0.0, 0.0), white) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_high_contrast_palettes_pass() {
+        assert_eq!(check_contrast(&ShellPalette::high_contrast_light()), Vec::new());
+        assert_eq!(check_contrast(&ShellPalette::high_contrast_dark()), Vec::new());
+    }
+
+    #[test]
+    fn test_low_contrast_is_reported() {
+        let mut palette = ShellPalette::high_contrast_light();
+        palette.text_muted = palette.bg_list;
+
+        let issues = check_contrast(&palette);
+        assert!(issues.iter().any(|issue| issue.foreground == "text_muted" && issue.background == "bg_list"));
+        assert!(issues.iter().all(|issue| issue.foreground == "text_muted"));
+    }
+
+    #[test]
+    fn test_contrast_pairs_name_palette_colors() {
+        let palette = ShellPalette::light();
+        for (foreground, background, _) in CONTRAST_PAIRS {
+            assert!(palette.color(foreground).is_some(), "{}", foreground);
+            assert!(palette.color(background).is_some(), "{}", background);
+        }
+    }
+}
@@ -1,7 +1,12 @@
 //! Theme system for the app shell
 //!
-//! Provides dark/light mode switching with smooth animations using
-//! shader instance variables and the `apply_over()` pattern.
+//! Provides semantic color palettes and dark/light mode switching with
+//! smooth animations. Shaders read palette colors through uniforms that are
+//! pushed with the `apply_over()` pattern. Each `ShellLayout` provides its theme
+//! to descendants as a `ThemeContext` (see `context`).
+//! Palettes, font sizes, spacing and radii can also be loaded from JSON/TOML
+//! theme files (see `theme_file`), and the light/dark mode can follow the
+//! platform appearance (see `appearance`).
 //!
 //! ## Usage
 //!
@@ -9,47 +14,35 @@
 //! // In your widget's live_design:
 //! MyWidget = <View> {
 //!     draw_bg: {
-//!         instance dark_mode: 0.0
+//!         uniform bg_panel: (PALETTE_BG_PANEL)
 //!         fn pixel(self) -> vec4 {
-//!             return mix((BG_PANEL), (BG_PANEL_DARK), self.dark_mode);
+//!             return self.bg_panel;
 //!         }
 //!     }
 //! }
 //!
-//! // At runtime:
-//! widget.apply_over(cx, live!{
-//!     draw_bg: { dark_mode: (theme.dark_mode_anim) }
-//! });
+//! // At runtime (during draw):
+//! let palette = current_palette();
+//! widget.apply_over(cx, &palette.live_nodes(&[live_id!(draw_bg)]));
 //! ```
 
+pub mod appearance;
 pub mod colors;
+pub mod context;
+pub mod contrast;
+pub mod palette;
 pub mod styles;
+pub mod theme_file;
 
+pub use appearance::*;
 pub use colors::*;
+pub use context::*;
+pub use contrast::*;
+pub use palette::*;
 pub use styles::*;
+pub use theme_file::*;
 
 use makepad_widgets::*;
-use std::cell::RefCell;
-
-// ============================================================================
-// GLOBAL THEME STATE (for widgets that can't be accessed via id lookup)
-// ============================================================================
-
-thread_local! {
-    /// Global dark mode animation value (0.0 = light, 1.0 = dark)
-    /// Widgets can read this during draw to get the current theme state
-    static GLOBAL_DARK_MODE: RefCell<f64> = RefCell::new(0.0);
-}
-
-/// Set the global dark mode value (called by ShellLayout during theme changes)
-pub fn set_global_dark_mode(value: f64) {
-    GLOBAL_DARK_MODE.with(|dm| *dm.borrow_mut() = value);
-}
-
-/// Get the current global dark mode value (called by widgets during draw)
-pub fn get_global_dark_mode() -> f64 {
-    GLOBAL_DARK_MODE.with(|dm| *dm.borrow())
-}
 
 // ============================================================================
 // SHELL THEME
@@ -57,7 +50,8 @@ pub fn get_global_dark_mode() -> f64 {
 
 /// Theme state for the shell
 ///
-/// Tracks dark mode setting and animation progress for smooth transitions.
+/// Holds the light and dark palettes and tracks the dark mode setting and
+/// animation progress for smooth transitions between them.
 #[derive(Clone, Debug)]
 pub struct ShellTheme {
     /// Whether dark mode is enabled
@@ -66,14 +60,20 @@ pub struct ShellTheme {
     /// Animation progress (0.0 = light, 1.0 = dark)
     /// Used for smooth transitions between themes
     pub dark_mode_anim: f64,
+
+    /// Palette used in light mode
+    pub light_palette: ShellPalette,
+
+    /// Palette used in dark mode
+    pub dark_palette: ShellPalette,
+
+    /// Font sizes, spacing and radii
+    pub metrics: ThemeMetrics,
 }
 
 impl Default for ShellTheme {
     fn default() -> Self {
-        Self {
-            dark_mode: false,
-            dark_mode_anim: 0.0,
-        }
+        Self::light()
     }
 }
 
@@ -83,6 +83,9 @@ impl ShellTheme {
         Self {
             dark_mode: false,
             dark_mode_anim: 0.0,
+            light_palette: ShellPalette::light(),
+            dark_palette: ShellPalette::dark(),
+            metrics: ThemeMetrics::default(),
         }
     }
 
@@ -91,6 +94,40 @@ impl ShellTheme {
         Self {
             dark_mode: true,
             dark_mode_anim: 1.0,
+            ..Self::light()
+        }
+    }
+
+    /// Use custom light and dark palettes (e.g. a brand theme)
+    pub fn with_palettes(mut self, light: ShellPalette, dark: ShellPalette) -> Self {
+        self.set_palettes(light, dark);
+        self
+    }
+
+    /// Replace the light and dark palettes
+    pub fn set_palettes(&mut self, light: ShellPalette, dark: ShellPalette) {
+        self.light_palette = light;
+        self.dark_palette = dark;
+    }
+
+    /// Use the palettes and metrics of a theme file
+    pub fn set_definition(&mut self, definition: &ThemeDefinition) {
+        self.set_palettes(definition.light.clone(), definition.dark.clone());
+        self.metrics = definition.metrics;
+    }
+
+    /// Current palette, blended by the dark mode animation progress
+    pub fn palette(&self) -> ShellPalette {
+        self.light_palette.lerp(&self.dark_palette, self.dark_mode_anim)
+    }
+
+    /// Snapshot of the current theme for passing down the widget tree
+    pub fn context(&self, generation: u64) -> ThemeContext {
+        ThemeContext {
+            dark_mode: self.dark_mode_anim,
+            palette: self.palette(),
+            metrics: self.metrics,
+            generation,
         }
     }
 
@@ -111,8 +148,7 @@ impl ShellTheme {
     pub fn update_animation(&mut self, elapsed: f64, duration: f64) -> bool {
         let t = (elapsed / duration).min(1.0);
 
-        // Ease-out cubic: 1 - (1 - t)^3
-        let eased = 1.0 - (1.0 - t).powi(3);
+        let eased = ease_out_cubic(t);
 
         let target = self.target_anim();
         let start = if self.dark_mode { 0.0 } else { 1.0 };
@@ -128,28 +164,55 @@ impl ShellTheme {
 
 /// Trait for widgets that respond to theme changes
 ///
-/// Implement this trait on your widget's Ref type to receive theme updates.
+/// Implement this trait on your widget and call `sync_theme` at the start of
+/// `draw_walk`. The enclosing `ShellLayout` redraws on every theme change, so
+/// the listener is notified without being registered anywhere.
 ///
 /// ## Example
 ///
 /// ```rust,ignore
-/// impl ThemeListener for MyWidgetRef {
-///     fn on_dark_mode_change(&self, cx: &mut Cx, dark_mode: f64) {
-///         if let Some(mut inner) = self.borrow_mut() {
-///             inner.view.apply_over(cx, live!{
-///                 draw_bg: { dark_mode: (dark_mode) }
-///             });
-///         }
+/// impl ThemeListener for MyWidget {
+///     fn theme_tracker(&mut self) -> &mut ThemeTracker {
+///         &mut self.theme_tracker
+///     }
+///
+///     fn on_dark_mode_change(&mut self, cx: &mut Cx, dark_mode: f64) {
+///         self.view.apply_over(cx, live!{
+///             draw_bg: { dark_mode: (dark_mode) }
+///         });
+///     }
+/// }
+///
+/// impl Widget for MyWidget {
+///     fn draw_walk(&mut self, cx: &mut Cx2d, scope: &mut Scope, walk: Walk) -> DrawStep {
+///         self.sync_theme(cx);
+///         self.view.draw_walk(cx, scope, walk)
 ///     }
 /// }
 /// ```
 pub trait ThemeListener {
+    /// Tracker remembering the theme generation this listener last saw
+    fn theme_tracker(&mut self) -> &mut ThemeTracker;
+
     /// Called when dark mode value changes
     ///
     /// # Arguments
     /// * `cx` - Makepad context for applying UI updates
     /// * `dark_mode` - Animation value (0.0 = light, 1.0 = dark)
-    fn on_dark_mode_change(&self, cx: &mut Cx, dark_mode: f64);
+    fn on_dark_mode_change(&mut self, cx: &mut Cx, dark_mode: f64);
+
+    /// Called when the active palette changes (including during transitions)
+    ///
+    /// The default implementation does nothing.
+    fn on_palette_change(&mut self, _cx: &mut Cx, _palette: &ShellPalette) {}
+
+    /// Notify this listener if the enclosing shell's theme changed since the last call
+    fn sync_theme(&mut self, cx: &mut Cx) {
+        if let Some(theme) = self.theme_tracker().changed() {
+            self.on_dark_mode_change(cx, theme.dark_mode);
+            self.on_palette_change(cx, &theme.palette);
+        }
+    }
 }
 
 // ============================================================================
@@ -158,33 +221,36 @@ pub trait ThemeListener {
 
 /// Generate live_design color constants
 ///
-/// This macro is used internally to define colors in live_design! blocks.
+/// Deprecated along with `theme::colors`: shaders take their colors from the
+/// `PALETTE_*` uniforms of `ShellPalette`. The semantic names below hold the
+/// `ShellPalette::light()` / `ShellPalette::dark()` values.
+#[deprecated(note = "use the `PALETTE_*` uniforms of `ShellPalette` instead")]
 #[macro_export]
 macro_rules! shell_colors {
     () => {
         // Light mode semantic colors
-        BG_APP = #f5f7fa
-        BG_HEADER = #4080c0
-        BG_SIDEBAR = #80a0d0
-        BG_FOOTER = #60a060
-        BG_CONTENT = #e8e8f0
+        BG_APP = #ffffff
+        BG_HEADER = #ffffff
+        BG_SIDEBAR = #f1f5f9
+        BG_FOOTER = #f1f5f9
+        BG_CONTENT = #e2e8f0
         BG_PANEL = #ffffff
-        TEXT_PRIMARY = #202020
-        TEXT_SECONDARY = #606060
-        ACCENT = #2060a0
-        BORDER = #a0a0b0
+        TEXT_PRIMARY = #1f2937
+        TEXT_SECONDARY = #3f4855
+        ACCENT = #3b82f6
+        BORDER = #e2e8f0
 
         // Dark mode semantic colors
         BG_APP_DARK = #0f172a
-        BG_HEADER_DARK = #264060
-        BG_SIDEBAR_DARK = #1e2633
-        BG_FOOTER_DARK = #264026
-        BG_CONTENT_DARK = #1a1a1f
+        BG_HEADER_DARK = #0f172a
+        BG_SIDEBAR_DARK = #1f293b
+        BG_FOOTER_DARK = #1f293b
+        BG_CONTENT_DARK = #0f172a
         BG_PANEL_DARK = #1f293b
         TEXT_PRIMARY_DARK = #f1f5f9
-        TEXT_SECONDARY_DARK = #94a3b8
+        TEXT_SECONDARY_DARK = #e2e8f0
         ACCENT_DARK = #60a5fa
-        BORDER_DARK = #4d4d59
+        BORDER_DARK = #334155
 
         // Slate palette
         SLATE_50 = #f8fafc
@@ -0,0 +1,436 @@
+//! Semantic color palettes for the app shell
+//!
+//! A `ShellPalette` names every color the shell draws with by its role
+//! (backgrounds, text, borders, accent, hover/selected states). Shell shaders
+//! declare the roles they use as `uniform`s and `ShellLayout` pushes the
+//! active palette into them, so an app can ship its own brand theme by
+//! supplying light and dark palettes instead of editing shaders.
+//!
+//! ## Usage
+//!
+//! ```rust,ignore
+//! // In your widget's live_design:
+//! MyWidget = <View> {
+//!     draw_bg: {
+//!         uniform bg_panel: (PALETTE_BG_PANEL)
+//!         fn pixel(self) -> vec4 {
+//!             return self.bg_panel;
+//!         }
+//!     }
+//! }
+//!
+//! // At runtime:
+//! let palette = current_palette();
+//! widget.apply_over(cx, &palette.live_nodes(&[live_id!(draw_bg)]));
+//! ```
+
+use makepad_widgets::*;
+
+/// Full semantic palette used by shell shaders
+///
+/// Field names double as shader uniform names.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ShellPalette {
+    /// Window background, header and popups
+    pub bg_app: Vec4,
+    /// Area behind the panel grid
+    pub bg_canvas: Vec4,
+    /// Panel body
+    pub bg_panel: Vec4,
+    /// Sidebar and footer surfaces
+    pub bg_sidebar: Vec4,
+    /// Lists inside sidebars (menu items, tree rows)
+    pub bg_list: Vec4,
+    /// Panel title bars
+    pub bg_title_bar: Vec4,
+    /// Activity bar column
+    pub bg_activity_bar: Vec4,
+    /// Hovered list item / button
+    pub bg_hover: Vec4,
+    /// Selected list item
+    pub bg_selected: Vec4,
+    /// Pressed list item / button
+    pub bg_pressed: Vec4,
+
+    /// Headings, header icons and active icons
+    pub text_primary: Vec4,
+    /// Regular labels (panel titles, menu items)
+    pub text_secondary: Vec4,
+    /// Section headers, status text, chevrons
+    pub text_muted: Vec4,
+    /// Hints, drag handles and idle icons
+    pub text_faint: Vec4,
+    /// Panel title bar buttons
+    pub icon: Vec4,
+
+    /// Borders and dividers
+    pub border: Vec4,
+    /// Accent (focus rings, indicators, badges, hover strokes)
+    pub accent: Vec4,
+    /// Content drawn on top of the accent color
+    pub on_accent: Vec4,
+    /// Destructive actions (close button hover)
+    pub danger: Vec4,
+    /// Warnings (notification toasts)
+    pub warning: Vec4,
+    /// Success feedback (save flash)
+    pub success: Vec4,
+
+    /// Overlay sidebar background
+    pub bg_overlay: Vec4,
+    /// Overlay sidebar header
+    pub bg_overlay_header: Vec4,
+    /// Hovered overlay menu item
+    pub bg_overlay_hover: Vec4,
+    /// Pressed overlay menu item
+    pub bg_overlay_pressed: Vec4,
+    /// Overlay text
+    pub overlay_text: Vec4,
+    /// Overlay menu icons
+    pub overlay_icon: Vec4,
+    /// Overlay separators
+    pub overlay_border: Vec4,
+}
+
+impl Default for ShellPalette {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+impl ShellPalette {
+    /// Built-in light palette (slate/blue with a purple overlay sidebar)
+    pub fn light() -> Self {
+        Self {
+            bg_app: vec4(1.0, 1.0, 1.0, 1.0),                   // white
+            bg_canvas: vec4(0.886, 0.910, 0.941, 1.0),          // slate-200
+            bg_panel: vec4(1.0, 1.0, 1.0, 1.0),                 // white
+            bg_sidebar: vec4(0.945, 0.961, 0.976, 1.0),         // slate-100
+            bg_list: vec4(0.973, 0.980, 0.988, 1.0),            // slate-50
+            bg_title_bar: vec4(0.945, 0.961, 0.976, 1.0),       // slate-100
+            bg_activity_bar: vec4(0.945, 0.961, 0.976, 1.0),    // slate-100
+            bg_hover: vec4(0.886, 0.910, 0.941, 1.0),           // slate-200
+            bg_selected: vec4(0.859, 0.914, 0.988, 1.0),        // blue-100
+            bg_pressed: vec4(0.792, 0.835, 0.890, 1.0),         // slate-300
+
+            text_primary: vec4(0.122, 0.161, 0.216, 1.0),       // gray-800
+            text_secondary: vec4(0.247, 0.282, 0.333, 1.0),     // gray-700
+            text_muted: vec4(0.392, 0.455, 0.545, 1.0),         // slate-500
+            text_faint: vec4(0.580, 0.639, 0.722, 1.0),         // slate-400
+            icon: vec4(0.420, 0.447, 0.502, 1.0),               // gray-500
+
+            border: vec4(0.886, 0.910, 0.941, 1.0),             // slate-200
+            accent: vec4(0.231, 0.510, 0.965, 1.0),             // blue-500
+            on_accent: vec4(1.0, 1.0, 1.0, 1.0),                // white
+            danger: vec4(0.937, 0.267, 0.267, 1.0),             // red-500
+            warning: vec4(0.961, 0.620, 0.043, 1.0),             // amber-500
+            success: vec4(0.22, 0.80, 0.46, 1.0),               // green
+
+            bg_overlay: vec4(1.0, 1.0, 1.0, 1.0),               // white
+            bg_overlay_header: vec4(0.945, 0.929, 0.988, 1.0),  // purple-50
+            bg_overlay_hover: vec4(0.933, 0.918, 0.980, 1.0),   // purple-100
+            bg_overlay_pressed: vec4(0.882, 0.859, 0.957, 1.0), // purple-200
+            overlay_text: vec4(0.345, 0.290, 0.502, 1.0),       // purple-700
+            overlay_icon: vec4(0.545, 0.467, 0.757, 1.0),       // purple-500
+            overlay_border: vec4(0.847, 0.824, 0.941, 1.0),     // purple-200
+        }
+    }
+
+    /// Built-in dark palette
+    pub fn dark() -> Self {
+        Self {
+            bg_app: vec4(0.059, 0.090, 0.165, 1.0),             // slate-900
+            bg_canvas: vec4(0.059, 0.090, 0.165, 1.0),          // slate-900
+            bg_panel: vec4(0.122, 0.161, 0.231, 1.0),           // slate-800
+            bg_sidebar: vec4(0.122, 0.161, 0.231, 1.0),         // slate-800
+            bg_list: vec4(0.059, 0.090, 0.165, 1.0),            // slate-900
+            bg_title_bar: vec4(0.200, 0.255, 0.333, 1.0),       // slate-700
+            bg_activity_bar: vec4(0.008, 0.024, 0.090, 1.0),    // slate-950
+            bg_hover: vec4(0.122, 0.161, 0.231, 1.0),           // slate-800
+            bg_selected: vec4(0.165, 0.220, 0.310, 1.0),
+            bg_pressed: vec4(0.200, 0.255, 0.350, 1.0),
+
+            text_primary: vec4(0.945, 0.961, 0.976, 1.0),       // slate-100
+            text_secondary: vec4(0.886, 0.910, 0.941, 1.0),     // slate-200
+            text_muted: vec4(0.580, 0.639, 0.722, 1.0),         // slate-400
+            text_faint: vec4(0.392, 0.455, 0.545, 1.0),         // slate-500
+            icon: vec4(0.580, 0.639, 0.722, 1.0),               // slate-400
+
+            border: vec4(0.200, 0.255, 0.333, 1.0),             // slate-700
+            accent: vec4(0.376, 0.647, 0.980, 1.0),             // blue-400
+            on_accent: vec4(1.0, 1.0, 1.0, 1.0),                // white
+            danger: vec4(0.937, 0.267, 0.267, 1.0),             // red-500
+            warning: vec4(0.984, 0.749, 0.141, 1.0),             // amber-400
+            success: vec4(0.22, 0.80, 0.46, 1.0),               // green
+
+            bg_overlay: vec4(0.067, 0.055, 0.110, 1.0),         // very dark purple
+            bg_overlay_header: vec4(0.110, 0.090, 0.180, 1.0),
+            bg_overlay_hover: vec4(0.150, 0.130, 0.210, 1.0),
+            bg_overlay_pressed: vec4(0.180, 0.160, 0.250, 1.0),
+            overlay_text: vec4(0.847, 0.824, 0.941, 1.0),       // purple-200
+            overlay_icon: vec4(0.694, 0.631, 0.871, 1.0),       // purple-400
+            overlay_border: vec4(0.200, 0.180, 0.280, 1.0),
+        }
+    }
+
+    /// Built-in high-contrast light palette (black on white, WCAG AA and above)
+    pub fn high_contrast_light() -> Self {
+        Self {
+            bg_app: vec4(1.0, 1.0, 1.0, 1.0),
+            bg_canvas: vec4(0.878, 0.878, 0.878, 1.0),
+            bg_panel: vec4(1.0, 1.0, 1.0, 1.0),
+            bg_sidebar: vec4(1.0, 1.0, 1.0, 1.0),
+            bg_list: vec4(1.0, 1.0, 1.0, 1.0),
+            bg_title_bar: vec4(0.933, 0.933, 0.933, 1.0),
+            bg_activity_bar: vec4(0.933, 0.933, 0.933, 1.0),
+            bg_hover: vec4(0.878, 0.878, 0.878, 1.0),
+            bg_selected: vec4(0.831, 0.894, 1.0, 1.0),
+            bg_pressed: vec4(0.800, 0.800, 0.800, 1.0),
+
+            text_primary: vec4(0.0, 0.0, 0.0, 1.0),
+            text_secondary: vec4(0.0, 0.0, 0.0, 1.0),
+            text_muted: vec4(0.200, 0.200, 0.200, 1.0),
+            text_faint: vec4(0.333, 0.333, 0.333, 1.0),
+            icon: vec4(0.102, 0.102, 0.102, 1.0),
+
+            border: vec4(0.0, 0.0, 0.0, 1.0),
+            accent: vec4(0.0, 0.278, 0.702, 1.0),               // deep blue
+            on_accent: vec4(1.0, 1.0, 1.0, 1.0),
+            danger: vec4(0.690, 0.0, 0.125, 1.0),               // deep red
+            warning: vec4(0.541, 0.298, 0.0, 1.0),               // deep amber
+            success: vec4(0.0, 0.420, 0.176, 1.0),              // deep green
+
+            bg_overlay: vec4(1.0, 1.0, 1.0, 1.0),
+            bg_overlay_header: vec4(0.933, 0.933, 0.933, 1.0),
+            bg_overlay_hover: vec4(0.878, 0.878, 0.878, 1.0),
+            bg_overlay_pressed: vec4(0.800, 0.800, 0.800, 1.0),
+            overlay_text: vec4(0.0, 0.0, 0.0, 1.0),
+            overlay_icon: vec4(0.231, 0.122, 0.478, 1.0),       // deep purple
+            overlay_border: vec4(0.0, 0.0, 0.0, 1.0),
+        }
+    }
+
+    /// Built-in high-contrast dark palette (white on black, WCAG AA and above)
+    pub fn high_contrast_dark() -> Self {
+        Self {
+            bg_app: vec4(0.0, 0.0, 0.0, 1.0),
+            bg_canvas: vec4(0.0, 0.0, 0.0, 1.0),
+            bg_panel: vec4(0.0, 0.0, 0.0, 1.0),
+            bg_sidebar: vec4(0.0, 0.0, 0.0, 1.0),
+            bg_list: vec4(0.0, 0.0, 0.0, 1.0),
+            bg_title_bar: vec4(0.102, 0.102, 0.102, 1.0),
+            bg_activity_bar: vec4(0.0, 0.0, 0.0, 1.0),
+            bg_hover: vec4(0.200, 0.200, 0.200, 1.0),
+            bg_selected: vec4(0.0, 0.200, 0.400, 1.0),
+            bg_pressed: vec4(0.267, 0.267, 0.267, 1.0),
+
+            text_primary: vec4(1.0, 1.0, 1.0, 1.0),
+            text_secondary: vec4(1.0, 1.0, 1.0, 1.0),
+            text_muted: vec4(0.851, 0.851, 0.851, 1.0),
+            text_faint: vec4(0.702, 0.702, 0.702, 1.0),
+            icon: vec4(0.902, 0.902, 0.902, 1.0),
+
+            border: vec4(1.0, 1.0, 1.0, 1.0),
+            accent: vec4(0.302, 0.639, 1.0, 1.0),               // light blue
+            on_accent: vec4(0.0, 0.0, 0.0, 1.0),
+            danger: vec4(1.0, 0.420, 0.420, 1.0),               // light red
+            warning: vec4(1.0, 0.800, 0.302, 1.0),               // light amber
+            success: vec4(0.361, 0.839, 0.478, 1.0),            // light green
+
+            bg_overlay: vec4(0.0, 0.0, 0.0, 1.0),
+            bg_overlay_header: vec4(0.102, 0.102, 0.102, 1.0),
+            bg_overlay_hover: vec4(0.200, 0.200, 0.200, 1.0),
+            bg_overlay_pressed: vec4(0.267, 0.267, 0.267, 1.0),
+            overlay_text: vec4(1.0, 1.0, 1.0, 1.0),
+            overlay_icon: vec4(0.800, 0.729, 1.0, 1.0),         // light purple
+            overlay_border: vec4(1.0, 1.0, 1.0, 1.0),
+        }
+    }
+
+    /// The built-in palettes blended by a dark mode value (0.0 = light, 1.0 = dark)
+    pub fn for_dark_mode(dark_mode: f64) -> Self {
+        Self::light().lerp(&Self::dark(), dark_mode)
+    }
+
+    /// All colors with their uniform names
+    pub fn colors(&self) -> [(LiveId, Vec4); 28] {
+        [
+            (live_id!(bg_app), self.bg_app),
+            (live_id!(bg_canvas), self.bg_canvas),
+            (live_id!(bg_panel), self.bg_panel),
+            (live_id!(bg_sidebar), self.bg_sidebar),
+            (live_id!(bg_list), self.bg_list),
+            (live_id!(bg_title_bar), self.bg_title_bar),
+            (live_id!(bg_activity_bar), self.bg_activity_bar),
+            (live_id!(bg_hover), self.bg_hover),
+            (live_id!(bg_selected), self.bg_selected),
+            (live_id!(bg_pressed), self.bg_pressed),
+            (live_id!(text_primary), self.text_primary),
+            (live_id!(text_secondary), self.text_secondary),
+            (live_id!(text_muted), self.text_muted),
+            (live_id!(text_faint), self.text_faint),
+            (live_id!(icon), self.icon),
+            (live_id!(border), self.border),
+            (live_id!(accent), self.accent),
+            (live_id!(on_accent), self.on_accent),
+            (live_id!(danger), self.danger),
+            (live_id!(warning), self.warning),
+            (live_id!(success), self.success),
+            (live_id!(bg_overlay), self.bg_overlay),
+            (live_id!(bg_overlay_header), self.bg_overlay_header),
+            (live_id!(bg_overlay_hover), self.bg_overlay_hover),
+            (live_id!(bg_overlay_pressed), self.bg_overlay_pressed),
+            (live_id!(overlay_text), self.overlay_text),
+            (live_id!(overlay_icon), self.overlay_icon),
+            (live_id!(overlay_border), self.overlay_border),
+        ]
+    }
+
+    /// Mutable access to all colors with their uniform names
+    pub fn colors_mut(&mut self) -> [(LiveId, &mut Vec4); 28] {
+        [
+            (live_id!(bg_app), &mut self.bg_app),
+            (live_id!(bg_canvas), &mut self.bg_canvas),
+            (live_id!(bg_panel), &mut self.bg_panel),
+            (live_id!(bg_sidebar), &mut self.bg_sidebar),
+            (live_id!(bg_list), &mut self.bg_list),
+            (live_id!(bg_title_bar), &mut self.bg_title_bar),
+            (live_id!(bg_activity_bar), &mut self.bg_activity_bar),
+            (live_id!(bg_hover), &mut self.bg_hover),
+            (live_id!(bg_selected), &mut self.bg_selected),
+            (live_id!(bg_pressed), &mut self.bg_pressed),
+            (live_id!(text_primary), &mut self.text_primary),
+            (live_id!(text_secondary), &mut self.text_secondary),
+            (live_id!(text_muted), &mut self.text_muted),
+            (live_id!(text_faint), &mut self.text_faint),
+            (live_id!(icon), &mut self.icon),
+            (live_id!(border), &mut self.border),
+            (live_id!(accent), &mut self.accent),
+            (live_id!(on_accent), &mut self.on_accent),
+            (live_id!(danger), &mut self.danger),
+            (live_id!(warning), &mut self.warning),
+            (live_id!(success), &mut self.success),
+            (live_id!(bg_overlay), &mut self.bg_overlay),
+            (live_id!(bg_overlay_header), &mut self.bg_overlay_header),
+            (live_id!(bg_overlay_hover), &mut self.bg_overlay_hover),
+            (live_id!(bg_overlay_pressed), &mut self.bg_overlay_pressed),
+            (live_id!(overlay_text), &mut self.overlay_text),
+            (live_id!(overlay_icon), &mut self.overlay_icon),
+            (live_id!(overlay_border), &mut self.overlay_border),
+        ]
+    }
+
+    /// A color by its uniform name (e.g. `"text_muted"`)
+    pub fn color(&self, name: &str) -> Option<Vec4> {
+        let id = LiveId::from_str(name);
+        self.colors().into_iter().find(|(color_id, _)| *color_id == id).map(|(_, color)| color)
+    }
+
+    /// Mutable access to a color by its uniform name
+    pub fn color_mut(&mut self, name: &str) -> Option<&mut Vec4> {
+        let id = LiveId::from_str(name);
+        self.colors_mut().into_iter().find(|(color_id, _)| *color_id == id).map(|(_, color)| color)
+    }
+
+    /// Blend every color towards `other` (t = 0.0 gives self, 1.0 gives other)
+    pub fn lerp(&self, other: &ShellPalette, t: f64) -> ShellPalette {
+        let t = t.clamp(0.0, 1.0) as f32;
+        let mut out = self.clone();
+        for ((_, color), (_, target)) in out.colors_mut().into_iter().zip(other.colors()) {
+            *color = lerp_color(*color, target, t);
+        }
+        out
+    }
+
+    /// Live nodes that set every palette color as a uniform on the given draw fields
+    ///
+    /// Shaders only declare the uniforms they use; the rest are ignored.
+    pub fn live_nodes(&self, draws: &[LiveId]) -> Vec<LiveNode> {
+        let mut nodes = vec![LiveNode::from_value(LiveValue::Object)];
+        for draw in draws {
+            nodes.push(LiveNode::from_id_value(*draw, LiveValue::Object));
+            self.push_colors(&mut nodes);
+            nodes.push(LiveNode::from_value(LiveValue::Close));
+        }
+        nodes.push(LiveNode::from_value(LiveValue::Close));
+        nodes
+    }
+
+    /// Live nodes for applying the palette directly to a draw shader
+    /// (e.g. a widget's own `DrawQuad`)
+    pub fn uniform_nodes(&self) -> Vec<LiveNode> {
+        let mut nodes = vec![LiveNode::from_value(LiveValue::Object)];
+        self.push_colors(&mut nodes);
+        nodes.push(LiveNode::from_value(LiveValue::Close));
+        nodes
+    }
+
+    fn push_colors(&self, nodes: &mut Vec<LiveNode>) {
+        for (id, color) in self.colors() {
+            nodes.push(LiveNode::from_id_value(id, LiveValue::Vec4(color)));
+        }
+    }
+}
+
+/// Linear blend between two colors
+pub fn lerp_color(a: Vec4, b: Vec4, t: f32) -> Vec4 {
+    vec4(
+        a.x + (b.x - a.x) * t,
+        a.y + (b.y - a.y) * t,
+        a.z + (b.z - a.z) * t,
+        a.w + (b.w - a.w) * t,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme::parse_hex_color;
+
+    fn close(a: Vec4, b: Vec4) -> bool {
+        [a.x - b.x, a.y - b.y, a.z - b.z, a.w - b.w].iter().all(|d| d.abs() < 0.003)
+    }
+
+    #[test]
+    fn test_lerp_blends_every_color() {
+        let light = ShellPalette::light();
+        let dark = ShellPalette::dark();
+
+        assert_eq!(light.lerp(&dark, 0.0), light);
+        assert_eq!(light.lerp(&dark, 1.0), dark);
+        assert_eq!(light.lerp(&dark, 2.0), dark, "t is clamped");
+
+        let half = light.lerp(&dark, 0.5);
+        for ((_, mid), ((_, a), (_, b))) in half.colors().into_iter().zip(light.colors().into_iter().zip(dark.colors())) {
+            assert_eq!(mid, lerp_color(a, b, 0.5));
+        }
+    }
+
+    #[test]
+    fn test_color_mut_by_uniform_name() {
+        let mut palette = ShellPalette::light();
+        *palette.color_mut("accent").unwrap() = vec4(1.0, 0.0, 0.0, 1.0);
+
+        assert_eq!(palette.accent, vec4(1.0, 0.0, 0.0, 1.0));
+        assert_eq!(palette.color("accent"), Some(palette.accent));
+        assert!(palette.color_mut("no_such_color").is_none());
+    }
+
+    #[test]
+    fn test_live_design_defaults_match_the_light_palette() {
+        // Shaders start from the PALETTE_* constants until the first palette is pushed
+        let light = ShellPalette::light();
+        let mut checked = 0;
+        for line in include_str!("../live_design.rs").lines() {
+            let Some((name, hex)) = line.trim().strip_prefix("pub PALETTE_").and_then(|l| l.split_once(" = ")) else {
+                continue;
+            };
+            let name = name.to_lowercase();
+            let expected = light.color(&name).unwrap_or_else(|| panic!("PALETTE_{} has no palette color", name));
+            let actual = parse_hex_color(hex.trim()).unwrap();
+            assert!(close(actual, expected), "PALETTE_{} is {} but the light palette has {:?}", name, hex, expected);
+            checked += 1;
+        }
+        assert_eq!(checked, light.colors().len(), "every palette color has a PALETTE_* constant");
+    }
+}
@@ -63,6 +63,100 @@ pub const RADIUS_LG: f64 = 8.0;
 /// Full/pill border radius
 pub const RADIUS_FULL: f64 = 9999.0;
 
+// ============================================================================
+// THEME METRICS
+// ============================================================================
+
+/// Six step size scale (font sizes, spacing)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SizeScale {
+    pub xs: f64,
+    pub sm: f64,
+    pub base: f64,
+    pub md: f64,
+    pub lg: f64,
+    pub xl: f64,
+}
+
+impl SizeScale {
+    /// Mutable access to a step by its theme file key
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut f64> {
+        match key {
+            "xs" => Some(&mut self.xs),
+            "sm" => Some(&mut self.sm),
+            "base" => Some(&mut self.base),
+            "md" => Some(&mut self.md),
+            "lg" => Some(&mut self.lg),
+            "xl" => Some(&mut self.xl),
+            _ => None,
+        }
+    }
+}
+
+/// Border radius scale
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RadiusScale {
+    pub sm: f64,
+    pub base: f64,
+    pub md: f64,
+    pub lg: f64,
+    pub full: f64,
+}
+
+impl RadiusScale {
+    /// Mutable access to a step by its theme file key
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut f64> {
+        match key {
+            "sm" => Some(&mut self.sm),
+            "base" => Some(&mut self.base),
+            "md" => Some(&mut self.md),
+            "lg" => Some(&mut self.lg),
+            "full" => Some(&mut self.full),
+            _ => None,
+        }
+    }
+}
+
+/// Font sizes, spacing and radii of a theme
+///
+/// Defaults to the constants above; theme files can override any step.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ThemeMetrics {
+    pub font_sizes: SizeScale,
+    pub spacing: SizeScale,
+    pub radii: RadiusScale,
+}
+
+impl Default for ThemeMetrics {
+    fn default() -> Self {
+        Self {
+            font_sizes: SizeScale {
+                xs: FONT_SIZE_XS,
+                sm: FONT_SIZE_SM,
+                base: FONT_SIZE_BASE,
+                md: FONT_SIZE_MD,
+                lg: FONT_SIZE_LG,
+                xl: FONT_SIZE_XL,
+            },
+            spacing: SizeScale {
+                xs: SPACING_XS,
+                sm: SPACING_SM,
+                base: SPACING_BASE,
+                md: SPACING_MD,
+                lg: SPACING_LG,
+                xl: SPACING_XL,
+            },
+            radii: RadiusScale {
+                sm: RADIUS_SM,
+                base: RADIUS_BASE,
+                md: RADIUS_MD,
+                lg: RADIUS_LG,
+                full: RADIUS_FULL,
+            },
+        }
+    }
+}
+
 // ============================================================================
 // COMPONENT SIZES
 // ============================================================================
@@ -118,3 +212,14 @@ pub const ANIMATION_DURATION: f64 = 0.2;
 
 /// Slow animation duration
 pub const ANIMATION_DURATION_SLOW: f64 = 0.4;
+
+/// Cubic ease-out of animation progress `t` (0..=1): fast start, gentle stop
+pub fn ease_out_cubic(t: f64) -> f64 {
+    1.0 - (1.0 - t).powi(3)
+}
+
+/// Seconds between theme directory scans for hot reload
+pub const THEME_POLL_INTERVAL: f64 = 1.0;
+
+/// Seconds between platform appearance checks while following the system theme
+pub const APPEARANCE_POLL_INTERVAL: f64 = 2.0;
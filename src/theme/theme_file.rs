@@ -0,0 +1,418 @@
+//! Theme files
+//!
+//! Themes can be edited without recompiling: a JSON or TOML file overrides
+//! any of the semantic palette colors (per light/dark mode), font sizes,
+//! spacing and radii. Keys that are left out keep the built-in values.
+//!
+//! ## Format
+//!
+//! ```toml
+//! name = "Solarized"
+//!
+//! [colors.light]
+//! bg_app = "#fdf6e3"
+//! accent = "#268bd2"
+//!
+//! [colors.dark]
+//! bg_app = "#002b36"
+//!
+//! [font_sizes]
+//! base = 13
+//!
+//! [radii]
+//! base = 6
+//! ```
+//!
+//! The JSON form uses the same structure. Color keys are the `ShellPalette`
+//! field names; colors are `#rgb`, `#rrggbb` or `#rrggbbaa`. Scales accept
+//! `xs`, `sm`, `base`, `md`, `lg`, `xl` (radii: `sm`, `base`, `md`, `lg`, `full`).
+//!
+//! ## Hot reload
+//!
+//! `ThemeLibrary` lists the theme files of a directory and reloads the ones
+//! whose modification time changed when `refresh()` is called. `ShellLayout`
+//! polls its library every `ShellConfig::theme_poll_interval` seconds.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use makepad_widgets::*;
+use serde_json::{Map, Value};
+use super::palette::ShellPalette;
+use super::styles::{RadiusScale, SizeScale, ThemeMetrics};
+
+/// Name of the built-in high-contrast theme
+pub const HIGH_CONTRAST_THEME: &str = "High Contrast";
+
+/// A named theme: palettes and metrics, built in or loaded from a theme file
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThemeDefinition {
+    /// Display name (the `name` key, or the file name without extension)
+    pub name: String,
+
+    /// Palette used in light mode
+    pub light: ShellPalette,
+
+    /// Palette used in dark mode
+    pub dark: ShellPalette,
+
+    /// Font sizes, spacing and radii
+    pub metrics: ThemeMetrics,
+}
+
+/// Syntax of a theme file
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ThemeFormat {
+    Json,
+    Toml,
+}
+
+impl ThemeFormat {
+    /// Detect the format from a file extension (`.json` / `.toml`)
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "toml" => Some(Self::Toml),
+            _ => None,
+        }
+    }
+}
+
+/// Error reading, parsing or validating a theme file
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThemeError {
+    /// Dotted path of the offending key (e.g. `colors.dark.accent`),
+    /// empty for I/O and syntax errors
+    pub key: String,
+
+    /// What is wrong with it
+    pub message: String,
+}
+
+impl ThemeError {
+    fn new(key: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { key: key.into(), message: message.into() }
+    }
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.key.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "`{}`: {}", self.key, self.message)
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {}
+
+impl ThemeDefinition {
+    /// Built-in light/dark palettes and default metrics under the given name
+    pub fn builtin(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            light: ShellPalette::light(),
+            dark: ShellPalette::dark(),
+            metrics: ThemeMetrics::default(),
+        }
+    }
+
+    /// Built-in high-contrast theme (see `ShellPalette::high_contrast_light`)
+    pub fn high_contrast() -> Self {
+        Self {
+            light: ShellPalette::high_contrast_light(),
+            dark: ShellPalette::high_contrast_dark(),
+            ..Self::builtin(HIGH_CONTRAST_THEME)
+        }
+    }
+
+    /// Themes that ship with the shell, listed before theme files in the picker
+    pub fn builtin_themes() -> Vec<Self> {
+        vec![Self::high_contrast()]
+    }
+
+    /// Load a theme file, detecting the format from its extension
+    pub fn load(path: &Path) -> Result<Self, ThemeError> {
+        let format = ThemeFormat::from_path(path)
+            .ok_or_else(|| ThemeError::new("", "unsupported file extension, expected .json or .toml"))?;
+        let source = fs::read_to_string(path)
+            .map_err(|e| ThemeError::new("", format!("failed to read file: {}", e)))?;
+        let default_name = path.file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("Untitled");
+        Self::parse(&source, format, default_name)
+    }
+
+    /// Parse and validate theme file contents
+    ///
+    /// `default_name` is used when the file has no `name` key.
+    pub fn parse(source: &str, format: ThemeFormat, default_name: &str) -> Result<Self, ThemeError> {
+        let value: Value = match format {
+            ThemeFormat::Json => serde_json::from_str(source)
+                .map_err(|e| ThemeError::new("", format!("invalid JSON: {}", e)))?,
+            ThemeFormat::Toml => toml::from_str(source)
+                .map_err(|e| ThemeError::new("", format!("invalid TOML: {}", e)))?,
+        };
+        Self::from_value(&value, default_name)
+    }
+
+    fn from_value(value: &Value, default_name: &str) -> Result<Self, ThemeError> {
+        let mut theme = Self::builtin(default_name);
+
+        for (key, value) in expect_object(value, "")? {
+            match key.as_str() {
+                "name" => {
+                    theme.name = value.as_str()
+                        .filter(|name| !name.trim().is_empty())
+                        .ok_or_else(|| ThemeError::new("name", "expected a non-empty string"))?
+                        .to_string();
+                }
+                "colors" => {
+                    for (mode, colors) in expect_object(value, "colors")? {
+                        let path = format!("colors.{}", mode);
+                        let palette = match mode.as_str() {
+                            "light" => &mut theme.light,
+                            "dark" => &mut theme.dark,
+                            _ => return Err(ThemeError::new(path, "unknown color mode, expected `light` or `dark`")),
+                        };
+                        read_palette(colors, &path, palette)?;
+                    }
+                }
+                "font_sizes" => read_scale(value, "font_sizes", &mut theme.metrics.font_sizes)?,
+                "spacing" => read_scale(value, "spacing", &mut theme.metrics.spacing)?,
+                "radii" => read_scale(value, "radii", &mut theme.metrics.radii)?,
+                _ => return Err(ThemeError::new(key.as_str(), "unknown key")),
+            }
+        }
+
+        Ok(theme)
+    }
+}
+
+fn expect_object<'a>(value: &'a Value, key: &str) -> Result<&'a Map<String, Value>, ThemeError> {
+    value.as_object().ok_or_else(|| ThemeError::new(key, "expected a table"))
+}
+
+fn read_palette(value: &Value, path: &str, palette: &mut ShellPalette) -> Result<(), ThemeError> {
+    for (name, color) in expect_object(value, path)? {
+        let key = format!("{}.{}", path, name);
+        let slot = palette.color_mut(name)
+            .ok_or_else(|| ThemeError::new(key.as_str(), "unknown color"))?;
+        *slot = color.as_str()
+            .and_then(parse_hex_color)
+            .ok_or_else(|| ThemeError::new(key.as_str(), "expected a hex color like \"#3b82f6\""))?;
+    }
+    Ok(())
+}
+
+/// Size scales that theme files can override step by step
+trait Scale {
+    fn step_mut(&mut self, key: &str) -> Option<&mut f64>;
+}
+
+impl Scale for SizeScale {
+    fn step_mut(&mut self, key: &str) -> Option<&mut f64> {
+        self.get_mut(key)
+    }
+}
+
+impl Scale for RadiusScale {
+    fn step_mut(&mut self, key: &str) -> Option<&mut f64> {
+        self.get_mut(key)
+    }
+}
+
+fn read_scale(value: &Value, path: &str, scale: &mut impl Scale) -> Result<(), ThemeError> {
+    for (name, size) in expect_object(value, path)? {
+        let key = format!("{}.{}", path, name);
+        let slot = scale.step_mut(name).ok_or_else(|| ThemeError::new(key.as_str(), "unknown size"))?;
+        *slot = size.as_f64()
+            .filter(|size| size.is_finite() && *size >= 0.0)
+            .ok_or_else(|| ThemeError::new(key.as_str(), "expected a non-negative number"))?;
+    }
+    Ok(())
+}
+
+/// Parse `#rgb`, `#rrggbb` or `#rrggbbaa` into a color
+pub fn parse_hex_color(hex: &str) -> Option<Vec4> {
+    let digits = hex.strip_prefix('#')?;
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |i: usize, len: usize| {
+        let value = u8::from_str_radix(&digits[i * len..(i + 1) * len], 16).ok()?;
+        // Short form repeats each digit (#f80 == #ff8800)
+        Some(if len == 1 { value * 17 } else { value } as f32 / 255.0)
+    };
+    match digits.len() {
+        3 => Some(vec4(channel(0, 1)?, channel(1, 1)?, channel(2, 1)?, 1.0)),
+        6 => Some(vec4(channel(0, 2)?, channel(1, 2)?, channel(2, 2)?, 1.0)),
+        8 => Some(vec4(channel(0, 2)?, channel(1, 2)?, channel(2, 2)?, channel(3, 2)?)),
+        _ => None,
+    }
+}
+
+// ============================================================================
+// THEME LIBRARY
+// ============================================================================
+
+/// A theme file tracked by a `ThemeLibrary`
+#[derive(Clone, Debug)]
+pub struct ThemeFileEntry {
+    /// Path of the file
+    pub path: PathBuf,
+
+    /// The loaded theme, or why it failed to load
+    pub theme: Result<ThemeDefinition, ThemeError>,
+
+    modified: Option<SystemTime>,
+}
+
+/// The theme files of a directory, reloaded when they change on disk
+#[derive(Clone, Debug, Default)]
+pub struct ThemeLibrary {
+    dir: PathBuf,
+    entries: Vec<ThemeFileEntry>,
+}
+
+impl ThemeLibrary {
+    /// Create an empty library for a directory (call `refresh()` to load it)
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into(), entries: Vec::new() }
+    }
+
+    /// Directory the themes are loaded from
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Rescan the directory, (re)loading new and modified files
+    ///
+    /// Returns the paths of files that were added, modified or removed.
+    /// A missing directory simply yields no themes.
+    pub fn refresh(&mut self) -> Vec<PathBuf> {
+        let mut found: Vec<(PathBuf, Option<SystemTime>)> = fs::read_dir(&self.dir)
+            .map(|dir| {
+                dir.filter_map(|entry| entry.ok())
+                    .map(|entry| entry.path())
+                    .filter(|path| path.is_file() && ThemeFormat::from_path(path).is_some())
+                    .map(|path| {
+                        let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+                        (path, modified)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        found.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut changed: Vec<PathBuf> = self.entries.iter()
+            .filter(|entry| !found.iter().any(|(path, _)| *path == entry.path))
+            .map(|entry| entry.path.clone())
+            .collect();
+
+        let mut entries = Vec::with_capacity(found.len());
+        for (path, modified) in found {
+            match self.entries.iter().position(|entry| entry.path == path) {
+                Some(index) if self.entries[index].modified == modified => {
+                    entries.push(self.entries[index].clone());
+                }
+                _ => {
+                    entries.push(ThemeFileEntry {
+                        theme: ThemeDefinition::load(&path),
+                        path: path.clone(),
+                        modified,
+                    });
+                    changed.push(path);
+                }
+            }
+        }
+        self.entries = entries;
+
+        changed
+    }
+
+    /// All tracked theme files, including ones that failed to load
+    pub fn entries(&self) -> &[ThemeFileEntry] {
+        &self.entries
+    }
+
+    /// The tracked file at `path`
+    pub fn entry(&self, path: &Path) -> Option<&ThemeFileEntry> {
+        self.entries.iter().find(|entry| entry.path == path)
+    }
+
+    /// Successfully loaded themes, in file name order
+    pub fn themes(&self) -> impl Iterator<Item = &ThemeDefinition> {
+        self.entries.iter().filter_map(|entry| entry.theme.as_ref().ok())
+    }
+
+    /// Names of the loaded themes, in file name order
+    pub fn theme_names(&self) -> Vec<String> {
+        self.themes().map(|theme| theme.name.clone()).collect()
+    }
+
+    /// Find a loaded theme by name
+    pub fn find(&self, name: &str) -> Option<&ThemeDefinition> {
+        self.themes().find(|theme| theme.name == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_json_theme() {
+        let theme = ThemeDefinition::parse(
+            r##"{ "name": "Brand", "colors": { "light": { "accent": "#ff0000" } }, "spacing": { "md": 20 } }"##,
+            ThemeFormat::Json,
+            "brand",
+        ).unwrap();
+
+        assert_eq!(theme.name, "Brand");
+        assert_eq!(theme.light.accent, vec4(1.0, 0.0, 0.0, 1.0));
+        assert_eq!(theme.light.bg_app, ShellPalette::light().bg_app);
+        assert_eq!(theme.dark, ShellPalette::dark());
+        assert_eq!(theme.metrics.spacing.md, 20.0);
+        assert_eq!(theme.metrics.spacing.sm, ThemeMetrics::default().spacing.sm);
+    }
+
+    #[test]
+    fn test_toml_theme() {
+        let theme = ThemeDefinition::parse(
+            "[colors.dark]\nbg_app = \"#000\"\n\n[font_sizes]\nbase = 13\n\n[radii]\nfull = 12.5\n",
+            ThemeFormat::Toml,
+            "midnight",
+        ).unwrap();
+
+        assert_eq!(theme.name, "midnight");
+        assert_eq!(theme.dark.bg_app, vec4(0.0, 0.0, 0.0, 1.0));
+        assert_eq!(theme.metrics.font_sizes.base, 13.0);
+        assert_eq!(theme.metrics.radii.full, 12.5);
+    }
+
+    #[test]
+    fn test_errors_report_offending_key() {
+        let error = |source: &str| ThemeDefinition::parse(source, ThemeFormat::Json, "t").unwrap_err().key;
+
+        assert_eq!(error(r#"{ "colours": {} }"#), "colours");
+        assert_eq!(error(r#"{ "colors": { "dusk": {} } }"#), "colors.dusk");
+        assert_eq!(error(r#"{ "colors": { "light": { "acent": "#fff" } } }"#), "colors.light.acent");
+        assert_eq!(error(r#"{ "colors": { "dark": { "accent": "blue" } } }"#), "colors.dark.accent");
+        assert_eq!(error(r#"{ "font_sizes": { "huge": 30 } }"#), "font_sizes.huge");
+        assert_eq!(error(r#"{ "radii": { "sm": -1 } }"#), "radii.sm");
+        assert_eq!(error(r#"{ "name": 3 }"#), "name");
+        assert_eq!(error("{ not json"), "");
+    }
+
+    #[test]
+    fn test_parse_hex_color() {
+        assert_eq!(parse_hex_color("#fff"), Some(vec4(1.0, 1.0, 1.0, 1.0)));
+        assert_eq!(parse_hex_color("#00000000"), Some(vec4(0.0, 0.0, 0.0, 0.0)));
+        assert_eq!(parse_hex_color("ffffff"), None);
+        assert_eq!(parse_hex_color("#ffff"), None);
+        assert_eq!(parse_hex_color("#gggggg"), None);
+    }
+}